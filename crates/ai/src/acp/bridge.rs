@@ -1,14 +1,17 @@
 use super::{
    AcpConnection,
    bridge_commands::{AcpCommand, run_worker_loop},
-   bridge_init::initialize_worker,
+   bridge_init::{
+      create_session, emit_initial_session_state, initialize_worker, map_mcp_servers,
+      map_mode_state,
+   },
    bridge_prompt::run_prompt,
    client::{AthasAcpClient, PermissionResponse},
    config::AgentRegistry,
    process::{stop_child_tree, terminate_process_group},
    types::{
       AcpAgentCapabilities, AcpAgentStatus, AcpEvent, AcpSessionInfo, AcpSessionList, AgentConfig,
-      SessionConfigOption,
+      McpServerConfig, SessionConfigOption, SlashCommand, StopReason,
    },
    workspace_path::{path_to_string, resolve_workspace_path},
 };
@@ -33,10 +36,12 @@ pub(super) struct AcpWorker {
    process: Option<Child>,
    process_group_id: Option<u32>,
    io_handle: Option<tokio::task::JoinHandle<()>>,
+   prompt_handle: Option<tokio::task::JoinHandle<()>>,
    client: Option<Arc<AthasAcpClient>>,
    workspace_path: Option<PathBuf>,
    agent_id: Option<String>,
    agent_capabilities: Option<AcpAgentCapabilities>,
+   mcp_servers: Vec<McpServerConfig>,
    app_handle: Option<AppHandle>,
 }
 
@@ -49,10 +54,12 @@ impl AcpWorker {
          process: None,
          process_group_id: None,
          io_handle: None,
+         prompt_handle: None,
          client: None,
          workspace_path: None,
          agent_id: None,
          agent_capabilities: None,
+         mcp_servers: Vec::new(),
          app_handle: None,
       }
    }
@@ -148,6 +155,7 @@ impl AcpWorker {
       self.workspace_path = initialized.workspace_path;
       self.agent_id = Some(agent_id.clone());
       self.agent_capabilities = Some(initialized.agent_capabilities);
+      self.mcp_servers = config.mcp_servers;
       self.app_handle = Some(app_handle.clone());
 
       let status = AcpAgentStatus {
@@ -163,6 +171,73 @@ impl AcpWorker {
       Ok((status, initialized.permission_sender))
    }
 
+   /// Start a fresh session on the running agent, closing the current one when the agent
+   /// supports it. Returns the id of the session that was replaced.
+   pub(super) async fn new_session(
+      &mut self,
+      workspace_path: Option<String>,
+   ) -> Result<Option<acp::SessionId>> {
+      self.ensure_process_alive().await?;
+
+      let connection = self
+         .connection
+         .as_ref()
+         .context("No active connection")?
+         .clone();
+      let app_handle = self
+         .app_handle
+         .as_ref()
+         .context("No app handle available")?
+         .clone();
+      let workspace_path = match resolve_workspace_path(workspace_path)? {
+         Some(path) => Some(path),
+         None => self.workspace_path.clone(),
+      };
+      let cwd = workspace_path
+         .clone()
+         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+
+      let mcp_capabilities = self
+         .agent_capabilities
+         .as_ref()
+         .map(|capabilities| capabilities.mcp_capabilities.clone())
+         .unwrap_or_default();
+      let mcp_servers = map_mcp_servers(&self.mcp_servers, &mcp_capabilities);
+      let session = match create_session(connection.clone(), cwd, mcp_servers).await {
+         Ok(Ok(session)) => session,
+         Ok(Err(e)) => bail!("Failed to create ACP session: {}", e),
+         Err(_) => bail!("ACP session creation timed out"),
+      };
+      log::info!("ACP session created: {}", session.session_id);
+
+      let previous_session_id = self.session_id.take();
+      if self.supports_session_close()
+         && let Some(previous_session_id) = previous_session_id.as_ref()
+         && let Err(error) = connection
+            .send_request(acp::CloseSessionRequest::new(previous_session_id.clone()))
+            .block_task()
+            .await
+      {
+         log::warn!("Failed to close previous ACP session: {}", error);
+      }
+
+      if let Some(client) = self.client.as_ref() {
+         client.set_session_id(session.session_id.to_string()).await;
+         client.set_workspace_path(workspace_path.clone());
+      }
+      self.session_id = Some(session.session_id.clone());
+      self.workspace_path = workspace_path;
+
+      emit_initial_session_state(
+         &app_handle,
+         Some(&session.session_id),
+         session.modes.map(map_mode_state),
+         session.config_options.map(Self::map_config_options),
+      );
+
+      Ok(previous_session_id)
+   }
+
    pub(super) async fn send_prompt(&mut self, prompt: Vec<serde_json::Value>) -> Result<()> {
       self.ensure_process_alive().await?;
 
@@ -183,7 +258,11 @@ impl AcpWorker {
          .clone();
       let auth_method_id = self.auth_method_id.clone();
 
-      tokio::task::spawn_local(async move {
+      if let Some(client) = self.client.as_ref() {
+         client.set_prompt_cancelled(false);
+      }
+
+      let prompt_handle = tokio::task::spawn_local(async move {
          if let Err(err) = run_prompt(
             connection,
             session_id.clone(),
@@ -203,6 +282,7 @@ impl AcpWorker {
             );
          }
       });
+      self.prompt_handle = Some(prompt_handle);
 
       Ok(())
    }
@@ -214,12 +294,31 @@ impl AcpWorker {
       let session_id = self.session_id.as_ref().context("No active session")?;
 
       let cancel_notification = acp::CancelNotification::new(session_id.clone());
-
-      connection
+      let notified = connection
          .send_notification(cancel_notification)
-         .context("Failed to cancel prompt")?;
+         .context("Failed to cancel prompt");
 
-      Ok(())
+      // Agents are not required to stop promptly, so stop listening to this turn right away
+      // instead of waiting for them to honor the notification.
+      if let Some(prompt_handle) = self.prompt_handle.take()
+         && !prompt_handle.is_finished()
+      {
+         prompt_handle.abort();
+         if let Some(client) = self.client.as_ref() {
+            client.set_prompt_cancelled(true);
+         }
+         if let Some(app_handle) = self.app_handle.as_ref() {
+            let _ = app_handle.emit(
+               "acp-event",
+               AcpEvent::PromptComplete {
+                  session_id: session_id.to_string(),
+                  stop_reason: StopReason::Cancelled,
+               },
+            );
+         }
+      }
+
+      notified
    }
 
    pub(super) async fn set_mode(&mut self, mode_id: &str) -> Result<()> {
@@ -366,6 +465,14 @@ impl AcpWorker {
       Ok(())
    }
 
+   pub(super) fn slash_commands(&self) -> Vec<SlashCommand> {
+      self
+         .client
+         .as_ref()
+         .map(|client| client.slash_commands())
+         .unwrap_or_default()
+   }
+
    fn supports_session_list(&self) -> bool {
       self
          .agent_capabilities
@@ -413,6 +520,10 @@ impl AcpWorker {
          );
       }
 
+      if let Some(handle) = self.prompt_handle.take() {
+         handle.abort();
+      }
+
       if let Some(handle) = self.io_handle.take() {
          handle.abort();
       }
@@ -452,6 +563,10 @@ impl AcpWorker {
 
 impl Drop for AcpWorker {
    fn drop(&mut self) {
+      if let Some(handle) = self.prompt_handle.take() {
+         handle.abort();
+      }
+
       if let Some(handle) = self.io_handle.take() {
          handle.abort();
       }
@@ -516,6 +631,15 @@ impl AcpAgentBridge {
       self.registry.invalidate_detection_cache();
    }
 
+   /// Replace the MCP servers passed to `agent_id` on new sessions and persist them
+   pub fn set_agent_mcp_servers(
+      &mut self,
+      agent_id: &str,
+      mcp_servers: Vec<McpServerConfig>,
+   ) -> Result<AgentConfig> {
+      self.registry.set_mcp_servers(agent_id, mcp_servers)
+   }
+
    /// Start an ACP agent by ID
    pub async fn start_agent(
       &self,
@@ -559,6 +683,45 @@ impl AcpAgentBridge {
       Ok(status)
    }
 
+   /// Start a new session for `agent_id`. A running instance of the agent keeps its process and
+   /// only switches sessions; otherwise the agent is started.
+   pub async fn new_session(
+      &self,
+      agent_id: &str,
+      workspace_path: Option<String>,
+   ) -> Result<AcpAgentStatus> {
+      let current_status = self.status.lock().await.clone();
+      if !current_status.running || current_status.agent_id != agent_id {
+         return self.start_agent(agent_id, workspace_path, None).await;
+      }
+
+      let (response_tx, response_rx) = oneshot::channel();
+
+      self
+         .command_tx
+         .send(AcpCommand::NewSession {
+            workspace_path,
+            response_tx,
+         })
+         .await
+         .context("Failed to send command to ACP worker")?;
+
+      let previous_session_id = response_rx.await.context("Worker disconnected")??;
+      if let Some(session_id) = previous_session_id {
+         let _ = self.app_handle.emit(
+            "acp-event",
+            AcpEvent::SessionComplete {
+               session_id: session_id.to_string(),
+            },
+         );
+      }
+
+      let status = self.get_status().await;
+      self.emit_status_change(&status);
+
+      Ok(status)
+   }
+
    /// Send a prompt to the active agent
    pub async fn send_prompt(&self, prompt: Vec<serde_json::Value>) -> Result<()> {
       let (response_tx, response_rx) = oneshot::channel();
@@ -725,6 +888,24 @@ impl AcpAgentBridge {
       response_rx.await.context("Worker disconnected")?
    }
 
+   /// Slash commands last advertised by `agent_id`, empty when another agent is running
+   pub async fn list_slash_commands(&self, agent_id: &str) -> Result<Vec<SlashCommand>> {
+      let current_status = self.status.lock().await.clone();
+      if !current_status.running || current_status.agent_id != agent_id {
+         return Ok(Vec::new());
+      }
+
+      let (response_tx, response_rx) = oneshot::channel();
+
+      self
+         .command_tx
+         .send(AcpCommand::ListSlashCommands { response_tx })
+         .await
+         .context("Failed to send command to ACP worker")?;
+
+      response_rx.await.context("Worker disconnected")
+   }
+
    /// Cancel the current prompt turn
    pub async fn cancel_prompt(&self) -> Result<()> {
       let (response_tx, response_rx) = oneshot::channel();
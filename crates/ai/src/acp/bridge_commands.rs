@@ -1,9 +1,10 @@
 use super::{
    bridge::AcpWorker,
    client::PermissionResponse,
-   types::{AcpAgentStatus, AcpSessionList, AgentConfig},
+   types::{AcpAgentStatus, AcpSessionList, AgentConfig, SlashCommand},
 };
 use crate::runtime::AthasAppHandle as AppHandle;
+use agent_client_protocol::schema as acp;
 use anyhow::Result;
 use athas_terminal::TerminalManager;
 use std::sync::Arc;
@@ -21,6 +22,10 @@ pub(super) enum AcpCommand {
       terminal_manager: Arc<TerminalManager>,
       response_tx: oneshot::Sender<Result<(AcpAgentStatus, mpsc::Sender<PermissionResponse>)>>,
    },
+   NewSession {
+      workspace_path: Option<String>,
+      response_tx: oneshot::Sender<Result<Option<acp::SessionId>>>,
+   },
    SendPrompt {
       prompt: Vec<serde_json::Value>,
       response_tx: oneshot::Sender<Result<()>>,
@@ -43,6 +48,9 @@ pub(super) enum AcpCommand {
       session_id: String,
       response_tx: oneshot::Sender<Result<()>>,
    },
+   ListSlashCommands {
+      response_tx: oneshot::Sender<Vec<SlashCommand>>,
+   },
    Logout {
       response_tx: oneshot::Sender<Result<()>>,
    },
@@ -97,6 +105,17 @@ pub(super) async fn run_worker_loop(
 
                   let _ = response_tx.send(result);
                }
+               AcpCommand::NewSession {
+                  workspace_path,
+                  response_tx,
+               } => {
+                  let result = worker.new_session(workspace_path).await;
+                  {
+                     let mut s = status.lock().await;
+                     *s = worker.get_status();
+                  }
+                  let _ = response_tx.send(result);
+               }
                AcpCommand::SendPrompt {
                   prompt,
                   response_tx,
@@ -162,6 +181,9 @@ pub(super) async fn run_worker_loop(
                   }
                   let _ = response_tx.send(result);
                }
+               AcpCommand::ListSlashCommands { response_tx } => {
+                  let _ = response_tx.send(worker.slash_commands());
+               }
                AcpCommand::Logout { response_tx } => {
                   let result = worker.logout().await;
                   {
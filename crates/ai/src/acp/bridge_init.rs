@@ -3,8 +3,8 @@ use super::{
    client::{AthasAcpClient, PermissionResponse},
    process::{force_kill_process_group, stop_child_tree_mut, terminate_process_group},
    types::{
-      AcpAgentCapabilities, AcpEvent, AgentConfig, SessionConfigOption, SessionMode,
-      SessionModeState,
+      AcpAgentCapabilities, AcpEvent, AcpMcpCapabilities, AgentConfig, McpServerConfig,
+      SessionConfigOption, SessionMode, SessionModeState,
    },
    workspace_path::{path_to_string, resolve_workspace_path},
 };
@@ -14,6 +14,7 @@ use anyhow::{Result, bail};
 use athas_terminal::TerminalManager;
 use serde_json::json;
 use std::{
+   collections::HashMap,
    path::{Path, PathBuf},
    process::Stdio,
    sync::Arc,
@@ -125,7 +126,8 @@ pub(super) async fn initialize_worker(
       .session_capabilities
       .resume
       .is_some();
-   let agent_capabilities = init_response.agent_capabilities.into();
+   let agent_capabilities: AcpAgentCapabilities = init_response.agent_capabilities.into();
+   let mcp_servers = map_mcp_servers(&config.mcp_servers, &agent_capabilities.mcp_capabilities);
 
    let cwd = workspace_path
       .clone()
@@ -143,6 +145,7 @@ pub(super) async fn initialize_worker(
       SessionBootstrapContext {
          auth_methods,
          supports_session_resume,
+         mcp_servers,
          map_config_options,
          child: &mut child,
          io_handle: &io_handle,
@@ -183,6 +186,7 @@ where
 {
    auth_methods: Vec<acp::AuthMethod>,
    supports_session_resume: bool,
+   mcp_servers: Vec<acp::McpServer>,
    map_config_options: F,
    child: &'a mut Child,
    io_handle: &'a tokio::task::JoinHandle<()>,
@@ -271,6 +275,7 @@ async fn initialize_connection(
          "extensionMethods": [
             { "name": "athas.openWebViewer", "description": "Open a URL in Athas web viewer", "params": { "url": "string" } },
             { "name": "athas.openTerminal", "description": "Open a terminal tab in Athas", "params": { "command": "string|null" } },
+            { "name": "athas.openFile", "description": "Open a file in the Athas editor, optionally at a 1-based line and column", "params": { "path": "string", "line": "number|null", "column": "number|null" } },
             { "name": "athas.setChatTitle", "description": "Rename the active Athas chat title", "params": { "title": "string" } }
          ],
          "notes": "Call these via ACP extension methods, not shell commands."
@@ -364,8 +369,13 @@ async fn bootstrap_session(
    };
 
    if let Some(existing_session_id) = requested_session_id {
-      let mut load_result =
-         load_session(connection.clone(), cwd.clone(), existing_session_id.clone()).await;
+      let mut load_result = load_session(
+         connection.clone(),
+         cwd.clone(),
+         existing_session_id.clone(),
+         ctx.mcp_servers.clone(),
+      )
+      .await;
 
       if let Ok(Err(err)) = &load_result
          && matches!(err.code, acp::ErrorCode::AuthRequired)
@@ -376,8 +386,13 @@ async fn bootstrap_session(
             let _ = ctx.child.kill().await;
             bail!("{}", e);
          }
-         load_result =
-            load_session(connection.clone(), cwd.clone(), existing_session_id.clone()).await;
+         load_result = load_session(
+            connection.clone(),
+            cwd.clone(),
+            existing_session_id.clone(),
+            ctx.mcp_servers.clone(),
+         )
+         .await;
       }
 
       match load_result {
@@ -398,8 +413,13 @@ async fn bootstrap_session(
                "ACP session/load unavailable ({}), trying session/resume",
                err
             );
-            let mut resume_result =
-               resume_session(connection.clone(), cwd.clone(), existing_session_id.clone()).await;
+            let mut resume_result = resume_session(
+               connection.clone(),
+               cwd.clone(),
+               existing_session_id.clone(),
+               ctx.mcp_servers.clone(),
+            )
+            .await;
 
             if let Ok(Err(err)) = &resume_result
                && matches!(err.code, acp::ErrorCode::AuthRequired)
@@ -410,9 +430,13 @@ async fn bootstrap_session(
                   let _ = ctx.child.kill().await;
                   bail!("{}", e);
                }
-               resume_result =
-                  resume_session(connection.clone(), cwd.clone(), existing_session_id.clone())
-                     .await;
+               resume_result = resume_session(
+                  connection.clone(),
+                  cwd.clone(),
+                  existing_session_id.clone(),
+                  ctx.mcp_servers.clone(),
+               )
+               .await;
             }
 
             match resume_result {
@@ -487,7 +511,8 @@ async fn bootstrap_session(
       }
    }
 
-   let mut session_result = create_session(connection.clone(), cwd.clone()).await;
+   let mut session_result =
+      create_session(connection.clone(), cwd.clone(), ctx.mcp_servers.clone()).await;
    if let Ok(Err(err)) = &session_result
       && matches!(err.code, acp::ErrorCode::AuthRequired)
    {
@@ -498,7 +523,7 @@ async fn bootstrap_session(
          bail!("{}", e);
       }
       log::info!("ACP authentication succeeded, retrying session creation");
-      session_result = create_session(connection.clone(), cwd).await;
+      session_result = create_session(connection.clone(), cwd, ctx.mcp_servers).await;
    }
 
    let session = match session_result {
@@ -529,11 +554,12 @@ async fn bootstrap_session(
    })
 }
 
-async fn create_session(
+pub(super) async fn create_session(
    connection: Arc<AcpConnection>,
    cwd: PathBuf,
+   mcp_servers: Vec<acp::McpServer>,
 ) -> Result<Result<acp::NewSessionResponse, acp::Error>, tokio::time::error::Elapsed> {
-   let session_request = acp::NewSessionRequest::new(cwd);
+   let session_request = acp::NewSessionRequest::new(cwd).mcp_servers(mcp_servers);
    tokio::time::timeout(
       std::time::Duration::from_secs(30),
       connection.send_request(session_request).block_task(),
@@ -545,8 +571,9 @@ async fn load_session(
    connection: Arc<AcpConnection>,
    cwd: PathBuf,
    existing_session_id: String,
+   mcp_servers: Vec<acp::McpServer>,
 ) -> Result<Result<acp::LoadSessionResponse, acp::Error>, tokio::time::error::Elapsed> {
-   let request = acp::LoadSessionRequest::new(existing_session_id, cwd);
+   let request = acp::LoadSessionRequest::new(existing_session_id, cwd).mcp_servers(mcp_servers);
    tokio::time::timeout(
       std::time::Duration::from_secs(30),
       connection.send_request(request).block_task(),
@@ -558,8 +585,9 @@ async fn resume_session(
    connection: Arc<AcpConnection>,
    cwd: PathBuf,
    existing_session_id: String,
+   mcp_servers: Vec<acp::McpServer>,
 ) -> Result<Result<acp::ResumeSessionResponse, acp::Error>, tokio::time::error::Elapsed> {
-   let request = acp::ResumeSessionRequest::new(existing_session_id, cwd);
+   let request = acp::ResumeSessionRequest::new(existing_session_id, cwd).mcp_servers(mcp_servers);
    tokio::time::timeout(
       std::time::Duration::from_secs(30),
       connection.send_request(request).block_task(),
@@ -567,7 +595,67 @@ async fn resume_session(
    .await
 }
 
-fn map_mode_state(modes: acp::SessionModeState) -> SessionModeState {
+/// The configured MCP servers in the form sent with session/new, session/load and
+/// session/resume. HTTP and SSE servers are left out unless the agent advertised support for
+/// that transport, since agents may reject a session that lists one.
+pub(super) fn map_mcp_servers(
+   servers: &[McpServerConfig],
+   capabilities: &AcpMcpCapabilities,
+) -> Vec<acp::McpServer> {
+   servers
+      .iter()
+      .filter(|server| {
+         let (name, supported, transport) = match server {
+            McpServerConfig::Stdio { .. } => return true,
+            McpServerConfig::Http { name, .. } => (name, capabilities.http, "HTTP"),
+            McpServerConfig::Sse { name, .. } => (name, capabilities.sse, "SSE"),
+         };
+         if !supported {
+            log::warn!(
+               "Skipping MCP server {}: the agent does not support {} MCP servers",
+               name,
+               transport
+            );
+         }
+         supported
+      })
+      .map(map_mcp_server)
+      .collect()
+}
+
+fn map_mcp_server(server: &McpServerConfig) -> acp::McpServer {
+   match server {
+      McpServerConfig::Stdio {
+         name,
+         command,
+         args,
+         env,
+      } => acp::McpServer::Stdio(
+         acp::McpServerStdio::new(name.clone(), command.clone())
+            .args(args.clone())
+            .env(
+               env.iter()
+                  .map(|(name, value)| acp::EnvVariable::new(name.clone(), value.clone()))
+                  .collect::<Vec<_>>(),
+            ),
+      ),
+      McpServerConfig::Http { name, url, headers } => acp::McpServer::Http(
+         acp::McpServerHttp::new(name.clone(), url.clone()).headers(map_http_headers(headers)),
+      ),
+      McpServerConfig::Sse { name, url, headers } => acp::McpServer::Sse(
+         acp::McpServerSse::new(name.clone(), url.clone()).headers(map_http_headers(headers)),
+      ),
+   }
+}
+
+fn map_http_headers(headers: &HashMap<String, String>) -> Vec<acp::HttpHeader> {
+   headers
+      .iter()
+      .map(|(name, value)| acp::HttpHeader::new(name.clone(), value.clone()))
+      .collect()
+}
+
+pub(super) fn map_mode_state(modes: acp::SessionModeState) -> SessionModeState {
    SessionModeState {
       current_mode_id: Some(modes.current_mode_id.to_string()),
       available_modes: modes
@@ -582,7 +670,7 @@ fn map_mode_state(modes: acp::SessionModeState) -> SessionModeState {
    }
 }
 
-fn emit_initial_session_state(
+pub(super) fn emit_initial_session_state(
    app_handle: &AppHandle,
    session_id: Option<&acp::SessionId>,
    initial_modes: Option<SessionModeState>,
@@ -612,3 +700,57 @@ fn emit_initial_session_state(
       log::warn!("Failed to emit initial session config options: {}", e);
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn sends_only_mcp_transports_the_agent_supports() {
+      let servers = vec![
+         McpServerConfig::Stdio {
+            name: "local".to_string(),
+            command: "mcp-local".to_string(),
+            args: Vec::new(),
+            env: HashMap::new(),
+         },
+         McpServerConfig::Http {
+            name: "remote".to_string(),
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::new(),
+         },
+         McpServerConfig::Sse {
+            name: "events".to_string(),
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+         },
+      ];
+      let transports = |capabilities: AcpMcpCapabilities| {
+         map_mcp_servers(&servers, &capabilities)
+            .iter()
+            .map(|server| match server {
+               acp::McpServer::Stdio(_) => "stdio",
+               acp::McpServer::Http(_) => "http",
+               acp::McpServer::Sse(_) => "sse",
+               _ => "other",
+            })
+            .collect::<Vec<_>>()
+      };
+
+      assert_eq!(transports(AcpMcpCapabilities::default()), vec!["stdio"]);
+      assert_eq!(
+         transports(AcpMcpCapabilities {
+            http: true,
+            sse: false,
+         }),
+         vec!["stdio", "http"]
+      );
+      assert_eq!(
+         transports(AcpMcpCapabilities {
+            http: true,
+            sse: true,
+         }),
+         vec!["stdio", "http", "sse"]
+      );
+   }
+}
@@ -1,10 +1,11 @@
 use super::{
    AcpConnection,
+   permission_rules::{PermissionDecision, PermissionRules, ToolCallSubject},
    terminal_state::AcpTerminalState,
    types::{
       AcpContentBlock, AcpEvent, AcpPlanEntry, AcpPlanEntryPriority, AcpPlanEntryStatus,
       AcpToolCallLocation, AcpToolCallStatus, AcpToolKind, AcpUsageUpdate, SessionConfigOption,
-      SessionConfigOptionKind, SessionConfigOptionValue, UiAction,
+      SessionConfigOptionKind, SessionConfigOptionValue, SlashCommand, SlashCommandInput, UiAction,
    },
    workspace_path::{path_to_string, resolve_path_against_workspace},
 };
@@ -16,9 +17,12 @@ use athas_terminal::{
 use std::{
    collections::HashMap,
    path::PathBuf,
-   sync::{Arc, Mutex as StdMutex},
+   sync::{
+      Arc, Mutex as StdMutex,
+      atomic::{AtomicBool, Ordering},
+   },
 };
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 use tokio::sync::{Mutex, mpsc, oneshot};
 
 /// Response for permission requests
@@ -33,10 +37,15 @@ pub struct PermissionResponse {
 /// Handles requests from the agent (file access, terminals, permissions)
 pub struct AthasAcpClient {
    app_handle: AppHandle,
-   workspace_path: Option<PathBuf>,
+   /// Follows the workspace of the current session, which can change without a restart
+   workspace_path: StdMutex<Option<PathBuf>>,
    permission_tx: mpsc::Sender<PermissionResponse>,
    permission_rx: Arc<Mutex<mpsc::Receiver<PermissionResponse>>>,
    current_session_id: Arc<Mutex<Option<String>>>,
+   /// Slash commands from the last `AvailableCommandsUpdate` of the current session
+   slash_commands: Arc<StdMutex<Vec<SlashCommand>>>,
+   /// Set when the user cancelled the prompt turn, so output the agent keeps sending is dropped
+   prompt_cancelled: AtomicBool,
    terminal_manager: Arc<TerminalManager>,
    /// Maps ACP terminal IDs to terminal state (uses StdMutex for sync access from event listeners)
    terminal_states: Arc<StdMutex<HashMap<String, AcpTerminalState>>>,
@@ -51,10 +60,12 @@ impl AthasAcpClient {
       let (permission_tx, permission_rx) = mpsc::channel(32);
       Self {
          app_handle,
-         workspace_path,
+         workspace_path: StdMutex::new(workspace_path),
          permission_tx,
          permission_rx: Arc::new(Mutex::new(permission_rx)),
          current_session_id: Arc::new(Mutex::new(None)),
+         slash_commands: Arc::new(StdMutex::new(Vec::new())),
+         prompt_cancelled: AtomicBool::new(false),
          terminal_manager,
          terminal_states: Arc::new(StdMutex::new(HashMap::new())),
       }
@@ -64,11 +75,49 @@ impl AthasAcpClient {
       self.permission_tx.clone()
    }
 
+   /// Agents may advertise commands while the first session is still being created, so the
+   /// cached commands are only dropped when switching from one session to another.
    pub async fn set_session_id(&self, session_id: String) {
       let mut current = self.current_session_id.lock().await;
+      if current
+         .as_deref()
+         .is_some_and(|current| current != session_id)
+         && let Ok(mut slash_commands) = self.slash_commands.lock()
+      {
+         slash_commands.clear();
+      }
       *current = Some(session_id);
    }
 
+   /// Point file access, terminals and permission rules at the workspace of a new session
+   pub fn set_workspace_path(&self, workspace_path: Option<PathBuf>) {
+      if let Ok(mut current) = self.workspace_path.lock() {
+         *current = workspace_path;
+      }
+   }
+
+   fn workspace_path(&self) -> Option<PathBuf> {
+      self
+         .workspace_path
+         .lock()
+         .ok()
+         .and_then(|workspace_path| workspace_path.clone())
+   }
+
+   /// Drop (or resume forwarding) the streamed output of the current prompt turn
+   pub fn set_prompt_cancelled(&self, cancelled: bool) {
+      self.prompt_cancelled.store(cancelled, Ordering::Relaxed);
+   }
+
+   /// Slash commands last advertised for the current session
+   pub fn slash_commands(&self) -> Vec<SlashCommand> {
+      self
+         .slash_commands
+         .lock()
+         .map(|commands| commands.clone())
+         .unwrap_or_default()
+   }
+
    fn emit_event(&self, event: AcpEvent) {
       if let Err(e) = self.app_handle.emit("acp-event", &event) {
          log::error!("Failed to emit ACP event: {}", e);
@@ -76,7 +125,7 @@ impl AthasAcpClient {
    }
 
    fn resolve_path(&self, path: &str) -> PathBuf {
-      resolve_path_against_workspace(self.workspace_path.as_deref(), path)
+      resolve_path_against_workspace(self.workspace_path().as_deref(), path)
    }
 
    fn extract_first_url(text: &str) -> Option<String> {
@@ -222,6 +271,47 @@ impl AthasAcpClient {
       }
    }
 
+   /// Answer a permission request from a workspace rule. Allow rules pick a one-time allow so
+   /// the agent does not remember more than the rule says; when the agent offers none, the
+   /// request falls through to the prompt. Reject rules cancel when there is no reject option.
+   fn rule_permission_response(
+      args: &acp::RequestPermissionRequest,
+      decision: PermissionDecision,
+   ) -> Option<acp::RequestPermissionResponse> {
+      let preferred = |kinds: &[acp::PermissionOptionKind]| {
+         kinds.iter().find_map(|kind| {
+            args
+               .options
+               .iter()
+               .find(|opt| opt.kind == *kind)
+               .map(|opt| acp::SelectedPermissionOutcome::new(opt.option_id.clone()))
+         })
+      };
+
+      match decision {
+         PermissionDecision::Allow => preferred(&[
+            acp::PermissionOptionKind::AllowOnce,
+            acp::PermissionOptionKind::AllowAlways,
+         ])
+         .map(|selected| {
+            acp::RequestPermissionResponse::new(acp::RequestPermissionOutcome::Selected(selected))
+         }),
+         PermissionDecision::Reject => Some(
+            match preferred(&[
+               acp::PermissionOptionKind::RejectOnce,
+               acp::PermissionOptionKind::RejectAlways,
+            ]) {
+               Some(selected) => acp::RequestPermissionResponse::new(
+                  acp::RequestPermissionOutcome::Selected(selected),
+               ),
+               None => {
+                  acp::RequestPermissionResponse::new(acp::RequestPermissionOutcome::Cancelled)
+               }
+            },
+         ),
+      }
+   }
+
    fn map_plan_priority(priority: acp::PlanEntryPriority) -> AcpPlanEntryPriority {
       match priority {
          acp::PlanEntryPriority::High => AcpPlanEntryPriority::High,
@@ -465,6 +555,28 @@ impl AthasAcpClient {
          .title
          .as_deref()
          .unwrap_or("Tool call");
+
+      let workspace_path = self.workspace_path();
+      let subject = ToolCallSubject::new(
+         args.tool_call.fields.kind.map(Self::map_tool_kind),
+         args.tool_call.fields.raw_input.as_ref(),
+         args
+            .tool_call
+            .fields
+            .locations
+            .iter()
+            .flatten()
+            .map(|location| location.path.as_path()),
+         workspace_path.as_deref(),
+      );
+      let config_dir = self.app_handle.path().app_config_dir().ok();
+      if let Some(decision) =
+         PermissionRules::load(config_dir.as_deref(), workspace_path.as_deref()).decide(&subject)
+         && let Some(response) = Self::rule_permission_response(&args, decision)
+      {
+         return Ok(response);
+      }
+
       let fallback_webviewer_url =
          Self::extract_webviewer_fallback_url(tool_title, args.tool_call.fields.raw_input.as_ref());
       let fallback_terminal_command = Self::extract_terminal_fallback_command(
@@ -610,6 +722,19 @@ impl AthasAcpClient {
    async fn session_notification(&self, args: acp::SessionNotification) -> acp::Result<()> {
       let session_id = args.session_id.to_string();
 
+      if self.prompt_cancelled.load(Ordering::Relaxed)
+         && matches!(
+            args.update,
+            acp::SessionUpdate::AgentMessageChunk(_)
+               | acp::SessionUpdate::AgentThoughtChunk(_)
+               | acp::SessionUpdate::ToolCall(_)
+               | acp::SessionUpdate::ToolCallUpdate(_)
+               | acp::SessionUpdate::Plan(_)
+         )
+      {
+         return Ok(());
+      }
+
       match args.update {
          acp::SessionUpdate::UserMessageChunk(chunk) => {
             let Some(content) = Self::map_content_block(chunk.content) else {
@@ -762,26 +887,31 @@ impl AthasAcpClient {
             });
          }
          acp::SessionUpdate::AvailableCommandsUpdate(commands_update) => {
+            let commands: Vec<SlashCommand> = commands_update
+               .available_commands
+               .iter()
+               .map(|c| SlashCommand {
+                  name: c.name.clone(),
+                  description: c.description.clone(),
+                  input: c.input.as_ref().and_then(|input| {
+                     // Extract hint from unstructured command input
+                     if let acp::AvailableCommandInput::Unstructured(unstructured) = input {
+                        Some(SlashCommandInput {
+                           hint: unstructured.hint.clone(),
+                        })
+                     } else {
+                        None
+                     }
+                  }),
+               })
+               .collect();
+
+            if let Ok(mut slash_commands) = self.slash_commands.lock() {
+               *slash_commands = commands.clone();
+            }
             self.emit_event(AcpEvent::SlashCommandsUpdate {
                session_id,
-               commands: commands_update
-                  .available_commands
-                  .iter()
-                  .map(|c| super::types::SlashCommand {
-                     name: c.name.clone(),
-                     description: c.description.clone(),
-                     input: c.input.as_ref().and_then(|input| {
-                        // Extract hint from unstructured command input
-                        if let acp::AvailableCommandInput::Unstructured(unstructured) = input {
-                           Some(super::types::SlashCommandInput {
-                              hint: unstructured.hint.clone(),
-                           })
-                        } else {
-                           None
-                        }
-                     }),
-                  })
-                  .collect(),
+               commands,
             });
          }
          acp::SessionUpdate::Plan(plan) => {
@@ -901,7 +1031,7 @@ impl AthasAcpClient {
          .cwd
          .as_ref()
          .map(|p| p.to_string_lossy().to_string())
-         .or_else(|| self.workspace_path.as_deref().map(path_to_string));
+         .or_else(|| self.workspace_path().as_deref().map(path_to_string));
 
       let env_map: Option<HashMap<String, String>> = if args.env.is_empty() {
          None
@@ -927,10 +1057,12 @@ impl AthasAcpClient {
          wsl_distribution: None,
          wsl_working_directory: None,
          environment: env_map,
+         clear_env: false,
          command: Some(command),
          args: command_args,
          size: TerminalSize::default(),
          term_program_version: Some(self.app_handle.package_info().version.to_string()),
+         output_batch_interval_ms: None,
       };
 
       let states_for_events = self.terminal_states.clone();
@@ -1144,6 +1276,36 @@ impl AthasAcpClient {
                serde_json::value::to_raw_value(&response).unwrap().into(),
             ))
          }
+         "athas.openFile" => {
+            let path = params
+               .get("path")
+               .and_then(|v| v.as_str())
+               .filter(|path| !path.is_empty())
+               .ok_or_else(|| {
+                  acp::Error::new(-32602, "athas.openFile requires a path".to_string())
+               })?;
+            let path = path_to_string(&self.resolve_path(path));
+            let position = |key: &str| {
+               params
+                  .get(key)
+                  .and_then(|v| v.as_u64())
+                  .and_then(|v| u32::try_from(v).ok())
+            };
+
+            self.emit_event(AcpEvent::UiAction {
+               session_id,
+               action: UiAction::OpenFile {
+                  path,
+                  line: position("line"),
+                  column: position("column"),
+               },
+            });
+
+            let response = serde_json::json!({ "success": true });
+            Ok(acp::ExtResponse::new(
+               serde_json::value::to_raw_value(&response).unwrap().into(),
+            ))
+         }
          "athas.setChatTitle" => {
             let title = params
                .get("title")
@@ -1,5 +1,7 @@
-use super::types::AgentConfig;
+use super::types::{AgentConfig, McpServerConfig};
 use crate::runtime::AthasAppHandle as AppHandle;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::{
    collections::HashMap,
    env, fs,
@@ -13,6 +15,17 @@ use tauri::Manager;
 /// Cache duration for binary detection (60 seconds)
 const DETECTION_CACHE_SECONDS: u64 = 60;
 
+/// File in the app config directory holding user settings for ACP agents.
+const AGENT_SETTINGS_FILE_NAME: &str = "acp-agents.json";
+
+/// User settings for one agent, keyed by agent id in the settings file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AgentSettings {
+   #[serde(default)]
+   mcp_servers: Vec<McpServerConfig>,
+}
+
 /// Get the user's login shell PATH. Bundled apps inherit a minimal PATH,
 /// so we source the full one from the user's shell and cache it.
 pub(crate) fn user_shell_path() -> Option<&'static str> {
@@ -39,14 +52,28 @@ pub struct AgentRegistry {
    agents: HashMap<String, AgentConfig>,
    last_detection: Option<Instant>,
    managed_bin_dir: Option<PathBuf>,
+   settings_path: Option<PathBuf>,
+   settings: HashMap<String, AgentSettings>,
 }
 
 impl AgentRegistry {
    pub fn new(app_handle: &AppHandle) -> Self {
+      let settings_path = app_handle
+         .path()
+         .app_config_dir()
+         .ok()
+         .map(|dir| dir.join(AGENT_SETTINGS_FILE_NAME));
+      let settings = settings_path
+         .as_deref()
+         .map(load_agent_settings)
+         .unwrap_or_default();
+
       Self {
          agents: HashMap::new(),
          last_detection: None,
          managed_bin_dir: managed_acp_bin_dir(app_handle),
+         settings_path,
+         settings,
       }
    }
 
@@ -63,11 +90,42 @@ impl AgentRegistry {
    pub fn replace_agents(&mut self, agents: Vec<AgentConfig>) {
       self.agents = agents
          .into_iter()
-         .map(|agent| (agent.id.clone(), agent))
+         .map(|mut agent| {
+            if let Some(settings) = self.settings.get(&agent.id) {
+               agent.mcp_servers = settings.mcp_servers.clone();
+            }
+            (agent.id.clone(), agent)
+         })
          .collect();
       self.invalidate_detection_cache();
    }
 
+   /// Replace the MCP servers of `agent_id` and persist them in the agent settings file.
+   pub fn set_mcp_servers(
+      &mut self,
+      agent_id: &str,
+      mcp_servers: Vec<McpServerConfig>,
+   ) -> Result<AgentConfig> {
+      let agent = self
+         .agents
+         .get_mut(agent_id)
+         .with_context(|| format!("Unknown ACP agent: {}", agent_id))?;
+
+      let mut settings = self.settings.clone();
+      settings
+         .entry(agent_id.to_string())
+         .or_default()
+         .mcp_servers = mcp_servers.clone();
+      settings.retain(|_, agent_settings| !agent_settings.mcp_servers.is_empty());
+      if let Some(path) = self.settings_path.as_deref() {
+         save_agent_settings(path, &settings)?;
+      }
+
+      self.settings = settings;
+      agent.mcp_servers = mcp_servers;
+      Ok(agent.clone())
+   }
+
    pub fn detect_installed(&mut self) {
       // Check if we should skip detection due to caching
       if let Some(last) = self.last_detection {
@@ -123,6 +181,26 @@ pub fn managed_wrapper_path(managed_bin_dir: Option<&Path>, agent_id: &str) -> O
    path.is_file().then_some(path)
 }
 
+/// A missing or unreadable settings file yields no settings rather than failing agent startup.
+fn load_agent_settings(path: &Path) -> HashMap<String, AgentSettings> {
+   let Ok(contents) = fs::read_to_string(path) else {
+      return HashMap::new();
+   };
+   serde_json::from_str(&contents).unwrap_or_else(|error| {
+      log::warn!("Ignoring invalid {}: {}", AGENT_SETTINGS_FILE_NAME, error);
+      HashMap::new()
+   })
+}
+
+fn save_agent_settings(path: &Path, settings: &HashMap<String, AgentSettings>) -> Result<()> {
+   if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).context("Failed to create config directory")?;
+   }
+   let contents = serde_json::to_string_pretty(settings)?;
+   fs::write(path, contents)
+      .with_context(|| format!("Failed to write {}", AGENT_SETTINGS_FILE_NAME))
+}
+
 fn managed_acp_bin_dir(app_handle: &AppHandle) -> Option<PathBuf> {
    let data_dir = app_handle.path().app_data_dir().ok()?;
    Some(data_dir.join("tools").join("acp"))
@@ -320,8 +398,11 @@ fn check_dir_for_binary(dir: &Path, binary_name: &str) -> Option<PathBuf> {
 
 #[cfg(test)]
 mod tests {
-   use super::{check_dir_for_binary, managed_wrapper_path};
-   use std::{fs, path::PathBuf};
+   use super::{
+      AgentSettings, McpServerConfig, check_dir_for_binary, load_agent_settings,
+      managed_wrapper_path, save_agent_settings,
+   };
+   use std::{collections::HashMap, fs, path::PathBuf};
 
    #[test]
    fn managed_wrapper_path_prefers_expected_wrapper_name() {
@@ -343,4 +424,28 @@ mod tests {
       let missing = check_dir_for_binary(PathBuf::from("/tmp/athas-missing").as_path(), "nope");
       assert!(missing.is_none());
    }
+
+   #[test]
+   fn agent_settings_round_trip_through_the_settings_file() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let path = temp_dir.path().join("config").join("acp-agents.json");
+      let server = McpServerConfig::Stdio {
+         name: "docs".to_string(),
+         command: "docs-mcp".to_string(),
+         args: vec!["--stdio".to_string()],
+         env: HashMap::new(),
+      };
+      let settings = HashMap::from([(
+         "gemini".to_string(),
+         AgentSettings {
+            mcp_servers: vec![server.clone()],
+         },
+      )]);
+
+      save_agent_settings(&path, &settings).expect("save settings");
+      let loaded = load_agent_settings(&path);
+
+      assert_eq!(loaded["gemini"].mcp_servers, vec![server]);
+      assert!(load_agent_settings(&temp_dir.path().join("missing.json")).is_empty());
+   }
 }
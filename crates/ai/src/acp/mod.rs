@@ -4,12 +4,16 @@ mod bridge_init;
 mod bridge_prompt;
 mod client;
 mod config;
+mod permission_rules;
 mod process;
 mod terminal_state;
 pub mod types;
 mod workspace_path;
 
 pub use bridge::AcpAgentBridge;
-pub use types::{AcpAgentStatus, AcpSessionInfo, AcpSessionList, AgentConfig, AgentRuntime};
+pub use types::{
+   AcpAgentStatus, AcpSessionInfo, AcpSessionList, AgentConfig, AgentRuntime, McpServerConfig,
+   SlashCommand,
+};
 
 pub(super) type AcpConnection = agent_client_protocol::ConnectionTo<agent_client_protocol::Agent>;
@@ -73,10 +73,10 @@ impl AcpTerminalState {
             self.set_exit_status(exit_code, signal);
          }
          TerminalEvent::Closed => {
+            // Closing without an exit event means the status could not be read, which must not
+            // look like success to the agent.
             self.flush_pending_utf8();
-            if self.exit_status.is_none() {
-               self.set_exit_status(Some(0), None);
-            }
+            self.set_exit_status(None, None);
          }
       }
    }
@@ -141,6 +141,7 @@ impl AcpTerminalState {
 #[cfg(test)]
 mod tests {
    use super::AcpTerminalState;
+   use athas_terminal::TerminalEvent;
 
    #[test]
    fn append_output_truncates_from_beginning() {
@@ -182,4 +183,27 @@ mod tests {
       state.append_output_bytes(&emoji[2..]);
       assert_eq!(state.output_buffer, "🙂");
    }
+
+   #[test]
+   fn closed_terminal_keeps_the_reported_exit_code() {
+      let mut state = AcpTerminalState::new("terminal-5".to_string(), None);
+      state.handle_event(TerminalEvent::Exit {
+         exit_code: Some(2),
+         signal: None,
+      });
+      state.handle_event(TerminalEvent::Closed);
+
+      let status = state.exit_status.expect("exit status should be set");
+      assert_eq!(status.exit_code, Some(2));
+   }
+
+   #[test]
+   fn closed_terminal_without_exit_event_reports_unknown_exit_code() {
+      let mut state = AcpTerminalState::new("terminal-6".to_string(), None);
+      state.handle_event(TerminalEvent::Closed);
+
+      let status = state.exit_status.expect("exit status should be set");
+      assert_eq!(status.exit_code, None);
+      assert_eq!(status.signal, None);
+   }
 }
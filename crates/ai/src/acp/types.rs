@@ -173,7 +173,7 @@ impl From<agent_client_protocol::schema::AgentCapabilities> for AcpAgentCapabili
    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AcpToolKind {
    Read,
@@ -204,6 +204,32 @@ pub struct AcpToolCallLocation {
    pub line: Option<u32>,
 }
 
+/// An MCP server handed to an ACP agent when a session is created
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum McpServerConfig {
+   Stdio {
+      name: String,
+      command: String,
+      #[serde(default)]
+      args: Vec<String>,
+      #[serde(default)]
+      env: HashMap<String, String>,
+   },
+   Http {
+      name: String,
+      url: String,
+      #[serde(default)]
+      headers: HashMap<String, String>,
+   },
+   Sse {
+      name: String,
+      url: String,
+      #[serde(default)]
+      headers: HashMap<String, String>,
+   },
+}
+
 /// Configuration for an ACP-compatible agent
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -223,6 +249,9 @@ pub struct AgentConfig {
    pub install_download_url: Option<String>,
    pub install_command: Option<String>,
    pub can_install: bool,
+   /// User-configured MCP servers, persisted in the agent settings file
+   #[serde(default)]
+   pub mcp_servers: Vec<McpServerConfig>,
 }
 
 impl AgentConfig {
@@ -242,6 +271,7 @@ impl AgentConfig {
          install_download_url: None,
          install_command: None,
          can_install: false,
+         mcp_servers: Vec::new(),
       }
    }
 
@@ -327,6 +357,13 @@ pub enum UiAction {
    /// Open a terminal with an optional command
    #[serde(rename_all = "camelCase")]
    OpenTerminal { command: Option<String> },
+   /// Open a file in the editor, optionally at a 1-based line and column
+   #[serde(rename_all = "camelCase")]
+   OpenFile {
+      path: String,
+      line: Option<u32>,
+      column: Option<u32>,
+   },
    /// Set the active Athas chat title
    #[serde(rename_all = "camelCase")]
    SetChatTitle { title: String },
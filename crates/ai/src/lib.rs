@@ -4,6 +4,7 @@ mod runtime;
 
 pub use acp::{
    AcpAgentBridge, AcpAgentStatus, AcpSessionInfo, AcpSessionList, AgentConfig, AgentRuntime,
+   McpServerConfig, SlashCommand,
 };
 pub use chat_history::{
    ChatData, ChatHistoryRepository, ChatStats, ChatWithMessages, MessageData, ToolCallData,
@@ -2,6 +2,7 @@ pub mod connection_manager;
 pub mod providers;
 pub mod sidecar;
 pub mod sql_common;
+pub mod sql_format;
 
 pub use connection_manager::{
    ConnectionConfig, ConnectionManager, ConnectionResult, DatabasePool, connect_database,
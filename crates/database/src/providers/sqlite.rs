@@ -1,5 +1,5 @@
 use crate::sql_common::{RowIdentity, build_row_identity_where_clause};
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -70,6 +70,37 @@ pub struct ForeignKeyInfo {
    pub to_column: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqliteAttachment {
+   pub alias: String,
+   pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SqliteBatchStatement {
+   pub sql: String,
+   #[serde(default)]
+   pub params: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SqliteBatchResult {
+   pub committed: bool,
+   pub rows_affected: i64,
+   #[serde(skip_serializing_if = "Option::is_none")]
+   pub failed_index: Option<usize>,
+   #[serde(skip_serializing_if = "Option::is_none")]
+   pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryPlanStep {
+   pub id: i64,
+   pub parent: i64,
+   pub detail: String,
+   pub children: Vec<QueryPlanStep>,
+}
+
 /// Escape a SQL identifier by wrapping in double quotes and escaping internal quotes
 fn escape_identifier(name: &str) -> String {
    format!("\"{}\"", name.replace('"', "\"\""))
@@ -163,6 +194,60 @@ fn execute_query(
    Ok(QueryResult { columns, rows })
 }
 
+/// Check that an attachment alias is a plain identifier that can be used unquoted
+fn validate_attachment_alias(alias: &str) -> Result<(), String> {
+   let mut chars = alias.chars();
+   let valid = chars
+      .next()
+      .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+      && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+
+   if !valid {
+      return Err(format!("Invalid attachment alias: {}", alias));
+   }
+   if alias.eq_ignore_ascii_case("main") || alias.eq_ignore_ascii_case("temp") {
+      return Err(format!("Attachment alias is reserved: {}", alias));
+   }
+   Ok(())
+}
+
+/// Open a database without allowing writes
+fn open_read_only(path: &str) -> Result<Connection, String> {
+   Connection::open_with_flags(
+      path,
+      OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+   )
+   .map_err(|e| format!("Failed to open database: {}", e))
+}
+
+/// Nest flat EXPLAIN QUERY PLAN rows under their parent ids, keeping row order
+fn build_query_plan_tree(rows: Vec<(i64, i64, String)>) -> Vec<QueryPlanStep> {
+   fn children_of(parent: i64, rows: &[(i64, i64, String)]) -> Vec<QueryPlanStep> {
+      rows
+         .iter()
+         .filter(|(id, row_parent, _)| *row_parent == parent && *id != parent)
+         .map(|(id, row_parent, detail)| QueryPlanStep {
+            id: *id,
+            parent: *row_parent,
+            detail: detail.clone(),
+            children: children_of(*id, rows),
+         })
+         .collect()
+   }
+
+   let ids: std::collections::HashSet<i64> = rows.iter().map(|(id, _, _)| *id).collect();
+   rows
+      .iter()
+      .filter(|(_, parent, _)| !ids.contains(parent))
+      .map(|(id, parent, detail)| QueryPlanStep {
+         id: *id,
+         parent: *parent,
+         detail: detail.clone(),
+         children: children_of(*id, &rows),
+      })
+      .collect()
+}
+
 /// Build WHERE clause and parameter values from structured filters
 fn build_where_clause(
    filters: &[ColumnFilter],
@@ -298,6 +383,52 @@ pub async fn execute_sqlite(path: String, statement: String) -> Result<i64, Stri
    Ok(result as i64)
 }
 
+/// Execute several statements in one transaction, rolling back all of them if any fails
+pub async fn execute_sqlite_batch(
+   path: String,
+   statements: Vec<SqliteBatchStatement>,
+) -> Result<SqliteBatchResult, String> {
+   let mut conn = Connection::open(&path).map_err(|e| format!("Failed to open database: {}", e))?;
+   let tx = conn
+      .transaction()
+      .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+   let mut rows_affected = 0;
+   for (index, statement) in statements.iter().enumerate() {
+      let values: Result<Vec<_>, String> = statement.params.iter().map(json_to_rusqlite).collect();
+      let outcome = values.and_then(|values| {
+         let params: Vec<&dyn rusqlite::ToSql> =
+            values.iter().map(|v| v as &dyn rusqlite::ToSql).collect();
+         tx.execute(&statement.sql, &params[..])
+            .map_err(|e| format!("Failed to execute statement: {}", e))
+      });
+
+      match outcome {
+         Ok(affected) => rows_affected += affected as i64,
+         Err(error) => {
+            tx.rollback()
+               .map_err(|e| format!("Failed to roll back transaction: {}", e))?;
+            return Ok(SqliteBatchResult {
+               committed: false,
+               rows_affected: 0,
+               failed_index: Some(index),
+               error: Some(error),
+            });
+         }
+      }
+   }
+
+   tx.commit()
+      .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+   Ok(SqliteBatchResult {
+      committed: true,
+      rows_affected,
+      failed_index: None,
+      error: None,
+   })
+}
+
 /// Insert a new row into a table
 pub async fn insert_sqlite_row(
    path: String,
@@ -500,6 +631,65 @@ pub async fn query_sqlite(path: String, query: String) -> Result<QueryResult, St
    execute_query(&conn, &query, &[])
 }
 
+/// Execute a query with additional databases attached under their aliases
+pub async fn query_sqlite_multi(
+   main_path: String,
+   attachments: Vec<SqliteAttachment>,
+   query: String,
+) -> Result<QueryResult, String> {
+   for attachment in &attachments {
+      validate_attachment_alias(&attachment.alias)?;
+   }
+
+   let conn =
+      Connection::open(&main_path).map_err(|e| format!("Failed to open database: {}", e))?;
+
+   let mut attached = Vec::new();
+   let mut result = Ok(());
+   for attachment in &attachments {
+      let sql = format!("ATTACH DATABASE ? AS {}", attachment.alias);
+      if let Err(e) = conn.execute(&sql, [&attachment.path]) {
+         result = Err(format!(
+            "Failed to attach {} as {}: {}",
+            attachment.path, attachment.alias, e
+         ));
+         break;
+      }
+      attached.push(attachment.alias.as_str());
+   }
+
+   let result = result.and_then(|_| execute_query(&conn, &query, &[]));
+
+   for alias in attached {
+      let _ = conn.execute(&format!("DETACH DATABASE {}", alias), []);
+   }
+
+   result
+}
+
+/// Run EXPLAIN QUERY PLAN for a query and return the plan as a tree
+pub async fn explain_sqlite(path: String, query: String) -> Result<Vec<QueryPlanStep>, String> {
+   let conn = open_read_only(&path)?;
+
+   let mut stmt = conn
+      .prepare(&format!("EXPLAIN QUERY PLAN {}", query))
+      .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+   let plan_iter = stmt
+      .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(3)?)))
+      .map_err(|e| format!("Failed to explain query: {}", e))?;
+
+   let mut rows = Vec::new();
+   for step in plan_iter {
+      match step {
+         Ok(step) => rows.push(step),
+         Err(e) => return Err(format!("Error reading query plan: {}", e)),
+      }
+   }
+
+   Ok(build_query_plan_tree(rows))
+}
+
 /// Query a table with structured filters, pagination, and sorting (parameterized, safe from
 /// injection)
 pub async fn query_sqlite_filtered(
@@ -599,6 +789,17 @@ pub async fn get_sqlite_foreign_keys(
 #[cfg(test)]
 mod tests {
    use super::*;
+   use std::path::{Path, PathBuf};
+
+   /// Create the database `name` in `dir` with `schema` applied, returning its path
+   fn create_database(dir: &Path, name: &str, schema: &str) -> PathBuf {
+      let path = dir.join(name);
+      Connection::open(&path)
+         .unwrap()
+         .execute_batch(schema)
+         .unwrap();
+      path
+   }
 
    #[test]
    fn test_escape_identifier_simple() {
@@ -777,24 +978,14 @@ mod tests {
 
    #[tokio::test]
    async fn test_get_sqlite_tables_includes_views() {
-      let path = std::env::temp_dir().join(format!(
-         "athas-sqlite-views-{}.sqlite",
-         std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
-      ));
-      let conn = Connection::open(&path).unwrap();
-      conn
-         .execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)", [])
-         .unwrap();
-      conn
-         .execute("CREATE VIEW active_users AS SELECT * FROM users", [])
-         .unwrap();
-      conn
-         .execute("CREATE INDEX users_name_idx ON users (name)", [])
-         .unwrap();
-      drop(conn);
+      let dir = tempfile::tempdir().unwrap();
+      let path = create_database(
+         dir.path(),
+         "views.sqlite",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);
+          CREATE VIEW active_users AS SELECT * FROM users;
+          CREATE INDEX users_name_idx ON users (name);",
+      );
 
       let objects = get_sqlite_tables(path.to_string_lossy().to_string())
          .await
@@ -813,8 +1004,6 @@ mod tests {
       assert!(objects.iter().any(|object| object.name == "users_name_idx"
          && object.kind == "index"
          && object.table_name.as_deref() == Some("users")));
-
-      let _ = std::fs::remove_file(path);
    }
 
    #[test]
@@ -890,4 +1079,149 @@ mod tests {
          .unwrap();
       assert_eq!(count, 1);
    }
+
+   #[test]
+   fn test_build_query_plan_tree_nests_children() {
+      let rows = vec![
+         (2, 0, "CO-ROUTINE sub".to_string()),
+         (5, 2, "SCAN users".to_string()),
+         (12, 0, "SCAN sub".to_string()),
+      ];
+
+      let tree = build_query_plan_tree(rows);
+      assert_eq!(tree.len(), 2);
+      assert_eq!(tree[0].detail, "CO-ROUTINE sub");
+      assert_eq!(tree[0].children.len(), 1);
+      assert_eq!(tree[0].children[0].detail, "SCAN users");
+      assert_eq!(tree[1].detail, "SCAN sub");
+      assert!(tree[1].children.is_empty());
+   }
+
+   #[tokio::test]
+   async fn test_explain_sqlite_reports_index_usage() {
+      let dir = tempfile::tempdir().unwrap();
+      let path = create_database(
+         dir.path(),
+         "explain.sqlite",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);
+          CREATE INDEX users_name_idx ON users (name);",
+      );
+
+      let plan = explain_sqlite(
+         path.to_string_lossy().to_string(),
+         "SELECT * FROM users WHERE name = 'Alice'".to_string(),
+      )
+      .await
+      .unwrap();
+      assert!(
+         plan
+            .iter()
+            .any(|step| step.detail.contains("users_name_idx"))
+      );
+   }
+
+   #[test]
+   fn test_validate_attachment_alias() {
+      assert!(validate_attachment_alias("other_db").is_ok());
+      assert!(validate_attachment_alias("_db2").is_ok());
+      assert!(validate_attachment_alias("").is_err());
+      assert!(validate_attachment_alias("2db").is_err());
+      assert!(validate_attachment_alias("db; DROP TABLE users").is_err());
+      assert!(validate_attachment_alias("main").is_err());
+      assert!(validate_attachment_alias("TEMP").is_err());
+   }
+
+   #[tokio::test]
+   async fn test_query_sqlite_multi_joins_across_databases() {
+      let dir = tempfile::tempdir().unwrap();
+      let main_path = create_database(
+         dir.path(),
+         "main.sqlite",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);
+          INSERT INTO users VALUES (1, 'Alice');",
+      );
+      let other_path = create_database(
+         dir.path(),
+         "other.sqlite",
+         "CREATE TABLE orders (user_id INTEGER, total INTEGER);
+          INSERT INTO orders VALUES (1, 42);",
+      );
+
+      let result = query_sqlite_multi(
+         main_path.to_string_lossy().to_string(),
+         vec![SqliteAttachment {
+            alias: "other".to_string(),
+            path: other_path.to_string_lossy().to_string(),
+         }],
+         "SELECT users.name, orders.total FROM users JOIN other.orders ON orders.user_id = \
+          users.id"
+            .to_string(),
+      )
+      .await
+      .unwrap();
+      assert_eq!(result.rows.len(), 1);
+      assert_eq!(result.rows[0][0], serde_json::json!("Alice"));
+      assert_eq!(result.rows[0][1], serde_json::json!(42));
+
+      let invalid = query_sqlite_multi(
+         main_path.to_string_lossy().to_string(),
+         vec![SqliteAttachment {
+            alias: "bad alias".to_string(),
+            path: other_path.to_string_lossy().to_string(),
+         }],
+         "SELECT 1".to_string(),
+      )
+      .await;
+      assert!(invalid.is_err());
+   }
+
+   #[tokio::test]
+   async fn test_execute_sqlite_batch_rolls_back_on_failure() {
+      let dir = tempfile::tempdir().unwrap();
+      let path = create_database(
+         dir.path(),
+         "batch.sqlite",
+         "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);",
+      );
+      let path_str = path.to_string_lossy().to_string();
+
+      let insert = |id: i64, name: serde_json::Value| SqliteBatchStatement {
+         sql: "INSERT INTO users (id, name) VALUES (?, ?)".to_string(),
+         params: vec![serde_json::json!(id), name],
+      };
+
+      let failed = execute_sqlite_batch(
+         path_str.clone(),
+         vec![
+            insert(1, serde_json::json!("Alice")),
+            insert(2, serde_json::Value::Null),
+         ],
+      )
+      .await
+      .unwrap();
+      assert!(!failed.committed);
+      assert_eq!(failed.failed_index, Some(1));
+      assert!(failed.error.is_some());
+
+      let count = |path: &Path| -> i64 {
+         Connection::open(path)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))
+            .unwrap()
+      };
+      assert_eq!(count(&path), 0);
+
+      let committed = execute_sqlite_batch(
+         path_str,
+         vec![
+            insert(1, serde_json::json!("Alice")),
+            insert(2, serde_json::json!("Bob")),
+         ],
+      )
+      .await
+      .unwrap();
+      assert!(committed.committed);
+      assert_eq!(committed.rows_affected, 2);
+      assert_eq!(count(&path), 2);
+   }
 }
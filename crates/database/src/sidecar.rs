@@ -149,6 +149,7 @@ async fn run_request(request: SidecarRequest) -> Result<Value, String> {
       command
          if command.starts_with("get_sqlite_")
             || command.starts_with("query_sqlite")
+            || command.starts_with("explain_sqlite")
             || command.starts_with("execute_sqlite")
             || command.contains("_sqlite_row") =>
       {
@@ -321,6 +322,17 @@ async fn run_sqlite(command: &str, payload: Value) -> Result<Value, String> {
       "query_sqlite" => {
          serde_json::to_value(query_sqlite(path, read_field(&payload, &["query"])?).await?)
       }
+      "query_sqlite_multi" => serde_json::to_value(
+         query_sqlite_multi(
+            path,
+            read_field(&payload, &["attachments"])?,
+            read_field(&payload, &["query"])?,
+         )
+         .await?,
+      ),
+      "explain_sqlite" => {
+         serde_json::to_value(explain_sqlite(path, read_field(&payload, &["query"])?).await?)
+      }
       "query_sqlite_filtered" => {
          let params: crate::providers::sqlite::FilteredQueryParams =
             read_field(&payload, &["params"])?;
@@ -329,6 +341,9 @@ async fn run_sqlite(command: &str, payload: Value) -> Result<Value, String> {
       "execute_sqlite" => {
          serde_json::to_value(execute_sqlite(path, read_field(&payload, &["statement"])?).await?)
       }
+      "execute_sqlite_batch" => serde_json::to_value(
+         execute_sqlite_batch(path, read_field(&payload, &["statements"])?).await?,
+      ),
       "insert_sqlite_row" => serde_json::to_value(
          insert_sqlite_row(
             path,
@@ -0,0 +1,38 @@
+use sqlformat::{FormatOptions, Indent, QueryParams};
+
+/// Pretty-print a SQL query for the query editor: uppercase keywords, one clause per line with
+/// two-space indentation, and a blank line between statements. The query is only rewritten, never
+/// parsed for validity, so anything the formatter does not recognize is kept as written.
+pub fn format_sql(query: &str) -> String {
+   let options = FormatOptions {
+      indent: Indent::Spaces(2),
+      uppercase: Some(true),
+      lines_between_queries: 2,
+      ..FormatOptions::default()
+   };
+   sqlformat::format(query, &QueryParams::None, &options)
+      .trim()
+      .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn puts_clauses_on_their_own_lines() {
+      let formatted = format_sql("select id, name from users where active = 1 order by name");
+
+      assert!(formatted.starts_with("SELECT\n  id,\n  name\nFROM\n  users\nWHERE"));
+      assert!(formatted.contains("ORDER BY"));
+   }
+
+   #[test]
+   fn keeps_string_literals_and_separates_statements() {
+      let formatted = format_sql("select 'select' as word; delete from logs");
+
+      assert!(formatted.contains("'select'"));
+      assert!(formatted.contains(";\n\nDELETE FROM"));
+      assert_eq!(format_sql("   "), "");
+   }
+}
@@ -65,6 +65,28 @@ impl FffSearch {
       Ok(())
    }
 
+   /// Drop the indexes of `base_paths` and scan them again. The watcher keeps indexes current,
+   /// so this is only needed when it missed changes, such as on network file systems.
+   pub fn refresh_workspaces<'a, I>(&self, base_paths: I) -> Result<()>
+   where
+      I: IntoIterator<Item = &'a Path>,
+   {
+      let base_paths = deduplicate_paths(base_paths);
+      {
+         let mut workspaces = self
+            .workspaces
+            .write()
+            .map_err(|error| anyhow::anyhow!("writing workspace indexes: {error}"))?;
+         for base_path in &base_paths {
+            workspaces.remove(base_path);
+         }
+      }
+      for base_path in base_paths {
+         self.ensure_workspace(&base_path)?;
+      }
+      Ok(())
+   }
+
    pub fn search<'a, I>(
       &self,
       base_paths: I,
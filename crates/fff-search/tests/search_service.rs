@@ -55,6 +55,30 @@ fn indexes_and_searches_multiple_workspace_roots() {
    assert_eq!(search.indexed_workspace_count().unwrap(), 2);
 }
 
+#[test]
+fn refresh_picks_up_changes_missed_without_a_watcher() {
+   let _guard = lock_tests();
+   let temp_dir = TempDir::new().unwrap();
+   let root = temp_dir.path().join("root");
+   fs::create_dir_all(&root).unwrap();
+   fs::write(root.join("existing.rs"), "fn main() {}").unwrap();
+
+   let search = create_search(false);
+   let roots = [root.as_path()];
+   search.ensure_workspaces(roots).unwrap();
+   assert!(search.wait_for_scan(roots, Duration::from_secs(5)).unwrap());
+
+   fs::write(root.join("created-later.rs"), "fn later() {}").unwrap();
+   let files = search.list_files(roots).unwrap();
+   assert!(!files.iter().any(|file| file.name == "created-later.rs"));
+
+   search.refresh_workspaces(roots).unwrap();
+   assert!(search.wait_for_scan(roots, Duration::from_secs(5)).unwrap());
+   let files = search.list_files(roots).unwrap();
+   assert!(files.iter().any(|file| file.name == "created-later.rs"));
+   assert_eq!(search.indexed_workspace_count().unwrap(), 1);
+}
+
 #[test]
 fn paginates_content_search_across_workspace_roots() {
    let _guard = lock_tests();
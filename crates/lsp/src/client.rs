@@ -1,4 +1,8 @@
-use crate::runtime::AthasAppHandle as AppHandle;
+use crate::{
+   runtime::AthasAppHandle as AppHandle,
+   server_logs::ServerLogWriter,
+   types::{LspServerMessage, LspStderrLine},
+};
 use anyhow::{Context, Result, bail};
 use athas_runtime::{NodeRuntime, process::configure_background_command};
 use crossbeam_channel::{Sender, bounded};
@@ -22,8 +26,16 @@ use tauri::{Emitter, Manager};
 use tokio::sync::oneshot;
 
 type PendingRequests = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value>>>>>;
+/// Requests awaiting a response, by method, so they can be cancelled by operation
+type InFlightRequests = Arc<Mutex<HashMap<&'static str, Vec<InFlightRequest>>>>;
 pub type LspServerEnv = HashMap<String, String>;
 
+struct InFlightRequest {
+   id: u64,
+   /// `textDocument.uri` of the request, so cancelling on one document leaves others alone
+   uri: Option<String>,
+}
+
 fn find_node_modules_dir(server_path: &Path) -> Option<PathBuf> {
    server_path
       .ancestors()
@@ -96,12 +108,32 @@ fn is_node_script_server(server_path: &Path) -> bool {
    has_javascript_extension(server_path) || has_node_shebang(server_path)
 }
 
+/// The part of `settings` named by a dotted configuration section such as `python.analysis`
+fn configuration_section(settings: &Value, section: &str) -> Option<Value> {
+   section
+      .split('.')
+      .try_fold(settings, |value, key| value.get(key))
+      .cloned()
+}
+
+fn message_type_label(message_type: MessageType) -> &'static str {
+   match message_type {
+      MessageType::ERROR => "error",
+      MessageType::WARNING => "warning",
+      MessageType::INFO => "info",
+      _ => "log",
+   }
+}
+
 #[derive(Clone)]
 pub struct LspClient {
    request_counter: Arc<AtomicU64>,
    stdin_tx: Sender<String>,
    pending_requests: PendingRequests,
+   in_flight_requests: InFlightRequests,
    capabilities: Arc<Mutex<Option<ServerCapabilities>>>,
+   /// Initialization options, also used to answer `workspace/configuration` requests
+   settings: Arc<Mutex<Option<Value>>>,
    is_running: Arc<AtomicBool>,
 }
 
@@ -113,6 +145,7 @@ impl LspClient {
       app_handle: Option<AppHandle>,
       workspace_path: Option<PathBuf>,
       mut env_overrides: LspServerEnv,
+      stderr_log: ServerLogWriter,
    ) -> Result<(Self, Child)> {
       // Check if this is a JavaScript-based language server. Some npm package
       // bins are extensionless shebang scripts, which cannot be spawned
@@ -192,6 +225,8 @@ impl LspClient {
       let pending_requests_clone = Arc::clone(&pending_requests);
       let app_handle_clone = app_handle.clone();
       let server_request_stdin_tx = stdin_tx.clone();
+      let settings = Arc::new(Mutex::new(None));
+      let server_request_settings = Arc::clone(&settings);
       let is_running = Arc::new(AtomicBool::new(true));
       let is_running_clone = Arc::clone(&is_running);
 
@@ -206,6 +241,7 @@ impl LspClient {
          };
 
       // Stderr reader thread
+      let stderr_app_handle = app_handle.clone();
       thread::spawn(move || {
          let mut stderr = BufReader::new(stderr);
          let mut line = String::new();
@@ -214,8 +250,20 @@ impl LspClient {
             match stderr.read_line(&mut line) {
                Ok(0) => break, // EOF
                Ok(_) => {
-                  if !line.trim().is_empty() {
-                     log::error!("LSP stderr: {}", line.trim());
+                  let trimmed = line.trim();
+                  if !trimmed.is_empty() {
+                     log::error!("LSP stderr ({}): {}", stderr_log.server_name(), trimmed);
+                     stderr_log.push(trimmed.to_string());
+
+                     if let Some(app) = stderr_app_handle.as_ref() {
+                        let payload = LspStderrLine {
+                           server_name: stderr_log.server_name().to_string(),
+                           line: trimmed.to_string(),
+                        };
+                        if let Err(e) = app.emit("lsp://stderr", &payload) {
+                           log::error!("Failed to emit LSP stderr: {}", e);
+                        }
+                     }
                   }
                }
                Err(e) => {
@@ -313,7 +361,11 @@ impl LspClient {
                }
 
                if message.get("id").is_some() && message.get("method").is_some() {
-                  Self::handle_server_request(message, &server_request_stdin_tx);
+                  Self::handle_server_request(
+                     message,
+                     &server_request_stdin_tx,
+                     &server_request_settings,
+                  );
                } else if message.get("id").is_some() {
                   Self::handle_response(message, &pending_requests_clone);
                } else if message.get("method").is_some() {
@@ -327,7 +379,9 @@ impl LspClient {
          request_counter: Arc::new(AtomicU64::new(1)),
          stdin_tx,
          pending_requests,
+         in_flight_requests: Arc::new(Mutex::new(HashMap::new())),
          capabilities: Arc::new(Mutex::new(None)),
+         settings,
          is_running,
       };
 
@@ -343,6 +397,7 @@ impl LspClient {
       initialization_options: Option<Value>,
    ) -> Result<()> {
       log::info!("Initializing LSP server with root_uri: {}", root_uri);
+      *self.settings.lock().unwrap() = initialization_options.clone();
 
       // Build client capabilities with text document sync and diagnostics support
       let text_document_capabilities = TextDocumentClientCapabilities {
@@ -442,6 +497,11 @@ impl LspClient {
             hierarchical_document_symbol_support: Some(true),
             tag_support: None,
          }),
+         folding_range: Some(FoldingRangeClientCapabilities {
+            dynamic_registration: Some(true),
+            line_folding_only: Some(true),
+            ..Default::default()
+         }),
          references: Some(DynamicRegistrationClientCapabilities {
             dynamic_registration: Some(true),
          }),
@@ -470,6 +530,10 @@ impl LspClient {
             code_description_support: Some(true),
             data_support: Some(true),
          }),
+         diagnostic: Some(DiagnosticClientCapabilities {
+            dynamic_registration: Some(false),
+            related_document_support: Some(false),
+         }),
          ..Default::default()
       };
 
@@ -486,6 +550,15 @@ impl LspClient {
                   dynamic_registration: Some(true),
                }),
                workspace_folders: Some(true),
+               workspace_edit: Some(WorkspaceEditClientCapabilities {
+                  document_changes: Some(true),
+                  resource_operations: Some(vec![
+                     ResourceOperationKind::Create,
+                     ResourceOperationKind::Rename,
+                     ResourceOperationKind::Delete,
+                  ]),
+                  ..Default::default()
+               }),
                symbol: Some(WorkspaceSymbolClientCapabilities {
                   dynamic_registration: Some(false),
                   ..Default::default()
@@ -559,19 +632,35 @@ impl LspClient {
       }
    }
 
-   fn handle_server_request(request: Value, stdin_tx: &Sender<String>) {
+   fn handle_server_request(
+      request: Value,
+      stdin_tx: &Sender<String>,
+      settings: &Arc<Mutex<Option<Value>>>,
+   ) {
       let id = request.get("id").cloned().unwrap_or(Value::Null);
       let method = request.get("method").and_then(|method| method.as_str());
 
       let response = match method {
          Some("workspace/configuration") => {
-            let item_count = request
+            let settings = settings.lock().unwrap().clone();
+            let items = request
                .get("params")
                .and_then(|params| params.get("items"))
                .and_then(|items| items.as_array())
-               .map(|items| items.len())
-               .unwrap_or(0);
-            Self::send_server_response(stdin_tx, id, Value::Array(vec![Value::Null; item_count]))
+               .cloned()
+               .unwrap_or_default();
+            let results = items
+               .iter()
+               .map(|item| {
+                  item
+                     .get("section")
+                     .and_then(|section| section.as_str())
+                     .zip(settings.as_ref())
+                     .and_then(|(section, settings)| configuration_section(settings, section))
+                     .unwrap_or(Value::Null)
+               })
+               .collect();
+            Self::send_server_response(stdin_tx, id, Value::Array(results))
          }
          Some("workspace/workspaceFolders") => Self::send_server_response(stdin_tx, id, json!([])),
          Some("client/registerCapability" | "client/unregisterCapability") => {
@@ -646,25 +735,42 @@ impl LspClient {
                log::warn!("publishDiagnostics notification has no params");
             }
          }
-         Some("window/logMessage") => {
-            if let Some(params) = params {
-               match serde_json::from_value::<LogMessageParams>(params.clone()) {
-                  Ok(log_message) => match log_message.typ {
-                     MessageType::ERROR => log::error!("LSP logMessage: {}", log_message.message),
-                     MessageType::WARNING => log::warn!("LSP logMessage: {}", log_message.message),
-                     MessageType::INFO => log::info!("LSP logMessage: {}", log_message.message),
-                     MessageType::LOG => log::debug!("LSP logMessage: {}", log_message.message),
-                     _ => log::debug!("LSP logMessage: {}", log_message.message),
-                  },
-                  Err(e) => {
-                     log::warn!(
-                        "Failed to parse window/logMessage notification params: {}",
-                        e
-                     )
+         Some(method_name @ ("window/logMessage" | "window/showMessage")) => {
+            let Some(params) = params else {
+               log::warn!("{} notification has no params", method_name);
+               return;
+            };
+
+            // showMessage and logMessage share the same `{ type, message }` shape.
+            match serde_json::from_value::<LogMessageParams>(params.clone()) {
+               Ok(log_message) => {
+                  match log_message.typ {
+                     MessageType::ERROR => {
+                        log::error!("LSP {}: {}", method_name, log_message.message)
+                     }
+                     MessageType::WARNING => {
+                        log::warn!("LSP {}: {}", method_name, log_message.message)
+                     }
+                     MessageType::INFO => {
+                        log::info!("LSP {}: {}", method_name, log_message.message)
+                     }
+                     _ => log::debug!("LSP {}: {}", method_name, log_message.message),
+                  }
+
+                  if let Some(app) = app_handle {
+                     let payload = LspServerMessage {
+                        method: method_name.to_string(),
+                        message_type: message_type_label(log_message.typ).to_string(),
+                        message: log_message.message,
+                     };
+                     if let Err(e) = app.emit("lsp://message", &payload) {
+                        log::error!("Failed to emit LSP message: {}", e);
+                     }
                   }
                }
-            } else {
-               log::warn!("window/logMessage notification has no params");
+               Err(e) => {
+                  log::warn!("Failed to parse {} notification params: {}", method_name, e)
+               }
             }
          }
          Some(method_name) => {
@@ -686,10 +792,22 @@ impl LspClient {
          bail!("LSP server is not running");
       }
 
+      let params = serde_json::to_value(params).context("Failed to serialize request")?;
+      let uri = params
+         .pointer("/textDocument/uri")
+         .and_then(Value::as_str)
+         .map(str::to_string);
       let id = self.request_counter.fetch_add(1, Ordering::SeqCst);
       let (tx, rx) = oneshot::channel();
 
       self.pending_requests.lock().unwrap().insert(id, tx);
+      self
+         .in_flight_requests
+         .lock()
+         .unwrap()
+         .entry(R::METHOD)
+         .or_default()
+         .push(InFlightRequest { id, uri });
 
       let request = json!({
           "jsonrpc": "2.0",
@@ -706,10 +824,43 @@ impl LspClient {
          request
       );
 
-      self.stdin_tx.send(msg).context("Failed to send request")?;
+      let response = match self.stdin_tx.send(msg) {
+         Ok(()) => rx.await.context("Request cancelled"),
+         Err(error) => Err(error).context("Failed to send request"),
+      };
+      if let Some(requests) = self.in_flight_requests.lock().unwrap().get_mut(R::METHOD) {
+         requests.retain(|request| request.id != id);
+      }
+
+      serde_json::from_value(response??).context("Failed to deserialize response")
+   }
 
-      let response = rx.await.context("Request cancelled")??;
-      serde_json::from_value(response).context("Failed to deserialize response")
+   /// Cancel the in-flight requests for `method`, such as `textDocument/completion`, on the
+   /// document `uri`. The callers awaiting them fail with "Request cancelled" and the server is
+   /// told to stop working on them. Returns how many requests were cancelled.
+   pub fn cancel_requests(&self, method: &str, uri: &str) -> Result<usize> {
+      let ids: Vec<u64> = {
+         let mut in_flight = self.in_flight_requests.lock().unwrap();
+         let Some(requests) = in_flight.get_mut(method) else {
+            return Ok(0);
+         };
+         let (cancelled, kept) = std::mem::take(requests)
+            .into_iter()
+            .partition(|request| request.uri.as_deref() == Some(uri));
+         *requests = kept;
+         cancelled.into_iter().map(|request| request.id).collect()
+      };
+
+      for &id in &ids {
+         self.pending_requests.lock().unwrap().remove(&id);
+         self.notify::<notification::Cancel>(CancelParams {
+            id: NumberOrString::Number(id as i32),
+         })?;
+      }
+      if !ids.is_empty() {
+         log::debug!("Cancelled {} LSP request(s): {}", ids.len(), method);
+      }
+      Ok(ids.len())
    }
 
    pub fn notify<N>(&self, params: N::Params) -> Result<()>
@@ -752,6 +903,11 @@ impl LspClient {
          "Sending completion request to LSP server: {:?}",
          params.text_document_position.position
       );
+      // A new position makes earlier completion requests on the document stale
+      self.cancel_requests(
+         <request::Completion as request::Request>::METHOD,
+         params.text_document_position.text_document.uri.as_str(),
+      )?;
       let result = self.request::<request::Completion>(params).await;
       match &result {
          Ok(Some(response)) => {
@@ -893,6 +1049,98 @@ impl LspClient {
          .unwrap_or_default()
    }
 
+   /// Capabilities the server advertised during initialization, once it has initialized.
+   pub fn server_capabilities(&self) -> Option<ServerCapabilities> {
+      self.capabilities.lock().unwrap().clone()
+   }
+
+   /// Names of the features the server advertised during initialization.
+   pub fn capability_summary(&self) -> Vec<String> {
+      let capabilities = self.capabilities.lock().unwrap();
+      let Some(caps) = capabilities.as_ref() else {
+         return Vec::new();
+      };
+
+      fn enabled<T>(provider: &Option<OneOf<bool, T>>) -> bool {
+         !matches!(provider, None | Some(OneOf::Left(false)))
+      }
+
+      let features = [
+         ("completion", caps.completion_provider.is_some()),
+         (
+            "hover",
+            !matches!(
+               caps.hover_provider,
+               None | Some(HoverProviderCapability::Simple(false))
+            ),
+         ),
+         ("signatureHelp", caps.signature_help_provider.is_some()),
+         ("definition", enabled(&caps.definition_provider)),
+         ("typeDefinition", caps.type_definition_provider.is_some()),
+         ("implementation", caps.implementation_provider.is_some()),
+         ("references", enabled(&caps.references_provider)),
+         ("documentSymbol", enabled(&caps.document_symbol_provider)),
+         ("workspaceSymbol", enabled(&caps.workspace_symbol_provider)),
+         ("codeAction", caps.code_action_provider.is_some()),
+         ("codeLens", caps.code_lens_provider.is_some()),
+         ("formatting", enabled(&caps.document_formatting_provider)),
+         (
+            "rangeFormatting",
+            enabled(&caps.document_range_formatting_provider),
+         ),
+         ("rename", enabled(&caps.rename_provider)),
+         ("semanticTokens", caps.semantic_tokens_provider.is_some()),
+         ("inlayHint", enabled(&caps.inlay_hint_provider)),
+         ("diagnostic", caps.diagnostic_provider.is_some()),
+      ];
+
+      features
+         .into_iter()
+         .filter(|(_, supported)| *supported)
+         .map(|(name, _)| name.to_string())
+         .collect()
+   }
+
+   pub async fn text_document_document_highlight(
+      &self,
+      params: DocumentHighlightParams,
+   ) -> Result<Option<Vec<DocumentHighlight>>> {
+      self
+         .request::<request::DocumentHighlightRequest>(params)
+         .await
+   }
+
+   pub fn supports_document_highlight(&self) -> bool {
+      !matches!(
+         self
+            .capabilities
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|capabilities| capabilities.document_highlight_provider.as_ref()),
+         None | Some(OneOf::Left(false))
+      )
+   }
+
+   pub async fn text_document_folding_range(
+      &self,
+      params: FoldingRangeParams,
+   ) -> Result<Option<Vec<FoldingRange>>> {
+      self.request::<request::FoldingRangeRequest>(params).await
+   }
+
+   pub fn supports_folding_range(&self) -> bool {
+      !matches!(
+         self
+            .capabilities
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|capabilities| capabilities.folding_range_provider.as_ref()),
+         None | Some(FoldingRangeProviderCapability::Simple(false))
+      )
+   }
+
    pub async fn text_document_references(
       &self,
       params: ReferenceParams,
@@ -911,6 +1159,41 @@ impl LspClient {
       self.request::<request::PrepareRenameRequest>(params).await
    }
 
+   pub async fn text_document_diagnostic(
+      &self,
+      params: DocumentDiagnosticParams,
+   ) -> Result<DocumentDiagnosticReportResult> {
+      self
+         .request::<request::DocumentDiagnosticRequest>(params)
+         .await
+   }
+
+   /// Whether the server supports pull diagnostics (`diagnosticProvider`).
+   pub fn supports_pull_diagnostics(&self) -> bool {
+      self
+         .capabilities
+         .lock()
+         .unwrap()
+         .as_ref()
+         .is_some_and(|capabilities| capabilities.diagnostic_provider.is_some())
+   }
+
+   /// Whether the server advertises `renameProvider.prepareProvider`.
+   pub fn supports_prepare_rename(&self) -> bool {
+      matches!(
+         self
+            .capabilities
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|capabilities| capabilities.rename_provider.as_ref()),
+         Some(OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            ..
+         }))
+      )
+   }
+
    pub async fn workspace_execute_command(
       &self,
       params: ExecuteCommandParams,
@@ -933,6 +1216,12 @@ impl LspClient {
    pub fn text_document_did_close(&self, params: DidCloseTextDocumentParams) -> Result<()> {
       self.notify::<notification::DidCloseTextDocument>(params)
    }
+
+   /// Ask the server to shut down and exit, as the protocol expects before killing it
+   pub async fn shutdown(&self) -> Result<()> {
+      self.request::<request::Shutdown>(()).await?;
+      self.notify::<notification::Exit>(())
+   }
 }
 
 #[cfg(test)]
@@ -971,6 +1260,21 @@ mod tests {
       assert_eq!(env::split_paths(OsStr::new(path)).next().unwrap(), bin_dir);
    }
 
+   #[test]
+   fn answers_configuration_sections_from_settings() {
+      let settings = json!({ "python": { "pythonPath": "/repo/.venv/bin/python" } });
+
+      assert_eq!(
+         configuration_section(&settings, "python"),
+         Some(json!({ "pythonPath": "/repo/.venv/bin/python" }))
+      );
+      assert_eq!(
+         configuration_section(&settings, "python.pythonPath"),
+         Some(json!("/repo/.venv/bin/python"))
+      );
+      assert_eq!(configuration_section(&settings, "python.analysis"), None);
+   }
+
    #[test]
    fn treats_extensionless_node_shebang_as_node_script_server() {
       let temp = tempfile::tempdir().unwrap();
@@ -1006,4 +1310,60 @@ mod tests {
       assert_eq!(workspace_cwd(Some(&file_path)), None);
       assert_eq!(workspace_cwd(None), None);
    }
+
+   #[cfg(unix)]
+   #[tokio::test]
+   async fn cancels_requests_only_on_the_given_document() {
+      let (client, mut child) = LspClient::start(
+         PathBuf::from("sleep"),
+         vec!["60".to_string()],
+         Url::parse("file:///").unwrap(),
+         None,
+         None,
+         LspServerEnv::new(),
+         crate::server_logs::ServerLogs::default().writer("idle"),
+      )
+      .await
+      .unwrap();
+      let hover = |uri: &str| HoverParams {
+         text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+               uri: Url::parse(uri).unwrap(),
+            },
+            position: Position::default(),
+         },
+         work_done_progress_params: Default::default(),
+      };
+
+      let stale = tokio::spawn({
+         let client = client.clone();
+         let params = hover("file:///repo/a.rs");
+         async move { client.request::<request::HoverRequest>(params).await }
+      });
+      let other = tokio::spawn({
+         let client = client.clone();
+         let params = hover("file:///repo/b.rs");
+         async move { client.request::<request::HoverRequest>(params).await }
+      });
+      while client.pending_requests.lock().unwrap().len() < 2 {
+         tokio::task::yield_now().await;
+      }
+
+      let method = <request::HoverRequest as request::Request>::METHOD;
+      assert_eq!(
+         client.cancel_requests(method, "file:///repo/a.rs").unwrap(),
+         1
+      );
+      let error = stale.await.unwrap().unwrap_err();
+      assert!(format!("{:#}", error).contains("Request cancelled"));
+      assert!(!other.is_finished());
+      assert_eq!(
+         client.cancel_requests(method, "file:///repo/a.rs").unwrap(),
+         0
+      );
+
+      other.abort();
+      let _ = child.kill();
+      let _ = child.wait();
+   }
 }
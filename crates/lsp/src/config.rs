@@ -45,13 +45,15 @@ impl LspRegistry {
 
    pub fn find_server_for_file(&self, file_path: &Path) -> Option<&LspServerConfig> {
       // Get file extension
-      let extension = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+      let extension = file_path.extension().and_then(|e| e.to_str())?;
 
-      // Find server that handles this extension
-      self
-         .servers
-         .iter()
-         .find(|s| s.file_extensions.contains(&extension.to_string()))
+      // Find server that handles this extension. Manifests list extensions with a leading dot
+      // (".toml"), so compare without it and ignore case (".YML", ".Md").
+      self.servers.iter().find(|s| {
+         s.file_extensions
+            .iter()
+            .any(|ext| ext.trim_start_matches('.').eq_ignore_ascii_case(extension))
+      })
    }
 
    pub fn find_server_for_workspace(&self, workspace: &Path) -> Option<&LspServerConfig> {
@@ -114,3 +116,43 @@ impl LspRegistry {
       true // Default to yes for broader compatibility
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn server(name: &str, language_id: &str, file_extensions: &[&str]) -> LspServerConfig {
+      LspServerConfig {
+         name: name.to_string(),
+         language_id: language_id.to_string(),
+         command: PathBuf::from(name),
+         args: Vec::new(),
+         file_extensions: file_extensions.iter().map(|ext| ext.to_string()).collect(),
+      }
+   }
+
+   #[test]
+   fn finds_server_by_dotted_or_bare_extension() {
+      let registry = LspRegistry {
+         servers: vec![
+            server("marksman", "markdown", &[".md", ".markdown", ".mdx"]),
+            server("yaml-language-server", "yaml", &[".yaml", ".yml"]),
+            server("taplo", "toml", &["toml"]),
+         ],
+      };
+      let name_for = |path: &str| {
+         registry
+            .find_server_for_file(Path::new(path))
+            .map(|server| server.name.as_str())
+      };
+
+      assert_eq!(name_for("/repo/README.md"), Some("marksman"));
+      assert_eq!(
+         name_for("/repo/.github/ci.YML"),
+         Some("yaml-language-server")
+      );
+      assert_eq!(name_for("/repo/Cargo.toml"), Some("taplo"));
+      assert_eq!(name_for("/repo/Makefile"), None);
+      assert_eq!(name_for("/repo/notes.txt"), None);
+   }
+}
@@ -3,9 +3,14 @@ pub mod config;
 pub mod manager;
 mod manager_state;
 mod manager_support;
+pub mod position;
 mod runtime;
+pub mod server_logs;
 pub mod types;
 pub mod utils;
+pub mod workspace_edit;
 
 pub use manager::LspManager;
-pub use types::{LspError, LspResult};
+pub use types::{
+   LspCapabilities, LspError, LspResult, LspServerMessage, LspServerStatus, LspStderrLine,
+};
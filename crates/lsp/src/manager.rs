@@ -1,9 +1,11 @@
 use super::{
    client::{LspClient, LspServerEnv},
    config::{LspRegistry, LspSettings},
-   manager_state::{LspInstance, WorkspaceClients},
+   manager_state::{LspInstance, LspLaunch, WorkspaceClients, WorkspaceRoots},
    manager_support,
    runtime::AthasAppHandle as AppHandle,
+   server_logs::ServerLogs,
+   types::{LspCapabilities, LspServerStatus},
    utils,
 };
 use anyhow::{Context, Result, bail};
@@ -11,6 +13,7 @@ use lsp_types::*;
 use std::{
    fs,
    path::{Path, PathBuf},
+   process::Child,
    time::Instant,
 };
 use tauri::Manager as TauriManager;
@@ -18,18 +21,22 @@ use tauri::Manager as TauriManager;
 pub struct LspManager {
    // Map (workspace path, language) to their LSP clients with reference counting
    workspace_clients: WorkspaceClients,
+   workspace_roots: WorkspaceRoots,
    registry: LspRegistry,
    app_handle: AppHandle,
    settings: LspSettings,
+   server_logs: ServerLogs,
 }
 
 impl LspManager {
    pub fn new(app_handle: AppHandle) -> Self {
       Self {
          workspace_clients: WorkspaceClients::new(),
+         workspace_roots: WorkspaceRoots::default(),
          registry: LspRegistry::new(),
          app_handle,
          settings: LspSettings::default(),
+         server_logs: ServerLogs::default(),
       }
    }
 
@@ -180,6 +187,7 @@ impl LspManager {
       initialization_options: Option<serde_json::Value>,
    ) -> Result<()> {
       log::info!("Starting LSP for workspace: {:?}", workspace_path);
+      self.workspace_roots.add(workspace_path.clone());
 
       // Use provided server path or find appropriate LSP server for workspace
       let (server_path, server_args, server_name) = if let Some(path) = server_path_override {
@@ -211,22 +219,14 @@ impl LspManager {
 
       Self::validate_server_path(&server_path)?;
 
-      let root_uri = Url::from_file_path(&workspace_path)
-         .map_err(|_| anyhow::anyhow!("Invalid workspace path"))?;
-
-      let (client, child) = LspClient::start(
+      let launch = LspLaunch {
          server_path,
          server_args,
-         root_uri.clone(),
-         Some(self.app_handle.clone()),
-         Some(workspace_path.clone()),
-         server_env_override.unwrap_or_default(),
-      )
-      .await?;
-
-      // Initialize the client
-      client
-         .initialize(root_uri, initialization_options.clone())
+         server_env: server_env_override.unwrap_or_default(),
+         initialization_options,
+      };
+      let (client, child) = self
+         .launch_server(&workspace_path, &server_name, &launch)
          .await?;
 
       // Check if LSP already running for this workspace+language
@@ -251,6 +251,7 @@ impl LspManager {
             server_name: server_name.clone(),
             ref_count: 0,
             files: Vec::new(),
+            launch,
          },
       );
 
@@ -258,13 +259,89 @@ impl LspManager {
       Ok(())
    }
 
+   /// Spawn and initialize a server for `workspace_path`
+   async fn launch_server(
+      &self,
+      workspace_path: &Path,
+      server_name: &str,
+      launch: &LspLaunch,
+   ) -> Result<(LspClient, Child)> {
+      let root_uri = Url::from_file_path(workspace_path)
+         .map_err(|_| anyhow::anyhow!("Invalid workspace path"))?;
+
+      // Finding the project interpreter may run `poetry`, so keep it off the async runtime
+      let initialization_options = {
+         let options = launch.initialization_options.clone();
+         let workspace_path = workspace_path.to_path_buf();
+         let server_name = server_name.to_string();
+         tokio::task::spawn_blocking(move || {
+            manager_support::with_project_python(options, &workspace_path, &server_name)
+         })
+         .await?
+      };
+
+      let (client, child) = LspClient::start(
+         launch.server_path.clone(),
+         launch.server_args.clone(),
+         root_uri.clone(),
+         Some(self.app_handle.clone()),
+         Some(workspace_path.to_path_buf()),
+         launch.server_env.clone(),
+         self.server_logs.writer(server_name),
+      )
+      .await?;
+
+      // Initialize the client
+      client.initialize(root_uri, initialization_options).await?;
+
+      Ok((client, child))
+   }
+
+   /// Register a workspace folder so files under it are served from that root.
+   pub fn add_workspace_root(&self, root: PathBuf) {
+      if self.workspace_roots.add(root.clone()) {
+         log::info!("Added LSP workspace root: {:?}", root);
+      }
+   }
+
+   /// Unregister a workspace folder and shut down the servers started for it.
+   pub fn remove_workspace_root(&self, root: &Path) -> Result<()> {
+      if self.workspace_roots.remove(root) {
+         log::info!("Removed LSP workspace root: {:?}", root);
+      }
+      self.shutdown_workspace(root)
+   }
+
+   pub fn workspace_roots(&self) -> Vec<PathBuf> {
+      self.workspace_roots.list()
+   }
+
+   /// Root a file's server should be started in: the given workspace when it contains the file,
+   /// else the innermost registered root, else the detected project root of the file.
+   pub fn resolve_workspace_root(
+      &self,
+      file_path: &Path,
+      workspace_path: Option<&Path>,
+   ) -> Option<PathBuf> {
+      if let Some(workspace_path) = workspace_path
+         && file_path.starts_with(workspace_path)
+      {
+         return Some(workspace_path.to_path_buf());
+      }
+
+      self
+         .workspace_roots
+         .root_for_file(file_path)
+         .or_else(|| manager_support::detect_project_root(file_path))
+   }
+
    /// Start LSP server for a specific file (buffer-scoped)
    /// This will start the LSP server if it's not already running for the workspace/language
    /// and increment the reference count
    pub async fn start_lsp_for_file(
       &self,
       file_path: PathBuf,
-      workspace_path: PathBuf,
+      workspace_path: Option<PathBuf>,
       server_path_override: Option<String>,
       server_args_override: Option<Vec<String>>,
       server_env_override: Option<LspServerEnv>,
@@ -272,6 +349,11 @@ impl LspManager {
    ) -> Result<()> {
       log::info!("Starting LSP for file: {:?}", file_path);
 
+      let workspace_path = self
+         .resolve_workspace_root(&file_path, workspace_path.as_deref())
+         .context("Could not determine a workspace root for file")?;
+      log::info!("Using workspace root {:?} for file", workspace_path);
+
       // Find appropriate LSP server for this file
       let (server_path, server_args, server_name) = if let Some(path) = server_path_override {
          log::info!("Using provided server path override: {}", path);
@@ -310,22 +392,14 @@ impl LspManager {
          return Ok(());
       }
 
-      let root_uri = Url::from_file_path(&workspace_path)
-         .map_err(|_| anyhow::anyhow!("Invalid workspace path"))?;
-
-      let (client, child) = LspClient::start(
+      let launch = LspLaunch {
          server_path,
          server_args,
-         root_uri.clone(),
-         Some(self.app_handle.clone()),
-         Some(workspace_path.clone()),
-         server_env_override.unwrap_or_default(),
-      )
-      .await?;
-
-      // Initialize the client
-      client
-         .initialize(root_uri, initialization_options.clone())
+         server_env: server_env_override.unwrap_or_default(),
+         initialization_options,
+      };
+      let (client, child) = self
+         .launch_server(&workspace_path, &server_name, &launch)
          .await?;
 
       // Store the new instance
@@ -338,6 +412,7 @@ impl LspManager {
             server_name: server_name.clone(),
             ref_count: 1,
             files: vec![file_path],
+            launch,
          },
       );
 
@@ -359,6 +434,36 @@ impl LspManager {
          .get_client_for_file(&PathBuf::from(file_path))
    }
 
+   /// Cancel the in-flight requests for `method` on `file_path`, e.g. a stale hover after the
+   /// pointer moved. Requests for other documents are left alone.
+   pub fn cancel_requests(&self, file_path: &str, method: &str) -> Result<usize> {
+      let Some(client) = self.get_client_for_file(file_path) else {
+         return Ok(0);
+      };
+      let uri = Url::from_file_path(file_path).map_err(|_| anyhow::anyhow!("Invalid file path"))?;
+      client.cancel_requests(method, uri.as_str())
+   }
+
+   /// Capabilities of the server handling `file_path`, or `None` when no initialized server
+   /// handles it.
+   pub fn get_capabilities(&self, file_path: &str) -> Option<LspCapabilities> {
+      let client = self.get_client_for_file(file_path)?;
+      Some(LspCapabilities {
+         server_capabilities: client.server_capabilities()?,
+         features: client.capability_summary(),
+      })
+   }
+
+   /// Per-workspace status of every language server the manager is tracking.
+   pub fn get_server_status(&self) -> Vec<LspServerStatus> {
+      self.workspace_clients.statuses()
+   }
+
+   /// Recent stderr output of a language server, oldest line first.
+   pub fn get_server_logs(&self, server_name: &str) -> Vec<String> {
+      self.server_logs.lines(server_name)
+   }
+
    pub fn get_semantic_token_type_names(&self, file_path: &str) -> Vec<String> {
       self
          .get_client_for_file(file_path)
@@ -670,6 +775,22 @@ impl LspManager {
       let clients = self
          .workspace_clients
          .get_clients_for_workspace(workspace_path);
+      Self::query_workspace_symbols(clients, query).await
+   }
+
+   /// Query `workspace/symbol` on every running server, regardless of workspace.
+   pub async fn get_all_workspace_symbols(
+      &self,
+      query: &str,
+   ) -> Result<Vec<WorkspaceSymbolResponse>> {
+      let clients = self.workspace_clients.all_clients();
+      Self::query_workspace_symbols(clients, query).await
+   }
+
+   async fn query_workspace_symbols(
+      clients: Vec<LspClient>,
+      query: &str,
+   ) -> Result<Vec<WorkspaceSymbolResponse>> {
       if clients.is_empty() {
          return Ok(Vec::new());
       }
@@ -705,7 +826,11 @@ impl LspManager {
       Ok(responses)
    }
 
-   pub async fn format_document(&self, file_path: &str) -> Result<Option<Vec<TextEdit>>> {
+   pub async fn format_document(
+      &self,
+      file_path: &str,
+      options: Option<FormattingOptions>,
+   ) -> Result<Option<Vec<TextEdit>>> {
       let Some(client) = self.get_client_for_file(file_path) else {
          return Ok(None);
       };
@@ -716,11 +841,7 @@ impl LspManager {
 
       let params = DocumentFormattingParams {
          text_document,
-         options: FormattingOptions {
-            tab_size: 3,
-            insert_spaces: true,
-            ..Default::default()
-         },
+         options: options.unwrap_or_else(manager_support::default_formatting_options),
          work_done_progress_params: Default::default(),
       };
 
@@ -743,6 +864,7 @@ impl LspManager {
       start_character: u32,
       end_line: u32,
       end_character: u32,
+      options: Option<FormattingOptions>,
    ) -> Result<Option<Vec<TextEdit>>> {
       let Some(client) = self.get_client_for_file(file_path) else {
          return Ok(None);
@@ -764,11 +886,7 @@ impl LspManager {
                character: end_character,
             },
          },
-         options: FormattingOptions {
-            tab_size: 3,
-            insert_spaces: true,
-            ..Default::default()
-         },
+         options: options.unwrap_or_else(manager_support::default_formatting_options),
          work_done_progress_params: Default::default(),
       };
 
@@ -784,6 +902,43 @@ impl LspManager {
       }
    }
 
+   /// Range-format each of `ranges` of `content` and merge the edits. The server is first sent
+   /// `content` as `version` of the document, so the ranges and the edits refer to the text being
+   /// formatted rather than whatever the editor synced last. Servers may widen a range to the
+   /// enclosing statement, so an edit overlapping one that was already taken is dropped.
+   pub async fn format_ranges(
+      &self,
+      file_path: &str,
+      content: String,
+      version: i32,
+      ranges: &[Range],
+      options: Option<FormattingOptions>,
+   ) -> Result<Option<Vec<TextEdit>>> {
+      if self.get_client_for_file(file_path).is_none() {
+         return Ok(None);
+      }
+      self.notify_document_change(file_path, content, version)?;
+
+      let mut merged: Vec<TextEdit> = Vec::new();
+      for range in ranges {
+         let Some(edits) = self
+            .format_range(
+               file_path,
+               range.start.line,
+               range.start.character,
+               range.end.line,
+               range.end.character,
+               options.clone(),
+            )
+            .await?
+         else {
+            return Ok(None);
+         };
+         manager_support::merge_range_edits(&mut merged, edits);
+      }
+      Ok(Some(merged))
+   }
+
    pub async fn get_signature_help(
       &self,
       file_path: &str,
@@ -864,6 +1019,45 @@ impl LspManager {
       }
    }
 
+   pub async fn get_document_highlights(
+      &self,
+      file_path: &str,
+      line: u32,
+      character: u32,
+   ) -> Result<Option<Vec<DocumentHighlight>>> {
+      let Some(client) = self.get_client_for_file(file_path) else {
+         return Ok(None);
+      };
+
+      if !client.supports_document_highlight() {
+         return Ok(None);
+      }
+
+      let text_document = TextDocumentIdentifier {
+         uri: manager_support::text_document_identifier(file_path)?.uri,
+      };
+
+      let params = DocumentHighlightParams {
+         text_document_position_params: TextDocumentPositionParams {
+            text_document,
+            position: Position { line, character },
+         },
+         work_done_progress_params: Default::default(),
+         partial_result_params: Default::default(),
+      };
+
+      match client.text_document_document_highlight(params).await {
+         Ok(value) => Ok(value),
+         Err(error) => {
+            if manager_support::is_unsupported_method(&error, "textDocument/documentHighlight") {
+               log::debug!("Document highlight method is not supported by this language server");
+               return Ok(None);
+            }
+            Err(error)
+         }
+      }
+   }
+
    pub async fn rename(
       &self,
       file_path: &str,
@@ -900,6 +1094,8 @@ impl LspManager {
       }
    }
 
+   /// Returns `None` when the position can't be renamed, including when the server rejects it
+   /// with an error response or doesn't support prepare rename at all.
    pub async fn prepare_rename(
       &self,
       file_path: &str,
@@ -909,6 +1105,9 @@ impl LspManager {
       let Some(client) = self.get_client_for_file(file_path) else {
          return Ok(None);
       };
+      if !client.supports_prepare_rename() {
+         return Ok(None);
+      }
 
       let text_document = TextDocumentIdentifier {
          uri: manager_support::text_document_identifier(file_path)?.uri,
@@ -926,6 +1125,45 @@ impl LspManager {
                log::debug!("PrepareRename method is not supported by this language server");
                return Ok(None);
             }
+            if manager_support::is_server_error_response(&error) {
+               log::debug!("Position is not renameable: {}", error);
+               return Ok(None);
+            }
+            Err(error)
+         }
+      }
+   }
+
+   /// Pull diagnostics for a file. Returns `None` when the server only pushes diagnostics, in
+   /// which case `lsp://diagnostics` events remain the source of truth.
+   pub async fn pull_diagnostics(&self, file_path: &str) -> Result<Option<Vec<Diagnostic>>> {
+      let Some(client) = self.get_client_for_file(file_path) else {
+         return Ok(None);
+      };
+      if !client.supports_pull_diagnostics() {
+         return Ok(None);
+      }
+
+      let params = DocumentDiagnosticParams {
+         text_document: manager_support::text_document_identifier(file_path)?,
+         identifier: None,
+         previous_result_id: None,
+         work_done_progress_params: Default::default(),
+         partial_result_params: Default::default(),
+      };
+
+      match client.text_document_diagnostic(params).await {
+         Ok(DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(report))) => {
+            Ok(Some(report.full_document_diagnostic_report.items))
+         }
+         // No previous result id is sent, so servers shouldn't answer with `unchanged` or a
+         // partial result; keep the pushed diagnostics if they do.
+         Ok(_) => Ok(None),
+         Err(error) => {
+            if manager_support::is_unsupported_method(&error, "textDocument/diagnostic") {
+               log::debug!("Pull diagnostics are not supported by this language server");
+               return Ok(None);
+            }
             Err(error)
          }
       }
@@ -958,6 +1196,37 @@ impl LspManager {
       }
    }
 
+   pub async fn get_folding_ranges(&self, file_path: &str) -> Result<Option<Vec<FoldingRange>>> {
+      let Some(client) = self.get_client_for_file(file_path) else {
+         return Ok(None);
+      };
+
+      if !client.supports_folding_range() {
+         return Ok(None);
+      }
+
+      let text_document = TextDocumentIdentifier {
+         uri: manager_support::text_document_identifier(file_path)?.uri,
+      };
+
+      let params = FoldingRangeParams {
+         text_document,
+         work_done_progress_params: Default::default(),
+         partial_result_params: Default::default(),
+      };
+
+      match client.text_document_folding_range(params).await {
+         Ok(value) => Ok(value),
+         Err(error) => {
+            if manager_support::is_unsupported_method(&error, "textDocument/foldingRange") {
+               log::debug!("Folding range method is not supported by this language server");
+               return Ok(None);
+            }
+            Err(error)
+         }
+      }
+   }
+
    pub async fn get_code_actions(
       &self,
       file_path: &str,
@@ -1071,9 +1340,6 @@ impl LspManager {
       content: String,
       language_id: Option<String>,
    ) -> Result<()> {
-      let path = PathBuf::from(file_path);
-      let _extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
-
       let client = self
          .get_client_for_file(file_path)
          .context("No LSP client for this file")?;
@@ -1081,7 +1347,8 @@ impl LspManager {
       let params = DidOpenTextDocumentParams {
          text_document: TextDocumentItem {
             uri: manager_support::text_document_identifier(file_path)?.uri,
-            language_id: language_id.unwrap_or_else(|| self.get_language_id_for_file(file_path)),
+            language_id: language_id
+               .unwrap_or_else(|| manager_support::language_id_for_file(file_path)),
             version: 1,
             text: content,
          },
@@ -1153,63 +1420,26 @@ impl LspManager {
       self.workspace_clients.shutdown_all();
    }
 
-   pub fn shutdown_workspace(&self, workspace_path: &Path) -> Result<()> {
-      Ok(self.workspace_clients.shutdown_workspace(workspace_path)?)
+   /// Gracefully shut down every running server. Returns how many were stopped.
+   pub async fn stop_all(&self) -> usize {
+      self.workspace_clients.stop_all().await
    }
 
-   fn get_language_id_for_file(&self, file_path: &str) -> String {
-      let path = PathBuf::from(file_path);
-      let file_name = path
-         .file_name()
-         .and_then(|name| name.to_str())
-         .unwrap_or_default();
-      let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
-
-      let mut language_id = match extension {
-         "sh" | "bash" | "zsh" => "bash",
-         "c" => "c",
-         "h" => "c",
-         "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => "cpp",
-         "cs" => "csharp",
-         "css" => "css",
-         "dart" => "dart",
-         "el" => "elisp",
-         "ex" | "exs" => "elixir",
-         "elm" => "elm",
-         "go" => "go",
-         "html" | "htm" | "xhtml" => "html",
-         "java" => "java",
-         "ts" => "typescript",
-         "tsx" => "typescriptreact",
-         "js" | "mjs" | "cjs" => "javascript",
-         "jsx" => "javascriptreact",
-         "jsonc" => "jsonc",
-         "json" => "json",
-         "kt" | "kts" => "kotlin",
-         "lua" => "lua",
-         "ml" | "mli" => "ocaml",
-         "php" | "phtml" | "php3" | "php4" | "php5" => "php",
-         "py" | "pyw" | "pyi" => "python",
-         "rb" | "rake" | "gemspec" => "ruby",
-         "rs" => "rust",
-         "scala" | "sc" => "scala",
-         "swift" => "swift",
-         "toml" => "toml",
-         "vue" => "vue",
-         "yaml" | "yml" => "yaml",
-         "zig" => "zig",
-         _ => "plaintext",
-      }
-      .to_string();
-
-      if language_id == "plaintext" {
-         language_id = match file_name {
-            ".bashrc" | ".zshrc" | ".bash_profile" | ".profile" => "bash".to_string(),
-            _ => language_id,
-         };
-      }
+   /// Restart every running server with the same launch settings, one at a time, re-opening
+   /// the documents it was tracking from disk. Returns how many were restarted.
+   pub async fn restart_all(&self) -> Result<usize> {
+      self
+         .workspace_clients
+         .restart_all(|workspace_path, server_name, launch| async move {
+            self
+               .launch_server(&workspace_path, &server_name, &launch)
+               .await
+         })
+         .await
+   }
 
-      language_id
+   pub fn shutdown_workspace(&self, workspace_path: &Path) -> Result<()> {
+      Ok(self.workspace_clients.shutdown_workspace(workspace_path)?)
    }
 }
 
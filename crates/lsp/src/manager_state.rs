@@ -1,4 +1,10 @@
-use super::client::LspClient;
+use super::{
+   client::{LspClient, LspServerEnv},
+   manager_support,
+   types::LspServerStatus,
+};
+use anyhow::{Result, bail};
+use serde_json::Value;
 use std::{
    collections::HashMap,
    path::{Path, PathBuf},
@@ -6,7 +12,16 @@ use std::{
    sync::{Arc, Mutex},
 };
 
-type WorkspaceKey = (PathBuf, String);
+pub(super) type WorkspaceKey = (PathBuf, String);
+
+/// How a server was launched, kept so it can be restarted the same way
+#[derive(Clone)]
+pub(super) struct LspLaunch {
+   pub server_path: PathBuf,
+   pub server_args: Vec<String>,
+   pub server_env: LspServerEnv,
+   pub initialization_options: Option<Value>,
+}
 
 pub(super) struct LspInstance {
    pub client: LspClient,
@@ -14,6 +29,46 @@ pub(super) struct LspInstance {
    pub server_name: String,
    pub ref_count: usize,
    pub files: Vec<PathBuf>,
+   pub launch: LspLaunch,
+}
+
+/// Workspace folders opened in the editor. A file is served by the innermost root containing it.
+#[derive(Clone, Default)]
+pub(super) struct WorkspaceRoots {
+   inner: Arc<Mutex<Vec<PathBuf>>>,
+}
+
+impl WorkspaceRoots {
+   pub(super) fn add(&self, root: PathBuf) -> bool {
+      let mut roots = self.inner.lock().unwrap();
+      if roots.contains(&root) {
+         return false;
+      }
+      roots.push(root);
+      true
+   }
+
+   pub(super) fn remove(&self, root: &Path) -> bool {
+      let mut roots = self.inner.lock().unwrap();
+      let before = roots.len();
+      roots.retain(|existing| existing != root);
+      roots.len() != before
+   }
+
+   pub(super) fn list(&self) -> Vec<PathBuf> {
+      self.inner.lock().unwrap().clone()
+   }
+
+   pub(super) fn root_for_file(&self, file_path: &Path) -> Option<PathBuf> {
+      self
+         .inner
+         .lock()
+         .unwrap()
+         .iter()
+         .filter(|root| file_path.starts_with(root))
+         .max_by_key(|root| root.components().count())
+         .cloned()
+   }
 }
 
 #[derive(Clone)]
@@ -38,17 +93,28 @@ impl WorkspaceClients {
       clients.contains_key(&(workspace_path.to_path_buf(), server_name.to_string()))
    }
 
+   /// Store `instance` for its workspace and server. A server already stored under that key,
+   /// such as one started while a restart was relaunching it, is killed so it can't linger.
    pub(super) fn insert(
       &self,
       workspace_path: PathBuf,
       server_name: String,
       instance: LspInstance,
    ) {
-      self
+      let replaced = self
          .inner
          .lock()
          .unwrap()
-         .insert((workspace_path, server_name), instance);
+         .insert((workspace_path.clone(), server_name), instance);
+      if let Some(mut replaced) = replaced {
+         log::info!(
+            "Replacing LSP '{}' for workspace {:?}",
+            replaced.server_name,
+            workspace_path
+         );
+         let _ = replaced.child.kill();
+         let _ = replaced.child.wait();
+      }
    }
 
    pub(super) fn track_file(
@@ -166,6 +232,134 @@ impl WorkspaceClients {
          .collect()
    }
 
+   pub(super) fn all_clients(&self) -> Vec<LspClient> {
+      let mut clients = self.inner.lock().unwrap();
+      Self::prune_dead_instances(&mut clients);
+      clients
+         .values()
+         .map(|instance| instance.client.clone())
+         .collect()
+   }
+
+   /// Reports every tracked server, including ones whose process has exited but
+   /// has not been pruned yet.
+   pub(super) fn statuses(&self) -> Vec<LspServerStatus> {
+      let mut clients = self.inner.lock().unwrap();
+      let mut statuses: Vec<LspServerStatus> = clients
+         .iter_mut()
+         .map(|((workspace_path, server_name), instance)| {
+            let alive =
+               matches!(instance.child.try_wait(), Ok(None)) && instance.client.is_running();
+            LspServerStatus {
+               workspace_path: workspace_path.to_string_lossy().to_string(),
+               server_name: server_name.clone(),
+               pid: instance.child.id(),
+               alive,
+               capabilities: instance.client.capability_summary(),
+               open_documents: instance.files.len(),
+            }
+         })
+         .collect();
+
+      statuses.sort_by(|a, b| {
+         (&a.workspace_path, &a.server_name).cmp(&(&b.workspace_path, &b.server_name))
+      });
+      statuses
+   }
+
+   fn live_keys(&self) -> Vec<WorkspaceKey> {
+      let mut clients = self.inner.lock().unwrap();
+      Self::prune_dead_instances(&mut clients);
+      clients.keys().cloned().collect()
+   }
+
+   fn take(&self, key: &WorkspaceKey) -> Option<LspInstance> {
+      self.inner.lock().unwrap().remove(key)
+   }
+
+   /// Gracefully shut down every live server. Returns how many were stopped.
+   pub(super) async fn stop_all(&self) -> usize {
+      let mut stopped = 0;
+      for key in self.live_keys() {
+         if let Some(instance) = self.take(&key) {
+            manager_support::stop_instance(&key.0, instance).await;
+            stopped += 1;
+         }
+      }
+      stopped
+   }
+
+   /// Restart every live server one at a time with `launch`: each is removed, shut down,
+   /// launched again with its previous settings, handed its tracked documents from disk and
+   /// stored again. The others keep serving meanwhile. Returns how many were restarted.
+   pub(super) async fn restart_all<F, Fut>(&self, launch: F) -> Result<usize>
+   where
+      F: Fn(PathBuf, String, LspLaunch) -> Fut,
+      Fut: Future<Output = Result<(LspClient, Child)>>,
+   {
+      let mut restarted = 0;
+      let mut failed = Vec::new();
+
+      for key in self.live_keys() {
+         // A server stopped since the keys were listed stays stopped.
+         let Some(instance) = self.take(&key) else {
+            continue;
+         };
+         let (workspace_path, server_name) = key;
+         let (ref_count, files, launch_settings) = (
+            instance.ref_count,
+            instance.files.clone(),
+            instance.launch.clone(),
+         );
+         manager_support::stop_instance(&workspace_path, instance).await;
+
+         log::info!(
+            "Restarting LSP '{}' for workspace {:?}",
+            server_name,
+            workspace_path
+         );
+         let (client, child) = match launch(
+            workspace_path.clone(),
+            server_name.clone(),
+            launch_settings.clone(),
+         )
+         .await
+         {
+            Ok(started) => started,
+            Err(error) => {
+               log::error!("Failed to restart LSP '{}': {}", server_name, error);
+               failed.push(server_name);
+               continue;
+            }
+         };
+
+         for file in &files {
+            if let Err(error) = manager_support::reopen_document(&client, file) {
+               log::warn!("Failed to re-open {:?} after restart: {}", file, error);
+            }
+         }
+
+         self.insert(
+            workspace_path,
+            server_name.clone(),
+            LspInstance {
+               client,
+               child,
+               server_name,
+               ref_count,
+               files,
+               launch: launch_settings,
+            },
+         );
+         restarted += 1;
+      }
+
+      if !failed.is_empty() {
+         bail!("Failed to restart {}", failed.join(", "));
+      }
+      Ok(restarted)
+   }
+
    pub(super) fn shutdown_all(&self) {
       let mut clients = self.inner.lock().unwrap();
       for ((workspace, server_name), mut instance) in clients.drain() {
@@ -237,3 +431,143 @@ impl WorkspaceClients {
       }
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::server_logs::ServerLogs;
+   use std::sync::atomic::{AtomicUsize, Ordering};
+
+   #[test]
+   fn picks_innermost_workspace_root() {
+      let roots = WorkspaceRoots::default();
+      assert!(roots.add(PathBuf::from("/repo")));
+      assert!(roots.add(PathBuf::from("/repo/packages/web")));
+      assert!(!roots.add(PathBuf::from("/repo")));
+
+      assert_eq!(
+         roots.root_for_file(Path::new("/repo/packages/web/src/app.ts")),
+         Some(PathBuf::from("/repo/packages/web"))
+      );
+      assert_eq!(
+         roots.root_for_file(Path::new("/repo/Cargo.toml")),
+         Some(PathBuf::from("/repo"))
+      );
+      assert_eq!(roots.root_for_file(Path::new("/other/main.rs")), None);
+
+      assert!(roots.remove(Path::new("/repo/packages/web")));
+      assert_eq!(
+         roots.root_for_file(Path::new("/repo/packages/web/src/app.ts")),
+         Some(PathBuf::from("/repo"))
+      );
+   }
+
+   /// A stand-in server that stays alive without answering, so shutdown falls back to a kill
+   #[cfg(unix)]
+   async fn start_idle_server() -> Result<(LspClient, Child)> {
+      LspClient::start(
+         PathBuf::from("sleep"),
+         vec!["60".to_string()],
+         lsp_types::Url::parse("file:///").unwrap(),
+         None,
+         None,
+         LspServerEnv::new(),
+         ServerLogs::default().writer("idle"),
+      )
+      .await
+   }
+
+   #[cfg(unix)]
+   async fn idle_instance(files: Vec<PathBuf>) -> LspInstance {
+      let (client, child) = start_idle_server().await.unwrap();
+      LspInstance {
+         client,
+         child,
+         server_name: "idle".to_string(),
+         ref_count: files.len(),
+         files,
+         launch: LspLaunch {
+            server_path: PathBuf::from("sleep"),
+            server_args: vec!["60".to_string()],
+            server_env: LspServerEnv::new(),
+            initialization_options: None,
+         },
+      }
+   }
+
+   #[cfg(unix)]
+   fn is_process_alive(pid: u32) -> bool {
+      std::process::Command::new("kill")
+         .args(["-0", &pid.to_string()])
+         .stderr(std::process::Stdio::null())
+         .status()
+         .is_ok_and(|status| status.success())
+   }
+
+   #[cfg(unix)]
+   #[tokio::test]
+   async fn insert_kills_the_instance_it_replaces() {
+      let clients = WorkspaceClients::new();
+      let first = idle_instance(Vec::new()).await;
+      let first_pid = first.child.id();
+
+      clients.insert(PathBuf::from("/repo"), "idle".to_string(), first);
+      assert!(is_process_alive(first_pid));
+      clients.insert(
+         PathBuf::from("/repo"),
+         "idle".to_string(),
+         idle_instance(Vec::new()).await,
+      );
+
+      assert!(!is_process_alive(first_pid));
+      assert_eq!(clients.statuses().len(), 1);
+      clients.shutdown_all();
+   }
+
+   #[cfg(unix)]
+   #[tokio::test]
+   async fn restarts_each_server_then_stops_them_all() {
+      let temp_dir = tempfile::tempdir().unwrap();
+      let file = temp_dir.path().join("main.rs");
+      std::fs::write(&file, "fn main() {}\n").unwrap();
+
+      let clients = WorkspaceClients::new();
+      let original = idle_instance(vec![file.clone()]).await;
+      let original_pid = original.child.id();
+      clients.insert(temp_dir.path().to_path_buf(), "idle".to_string(), original);
+
+      let launches = AtomicUsize::new(0);
+      let restarted = clients
+         .restart_all(|_, server_name, launch| {
+            launches.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(server_name, "idle");
+            assert_eq!(launch.server_args, vec!["60".to_string()]);
+            start_idle_server()
+         })
+         .await
+         .unwrap();
+
+      assert_eq!(restarted, 1);
+      assert_eq!(launches.load(Ordering::SeqCst), 1);
+      assert!(!is_process_alive(original_pid));
+      let statuses = clients.statuses();
+      assert_eq!(statuses.len(), 1);
+      assert_ne!(statuses[0].pid, original_pid);
+      assert_eq!(statuses[0].open_documents, 1);
+
+      let failed = clients
+         .restart_all(|_, _, _| async { bail!("server binary is gone") })
+         .await;
+      assert!(failed.is_err());
+      assert!(clients.statuses().is_empty());
+
+      clients.insert(
+         temp_dir.path().to_path_buf(),
+         "idle".to_string(),
+         idle_instance(Vec::new()).await,
+      );
+      assert_eq!(clients.stop_all().await, 1);
+      assert!(clients.statuses().is_empty());
+      assert_eq!(clients.stop_all().await, 0);
+   }
+}
@@ -1,5 +1,19 @@
-use anyhow::{Result, anyhow};
-use lsp_types::{ExecuteCommandParams, TextDocumentIdentifier, Url};
+use super::{client::LspClient, manager_state::LspInstance};
+use anyhow::{Context, Result, anyhow};
+use athas_runtime::detect_project_python;
+use lsp_types::{
+   DidOpenTextDocumentParams, ExecuteCommandParams, FormattingOptions, TextDocumentIdentifier,
+   TextDocumentItem, TextEdit, Url,
+};
+use serde_json::{Value, json};
+use std::{
+   fs,
+   path::{Path, PathBuf},
+   time::Duration,
+};
+
+/// How long a server gets to answer `shutdown` before it is killed
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub(super) fn text_document_identifier(file_path: &str) -> Result<TextDocumentIdentifier> {
    Ok(TextDocumentIdentifier {
@@ -7,6 +21,29 @@ pub(super) fn text_document_identifier(file_path: &str) -> Result<TextDocumentId
    })
 }
 
+/// Formatting options used when the editor does not send its own indentation settings
+pub(super) fn default_formatting_options() -> FormattingOptions {
+   FormattingOptions {
+      tab_size: 3,
+      insert_spaces: true,
+      ..Default::default()
+   }
+}
+
+/// Add the edits of one range-formatting response to those already taken. Servers may widen a
+/// range to the enclosing statement, so an edit overlapping one already taken is dropped rather
+/// than applied twice.
+pub(super) fn merge_range_edits(merged: &mut Vec<TextEdit>, edits: Vec<TextEdit>) {
+   for edit in edits {
+      let overlaps = merged
+         .iter()
+         .any(|taken| edit.range.start < taken.range.end && taken.range.start < edit.range.end);
+      if !overlaps && !merged.contains(&edit) {
+         merged.push(edit);
+      }
+   }
+}
+
 pub(super) fn is_unsupported_method(error: &anyhow::Error, method: &str) -> bool {
    let message = error.to_string();
    message.contains("-32601")
@@ -14,6 +51,60 @@ pub(super) fn is_unsupported_method(error: &anyhow::Error, method: &str) -> bool
       || message.contains(&format!("Unhandled method {}", method))
 }
 
+/// Whether the server answered the request with an error response, as opposed to the request
+/// never reaching it.
+pub(super) fn is_server_error_response(error: &anyhow::Error) -> bool {
+   error.to_string().starts_with("LSP error:")
+}
+
+const PROJECT_ROOT_MARKERS: &[&str] = &["Cargo.toml", "package.json", ".git"];
+
+/// Nearest ancestor of `file_path` that looks like a project root, falling back to the file's
+/// own directory so single files outside any project still get a server.
+pub(super) fn detect_project_root(file_path: &Path) -> Option<PathBuf> {
+   let parent = file_path.parent()?;
+   let root = parent
+      .ancestors()
+      .find(|dir| {
+         PROJECT_ROOT_MARKERS
+            .iter()
+            .any(|marker| dir.join(marker).exists())
+      })
+      .unwrap_or(parent);
+   Some(root.to_path_buf())
+}
+
+/// Point pyright-based servers at the workspace's own interpreter, such as a `.venv`, through
+/// `python.pythonPath` in the initialization options. A path the caller already set wins.
+pub(super) fn with_project_python(
+   initialization_options: Option<Value>,
+   workspace_path: &Path,
+   server_name: &str,
+) -> Option<Value> {
+   let has_python_path = initialization_options
+      .as_ref()
+      .and_then(|options| options.pointer("/python/pythonPath"))
+      .is_some();
+   if !server_name.contains("pyright") || has_python_path {
+      return initialization_options;
+   }
+   let Some(python) = detect_project_python(workspace_path) else {
+      return initialization_options;
+   };
+   log::info!("Using project Python {:?} for {}", python, server_name);
+
+   let mut options = initialization_options.unwrap_or_else(|| json!({}));
+   if let Some(options) = options.as_object_mut()
+      && let Some(python_settings) = options
+         .entry("python")
+         .or_insert_with(|| json!({}))
+         .as_object_mut()
+   {
+      python_settings.insert("pythonPath".to_string(), json!(python.to_string_lossy()));
+   }
+   Some(options)
+}
+
 pub(super) fn execute_command_params(
    command: String,
    arguments: Vec<serde_json::Value>,
@@ -24,3 +115,309 @@ pub(super) fn execute_command_params(
       work_done_progress_params: Default::default(),
    }
 }
+
+/// Send `shutdown` and `exit`, then kill the process in case the server ignored them.
+pub(super) async fn stop_instance(workspace_path: &Path, mut instance: LspInstance) {
+   log::info!(
+      "Shutting down LSP '{}' for workspace {:?}",
+      instance.server_name,
+      workspace_path
+   );
+   match tokio::time::timeout(SHUTDOWN_TIMEOUT, instance.client.shutdown()).await {
+      Ok(Ok(())) => {}
+      Ok(Err(error)) => log::warn!(
+         "LSP '{}' failed to shut down cleanly: {}",
+         instance.server_name,
+         error
+      ),
+      Err(_) => log::warn!(
+         "LSP '{}' did not answer shutdown within {:?}",
+         instance.server_name,
+         SHUTDOWN_TIMEOUT
+      ),
+   }
+   let _ = instance.child.kill();
+   let _ = instance.child.wait();
+}
+
+/// Open a tracked document on a freshly started server, using its contents on disk
+pub(super) fn reopen_document(client: &LspClient, file_path: &Path) -> Result<()> {
+   let text = fs::read_to_string(file_path).context("Failed to read document")?;
+   let path = file_path.to_string_lossy();
+   client.text_document_did_open(DidOpenTextDocumentParams {
+      text_document: TextDocumentItem {
+         uri: text_document_identifier(&path)?.uri,
+         language_id: language_id_for_file(&path),
+         version: 1,
+         text,
+      },
+   })
+}
+
+/// Language id sent in `didOpen` when the frontend does not supply one. Mirrors the language
+/// ids declared by the bundled extension manifests under `extensions/official`.
+pub(super) fn language_id_for_file(file_path: &str) -> String {
+   let path = Path::new(file_path);
+   let file_name = path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .unwrap_or_default();
+   let extension = path
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .unwrap_or_default()
+      .to_ascii_lowercase();
+
+   let language_id = match extension.as_str() {
+      "astro" => "astro",
+      "sh" | "bash" | "zsh" => "bash",
+      "c" | "h" => "c",
+      "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => "cpp",
+      "cs" => "csharp",
+      "css" => "css",
+      "scss" => "scss",
+      "sass" => "sass",
+      "less" => "less",
+      "dart" => "dart",
+      "dockerfile" => "dockerfile",
+      "el" | "emacs" => "elisp",
+      "ex" | "exs" => "elixir",
+      "elm" => "elm",
+      "go" => "go",
+      "graphql" | "gql" => "graphql",
+      "html" | "htm" | "xhtml" => "html",
+      "java" => "java",
+      "ts" | "mts" | "cts" => "typescript",
+      "tsx" => "typescriptreact",
+      "js" | "mjs" | "cjs" => "javascript",
+      "jsx" => "javascriptreact",
+      "json" => "json",
+      "jsonc" => "jsonc",
+      "ipynb" => "jupyter-notebook",
+      "kt" | "kts" => "kotlin",
+      "lua" => "lua",
+      "md" | "markdown" | "mdx" => "markdown",
+      "rmd" => "rmarkdown",
+      "nix" => "nix",
+      "m" | "mm" => "objc",
+      "ml" | "mli" => "ocaml",
+      "php" | "phtml" | "php3" | "php4" | "php5" | "php7" | "php8" | "phar" | "phps" => "php",
+      "proto" => "protobuf",
+      "py" | "pyw" | "pyi" | "ipy" => "python",
+      "ql" | "qll" => "ql",
+      "r" => "r",
+      "res" | "resi" => "rescript",
+      "rb" | "rbw" | "erb" | "rake" | "gemspec" | "ru" | "builder" | "jbuilder" | "rxml"
+      | "rjs" | "rabl" | "thor" | "podspec" | "arb" => "ruby",
+      "rs" => "rust",
+      "scala" | "sc" => "scala",
+      "sol" => "solidity",
+      "sql" | "dsql" => "sql",
+      "svelte" => "svelte",
+      "swift" => "swift",
+      "rdl" => "systemrdl",
+      "tf" | "tfvars" => "terraform",
+      "tla" => "tlaplus",
+      "toml" => "toml",
+      "vue" => "vue",
+      "xml" | "xsl" | "xslt" | "xsd" | "dtd" | "wsdl" | "plist" | "svg" | "csproj" | "vbproj"
+      | "fsproj" | "props" | "targets" => "xml",
+      "yaml" | "yml" => "yaml",
+      "zig" | "zon" => "zig",
+      _ => match file_name {
+         ".bashrc" | ".zshrc" | ".bash_profile" | ".profile" => "bash",
+         "Dockerfile" | "Containerfile" => "dockerfile",
+         "go.mod" | "go.sum" | "go.work" => "go",
+         ".Rprofile" => "r",
+         "Rakefile" | "Gemfile" | "Gemfile.lock" | "Guardfile" | "Capfile" | "Appraisals"
+         | "Berksfile" | "Brewfile" | "Cheffile" | "Dangerfile" | "Fastfile" | "Podfile"
+         | "Rantfile" | "Thorfile" | "Vagrantfile" | "Puppetfile" => "ruby",
+         _ => "plaintext",
+      },
+   };
+
+   language_id.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn edit(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+      TextEdit::new(
+         lsp_types::Range::new(
+            lsp_types::Position::new(start.0, start.1),
+            lsp_types::Position::new(end.0, end.1),
+         ),
+         new_text.to_string(),
+      )
+   }
+
+   #[test]
+   fn drops_range_edits_that_overlap_ones_already_taken() {
+      let mut merged = Vec::new();
+      merge_range_edits(
+         &mut merged,
+         vec![
+            edit((2, 0), (2, 8), "let a = 1;"),
+            edit((5, 4), (5, 4), "  "),
+         ],
+      );
+      // A server that widened the second range to the whole block repeats the first edit and
+      // reformats across it
+      merge_range_edits(
+         &mut merged,
+         vec![
+            edit((2, 0), (2, 8), "let a = 1;"),
+            edit((1, 0), (3, 1), "{\n   let a = 1;\n}"),
+            edit((5, 4), (5, 4), "  "),
+            edit((2, 8), (2, 8), "\n"),
+            edit((7, 0), (7, 3), "b;"),
+         ],
+      );
+
+      assert_eq!(
+         merged,
+         vec![
+            edit((2, 0), (2, 8), "let a = 1;"),
+            edit((5, 4), (5, 4), "  "),
+            edit((2, 8), (2, 8), "\n"),
+            edit((7, 0), (7, 3), "b;"),
+         ]
+      );
+   }
+
+   #[test]
+   fn detects_nearest_project_root() {
+      let temp = tempfile::tempdir().unwrap();
+      let repo = temp.path().join("repo");
+      let crate_dir = repo.join("crates/core");
+      let loose = temp.path().join("loose");
+      std::fs::create_dir_all(repo.join(".git")).unwrap();
+      std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+      std::fs::create_dir_all(repo.join("docs")).unwrap();
+      std::fs::create_dir_all(&loose).unwrap();
+      std::fs::write(crate_dir.join("Cargo.toml"), "").unwrap();
+
+      assert_eq!(
+         detect_project_root(&crate_dir.join("src/lib.rs")),
+         Some(crate_dir.clone())
+      );
+      assert_eq!(
+         detect_project_root(&repo.join("docs/guide.md")),
+         Some(repo.clone())
+      );
+      assert_eq!(
+         detect_project_root(&loose.join("script.py")),
+         Some(loose.clone())
+      );
+   }
+
+   #[test]
+   #[cfg(unix)]
+   fn passes_project_virtualenv_to_pyright() {
+      let workspace = tempfile::tempdir().unwrap();
+      let base = tempfile::tempdir().unwrap();
+      let base_python = base.path().join("python3");
+      std::fs::write(&base_python, "").unwrap();
+      let python = workspace.path().join(".venv/bin/python");
+      std::fs::create_dir_all(python.parent().unwrap()).unwrap();
+      std::os::unix::fs::symlink(&base_python, &python).unwrap();
+
+      let options = with_project_python(
+         Some(json!({ "python": { "analysis": { "typeCheckingMode": "basic" } } })),
+         workspace.path(),
+         "pyright-langserver",
+      )
+      .unwrap();
+      assert_eq!(
+         options["python"]["pythonPath"],
+         json!(python.to_string_lossy())
+      );
+      assert_eq!(
+         options["python"]["analysis"]["typeCheckingMode"],
+         json!("basic")
+      );
+
+      let pinned = json!({ "python": { "pythonPath": "/usr/bin/python3" } });
+      assert_eq!(
+         with_project_python(Some(pinned.clone()), workspace.path(), "pyright-langserver"),
+         Some(pinned)
+      );
+      assert_eq!(
+         with_project_python(None, workspace.path(), "rust-analyzer"),
+         None
+      );
+   }
+
+   #[test]
+   fn distinguishes_server_error_responses() {
+      let rejected = anyhow!(
+         "LSP error: {:?}",
+         serde_json::json!({ "code": -32602, "message": "No references found at position" })
+      );
+      assert!(is_server_error_response(&rejected));
+      assert!(!is_server_error_response(&anyhow!(
+         "LSP server is not running"
+      )));
+   }
+
+   #[test]
+   fn maps_common_extensions_to_language_ids() {
+      assert_eq!(language_id_for_file("/repo/main.py"), "python");
+      assert_eq!(language_id_for_file("/repo/src/lib.rs"), "rust");
+      assert_eq!(language_id_for_file("/repo/App.tsx"), "typescriptreact");
+      assert_eq!(language_id_for_file("/repo/App.jsx"), "javascriptreact");
+      assert_eq!(
+         language_id_for_file("/repo/.vscode/settings.jsonc"),
+         "jsonc"
+      );
+      assert_eq!(language_id_for_file("/repo/analysis.R"), "r");
+      assert_eq!(language_id_for_file("/repo/Dockerfile"), "dockerfile");
+      assert_eq!(language_id_for_file("/repo/README.md"), "markdown");
+      assert_eq!(language_id_for_file("/repo/.github/ci.YML"), "yaml");
+      assert_eq!(language_id_for_file("/repo/Cargo.toml"), "toml");
+      assert_eq!(language_id_for_file("/repo/notes.txt"), "plaintext");
+   }
+
+   #[test]
+   fn covers_every_bundled_manifest_extension() {
+      let manifests_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../extensions/official");
+      let Ok(entries) = std::fs::read_dir(&manifests_dir) else {
+         return;
+      };
+
+      for entry in entries.flatten() {
+         let Ok(contents) = std::fs::read_to_string(entry.path().join("extension.json")) else {
+            continue;
+         };
+         let manifest: serde_json::Value = serde_json::from_str(&contents).expect("manifest");
+         let languages = manifest["languages"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+
+         for language in languages {
+            let id = language["id"].as_str().expect("language id");
+            for extension in language["extensions"].as_array().into_iter().flatten() {
+               let extension = extension.as_str().expect("extension");
+               // `.h` is shared by C and Objective-C; C wins.
+               if id == "objc" && extension == ".h" {
+                  continue;
+               }
+               // Servers tell these apart from the editor language they are bundled with.
+               let expected = match extension {
+                  ".jsx" => "javascriptreact",
+                  ".jsonc" => "jsonc",
+                  _ => id,
+               };
+               assert_eq!(
+                  language_id_for_file(&format!("/repo/file{extension}")),
+                  expected,
+                  "extension {extension}"
+               );
+            }
+         }
+      }
+   }
+}
@@ -0,0 +1,154 @@
+use lsp_types::Position;
+use serde::{Deserialize, Serialize};
+
+/// How the `character` of an LSP position counts, named as in the `positionEncoding`
+/// capability. Servers default to UTF-16 code units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PositionEncoding {
+   #[serde(rename = "utf-8")]
+   Utf8,
+   #[default]
+   #[serde(rename = "utf-16")]
+   Utf16,
+   #[serde(rename = "utf-32")]
+   Utf32,
+}
+
+impl PositionEncoding {
+   fn units(self, c: char) -> usize {
+      match self {
+         PositionEncoding::Utf8 => c.len_utf8(),
+         PositionEncoding::Utf16 => c.len_utf16(),
+         PositionEncoding::Utf32 => 1,
+      }
+   }
+}
+
+/// Byte offset of `position` in `text`. A `character` past the end of its line resolves to the
+/// end of the line, as the LSP spec asks, and one that lands inside a character resolves to the
+/// start of that character. Returns `None` when the line does not exist.
+pub fn position_to_offset(
+   text: &str,
+   position: Position,
+   encoding: PositionEncoding,
+) -> Option<usize> {
+   let line_start = line_start_offset(text, position.line as usize)?;
+   let line = &text[line_start..];
+   let line = &line[..line.find('\n').unwrap_or(line.len())];
+   let line = line.strip_suffix('\r').unwrap_or(line);
+
+   let target = position.character as usize;
+   let mut units = 0;
+   for (index, c) in line.char_indices() {
+      let next = units + encoding.units(c);
+      if next > target {
+         return Some(line_start + index);
+      }
+      units = next;
+   }
+   Some(line_start + line.len())
+}
+
+/// Position of byte `offset` in `text`. An offset inside a multibyte character maps to the
+/// start of that character. Returns `None` when the offset is past the end of the text.
+pub fn offset_to_position(
+   text: &str,
+   offset: usize,
+   encoding: PositionEncoding,
+) -> Option<Position> {
+   if offset > text.len() {
+      return None;
+   }
+   let mut offset = offset;
+   while !text.is_char_boundary(offset) {
+      offset -= 1;
+   }
+
+   let before = &text[..offset];
+   let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+   let line = before.matches('\n').count();
+   let character: usize = before[line_start..]
+      .chars()
+      .map(|c| encoding.units(c))
+      .sum();
+
+   Some(Position::new(line as u32, character as u32))
+}
+
+fn line_start_offset(text: &str, line: usize) -> Option<usize> {
+   if line == 0 {
+      return Some(0);
+   }
+   text
+      .match_indices('\n')
+      .nth(line - 1)
+      .map(|(index, _)| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   const TEXT: &str = "let a = 1;\r\nconst 日本 = \"😀x\";\nend";
+
+   #[test]
+   fn resolves_positions_in_each_encoding() {
+      let x_offset = TEXT.find('x').unwrap();
+
+      assert_eq!(
+         position_to_offset(TEXT, Position::new(1, 14), PositionEncoding::Utf16),
+         Some(x_offset)
+      );
+      assert_eq!(
+         position_to_offset(TEXT, Position::new(1, 13), PositionEncoding::Utf32),
+         Some(x_offset)
+      );
+      assert_eq!(
+         position_to_offset(TEXT, Position::new(1, 20), PositionEncoding::Utf8),
+         Some(x_offset)
+      );
+   }
+
+   #[test]
+   fn clamps_characters_and_rejects_missing_lines() {
+      assert_eq!(
+         position_to_offset(TEXT, Position::new(0, 99), PositionEncoding::Utf16),
+         Some(10)
+      );
+      // Halfway through the surrogate pair of the emoji.
+      assert_eq!(
+         position_to_offset(TEXT, Position::new(1, 13), PositionEncoding::Utf16),
+         TEXT.find('😀')
+      );
+      assert_eq!(
+         position_to_offset(TEXT, Position::new(3, 0), PositionEncoding::Utf16),
+         None
+      );
+   }
+
+   #[test]
+   fn converts_offsets_back_to_positions() {
+      let x_offset = TEXT.find('x').unwrap();
+
+      assert_eq!(
+         offset_to_position(TEXT, x_offset, PositionEncoding::Utf16),
+         Some(Position::new(1, 14))
+      );
+      assert_eq!(
+         offset_to_position(TEXT, x_offset, PositionEncoding::Utf32),
+         Some(Position::new(1, 13))
+      );
+      assert_eq!(
+         offset_to_position(TEXT, TEXT.len(), PositionEncoding::Utf16),
+         Some(Position::new(2, 3))
+      );
+      assert_eq!(
+         offset_to_position(TEXT, x_offset - 1, PositionEncoding::Utf16),
+         Some(Position::new(1, 12))
+      );
+      assert_eq!(
+         offset_to_position(TEXT, TEXT.len() + 1, PositionEncoding::Utf16),
+         None
+      );
+   }
+}
@@ -0,0 +1,82 @@
+use std::{
+   collections::{HashMap, VecDeque},
+   sync::{Arc, Mutex},
+};
+
+const MAX_LINES_PER_SERVER: usize = 500;
+
+/// Rolling stderr buffers keyed by server name.
+///
+/// Kept on the manager rather than the client so the output of a crashed server is still
+/// available after its instance has been pruned.
+#[derive(Clone, Default)]
+pub struct ServerLogs {
+   inner: Arc<Mutex<HashMap<String, VecDeque<String>>>>,
+}
+
+impl ServerLogs {
+   pub fn writer(&self, server_name: &str) -> ServerLogWriter {
+      ServerLogWriter {
+         server_name: server_name.to_string(),
+         logs: self.clone(),
+      }
+   }
+
+   pub fn push(&self, server_name: &str, line: String) {
+      let mut logs = self.inner.lock().unwrap();
+      let lines = logs.entry(server_name.to_string()).or_default();
+      if lines.len() == MAX_LINES_PER_SERVER {
+         lines.pop_front();
+      }
+      lines.push_back(line);
+   }
+
+   pub fn lines(&self, server_name: &str) -> Vec<String> {
+      self
+         .inner
+         .lock()
+         .unwrap()
+         .get(server_name)
+         .map(|lines| lines.iter().cloned().collect())
+         .unwrap_or_default()
+   }
+}
+
+/// Handle given to a client so its stderr reader can append to the right buffer.
+#[derive(Clone)]
+pub struct ServerLogWriter {
+   server_name: String,
+   logs: ServerLogs,
+}
+
+impl ServerLogWriter {
+   pub fn server_name(&self) -> &str {
+      &self.server_name
+   }
+
+   pub fn push(&self, line: String) {
+      self.logs.push(&self.server_name, line);
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn keeps_only_the_most_recent_lines_per_server() {
+      let logs = ServerLogs::default();
+      let writer = logs.writer("rust-analyzer");
+
+      for index in 0..(MAX_LINES_PER_SERVER + 10) {
+         writer.push(format!("line {index}"));
+      }
+      logs.push("gopls", "other".to_string());
+
+      let lines = logs.lines("rust-analyzer");
+      assert_eq!(lines.len(), MAX_LINES_PER_SERVER);
+      assert_eq!(lines.first().map(String::as_str), Some("line 10"));
+      assert_eq!(logs.lines("gopls"), vec!["other".to_string()]);
+      assert!(logs.lines("missing").is_empty());
+   }
+}
@@ -49,3 +49,42 @@ impl From<anyhow::Error> for LspError {
 }
 
 pub type LspResult<T> = Result<T, LspError>;
+
+/// Payload of the `lsp://message` event, emitted for `window/showMessage` and
+/// `window/logMessage` notifications.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspServerMessage {
+   pub method: String,
+   pub message_type: String,
+   pub message: String,
+}
+
+/// Payload of the `lsp://stderr` event, emitted for each line a server writes to stderr.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspStderrLine {
+   pub server_name: String,
+   pub line: String,
+}
+
+/// What the language server handling a file supports, returned by `lsp_get_capabilities`.
+/// `features` uses the same names as `LspServerStatus::capabilities`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspCapabilities {
+   pub features: Vec<String>,
+   pub server_capabilities: lsp_types::ServerCapabilities,
+}
+
+/// Snapshot of a running language server, returned by `lsp_server_status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspServerStatus {
+   pub workspace_path: String,
+   pub server_name: String,
+   pub pid: u32,
+   pub alive: bool,
+   pub capabilities: Vec<String>,
+   pub open_documents: usize,
+}
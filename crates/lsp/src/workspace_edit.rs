@@ -0,0 +1,456 @@
+use crate::position::{PositionEncoding, position_to_offset};
+use anyhow::{Context, Result, bail};
+use athas_project::write_file_atomic;
+use lsp_types::{
+   CreateFile, DeleteFile, DocumentChangeOperation, DocumentChanges, OneOf,
+   OptionalVersionedTextDocumentIdentifier, RenameFile, ResourceOp, TextDocumentEdit, TextEdit,
+   Url, WorkspaceEdit,
+};
+use serde::Serialize;
+use std::{
+   cmp::Reverse,
+   fs,
+   path::{Component, Path, PathBuf},
+};
+
+/// Files touched by a workspace edit applied on disk
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedWorkspaceEdit {
+   pub edited_files: Vec<String>,
+   pub created_files: Vec<String>,
+   pub renamed_files: Vec<(String, String)>,
+   pub deleted_files: Vec<String>,
+}
+
+/// Apply `edit` to the files on disk, in order, for the files the editor does not have open.
+/// As the LSP spec asks, `documentChanges` is used when present and `changes` is ignored.
+/// Text edits are written atomically. Resource operations follow their `overwrite`,
+/// `ignoreIfExists`, `recursive` and `ignoreIfNotExists` options. With a `workspace_root`, every
+/// file must resolve inside it, and without one recursive deletes are refused. Application stops
+/// at the first failing change, keeping the changes before it.
+pub fn apply_workspace_edit(
+   edit: &WorkspaceEdit,
+   workspace_root: Option<&Path>,
+) -> Result<AppliedWorkspaceEdit> {
+   let root = workspace_root
+      .map(|root| {
+         root
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", root.display()))
+      })
+      .transpose()?;
+   let root = root.as_deref();
+   let mut applied = AppliedWorkspaceEdit::default();
+
+   let operations = match &edit.document_changes {
+      None => edit
+         .changes
+         .iter()
+         .flatten()
+         .map(|(uri, edits)| {
+            DocumentChangeOperation::Edit(TextDocumentEdit {
+               text_document: OptionalVersionedTextDocumentIdentifier {
+                  uri: uri.clone(),
+                  version: None,
+               },
+               edits: edits.iter().cloned().map(OneOf::Left).collect(),
+            })
+         })
+         .collect(),
+      Some(DocumentChanges::Edits(edits)) => edits
+         .iter()
+         .cloned()
+         .map(DocumentChangeOperation::Edit)
+         .collect(),
+      Some(DocumentChanges::Operations(operations)) => operations.clone(),
+   };
+   for operation in &operations {
+      match operation {
+         DocumentChangeOperation::Edit(document_edit) => {
+            let uri = &document_edit.text_document.uri;
+            let edits = document_edit.edits.iter().map(|edit| match edit {
+               OneOf::Left(edit) => edit,
+               OneOf::Right(annotated) => &annotated.text_edit,
+            });
+            apply_text_edits_to_file(&resolve_path(uri, root)?, edits)?;
+            applied.edited_files.push(display(&file_path(uri)?));
+         }
+         DocumentChangeOperation::Op(ResourceOp::Create(create)) => {
+            if create_file(create, root)? {
+               applied
+                  .created_files
+                  .push(display(&file_path(&create.uri)?));
+            }
+         }
+         DocumentChangeOperation::Op(ResourceOp::Rename(rename)) => {
+            if rename_file(rename, root)? {
+               applied.renamed_files.push((
+                  display(&file_path(&rename.old_uri)?),
+                  display(&file_path(&rename.new_uri)?),
+               ));
+            }
+         }
+         DocumentChangeOperation::Op(ResourceOp::Delete(delete)) => {
+            if delete_file(delete, root)? {
+               applied
+                  .deleted_files
+                  .push(display(&file_path(&delete.uri)?));
+            }
+         }
+      }
+   }
+
+   Ok(applied)
+}
+
+/// `text` with `edits` applied. Offsets are resolved against the original text, and edits
+/// starting at the same position are inserted in the order given.
+pub fn apply_text_edits<'a>(
+   text: &str,
+   edits: impl IntoIterator<Item = &'a TextEdit>,
+) -> Result<String> {
+   let mut resolved = Vec::new();
+   for edit in edits {
+      let start = position_to_offset(text, edit.range.start, PositionEncoding::Utf16);
+      let end = position_to_offset(text, edit.range.end, PositionEncoding::Utf16);
+      let (Some(start), Some(end)) = (start, end) else {
+         bail!("Edit range {:?} is outside the document", edit.range);
+      };
+      if start > end {
+         bail!("Edit range {:?} ends before it starts", edit.range);
+      }
+      resolved.push((start, end, edit.new_text.as_str()));
+   }
+
+   // Apply from the end so earlier offsets stay valid. Among inserts at the same position,
+   // later ones go first so the inserted texts end up in the order given.
+   let mut order: Vec<usize> = (0..resolved.len()).collect();
+   order.sort_by_key(|&index| Reverse((resolved[index].0, resolved[index].1, index)));
+
+   let mut result = text.to_string();
+   for index in order {
+      let (start, end, new_text) = resolved[index];
+      result.replace_range(start..end, new_text);
+   }
+   Ok(result)
+}
+
+fn apply_text_edits_to_file<'a>(
+   path: &Path,
+   edits: impl IntoIterator<Item = &'a TextEdit>,
+) -> Result<()> {
+   let text =
+      fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+   let edited = apply_text_edits(&text, edits)
+      .with_context(|| format!("Failed to edit {}", path.display()))?;
+   write_file_atomic(path, edited.as_bytes(), false)
+}
+
+fn create_file(create: &CreateFile, root: Option<&Path>) -> Result<bool> {
+   let path = resolve_path(&create.uri, root)?;
+   let options = create.options.as_ref();
+   let overwrite = options.and_then(|options| options.overwrite) == Some(true);
+   let ignore_if_exists = options.and_then(|options| options.ignore_if_exists) == Some(true);
+
+   if path.exists() && !overwrite {
+      if ignore_if_exists {
+         return Ok(false);
+      }
+      bail!("Cannot create {}: it already exists", path.display());
+   }
+   if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)
+         .with_context(|| format!("Failed to create {}", parent.display()))?;
+   }
+   write_file_atomic(&path, b"", false)?;
+   Ok(true)
+}
+
+fn rename_file(rename: &RenameFile, root: Option<&Path>) -> Result<bool> {
+   let old_path = resolve_path(&rename.old_uri, root)?;
+   let new_path = resolve_path(&rename.new_uri, root)?;
+   let options = rename.options.as_ref();
+   let overwrite = options.and_then(|options| options.overwrite) == Some(true);
+   let ignore_if_exists = options.and_then(|options| options.ignore_if_exists) == Some(true);
+
+   if new_path.exists() && !overwrite {
+      if ignore_if_exists {
+         return Ok(false);
+      }
+      bail!("Cannot rename to {}: it already exists", new_path.display());
+   }
+   if let Some(parent) = new_path.parent() {
+      fs::create_dir_all(parent)
+         .with_context(|| format!("Failed to create {}", parent.display()))?;
+   }
+   fs::rename(&old_path, &new_path).with_context(|| {
+      format!(
+         "Failed to rename {} to {}",
+         old_path.display(),
+         new_path.display()
+      )
+   })?;
+   Ok(true)
+}
+
+fn delete_file(delete: &DeleteFile, root: Option<&Path>) -> Result<bool> {
+   let path = resolve_path(&delete.uri, root)?;
+   let options = delete.options.as_ref();
+   let recursive = options.and_then(|options| options.recursive) == Some(true);
+   let ignore_if_not_exists =
+      options.and_then(|options| options.ignore_if_not_exists) == Some(true);
+
+   // A symlink is removed itself, never the file or directory it points at
+   let Ok(metadata) = fs::symlink_metadata(&path) else {
+      if ignore_if_not_exists {
+         return Ok(false);
+      }
+      bail!("Cannot delete {}: it does not exist", path.display());
+   };
+   let deleted = if !metadata.is_dir() {
+      fs::remove_file(&path)
+   } else if !recursive {
+      fs::remove_dir(&path)
+   } else if root.is_some() {
+      fs::remove_dir_all(&path)
+   } else {
+      bail!(
+         "Refusing to delete {} recursively without an open workspace",
+         path.display()
+      );
+   };
+   deleted.with_context(|| format!("Failed to delete {}", path.display()))?;
+   Ok(true)
+}
+
+/// Resolve the local path of `uri` the way the filesystem commands do: it must be absolute and
+/// free of `..`, and its existing ancestors are canonicalized. The final component is not
+/// followed, so a symlink stays a symlink. With a `root`, the path must lie strictly inside it,
+/// and so must the target of a symlink it names.
+fn resolve_path(uri: &Url, root: Option<&Path>) -> Result<PathBuf> {
+   let path = file_path(uri)?;
+   if path
+      .components()
+      .any(|component| component == Component::ParentDir)
+   {
+      bail!("Path must not contain '..': {}", path.display());
+   }
+   let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+      bail!("Path has no file name: {}", path.display());
+   };
+
+   let mut existing = parent;
+   let mut missing = Vec::new();
+   while !existing.exists() {
+      missing.extend(existing.file_name());
+      existing = existing
+         .parent()
+         .with_context(|| format!("Failed to resolve {}", path.display()))?;
+   }
+   let mut resolved = existing
+      .canonicalize()
+      .with_context(|| format!("Failed to resolve {}", existing.display()))?;
+   resolved.extend(missing.iter().rev());
+   resolved.push(file_name);
+
+   if let Some(root) = root {
+      let target = match fs::symlink_metadata(&resolved) {
+         Ok(metadata) if metadata.is_symlink() => resolved.canonicalize().ok(),
+         _ => Some(resolved.clone()),
+      };
+      let inside = |path: &Path| path != root && path.starts_with(root);
+      if !inside(&resolved) || !target.as_deref().is_some_and(inside) {
+         bail!("{} is outside the workspace", path.display());
+      }
+   }
+   Ok(resolved)
+}
+
+fn file_path(uri: &Url) -> Result<PathBuf> {
+   uri.to_file_path()
+      .map_err(|_| anyhow::anyhow!("Not a local file: {}", uri))
+}
+
+fn display(path: &Path) -> String {
+   path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use lsp_types::{CreateFileOptions, DeleteFileOptions, Position, Range};
+   use std::collections::HashMap;
+
+   fn edit(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+      TextEdit {
+         range: Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1)),
+         new_text: new_text.to_string(),
+      }
+   }
+
+   #[test]
+   fn applies_edits_against_the_original_text() {
+      let text = "use foo::Bar;\nlet x = Bar::new();\n";
+      let edits = [
+         edit((1, 8), (1, 11), "Baz"),
+         edit((0, 9), (0, 12), "Baz"),
+         edit((0, 0), (0, 0), "// a\n"),
+         edit((0, 0), (0, 0), "// b\n"),
+      ];
+
+      assert_eq!(
+         apply_text_edits(text, &edits).unwrap(),
+         "// a\n// b\nuse foo::Baz;\nlet x = Baz::new();\n"
+      );
+      assert!(apply_text_edits(text, &[edit((5, 0), (5, 1), "")]).is_err());
+   }
+
+   #[test]
+   fn applies_changes_and_resource_operations_in_order() {
+      let temp_dir = tempfile::tempdir().unwrap();
+      let old_path = temp_dir.path().join("old.rs");
+      let new_path = temp_dir.path().join("src/new.rs");
+      let other_path = temp_dir.path().join("other.rs");
+      let created_path = temp_dir.path().join("created.rs");
+      fs::write(&old_path, "fn old() {}\n").unwrap();
+      fs::write(&other_path, "old();\n").unwrap();
+
+      let uri = |path: &Path| Url::from_file_path(path).unwrap();
+      let workspace_edit = WorkspaceEdit {
+         changes: Some(HashMap::from([(
+            uri(&other_path),
+            vec![edit((0, 0), (0, 0), "ignored ")],
+         )])),
+         document_changes: Some(DocumentChanges::Operations(vec![
+            DocumentChangeOperation::Edit(TextDocumentEdit {
+               text_document: OptionalVersionedTextDocumentIdentifier {
+                  uri: uri(&other_path),
+                  version: None,
+               },
+               edits: vec![OneOf::Left(edit((0, 0), (0, 3), "new"))],
+            }),
+            DocumentChangeOperation::Op(ResourceOp::Rename(RenameFile {
+               old_uri: uri(&old_path),
+               new_uri: uri(&new_path),
+               options: None,
+               annotation_id: None,
+            })),
+            DocumentChangeOperation::Edit(TextDocumentEdit {
+               text_document: OptionalVersionedTextDocumentIdentifier {
+                  uri: uri(&new_path),
+                  version: None,
+               },
+               edits: vec![OneOf::Left(edit((0, 3), (0, 6), "new"))],
+            }),
+            DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+               uri: uri(&other_path),
+               options: Some(CreateFileOptions {
+                  overwrite: None,
+                  ignore_if_exists: Some(true),
+               }),
+               annotation_id: None,
+            })),
+            DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+               uri: uri(&created_path),
+               options: None,
+               annotation_id: None,
+            })),
+         ])),
+         change_annotations: None,
+      };
+
+      let applied = apply_workspace_edit(&workspace_edit, Some(temp_dir.path())).unwrap();
+
+      assert_eq!(fs::read_to_string(&other_path).unwrap(), "new();\n");
+      assert_eq!(fs::read_to_string(&new_path).unwrap(), "fn new() {}\n");
+      assert_eq!(fs::read_to_string(&created_path).unwrap(), "");
+      assert!(!old_path.exists());
+      assert_eq!(applied.edited_files.len(), 2);
+      assert_eq!(applied.renamed_files.len(), 1);
+      assert_eq!(applied.created_files, vec![display(&created_path)]);
+
+      let delete = WorkspaceEdit {
+         document_changes: Some(DocumentChanges::Operations(vec![
+            DocumentChangeOperation::Op(ResourceOp::Delete(DeleteFile {
+               uri: uri(&old_path),
+               options: None,
+            })),
+         ])),
+         ..Default::default()
+      };
+      assert!(apply_workspace_edit(&delete, Some(temp_dir.path())).is_err());
+   }
+
+   #[cfg(unix)]
+   #[test]
+   fn edits_symlinked_files_without_replacing_the_link() {
+      let temp_dir = tempfile::tempdir().unwrap();
+      let target = temp_dir.path().join("src/lib.rs");
+      let link = temp_dir.path().join("lib.rs");
+      fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+      fs::write(&target, "fn old() {}\n").unwrap();
+      std::os::unix::fs::symlink(&target, &link).unwrap();
+
+      let workspace_edit = WorkspaceEdit {
+         changes: Some(HashMap::from([(
+            Url::from_file_path(&link).unwrap(),
+            vec![edit((0, 3), (0, 6), "new")],
+         )])),
+         ..Default::default()
+      };
+      apply_workspace_edit(&workspace_edit, Some(temp_dir.path())).unwrap();
+
+      assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+      assert_eq!(fs::read_to_string(&target).unwrap(), "fn new() {}\n");
+   }
+
+   #[test]
+   fn keeps_resource_operations_inside_the_workspace() {
+      let temp_dir = tempfile::tempdir().unwrap();
+      let workspace = temp_dir.path().join("workspace");
+      let outside = temp_dir.path().join("outside");
+      fs::create_dir_all(workspace.join("src")).unwrap();
+      fs::create_dir_all(&outside).unwrap();
+      fs::write(outside.join("keep.rs"), "").unwrap();
+
+      let uri = |path: &Path| Url::from_file_path(path).unwrap();
+      let delete = |path: &Path, recursive: bool| WorkspaceEdit {
+         document_changes: Some(DocumentChanges::Operations(vec![
+            DocumentChangeOperation::Op(ResourceOp::Delete(DeleteFile {
+               uri: uri(path),
+               options: Some(DeleteFileOptions {
+                  recursive: Some(recursive),
+                  ignore_if_not_exists: None,
+                  annotation_id: None,
+               }),
+            })),
+         ])),
+         ..Default::default()
+      };
+
+      let root = Some(workspace.as_path());
+      assert!(apply_workspace_edit(&delete(&outside, true), root).is_err());
+      assert!(apply_workspace_edit(&delete(&workspace, true), root).is_err());
+      assert!(
+         apply_workspace_edit(&delete(&workspace.join("src/../../outside"), true), root).is_err()
+      );
+      assert!(apply_workspace_edit(&delete(&workspace.join("src"), true), None).is_err());
+      assert!(outside.join("keep.rs").exists());
+      assert!(workspace.join("src").exists());
+
+      #[cfg(unix)]
+      {
+         let link = workspace.join("link");
+         std::os::unix::fs::symlink(&outside, &link).unwrap();
+         apply_workspace_edit(&delete(&link, true), root).unwrap_err();
+         std::os::unix::fs::symlink(workspace.join("src"), workspace.join("src_link")).unwrap();
+         apply_workspace_edit(&delete(&workspace.join("src_link"), true), root).unwrap();
+         assert!(!workspace.join("src_link").exists());
+         assert!(workspace.join("src").exists());
+      }
+
+      apply_workspace_edit(&delete(&workspace.join("src"), true), root).unwrap();
+      assert!(!workspace.join("src").exists());
+   }
+}
@@ -0,0 +1,174 @@
+use anyhow::{Context, Result};
+use std::{
+   ffi::OsString,
+   fs::{self, File, OpenOptions},
+   io::{self, Write},
+   path::{Path, PathBuf},
+   sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Distinguishes temporary files of concurrent writes to the same path within this process
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Write `content` to `path` without ever leaving it half written. The content goes to a
+/// temporary sibling that is renamed over the target, so a crash or a full disk leaves the old
+/// file intact. The target's permissions carry over to the new file. With `backup`, the
+/// previous content is copied to `<path>.bak` first. A symlink is written through: the file it
+/// points at is replaced and the link itself stays in place.
+pub fn write_file_atomic(path: &Path, content: &[u8], backup: bool) -> Result<()> {
+   let path = &resolve_symlink(path);
+   let file_name = path
+      .file_name()
+      .with_context(|| format!("Invalid file path: {}", path.display()))?;
+   let (temp_path, mut file) = create_temp_sibling(path, &file_name.to_string_lossy())
+      .with_context(|| format!("Failed to write {}", path.display()))?;
+
+   let written = (|| -> io::Result<()> {
+      file.write_all(content)?;
+      file.sync_all()?;
+      if let Ok(metadata) = fs::metadata(path) {
+         fs::set_permissions(&temp_path, metadata.permissions())?;
+         if backup {
+            fs::copy(path, backup_path(path))?;
+         }
+      }
+      fs::rename(&temp_path, path)
+   })();
+
+   if written.is_err() {
+      let _ = fs::remove_file(&temp_path);
+   }
+   written.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The file a symlink at `path` ends up at, or `path` itself when it is not a symlink. A
+/// dangling link resolves to the path it names, so writing creates that file.
+fn resolve_symlink(path: &Path) -> PathBuf {
+   let mut resolved = path.to_path_buf();
+   // Bounded like the kernel's own limit, so a symlink loop cannot spin forever
+   for _ in 0..40 {
+      match fs::symlink_metadata(&resolved) {
+         Ok(metadata) if metadata.is_symlink() => {}
+         _ => return resolved,
+      }
+      let Ok(target) = fs::read_link(&resolved) else {
+         return resolved;
+      };
+      resolved = match resolved.parent() {
+         Some(parent) => parent.join(target),
+         None => target,
+      };
+   }
+   resolved
+}
+
+/// Create a new file next to `path` that no other writer is using, in this process or another
+fn create_temp_sibling(path: &Path, file_name: &str) -> io::Result<(PathBuf, File)> {
+   loop {
+      let temp_path = path.with_file_name(format!(
+         ".{}.athas-tmp-{}-{}",
+         file_name,
+         std::process::id(),
+         TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+      ));
+      match OpenOptions::new()
+         .write(true)
+         .create_new(true)
+         .open(&temp_path)
+      {
+         Ok(file) => return Ok((temp_path, file)),
+         Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+         Err(error) => return Err(error),
+      }
+   }
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+   let mut backup = OsString::from(path.as_os_str());
+   backup.push(".bak");
+   PathBuf::from(backup)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn replaces_content_and_keeps_a_backup() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let path = temp_dir.path().join("notes.txt");
+
+      write_file_atomic(&path, b"first", true).expect("create file");
+      assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+      assert!(!backup_path(&path).exists());
+
+      write_file_atomic(&path, b"second", true).expect("overwrite file");
+      assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+      assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "first");
+
+      let entries = fs::read_dir(temp_dir.path()).unwrap().count();
+      assert_eq!(entries, 2);
+   }
+
+   #[test]
+   fn concurrent_writes_use_separate_temp_files() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let path = temp_dir.path().join("shared.txt");
+
+      std::thread::scope(|scope| {
+         for index in 0..8 {
+            let path = &path;
+            scope.spawn(move || {
+               let content = format!("writer {index}");
+               write_file_atomic(path, content.as_bytes(), false).expect("write file");
+            });
+         }
+      });
+
+      assert!(fs::read_to_string(&path).unwrap().starts_with("writer "));
+      let entries = fs::read_dir(temp_dir.path()).unwrap().count();
+      assert_eq!(entries, 1);
+   }
+
+   #[cfg(unix)]
+   #[test]
+   fn preserves_permissions() {
+      use std::os::unix::fs::PermissionsExt;
+
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let path = temp_dir.path().join("run.sh");
+      fs::write(&path, "echo old").unwrap();
+      fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+      write_file_atomic(&path, b"echo new", false).expect("overwrite file");
+
+      let mode = fs::metadata(&path).unwrap().permissions().mode();
+      assert_eq!(mode & 0o777, 0o755);
+      assert!(!backup_path(&path).exists());
+   }
+
+   #[cfg(unix)]
+   #[test]
+   fn writes_through_symlinks() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let target = temp_dir.path().join("real/config.toml");
+      let link = temp_dir.path().join("config.toml");
+      fs::create_dir_all(target.parent().unwrap()).unwrap();
+      fs::write(&target, "old").unwrap();
+      std::os::unix::fs::symlink("real/config.toml", &link).unwrap();
+
+      write_file_atomic(&link, b"new", false).expect("write through link");
+
+      assert!(fs::symlink_metadata(&link).unwrap().is_symlink());
+      assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+
+      let dangling = temp_dir.path().join("dangling.toml");
+      std::os::unix::fs::symlink("real/missing.toml", &dangling).unwrap();
+      write_file_atomic(&dangling, b"created", false).expect("write through dangling link");
+      assert!(fs::symlink_metadata(&dangling).unwrap().is_symlink());
+      assert_eq!(
+         fs::read_to_string(temp_dir.path().join("real/missing.toml")).unwrap(),
+         "created"
+      );
+   }
+}
@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use ignore::WalkBuilder;
+use serde::Serialize;
+use std::{
+   collections::HashSet,
+   fs,
+   path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryEntry {
+   pub name: String,
+   pub path: String,
+   pub is_dir: bool,
+   pub is_symlink: bool,
+   pub is_ignored: bool,
+}
+
+/// List one level of `path`, directories first. Entries matched by `.gitignore`, `.ignore`, or
+/// the git exclude files are dropped when `respect_gitignore` is set and flagged with
+/// `is_ignored` otherwise. Dotfiles are dropped unless `show_hidden` is set.
+pub fn read_directory_filtered(
+   path: &Path,
+   respect_gitignore: bool,
+   show_hidden: bool,
+) -> Result<Vec<DirectoryEntry>> {
+   let not_ignored = non_ignored_children(path);
+   let read_dir = fs::read_dir(path)
+      .with_context(|| format!("Failed to read directory: {}", path.display()))?;
+
+   let mut entries = Vec::new();
+   for entry in read_dir.flatten() {
+      let name = entry.file_name().to_string_lossy().to_string();
+      if !show_hidden && name.starts_with('.') {
+         continue;
+      }
+
+      let entry_path = entry.path();
+      let is_ignored = !not_ignored.contains(&entry_path);
+      if respect_gitignore && is_ignored {
+         continue;
+      }
+
+      let Ok(file_type) = entry.file_type() else {
+         continue;
+      };
+      let is_symlink = file_type.is_symlink();
+      let is_dir = if is_symlink {
+         entry_path.is_dir()
+      } else {
+         file_type.is_dir()
+      };
+
+      entries.push(DirectoryEntry {
+         name,
+         path: entry_path.to_string_lossy().to_string(),
+         is_dir,
+         is_symlink,
+         is_ignored,
+      });
+   }
+
+   sort_directory_entries(&mut entries);
+   Ok(entries)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryTreeNode {
+   #[serde(flatten)]
+   pub entry: DirectoryEntry,
+   /// `None` for files, symlinks, unreadable directories, and directories at the depth limit.
+   pub children: Option<Vec<DirectoryTreeNode>>,
+}
+
+/// List `path` and its subdirectories up to `max_depth` levels, directories first at each
+/// level. A `max_depth` of 1 is the same listing as `read_directory_filtered`. Symlinked
+/// directories are not followed.
+pub fn read_directory_recursive(path: &Path, max_depth: usize) -> Result<Vec<DirectoryTreeNode>> {
+   let entries = read_directory_filtered(path, false, true)?;
+
+   Ok(entries
+      .into_iter()
+      .map(|entry| {
+         let children = (entry.is_dir && !entry.is_symlink && max_depth > 1)
+            .then(|| read_directory_recursive(Path::new(&entry.path), max_depth - 1).ok())
+            .flatten();
+         DirectoryTreeNode { entry, children }
+      })
+      .collect())
+}
+
+/// Directories first, then case-insensitive by name.
+fn sort_directory_entries(entries: &mut [DirectoryEntry]) {
+   entries.sort_by(|a, b| {
+      b.is_dir
+         .cmp(&a.is_dir)
+         .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+   });
+}
+
+fn non_ignored_children(path: &Path) -> HashSet<PathBuf> {
+   WalkBuilder::new(path)
+      .max_depth(Some(1))
+      .hidden(false)
+      .build()
+      .flatten()
+      .filter(|entry| entry.depth() == 1)
+      .map(|entry| entry.into_path())
+      .collect()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn names(entries: &[DirectoryEntry]) -> Vec<&str> {
+      entries.iter().map(|entry| entry.name.as_str()).collect()
+   }
+
+   fn fixture() -> tempfile::TempDir {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let root = temp_dir.path();
+      fs::create_dir(root.join(".git")).expect("git dir");
+      fs::write(root.join(".gitignore"), "target/\n*.log\n").expect("gitignore");
+      fs::create_dir(root.join("target")).expect("target dir");
+      fs::create_dir(root.join("src")).expect("src dir");
+      fs::write(root.join("debug.log"), "").expect("log file");
+      fs::write(root.join("README.md"), "").expect("readme");
+      temp_dir
+   }
+
+   #[test]
+   fn skips_ignored_and_hidden_entries() {
+      let temp_dir = fixture();
+
+      let entries = read_directory_filtered(temp_dir.path(), true, false).expect("read");
+
+      assert_eq!(names(&entries), vec!["src", "README.md"]);
+   }
+
+   #[test]
+   fn flags_ignored_entries_when_showing_everything() {
+      let temp_dir = fixture();
+
+      let entries = read_directory_filtered(temp_dir.path(), false, true).expect("read");
+
+      assert_eq!(
+         names(&entries),
+         vec![
+            ".git",
+            "src",
+            "target",
+            ".gitignore",
+            "debug.log",
+            "README.md"
+         ]
+      );
+      let ignored: Vec<_> = entries
+         .iter()
+         .filter(|entry| entry.is_ignored)
+         .map(|entry| entry.name.as_str())
+         .collect();
+      assert_eq!(ignored, vec!["target", "debug.log"]);
+   }
+
+   #[test]
+   fn reads_nested_directories_up_to_max_depth() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let root = temp_dir.path();
+      fs::create_dir_all(root.join("a/b/c")).expect("nested dirs");
+      fs::write(root.join("a/file.txt"), "").expect("file");
+
+      let tree = read_directory_recursive(root, 2).expect("read");
+
+      assert_eq!(tree.len(), 1);
+      let a = &tree[0];
+      assert_eq!(a.entry.name, "a");
+      let a_children = a.children.as_ref().expect("a expanded");
+      assert_eq!(
+         a_children
+            .iter()
+            .map(|node| node.entry.name.as_str())
+            .collect::<Vec<_>>(),
+         vec!["b", "file.txt"]
+      );
+      assert!(a_children[0].children.is_none());
+      assert!(a_children[1].children.is_none());
+   }
+}
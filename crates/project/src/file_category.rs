@@ -0,0 +1,104 @@
+use serde::Serialize;
+use std::{fs::File, io::Read, path::Path};
+
+/// Bytes read from the start of a file to tell text from binary content
+pub(crate) const SNIFF_LEN: usize = 8 * 1024;
+
+const IMAGE_EXTENSIONS: &[&str] = &[
+   "png", "jpg", "jpeg", "gif", "bmp", "svg", "webp", "ico", "tiff", "tif", "avif", "heic", "heif",
+   "jfif", "apng",
+];
+
+const ARCHIVE_EXTENSIONS: &[&str] = &[
+   "zip", "tar", "gz", "tgz", "bz2", "tbz2", "xz", "txz", "zst", "7z", "rar", "jar", "war", "dmg",
+   "iso",
+];
+
+/// How a file should be opened: text goes to the editor, images to the image viewer, and
+/// archives and other binaries to the OS default application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileCategory {
+   Text,
+   Image,
+   Archive,
+   Binary,
+}
+
+/// Classify `path` by its extension, falling back to sniffing its first bytes for NUL bytes
+/// and invalid UTF-8
+pub fn file_category(path: &Path) -> std::io::Result<FileCategory> {
+   let extension = path
+      .extension()
+      .map(|extension| extension.to_string_lossy().to_lowercase());
+   if let Some(extension) = extension.as_deref() {
+      if IMAGE_EXTENSIONS.contains(&extension) {
+         return Ok(FileCategory::Image);
+      }
+      if ARCHIVE_EXTENSIONS.contains(&extension) {
+         return Ok(FileCategory::Archive);
+      }
+   }
+
+   let mut head = Vec::with_capacity(SNIFF_LEN);
+   File::open(path)?
+      .take(SNIFF_LEN as u64)
+      .read_to_end(&mut head)?;
+   Ok(if looks_like_text(&head) {
+      FileCategory::Text
+   } else {
+      FileCategory::Binary
+   })
+}
+
+pub(crate) fn looks_like_text(head: &[u8]) -> bool {
+   if head.contains(&0) {
+      return false;
+   }
+   match std::str::from_utf8(head) {
+      Ok(_) => true,
+      // A multi-byte character cut off by the sniff length is still text
+      Err(error) => error.error_len().is_none() && head.len() - error.valid_up_to() < 4,
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use std::fs;
+
+   #[test]
+   fn classifies_by_extension_and_content() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let write = |name: &str, content: &[u8]| {
+         let path = temp_dir.path().join(name);
+         fs::write(&path, content).unwrap();
+         path
+      };
+
+      let categories = [
+         (write("main.rs", b"fn main() {}\n"), FileCategory::Text),
+         (write("notes", "caf\u{e9}\n".as_bytes()), FileCategory::Text),
+         (write("logo.PNG", b"\x89PNG"), FileCategory::Image),
+         (write("release.tar.gz", b"\x1f\x8b"), FileCategory::Archive),
+         (
+            write("app.bin", b"\x7fELF\x02\x01\x00\x00"),
+            FileCategory::Binary,
+         ),
+         (
+            write("latin1.txt", b"caf\xe9 au lait"),
+            FileCategory::Binary,
+         ),
+      ];
+      for (path, category) in categories {
+         assert_eq!(
+            file_category(&path).unwrap(),
+            category,
+            "{}",
+            path.display()
+         );
+      }
+
+      assert!(looks_like_text(&"\u{e9}".as_bytes()[..1]));
+   }
+}
@@ -1,6 +1,23 @@
+mod atomic_write;
+mod directory;
+mod file_category;
+mod line_ending;
+mod replace;
+mod scratch;
+mod stats;
+
 use anyhow::{Context, Result, bail};
+pub use atomic_write::write_file_atomic;
+pub use directory::{
+   DirectoryEntry, DirectoryTreeNode, read_directory_filtered, read_directory_recursive,
+};
+pub use file_category::{FileCategory, file_category};
+pub use line_ending::{LineEnding, write_text_file_atomic};
 use notify::RecursiveMode;
 use notify_debouncer_mini::{DebounceEventResult, Debouncer, new_debouncer};
+pub use replace::{FileReplaceResult, ReplaceOptions, replace_in_files};
+pub use scratch::{SCRATCH_FILE_MAX_AGE, cleanup_scratch_files, create_scratch_file, scratch_dir};
+pub use stats::{FileStats, count_file_stats};
 use std::{
    collections::{HashMap, HashSet},
    path::PathBuf,
@@ -8,6 +25,9 @@ use std::{
    time::{Duration, SystemTime},
 };
 
+/// How long after its creation a file may be written and still be reported as created.
+const NEW_FILE_WINDOW: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct FileChangeEvent {
    pub path: String,
@@ -18,6 +38,7 @@ pub struct FileChangeEvent {
 #[serde(rename_all = "snake_case")]
 pub enum FileChangeType {
    Opened,
+   Created,
    Reloaded,
    Deleted,
 }
@@ -53,6 +74,29 @@ impl FileWatcher {
       self.watch_path_with_mode(path, false).await
    }
 
+   /// Watch a workspace directory recursively. A root that is already watched shallowly is
+   /// upgraded to a recursive watch.
+   pub async fn watch_workspace(&self, path: String) -> Result<()> {
+      let path_buf = PathBuf::from(&path);
+      if !path_buf.is_dir() {
+         bail!("Workspace is not a directory: {}", path);
+      }
+
+      let already_watched = self.watched_paths.lock().unwrap().contains(&path_buf);
+      if !already_watched {
+         return self.watch_path_with_mode(path, true).await;
+      }
+
+      let mut debouncer_guard = self.debouncer.lock().unwrap();
+      if let Some(debouncer) = debouncer_guard.as_mut() {
+         debouncer
+            .watcher()
+            .watch(&path_buf, RecursiveMode::Recursive)?;
+      }
+
+      Ok(())
+   }
+
    async fn watch_path_with_mode(&self, path: String, recursive: bool) -> Result<()> {
       let path_buf = PathBuf::from(&path);
 
@@ -189,7 +233,12 @@ impl FileWatcher {
             }
          } else {
             files.insert(path.clone(), current_mtime);
-            Some(FileChangeType::Opened)
+            if Self::was_created_recently(&metadata, current_mtime) {
+               Some(FileChangeType::Created)
+            } else {
+               // An existing file we were not tracking yet, e.g. in a nested directory.
+               Some(FileChangeType::Reloaded)
+            }
          }
       } else {
          log::warn!(
@@ -200,6 +249,20 @@ impl FileWatcher {
       }
    }
 
+   /// Whether a file first seen by the watcher was created rather than modified, judged by its
+   /// birth time being close to its modification time. Platforms without birth times count
+   /// every newly seen file as created.
+   fn was_created_recently(metadata: &std::fs::Metadata, modified: SystemTime) -> bool {
+      let Ok(created) = metadata.created() else {
+         return true;
+      };
+
+      modified
+         .duration_since(created)
+         .map(|age| age <= NEW_FILE_WINDOW)
+         .unwrap_or(true)
+   }
+
    fn setup_path_watching(
       &self,
       path_buf: &PathBuf,
@@ -307,3 +370,40 @@ impl FileWatcher {
       Ok(())
    }
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn classifies_new_modified_and_deleted_files() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let path = temp_dir.path().join("notes.txt");
+      let known_files = Arc::new(Mutex::new(HashMap::new()));
+
+      std::fs::write(&path, "first").expect("write file");
+      assert!(matches!(
+         FileWatcher::determine_event_type(&path, &known_files),
+         Some(FileChangeType::Created)
+      ));
+      assert!(FileWatcher::determine_event_type(&path, &known_files).is_none());
+
+      let file = std::fs::File::options()
+         .write(true)
+         .open(&path)
+         .expect("open file");
+      file
+         .set_modified(SystemTime::now() + Duration::from_secs(60))
+         .expect("touch file");
+      assert!(matches!(
+         FileWatcher::determine_event_type(&path, &known_files),
+         Some(FileChangeType::Reloaded)
+      ));
+
+      std::fs::remove_file(&path).expect("remove file");
+      assert!(matches!(
+         FileWatcher::determine_event_type(&path, &known_files),
+         Some(FileChangeType::Deleted)
+      ));
+   }
+}
@@ -0,0 +1,125 @@
+use crate::write_file_atomic;
+use anyhow::{Result, bail};
+use std::{fs, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+   Lf,
+   Crlf,
+   Cr,
+}
+
+impl LineEnding {
+   /// Parse an editorconfig-style `end_of_line` value
+   pub fn parse(name: &str) -> Result<Self> {
+      match name.to_ascii_lowercase().as_str() {
+         "lf" => Ok(Self::Lf),
+         "crlf" => Ok(Self::Crlf),
+         "cr" => Ok(Self::Cr),
+         _ => bail!("Unknown line ending: {}", name),
+      }
+   }
+
+   /// The most common line ending in `text`, or `None` when it has no line breaks. Ties go
+   /// to LF, then CRLF.
+   pub fn detect(text: &str) -> Option<Self> {
+      let bytes = text.as_bytes();
+      let (mut lf, mut crlf, mut cr) = (0usize, 0usize, 0usize);
+      let mut index = 0;
+      while index < bytes.len() {
+         match bytes[index] {
+            b'\r' if bytes.get(index + 1) == Some(&b'\n') => {
+               crlf += 1;
+               index += 1;
+            }
+            b'\r' => cr += 1,
+            b'\n' => lf += 1,
+            _ => {}
+         }
+         index += 1;
+      }
+
+      if lf + crlf + cr == 0 {
+         None
+      } else if lf >= crlf && lf >= cr {
+         Some(Self::Lf)
+      } else if crlf >= cr {
+         Some(Self::Crlf)
+      } else {
+         Some(Self::Cr)
+      }
+   }
+
+   fn as_str(self) -> &'static str {
+      match self {
+         Self::Lf => "\n",
+         Self::Crlf => "\r\n",
+         Self::Cr => "\r",
+      }
+   }
+
+   /// `text` with every line break replaced by this line ending
+   pub fn apply(self, text: &str) -> String {
+      let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+      match self {
+         Self::Lf => normalized,
+         _ => normalized.replace('\n', self.as_str()),
+      }
+   }
+}
+
+/// Save `content` atomically as text. Line breaks are converted to `line_ending`, or when it
+/// is `None` to the dominant line ending of the file being replaced, so saving never flips a
+/// file's convention. New files keep the line breaks of `content`.
+pub fn write_text_file_atomic(
+   path: &Path,
+   content: &str,
+   line_ending: Option<LineEnding>,
+   backup: bool,
+) -> Result<()> {
+   let line_ending = line_ending.or_else(|| {
+      fs::read_to_string(path)
+         .ok()
+         .and_then(|existing| LineEnding::detect(&existing))
+   });
+   match line_ending {
+      Some(line_ending) => write_file_atomic(path, line_ending.apply(content).as_bytes(), backup),
+      None => write_file_atomic(path, content.as_bytes(), backup),
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn detects_the_dominant_line_ending() {
+      assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), Some(LineEnding::Crlf));
+      assert_eq!(LineEnding::detect("a\nb\r\n"), Some(LineEnding::Lf));
+      assert_eq!(LineEnding::detect("a\rb\r"), Some(LineEnding::Cr));
+      assert_eq!(LineEnding::detect("no breaks"), None);
+      assert_eq!(LineEnding::Crlf.apply("a\nb\r\nc\rd"), "a\r\nb\r\nc\r\nd");
+      assert!(LineEnding::parse("CRLF").is_ok());
+      assert!(LineEnding::parse("native").is_err());
+   }
+
+   #[test]
+   fn keeps_the_line_endings_of_the_replaced_file() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let path = temp_dir.path().join("windows.txt");
+      fs::write(&path, "one\r\ntwo\r\n").unwrap();
+
+      write_text_file_atomic(&path, "one\ntwo\nthree\n", None, false).expect("save");
+      assert_eq!(
+         fs::read_to_string(&path).unwrap(),
+         "one\r\ntwo\r\nthree\r\n"
+      );
+
+      write_text_file_atomic(&path, "one\r\ntwo\r\n", Some(LineEnding::Lf), false).expect("save");
+      assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+
+      let new_path = temp_dir.path().join("new.txt");
+      write_text_file_atomic(&new_path, "a\r\nb\n", None, false).expect("save");
+      assert_eq!(fs::read_to_string(&new_path).unwrap(), "a\r\nb\n");
+   }
+}
@@ -0,0 +1,281 @@
+use crate::write_file_atomic;
+use anyhow::{Context, Result, bail};
+use ignore::WalkBuilder;
+use regex::{NoExpand, Regex};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplaceOptions {
+   pub search: String,
+   pub replace: String,
+   /// Treat `search` as a regular expression and expand `$1`-style groups in `replace`.
+   pub regex: bool,
+   pub respect_gitignore: bool,
+   /// Compute the changes without writing them.
+   pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReplaceResult {
+   pub file_path: String,
+   pub replacements: usize,
+   /// Unified diff of the change, only filled in for dry runs.
+   pub diff: Option<String>,
+}
+
+/// Replace every match of `options.search` in the text files under `root`, one line at a time
+/// like the content search. Binary and non-UTF-8 files are skipped. Each file is written to a
+/// temporary sibling and renamed into place so a crash never leaves it half written.
+pub fn replace_in_files(root: &Path, options: &ReplaceOptions) -> Result<Vec<FileReplaceResult>> {
+   if options.search.is_empty() {
+      bail!("Search text must not be empty");
+   }
+   let pattern = if options.regex {
+      options.search.clone()
+   } else {
+      regex::escape(&options.search)
+   };
+   let regex = Regex::new(&pattern).with_context(|| format!("Invalid regex: {}", pattern))?;
+
+   let respect_gitignore = options.respect_gitignore;
+   let walker = WalkBuilder::new(root)
+      .hidden(false)
+      .git_ignore(respect_gitignore)
+      .git_global(respect_gitignore)
+      .git_exclude(respect_gitignore)
+      .ignore(respect_gitignore)
+      .parents(respect_gitignore)
+      .filter_entry(|entry| entry.file_name() != ".git")
+      .sort_by_file_name(|a, b| a.cmp(b))
+      .build();
+
+   let mut results = Vec::new();
+   for entry in walker.flatten() {
+      if !entry
+         .file_type()
+         .is_some_and(|file_type| file_type.is_file())
+      {
+         continue;
+      }
+      let path = entry.path();
+      let Ok(content) = fs::read_to_string(path) else {
+         continue;
+      };
+      if content.contains('\0') {
+         continue;
+      }
+
+      let Some(replaced) = replace_lines(&content, &regex, &options.replace, options.regex) else {
+         continue;
+      };
+
+      let diff = if options.dry_run {
+         let relative = path.strip_prefix(root).unwrap_or(path);
+         Some(unified_diff(
+            &relative.to_string_lossy(),
+            &replaced.changed_lines,
+         ))
+      } else {
+         write_file_atomic(path, replaced.content.as_bytes(), false)?;
+         None
+      };
+
+      results.push(FileReplaceResult {
+         file_path: path.to_string_lossy().to_string(),
+         replacements: replaced.replacements,
+         diff,
+      });
+   }
+
+   Ok(results)
+}
+
+struct ReplacedContent {
+   content: String,
+   replacements: usize,
+   /// `(line index, old line, new line)` for every line that changed.
+   changed_lines: Vec<(usize, String, String)>,
+}
+
+fn replace_lines(
+   content: &str,
+   regex: &Regex,
+   replacement: &str,
+   expand: bool,
+) -> Option<ReplacedContent> {
+   let mut output = String::with_capacity(content.len());
+   let mut replacements = 0;
+   let mut changed_lines = Vec::new();
+
+   for (index, line) in content.split_inclusive('\n').enumerate() {
+      let text = line.trim_end_matches(['\n', '\r']);
+      let ending = &line[text.len()..];
+
+      let count = regex.find_iter(text).count();
+      if count == 0 {
+         output.push_str(line);
+         continue;
+      }
+
+      let new_text = if expand {
+         regex.replace_all(text, replacement)
+      } else {
+         regex.replace_all(text, NoExpand(replacement))
+      };
+      replacements += count;
+      if new_text != text {
+         changed_lines.push((index, text.to_string(), new_text.to_string()));
+      }
+      output.push_str(&new_text);
+      output.push_str(ending);
+   }
+
+   (replacements > 0 && !changed_lines.is_empty()).then_some(ReplacedContent {
+      content: output,
+      replacements,
+      changed_lines,
+   })
+}
+
+/// Build a unified diff without context lines. Each run of adjacent changed lines becomes one
+/// hunk. A replacement containing line breaks turns one line into several, so the new side is
+/// counted from the replaced text and later hunks move down by the lines added before them.
+fn unified_diff(path: &str, changed_lines: &[(usize, String, String)]) -> String {
+   let mut diff = format!("--- a/{}\n+++ b/{}\n", path, path);
+
+   let mut added_lines = 0;
+   let mut start = 0;
+   while start < changed_lines.len() {
+      let mut end = start + 1;
+      while end < changed_lines.len() && changed_lines[end].0 == changed_lines[end - 1].0 + 1 {
+         end += 1;
+      }
+
+      let hunk = &changed_lines[start..end];
+      let new_lines: Vec<&str> = hunk
+         .iter()
+         .flat_map(|(_, _, new)| new.split('\n'))
+         .collect();
+      let first_line = hunk[0].0 + 1;
+      diff.push_str(&format!(
+         "@@ -{},{} +{},{} @@\n",
+         first_line,
+         hunk.len(),
+         first_line + added_lines,
+         new_lines.len()
+      ));
+      for (_, old, _) in hunk {
+         diff.push_str(&format!("-{}\n", old));
+      }
+      for new in &new_lines {
+         diff.push_str(&format!("+{}\n", new));
+      }
+
+      added_lines += new_lines.len() - hunk.len();
+      start = end;
+   }
+
+   diff
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn options(search: &str, replace: &str) -> ReplaceOptions {
+      ReplaceOptions {
+         search: search.to_string(),
+         replace: replace.to_string(),
+         regex: false,
+         respect_gitignore: true,
+         dry_run: false,
+      }
+   }
+
+   fn fixture() -> tempfile::TempDir {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let root = temp_dir.path();
+      fs::create_dir(root.join(".git")).expect("git dir");
+      fs::write(root.join(".gitignore"), "target/\n").expect("gitignore");
+      fs::create_dir(root.join("target")).expect("target dir");
+      fs::create_dir(root.join("src")).expect("src dir");
+      fs::write(root.join("src/main.rs"), "let foo = 1;\nfoo + foo\r\nbar\n").expect("main");
+      fs::write(root.join("target/out.rs"), "foo\n").expect("ignored file");
+      temp_dir
+   }
+
+   #[test]
+   fn rewrites_matches_and_counts_them_per_file() {
+      let temp_dir = fixture();
+      let root = temp_dir.path();
+
+      let results = replace_in_files(root, &options("foo", "$baz")).expect("replace");
+
+      assert_eq!(results.len(), 1);
+      assert_eq!(results[0].replacements, 3);
+      assert_eq!(
+         fs::read_to_string(root.join("src/main.rs")).unwrap(),
+         "let $baz = 1;\n$baz + $baz\r\nbar\n"
+      );
+      assert_eq!(
+         fs::read_to_string(root.join("target/out.rs")).unwrap(),
+         "foo\n"
+      );
+   }
+
+   #[test]
+   fn dry_run_returns_diffs_without_writing() {
+      let temp_dir = fixture();
+      let root = temp_dir.path();
+      let mut dry_run = options(r"(\w+) \+ (\w+)", "$2 - $1");
+      dry_run.regex = true;
+      dry_run.dry_run = true;
+      dry_run.respect_gitignore = false;
+
+      let results = replace_in_files(root, &dry_run).expect("replace");
+
+      assert_eq!(results.len(), 1);
+      assert_eq!(
+         results[0].diff.as_deref(),
+         Some("--- a/src/main.rs\n+++ b/src/main.rs\n@@ -2,1 +2,1 @@\n-foo + foo\n+foo - foo\n")
+      );
+      assert_eq!(
+         fs::read_to_string(root.join("src/main.rs")).unwrap(),
+         "let foo = 1;\nfoo + foo\r\nbar\n"
+      );
+   }
+
+   #[test]
+   fn groups_adjacent_lines_into_one_hunk() {
+      let diff = unified_diff(
+         "a.txt",
+         &[
+            (0, "a".into(), "b".into()),
+            (1, "a".into(), "b".into()),
+            (4, "a".into(), "b".into()),
+         ],
+      );
+
+      assert_eq!(
+         diff,
+         "--- a/a.txt\n+++ b/a.txt\n@@ -1,2 +1,2 @@\n-a\n-a\n+b\n+b\n@@ -5,1 +5,1 @@\n-a\n+b\n"
+      );
+   }
+
+   #[test]
+   fn counts_lines_added_by_the_replacement() {
+      let diff = unified_diff(
+         "a.txt",
+         &[
+            (0, "a, b".into(), "a,\nb".into()),
+            (3, "c".into(), "d".into()),
+         ],
+      );
+
+      assert_eq!(
+         diff,
+         "--- a/a.txt\n+++ b/a.txt\n@@ -1,1 +1,2 @@\n-a, b\n+a,\n+b\n@@ -4,1 +5,1 @@\n-c\n+d\n"
+      );
+   }
+}
@@ -0,0 +1,190 @@
+use anyhow::{Context, Result, bail};
+use std::{
+   env,
+   fs::{self, File},
+   io::ErrorKind,
+   path::{Path, PathBuf},
+   time::{Duration, SystemTime},
+};
+
+/// Scratch files untouched for this long are removed by `cleanup_scratch_files`
+pub const SCRATCH_FILE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Directory under the OS temp dir that holds scratch files. On Unix the temp dir is usually
+/// shared, so the name carries the user id.
+pub fn scratch_dir() -> PathBuf {
+   #[cfg(unix)]
+   let name = format!("athas-scratch-{}", unsafe { libc::geteuid() });
+   #[cfg(not(unix))]
+   let name = "athas-scratch".to_string();
+
+   env::temp_dir().join(name)
+}
+
+/// Create an empty `untitled-<n>` file in `dir`, with `extension` if given, so a buffer that
+/// has no location yet still has a real path for language servers and formatters.
+pub fn create_scratch_file(dir: &Path, extension: Option<&str>) -> Result<PathBuf> {
+   let extension = extension
+      .map(|ext| ext.trim_start_matches('.'))
+      .filter(|ext| !ext.is_empty());
+   if let Some(ext) = extension
+      && !ext
+         .chars()
+         .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+   {
+      bail!("Invalid scratch file extension: {ext}");
+   }
+
+   create_private_dir(dir)?;
+
+   for index in 1.. {
+      let name = match extension {
+         Some(ext) => format!("untitled-{index}.{ext}"),
+         None => format!("untitled-{index}"),
+      };
+      let path = dir.join(name);
+      match File::create_new(&path) {
+         Ok(_) => return Ok(path),
+         Err(error) if error.kind() == ErrorKind::AlreadyExists => continue,
+         Err(error) => {
+            return Err(error)
+               .with_context(|| format!("Failed to create scratch file {}", path.display()));
+         }
+      }
+   }
+   unreachable!("scratch file names are unbounded")
+}
+
+/// Create `dir` accessible only to the current user. In a shared temp dir another user could
+/// have created it first to read or swap the scratch files, so an existing `dir` must be a real
+/// directory owned by the current user; access for anyone else is removed.
+fn create_private_dir(dir: &Path) -> Result<()> {
+   #[cfg(unix)]
+   {
+      use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+      fs::DirBuilder::new()
+         .recursive(true)
+         .mode(0o700)
+         .create(dir)
+         .with_context(|| format!("Failed to create scratch directory {}", dir.display()))?;
+
+      let metadata = fs::symlink_metadata(dir)
+         .with_context(|| format!("Failed to read scratch directory {}", dir.display()))?;
+      if !metadata.is_dir() {
+         bail!("Scratch directory {} is not a directory", dir.display());
+      }
+      if metadata.uid() != unsafe { libc::geteuid() } {
+         bail!(
+            "Scratch directory {} is owned by another user",
+            dir.display()
+         );
+      }
+      if metadata.mode() & 0o077 != 0 {
+         fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("Failed to restrict scratch directory {}", dir.display()))?;
+      }
+   }
+
+   #[cfg(not(unix))]
+   fs::create_dir_all(dir)
+      .with_context(|| format!("Failed to create scratch directory {}", dir.display()))?;
+
+   Ok(())
+}
+
+/// Remove scratch files in `dir` not modified within `max_age`. Returns how many were removed.
+pub fn cleanup_scratch_files(dir: &Path, max_age: Duration) -> Result<usize> {
+   let entries = match fs::read_dir(dir) {
+      Ok(entries) => entries,
+      Err(error) if error.kind() == ErrorKind::NotFound => return Ok(0),
+      Err(error) => {
+         return Err(error)
+            .with_context(|| format!("Failed to read scratch directory {}", dir.display()));
+      }
+   };
+
+   let now = SystemTime::now();
+   let mut removed = 0;
+   for entry in entries.flatten() {
+      let Ok(metadata) = entry.metadata() else {
+         continue;
+      };
+      let stale = metadata
+         .modified()
+         .ok()
+         .and_then(|modified| now.duration_since(modified).ok())
+         .is_some_and(|age| age >= max_age);
+      if metadata.is_file() && stale && fs::remove_file(entry.path()).is_ok() {
+         removed += 1;
+      }
+   }
+   Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn creates_unique_scratch_files() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let dir = temp_dir.path().join("scratch");
+
+      let first = create_scratch_file(&dir, Some(".ts")).expect("first scratch file");
+      let second = create_scratch_file(&dir, Some("ts")).expect("second scratch file");
+      let plain = create_scratch_file(&dir, None).expect("plain scratch file");
+
+      assert_eq!(first, dir.join("untitled-1.ts"));
+      assert_eq!(second, dir.join("untitled-2.ts"));
+      assert_eq!(plain, dir.join("untitled-1"));
+      assert!(create_scratch_file(&dir, Some("../evil")).is_err());
+   }
+
+   #[test]
+   #[cfg(unix)]
+   fn keeps_the_scratch_directory_private() {
+      use std::os::unix::fs::{PermissionsExt, symlink};
+
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let mode = |path: &Path| fs::metadata(path).expect("metadata").permissions().mode() & 0o777;
+
+      let created = temp_dir.path().join("created");
+      create_scratch_file(&created, None).expect("scratch file");
+      assert_eq!(mode(&created), 0o700);
+
+      let shared = temp_dir.path().join("shared");
+      fs::create_dir(&shared).expect("shared dir");
+      fs::set_permissions(&shared, fs::Permissions::from_mode(0o777)).expect("open up dir");
+      create_scratch_file(&shared, None).expect("scratch file");
+      assert_eq!(mode(&shared), 0o700);
+
+      let link = temp_dir.path().join("link");
+      symlink(&created, &link).expect("symlink");
+      assert!(create_scratch_file(&link, None).is_err());
+   }
+
+   #[test]
+   fn removes_only_stale_scratch_files() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let stale = create_scratch_file(temp_dir.path(), Some("md")).expect("stale file");
+      let fresh = create_scratch_file(temp_dir.path(), Some("md")).expect("fresh file");
+      File::options()
+         .write(true)
+         .open(&stale)
+         .expect("open stale file")
+         .set_modified(SystemTime::now() - Duration::from_secs(120))
+         .expect("age stale file");
+
+      assert_eq!(
+         cleanup_scratch_files(temp_dir.path(), Duration::from_secs(60)).expect("cleanup"),
+         1
+      );
+      assert!(!stale.exists());
+      assert!(fresh.exists());
+      assert_eq!(
+         cleanup_scratch_files(&temp_dir.path().join("missing"), Duration::ZERO).expect("cleanup"),
+         0
+      );
+   }
+}
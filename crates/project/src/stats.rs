@@ -0,0 +1,187 @@
+use crate::file_category::{SNIFF_LEN, looks_like_text};
+use anyhow::{Context, Result};
+use ignore::WalkBuilder;
+use serde::Serialize;
+use std::{
+   fs::File,
+   io::{self, Read},
+   path::Path,
+};
+
+/// Files larger than this are left out of the counts rather than read into memory
+const MAX_STATS_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Text statistics of a file or the text files under a directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FileStats {
+   pub files: usize,
+   pub lines: usize,
+   pub words: usize,
+   pub chars: usize,
+   pub bytes: usize,
+}
+
+impl FileStats {
+   fn add_text(&mut self, content: &str) {
+      self.files += 1;
+      self.lines += content.lines().count();
+      self.words += content.split_whitespace().count();
+      self.chars += content.chars().count();
+      self.bytes += content.len();
+   }
+}
+
+/// Count lines, words, characters and bytes in `path`. A directory aggregates its text files,
+/// descending into subdirectories only when `recursive` is set. Binary, non-UTF-8 and very
+/// large files are skipped, and so are entries under a directory that can't be read.
+pub fn count_file_stats(
+   path: &Path,
+   recursive: bool,
+   respect_gitignore: bool,
+) -> Result<FileStats> {
+   let mut stats = FileStats::default();
+
+   if path.is_file() {
+      let content =
+         read_text_file(path).with_context(|| format!("Failed to read {}", path.display()))?;
+      if let Some(content) = content {
+         stats.add_text(&content);
+      }
+      return Ok(stats);
+   }
+
+   let walker = WalkBuilder::new(path)
+      .max_depth((!recursive).then_some(1))
+      .hidden(false)
+      .git_ignore(respect_gitignore)
+      .git_global(respect_gitignore)
+      .git_exclude(respect_gitignore)
+      .ignore(respect_gitignore)
+      .parents(respect_gitignore)
+      .filter_entry(|entry| entry.file_name() != ".git")
+      .build();
+
+   for entry in walker {
+      let entry = match entry {
+         Ok(entry) => entry,
+         Err(error) => {
+            log::debug!("Skipping unreadable entry in {}: {}", path.display(), error);
+            continue;
+         }
+      };
+      if !entry
+         .file_type()
+         .is_some_and(|file_type| file_type.is_file())
+      {
+         continue;
+      }
+      if let Ok(Some(content)) = read_text_file(entry.path()) {
+         stats.add_text(&content);
+      }
+   }
+
+   Ok(stats)
+}
+
+/// Read `path` as text, or `None` when it is too large, looks binary from its first bytes or
+/// turns out not to be UTF-8
+fn read_text_file(path: &Path) -> io::Result<Option<String>> {
+   let mut file = File::open(path)?;
+   if file.metadata()?.len() > MAX_STATS_FILE_SIZE {
+      return Ok(None);
+   }
+
+   let mut bytes = Vec::with_capacity(SNIFF_LEN);
+   (&mut file).take(SNIFF_LEN as u64).read_to_end(&mut bytes)?;
+   if !looks_like_text(&bytes) {
+      return Ok(None);
+   }
+   file.read_to_end(&mut bytes)?;
+
+   Ok(String::from_utf8(bytes)
+      .ok()
+      .filter(|content| !content.contains('\0')))
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use std::fs;
+
+   #[test]
+   fn aggregates_text_files() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let root = temp_dir.path();
+      fs::create_dir(root.join(".git")).unwrap();
+      fs::create_dir_all(root.join("docs")).unwrap();
+      fs::write(root.join(".gitignore"), "build/\n").unwrap();
+      fs::write(root.join("README.md"), "Hello world\nsecond line\n").unwrap();
+      fs::write(root.join("docs/guide.md"), "caf\u{e9} au lait").unwrap();
+      fs::write(root.join("logo.bin"), b"\x00\x01\x02").unwrap();
+      fs::create_dir_all(root.join("build")).unwrap();
+      fs::write(root.join("build/out.txt"), "ignored words here\n").unwrap();
+
+      let file = count_file_stats(&root.join("README.md"), false, true).unwrap();
+      assert_eq!(
+         file,
+         FileStats {
+            files: 1,
+            lines: 2,
+            words: 4,
+            chars: 24,
+            bytes: 24,
+         }
+      );
+
+      let shallow = count_file_stats(root, false, true).unwrap();
+      assert_eq!((shallow.files, shallow.words), (2, 5));
+
+      let recursive = count_file_stats(root, true, true).unwrap();
+      assert_eq!((recursive.files, recursive.words), (3, 8));
+      assert_eq!(recursive.chars, shallow.chars + 12);
+      assert_eq!(recursive.bytes, shallow.bytes + 13);
+
+      let unfiltered = count_file_stats(root, true, false).unwrap();
+      assert_eq!((unfiltered.files, unfiltered.words), (4, 11));
+   }
+
+   #[test]
+   fn skips_binary_and_oversized_files() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let root = temp_dir.path();
+      fs::write(root.join("notes.txt"), "two words").unwrap();
+
+      let mut late_binary = "text ".repeat(SNIFF_LEN).into_bytes();
+      late_binary.push(0);
+      fs::write(root.join("late.bin"), late_binary).unwrap();
+      let large = File::create(root.join("large.txt")).unwrap();
+      large.set_len(MAX_STATS_FILE_SIZE + 1).unwrap();
+      fs::write(root.join("image.dat"), b"\x89PNG\r\n\x1a\n\x00\x00").unwrap();
+
+      let stats = count_file_stats(root, true, true).unwrap();
+      assert_eq!((stats.files, stats.words), (1, 2));
+      assert_eq!(read_text_file(&root.join("large.txt")).unwrap(), None);
+   }
+
+   #[test]
+   #[cfg(unix)]
+   fn skips_directories_it_cannot_read() {
+      use std::os::unix::fs::PermissionsExt;
+
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let root = temp_dir.path();
+      let locked = root.join("locked");
+      fs::create_dir(&locked).unwrap();
+      fs::write(locked.join("secret.txt"), "hidden words").unwrap();
+      fs::write(root.join("notes.txt"), "two words").unwrap();
+      fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+      if fs::read_dir(&locked).is_ok() {
+         // Permissions don't apply, such as when running as root
+         return;
+      }
+
+      let stats = count_file_stats(root, true, true);
+      fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+      assert_eq!(stats.unwrap().words, 2);
+   }
+}
@@ -10,20 +10,20 @@ pub struct RemoteFileEntry {
    pub size: u64,
 }
 
-pub(super) async fn write_file(
-   connection_id: String,
-   file_path: String,
-   content: String,
+pub(super) fn write_file(
+   connection_id: &str,
+   file_path: &str,
+   content: &str,
 ) -> Result<(), String> {
    let connections = CONNECTIONS
       .lock()
       .map_err(|e| format!("Failed to lock connections: {}", e))?;
    let (session, sftp_opt) = connections
-      .get(&connection_id)
+      .get(connection_id)
       .ok_or("Connection not found")?;
 
    if let Some(sftp) = sftp_opt {
-      let remote_path = std::path::Path::new(&file_path);
+      let remote_path = std::path::Path::new(file_path);
       let mut file = sftp
          .create(remote_path)
          .map_err(|e| format!("Failed to create file: {}", e))?;
@@ -57,18 +57,18 @@ pub(super) async fn write_file(
    }
 }
 
-pub(super) async fn read_directory(
-   connection_id: String,
-   path: String,
+pub(super) fn read_directory(
+   connection_id: &str,
+   path: &str,
 ) -> Result<Vec<RemoteFileEntry>, String> {
    let connections = CONNECTIONS
       .lock()
       .map_err(|e| format!("Failed to lock connections: {}", e))?;
    let (session, sftp_opt) = connections
-      .get(&connection_id)
+      .get(connection_id)
       .ok_or("Connection not found")?;
 
-   let dir_path = if path.is_empty() { "/" } else { &path };
+   let dir_path = if path.is_empty() { "/" } else { path };
 
    if let Some(sftp) = sftp_opt {
       let remote_path = std::path::Path::new(dir_path);
@@ -150,16 +150,16 @@ pub(super) async fn read_directory(
    }
 }
 
-pub(super) async fn read_file(connection_id: String, file_path: String) -> Result<String, String> {
+pub(super) fn read_file(connection_id: &str, file_path: &str) -> Result<String, String> {
    let connections = CONNECTIONS
       .lock()
       .map_err(|e| format!("Failed to lock connections: {}", e))?;
    let (session, sftp_opt) = connections
-      .get(&connection_id)
+      .get(connection_id)
       .ok_or("Connection not found")?;
 
    if let Some(sftp) = sftp_opt {
-      let remote_path = std::path::Path::new(&file_path);
+      let remote_path = std::path::Path::new(file_path);
       let mut file = sftp
          .open(remote_path)
          .map_err(|e| format!("Failed to open file: {}", e))?;
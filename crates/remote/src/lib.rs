@@ -1,5 +1,8 @@
 mod file_ops;
+mod port_forward;
+mod reconnect;
 mod runtime;
+mod ssh_config;
 mod ssh_helpers;
 mod state;
 mod terminal;
@@ -9,9 +12,13 @@ use crate::{
       read_directory as read_directory_inner, read_file as read_file_inner,
       write_file as write_file_inner,
    },
+   port_forward::{
+      close_forward, close_forwards_for_connection, forward_local_port, list_forwards,
+   },
+   reconnect::{open_connection, reconnect_after_failure, start_keepalive_loop, with_reconnect},
    runtime::AthasAppHandle as AppHandle,
-   ssh_helpers::{create_ssh_session, exec_remote_command, shell_quote},
-   state::CONNECTIONS,
+   ssh_helpers::{exec_remote_command, shell_quote},
+   state::{CONNECTION_PARAMS, CONNECTIONS, ConnectionParams},
    terminal::{
       close_remote_terminal as close_remote_terminal_inner,
       create_remote_terminal as create_remote_terminal_inner, resize_remote_terminal,
@@ -20,6 +27,7 @@ use crate::{
 };
 use athas_terminal::{TerminalEvent, TerminalInput, TerminalSize};
 pub use file_ops::RemoteFileEntry;
+pub use port_forward::PortForwardInfo;
 use serde::{Deserialize, Serialize};
 use tauri::{Manager, ipc::Channel};
 
@@ -42,23 +50,15 @@ pub async fn ssh_connect(
    key_path: Option<String>,
    use_sftp: bool,
 ) -> Result<SshConnection, String> {
-   let session = create_ssh_session(
-      &host,
+   let params = ConnectionParams {
+      host: host.clone(),
       port,
-      &username,
-      password.as_deref(),
-      key_path.as_deref(),
-   )?;
-
-   let sftp = if use_sftp {
-      Some(
-         session
-            .sftp()
-            .map_err(|e| format!("Failed to create SFTP session: {}", e))?,
-      )
-   } else {
-      None
+      username: username.clone(),
+      password,
+      key_path,
+      use_sftp,
    };
+   let (session, sftp) = open_connection(&params)?;
 
    let connection = SshConnection {
       id: connection_id.clone(),
@@ -74,8 +74,13 @@ pub async fn ssh_connect(
       let mut connections = CONNECTIONS
          .lock()
          .map_err(|e| format!("Failed to lock connections: {}", e))?;
-      connections.insert(connection_id, (session, sftp));
+      connections.insert(connection_id.clone(), (session, sftp));
    }
+   CONNECTION_PARAMS
+      .lock()
+      .map_err(|e| format!("Failed to lock connections: {}", e))?
+      .insert(connection_id, params);
+   start_keepalive_loop();
 
    Ok(connection)
 }
@@ -84,6 +89,10 @@ pub async fn ssh_disconnect(app: AppHandle, connection_id: String) -> Result<(),
    let mut connections = CONNECTIONS
       .lock()
       .map_err(|e| format!("Failed to lock connections: {}", e))?;
+   if let Ok(mut params) = CONNECTION_PARAMS.lock() {
+      params.remove(&connection_id);
+   }
+   close_forwards_for_connection(&connection_id);
    if let Some((session, sftp_opt)) = connections.remove(&connection_id) {
       // Explicitly close SFTP handle before disconnecting session
       if let Some(sftp) = sftp_opt {
@@ -105,6 +114,10 @@ pub async fn ssh_disconnect_only(connection_id: String) -> Result<(), String> {
    let mut connections = CONNECTIONS
       .lock()
       .map_err(|e| format!("Failed to lock connections: {}", e))?;
+   if let Ok(mut params) = CONNECTION_PARAMS.lock() {
+      params.remove(&connection_id);
+   }
+   close_forwards_for_connection(&connection_id);
    if let Some((session, sftp_opt)) = connections.remove(&connection_id) {
       // Explicitly close SFTP handle before disconnecting session
       if let Some(sftp) = sftp_opt {
@@ -116,6 +129,23 @@ pub async fn ssh_disconnect_only(connection_id: String) -> Result<(), String> {
    Ok(())
 }
 
+pub async fn ssh_forward_local_port(
+   connection_id: String,
+   local_port: u16,
+   remote_host: String,
+   remote_port: u16,
+) -> Result<PortForwardInfo, String> {
+   forward_local_port(&connection_id, local_port, &remote_host, remote_port)
+}
+
+pub async fn ssh_close_forward(local_port: u16) -> Result<(), String> {
+   close_forward(local_port)
+}
+
+pub async fn ssh_list_forwards() -> Result<Vec<PortForwardInfo>, String> {
+   list_forwards()
+}
+
 pub async fn ssh_get_connected_ids() -> Result<Vec<String>, String> {
    let connections = CONNECTIONS
       .lock()
@@ -124,89 +154,62 @@ pub async fn ssh_get_connected_ids() -> Result<Vec<String>, String> {
    Ok(connections.keys().cloned().collect())
 }
 
-pub async fn ssh_create_file(connection_id: String, file_path: String) -> Result<(), String> {
-   let connections = CONNECTIONS
-      .lock()
-      .map_err(|e| format!("Failed to lock connections: {}", e))?;
-   let (session, _) = connections
-      .get(&connection_id)
-      .ok_or("Connection not found")?;
-
+pub async fn ssh_create_file(
+   app: AppHandle,
+   connection_id: String,
+   file_path: String,
+) -> Result<(), String> {
    let command = format!(
       "mkdir -p \"$(dirname {0})\" && : > {0}",
       shell_quote(&file_path)
    );
-   exec_remote_command(session, &command).map(|_| ())
+   run_remote_command(&app, &connection_id, &command)
 }
 
 pub async fn ssh_create_directory(
+   app: AppHandle,
    connection_id: String,
    directory_path: String,
 ) -> Result<(), String> {
-   let connections = CONNECTIONS
-      .lock()
-      .map_err(|e| format!("Failed to lock connections: {}", e))?;
-   let (session, _) = connections
-      .get(&connection_id)
-      .ok_or("Connection not found")?;
-
    let command = format!("mkdir -p {}", shell_quote(&directory_path));
-   exec_remote_command(session, &command).map(|_| ())
+   run_remote_command(&app, &connection_id, &command)
 }
 
 pub async fn ssh_delete_path(
+   app: AppHandle,
    connection_id: String,
    target_path: String,
    is_directory: bool,
 ) -> Result<(), String> {
-   let connections = CONNECTIONS
-      .lock()
-      .map_err(|e| format!("Failed to lock connections: {}", e))?;
-   let (session, _) = connections
-      .get(&connection_id)
-      .ok_or("Connection not found")?;
-
    let command = if is_directory {
       format!("rm -rf {}", shell_quote(&target_path))
    } else {
       format!("rm -f {}", shell_quote(&target_path))
    };
-   exec_remote_command(session, &command).map(|_| ())
+   run_remote_command(&app, &connection_id, &command)
 }
 
 pub async fn ssh_rename_path(
+   app: AppHandle,
    connection_id: String,
    source_path: String,
    target_path: String,
 ) -> Result<(), String> {
-   let connections = CONNECTIONS
-      .lock()
-      .map_err(|e| format!("Failed to lock connections: {}", e))?;
-   let (session, _) = connections
-      .get(&connection_id)
-      .ok_or("Connection not found")?;
-
    let command = format!(
       "mkdir -p \"$(dirname {target})\" && mv {source} {target}",
       source = shell_quote(&source_path),
       target = shell_quote(&target_path),
    );
-   exec_remote_command(session, &command).map(|_| ())
+   run_remote_command(&app, &connection_id, &command)
 }
 
 pub async fn ssh_copy_path(
+   app: AppHandle,
    connection_id: String,
    source_path: String,
    target_path: String,
    is_directory: bool,
 ) -> Result<(), String> {
-   let connections = CONNECTIONS
-      .lock()
-      .map_err(|e| format!("Failed to lock connections: {}", e))?;
-   let (session, _) = connections
-      .get(&connection_id)
-      .ok_or("Connection not found")?;
-
    let copy_flag = if is_directory { "-R" } else { "" };
    let command = format!(
       "mkdir -p \"$(dirname {target})\" && cp {flag} {source} {target}",
@@ -214,7 +217,19 @@ pub async fn ssh_copy_path(
       source = shell_quote(&source_path),
       target = shell_quote(&target_path),
    );
-   exec_remote_command(session, &command).map(|_| ())
+   run_remote_command(&app, &connection_id, &command)
+}
+
+fn run_remote_command(app: &AppHandle, connection_id: &str, command: &str) -> Result<(), String> {
+   reconnect_after_failure(app, connection_id, || {
+      let session = CONNECTIONS
+         .lock()
+         .map_err(|e| format!("Failed to lock connections: {}", e))?
+         .get(connection_id)
+         .map(|(session, _)| session.clone())
+         .ok_or("Connection not found")?;
+      exec_remote_command(&session, command).map(|_| ())
+   })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -260,20 +275,32 @@ pub async fn close_remote_terminal(id: String) -> Result<(), String> {
 }
 
 pub async fn ssh_write_file(
+   app: AppHandle,
    connection_id: String,
    file_path: String,
    content: String,
 ) -> Result<(), String> {
-   write_file_inner(connection_id, file_path, content).await
+   reconnect_after_failure(&app, &connection_id, || {
+      write_file_inner(&connection_id, &file_path, &content)
+   })
 }
 
 pub async fn ssh_read_directory(
+   app: AppHandle,
    connection_id: String,
    path: String,
 ) -> Result<Vec<RemoteFileEntry>, String> {
-   read_directory_inner(connection_id, path).await
+   with_reconnect(&app, &connection_id, || {
+      read_directory_inner(&connection_id, &path)
+   })
 }
 
-pub async fn ssh_read_file(connection_id: String, file_path: String) -> Result<String, String> {
-   read_file_inner(connection_id, file_path).await
+pub async fn ssh_read_file(
+   app: AppHandle,
+   connection_id: String,
+   file_path: String,
+) -> Result<String, String> {
+   with_reconnect(&app, &connection_id, || {
+      read_file_inner(&connection_id, &file_path)
+   })
 }
@@ -0,0 +1,291 @@
+use crate::{
+   ssh_helpers::create_ssh_session,
+   state::{CONNECTION_PARAMS, FORWARDS},
+};
+use serde::{Deserialize, Serialize};
+use ssh2::{Channel, Session};
+use std::{
+   io::{self, ErrorKind, Read, Write},
+   net::{TcpListener, TcpStream},
+   sync::{
+      Arc,
+      atomic::{AtomicBool, Ordering},
+   },
+   thread,
+   time::{Duration, Instant},
+};
+
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(5);
+const CHANNEL_OPEN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortForwardInfo {
+   pub connection_id: String,
+   pub local_port: u16,
+   pub remote_host: String,
+   pub remote_port: u16,
+}
+
+pub(super) struct PortForward {
+   pub info: PortForwardInfo,
+   pub stop: Arc<AtomicBool>,
+}
+
+/// Listen on `127.0.0.1:local_port` and tunnel each accepted connection to
+/// `remote_host:remote_port` as seen from the SSH server. Pass `0` to pick a free local port.
+///
+/// The forward runs on its own SSH session so pumping bytes never blocks file operations on
+/// the connection's main session.
+pub(super) fn forward_local_port(
+   connection_id: &str,
+   local_port: u16,
+   remote_host: &str,
+   remote_port: u16,
+) -> Result<PortForwardInfo, String> {
+   let params = CONNECTION_PARAMS
+      .lock()
+      .map_err(|e| format!("Failed to lock connections: {}", e))?
+      .get(connection_id)
+      .cloned()
+      .ok_or("Connection not found")?;
+
+   if local_port != 0
+      && FORWARDS
+         .lock()
+         .map_err(|e| format!("Failed to lock port forwards: {}", e))?
+         .contains_key(&local_port)
+   {
+      return Err(format!("Port {} is already forwarded", local_port));
+   }
+
+   let listener = TcpListener::bind(("127.0.0.1", local_port))
+      .map_err(|e| format!("Failed to listen on port {}: {}", local_port, e))?;
+   listener
+      .set_nonblocking(true)
+      .map_err(|e| format!("Failed to configure listener: {}", e))?;
+   let local_port = listener
+      .local_addr()
+      .map_err(|e| format!("Failed to read listener address: {}", e))?
+      .port();
+
+   let session = create_ssh_session(
+      &params.host,
+      params.port,
+      &params.username,
+      params.password.as_deref(),
+      params.key_path.as_deref(),
+   )?;
+
+   let info = PortForwardInfo {
+      connection_id: connection_id.to_string(),
+      local_port,
+      remote_host: remote_host.to_string(),
+      remote_port,
+   };
+   let stop = Arc::new(AtomicBool::new(false));
+   FORWARDS
+      .lock()
+      .map_err(|e| format!("Failed to lock port forwards: {}", e))?
+      .insert(
+         local_port,
+         PortForward {
+            info: info.clone(),
+            stop: stop.clone(),
+         },
+      );
+
+   let remote_host = remote_host.to_string();
+   thread::spawn(move || {
+      run_forward(session, listener, &remote_host, remote_port, &stop);
+      if let Ok(mut forwards) = FORWARDS.lock()
+         && forwards
+            .get(&local_port)
+            .is_some_and(|forward| Arc::ptr_eq(&forward.stop, &stop))
+      {
+         forwards.remove(&local_port);
+      }
+   });
+
+   log::info!(
+      "Forwarding 127.0.0.1:{} to {}:{} over {}",
+      local_port,
+      info.remote_host,
+      remote_port,
+      connection_id
+   );
+   Ok(info)
+}
+
+pub(super) fn close_forward(local_port: u16) -> Result<(), String> {
+   let forward = FORWARDS
+      .lock()
+      .map_err(|e| format!("Failed to lock port forwards: {}", e))?
+      .remove(&local_port)
+      .ok_or_else(|| format!("Port {} is not forwarded", local_port))?;
+   forward.stop.store(true, Ordering::Relaxed);
+   Ok(())
+}
+
+pub(super) fn close_forwards_for_connection(connection_id: &str) {
+   let Ok(mut forwards) = FORWARDS.lock() else {
+      return;
+   };
+   forwards.retain(|_, forward| {
+      let keep = forward.info.connection_id != connection_id;
+      if !keep {
+         forward.stop.store(true, Ordering::Relaxed);
+      }
+      keep
+   });
+}
+
+pub(super) fn list_forwards() -> Result<Vec<PortForwardInfo>, String> {
+   let forwards = FORWARDS
+      .lock()
+      .map_err(|e| format!("Failed to lock port forwards: {}", e))?;
+   let mut infos: Vec<PortForwardInfo> = forwards
+      .values()
+      .map(|forward| forward.info.clone())
+      .collect();
+   infos.sort_by_key(|info| info.local_port);
+   Ok(infos)
+}
+
+struct Tunnel {
+   stream: TcpStream,
+   channel: Channel,
+   to_remote: Vec<u8>,
+   to_local: Vec<u8>,
+}
+
+fn run_forward(
+   session: Session,
+   listener: TcpListener,
+   remote_host: &str,
+   remote_port: u16,
+   stop: &AtomicBool,
+) {
+   session.set_blocking(false);
+   let mut tunnels: Vec<Tunnel> = Vec::new();
+   let mut buffer = vec![0; 16 * 1024];
+
+   while !stop.load(Ordering::Relaxed) {
+      let mut progressed = false;
+
+      match listener.accept() {
+         Ok((stream, _)) => {
+            progressed = true;
+            match open_tunnel(&session, stream, remote_host, remote_port) {
+               Ok(tunnel) => tunnels.push(tunnel),
+               Err(e) => log::warn!("Failed to open forwarded channel: {}", e),
+            }
+         }
+         Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+         Err(e) => {
+            log::warn!("Port forward listener failed: {}", e);
+            break;
+         }
+      }
+
+      tunnels.retain_mut(|tunnel| match tunnel.pump(&mut buffer) {
+         Ok(Some(moved)) => {
+            progressed |= moved;
+            true
+         }
+         Ok(None) => false,
+         Err(e) => {
+            log::debug!("Forwarded connection closed: {}", e);
+            false
+         }
+      });
+
+      if !progressed {
+         thread::sleep(IDLE_POLL_INTERVAL);
+      }
+   }
+
+   for mut tunnel in tunnels {
+      let _ = tunnel.channel.close();
+   }
+   let _ = session.disconnect(None, "Port forward closed", None);
+}
+
+fn open_tunnel(
+   session: &Session,
+   stream: TcpStream,
+   remote_host: &str,
+   remote_port: u16,
+) -> io::Result<Tunnel> {
+   stream.set_nonblocking(true)?;
+   let deadline = Instant::now() + CHANNEL_OPEN_TIMEOUT;
+   let channel = loop {
+      match session.channel_direct_tcpip(remote_host, remote_port, None) {
+         Ok(channel) => break channel,
+         Err(e) => {
+            let error = io::Error::from(e);
+            if error.kind() != ErrorKind::WouldBlock || Instant::now() >= deadline {
+               return Err(error);
+            }
+            thread::sleep(IDLE_POLL_INTERVAL);
+         }
+      }
+   };
+
+   Ok(Tunnel {
+      stream,
+      channel,
+      to_remote: Vec::new(),
+      to_local: Vec::new(),
+   })
+}
+
+impl Tunnel {
+   /// Move whatever bytes are ready in both directions. Returns `None` once either side has
+   /// closed and everything it sent has been delivered.
+   fn pump(&mut self, buffer: &mut [u8]) -> io::Result<Option<bool>> {
+      let mut moved = false;
+      let mut local_closed = false;
+
+      if self.to_remote.is_empty() {
+         match self.stream.read(buffer) {
+            Ok(0) => local_closed = true,
+            Ok(read) => self.to_remote.extend_from_slice(&buffer[..read]),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e),
+         }
+      }
+      moved |= flush(&mut self.channel, &mut self.to_remote)?;
+
+      if self.to_local.is_empty() {
+         match self.channel.read(buffer) {
+            Ok(0) => {}
+            Ok(read) => self.to_local.extend_from_slice(&buffer[..read]),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e),
+         }
+      }
+      moved |= flush(&mut self.stream, &mut self.to_local)?;
+
+      let remote_closed = self.channel.eof() && self.to_local.is_empty();
+      if (local_closed && self.to_remote.is_empty()) || remote_closed {
+         let _ = self.channel.close();
+         return Ok(None);
+      }
+      Ok(Some(moved))
+   }
+}
+
+/// Write as much of `pending` as the writer accepts without blocking.
+fn flush(writer: &mut impl Write, pending: &mut Vec<u8>) -> io::Result<bool> {
+   if pending.is_empty() {
+      return Ok(false);
+   }
+   match writer.write(pending) {
+      Ok(written) => {
+         pending.drain(..written);
+         Ok(written > 0)
+      }
+      Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(false),
+      Err(e) => Err(e),
+   }
+}
@@ -0,0 +1,151 @@
+use crate::{
+   runtime::AthasAppHandle as AppHandle,
+   ssh_helpers::create_ssh_session,
+   state::{CONNECTION_PARAMS, CONNECTIONS, ConnectionParams},
+};
+use ssh2::{Session, Sftp};
+use std::{sync::Once, thread, time::Duration};
+use tauri::Emitter;
+
+/// Seconds between keepalive messages, short enough to survive typical NAT and server idle
+/// timeouts.
+pub(super) const KEEPALIVE_INTERVAL_SECS: u32 = 30;
+
+static KEEPALIVE_LOOP: Once = Once::new();
+
+/// libssh2 only sends keepalives when asked, so ping every stored session from one thread.
+pub(super) fn start_keepalive_loop() {
+   KEEPALIVE_LOOP.call_once(|| {
+      thread::spawn(|| {
+         loop {
+            thread::sleep(Duration::from_secs(u64::from(KEEPALIVE_INTERVAL_SECS)));
+            // Ping clones of the sessions so a slow server doesn't hold the lock
+            let sessions: Vec<(String, Session)> = match CONNECTIONS.lock() {
+               Ok(connections) => connections
+                  .iter()
+                  .map(|(connection_id, (session, _))| (connection_id.clone(), session.clone()))
+                  .collect(),
+               Err(_) => continue,
+            };
+            for (connection_id, session) in sessions {
+               if let Err(e) = session.keepalive_send() {
+                  log::debug!("SSH keepalive failed for {}: {}", connection_id, e);
+               }
+            }
+         }
+      });
+   });
+}
+
+pub(super) fn open_connection(
+   params: &ConnectionParams,
+) -> Result<(Session, Option<Sftp>), String> {
+   let session = create_ssh_session(
+      &params.host,
+      params.port,
+      &params.username,
+      params.password.as_deref(),
+      params.key_path.as_deref(),
+   )?;
+
+   let sftp = if params.use_sftp {
+      Some(
+         session
+            .sftp()
+            .map_err(|e| format!("Failed to create SFTP session: {}", e))?,
+      )
+   } else {
+      None
+   };
+
+   Ok((session, sftp))
+}
+
+/// Run `op`, which only reads from the remote, and if it fails because the session died,
+/// reconnect once and retry.
+///
+/// The session is probed with a throwaway channel so errors from a healthy session (missing
+/// file, permission denied) are returned as-is. Emits `ssh-reconnected` after reconnecting.
+pub(super) fn with_reconnect<T>(
+   app: &AppHandle,
+   connection_id: &str,
+   op: impl Fn() -> Result<T, String>,
+) -> Result<T, String> {
+   let error = match op() {
+      Ok(value) => return Ok(value),
+      Err(error) => error,
+   };
+
+   if !reconnect_if_dead(app, connection_id)? {
+      return Err(error);
+   }
+   op()
+}
+
+/// Run `op`, which changes the remote, and if it fails because the session died, reconnect so
+/// the next operation works. `op` is not retried, since the change may have been applied before
+/// the session dropped.
+pub(super) fn reconnect_after_failure<T>(
+   app: &AppHandle,
+   connection_id: &str,
+   op: impl FnOnce() -> Result<T, String>,
+) -> Result<T, String> {
+   let error = match op() {
+      Ok(value) => return Ok(value),
+      Err(error) => error,
+   };
+
+   if !reconnect_if_dead(app, connection_id)? {
+      return Err(error);
+   }
+   Err(format!(
+      "{} (the SSH connection dropped and was re-established; check whether the change was \
+       applied before trying again)",
+      error
+   ))
+}
+
+fn reconnect_if_dead(app: &AppHandle, connection_id: &str) -> Result<bool, String> {
+   let Some(session) = CONNECTIONS
+      .lock()
+      .map_err(|e| format!("Failed to lock connections: {}", e))?
+      .get(connection_id)
+      .map(|(session, _)| session.clone())
+   else {
+      return Ok(false);
+   };
+   if let Ok(mut channel) = session.channel_session() {
+      let _ = channel.close();
+      return Ok(false);
+   }
+
+   let Some(params) = CONNECTION_PARAMS
+      .lock()
+      .map_err(|e| format!("Failed to lock connections: {}", e))?
+      .get(connection_id)
+      .cloned()
+   else {
+      return Ok(false);
+   };
+
+   log::info!("SSH session {} dropped, reconnecting", connection_id);
+   let connection = open_connection(&params)
+      .map_err(|e| format!("SSH connection was lost and reconnecting failed: {}", e))?;
+
+   {
+      let mut connections = CONNECTIONS
+         .lock()
+         .map_err(|e| format!("Failed to lock connections: {}", e))?;
+      // A disconnect while we were reconnecting wins; don't resurrect the connection.
+      if !connections.contains_key(connection_id) {
+         return Ok(false);
+      }
+      connections.insert(connection_id.to_string(), connection);
+   }
+
+   let _ = app.emit(
+      "ssh-reconnected",
+      serde_json::json!({ "connectionId": connection_id }),
+   );
+   Ok(true)
+}
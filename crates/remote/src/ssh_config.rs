@@ -0,0 +1,352 @@
+use std::{
+   env, fs,
+   path::{Path, PathBuf},
+   sync::Mutex,
+   time::SystemTime,
+};
+
+/// Includes nested deeper than this are ignored, matching OpenSSH's limit.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct SshConfig {
+   pub hostname: Option<String>,
+   pub user: Option<String>,
+   pub identity_file: Option<String>,
+   pub port: Option<u16>,
+}
+
+#[derive(Debug, Clone)]
+struct HostBlock {
+   patterns: Vec<String>,
+   options: Vec<(String, String)>,
+}
+
+struct CachedConfig {
+   /// Every file read while parsing, with the mtime it had at the time.
+   sources: Vec<(PathBuf, Option<SystemTime>)>,
+   blocks: Vec<HostBlock>,
+}
+
+static CONFIG_CACHE: Mutex<Option<CachedConfig>> = Mutex::new(None);
+
+/// Resolve `host` against `~/.ssh/config`, including files pulled in with `Include`.
+///
+/// The parsed config is cached until one of the files it was read from changes.
+pub(super) fn get_ssh_config(host: &str) -> SshConfig {
+   let Ok(home_dir) = env::var("HOME") else {
+      return SshConfig::default();
+   };
+   let home = Path::new(&home_dir);
+   let config_path = home.join(".ssh").join("config");
+
+   let mut cache = match CONFIG_CACHE.lock() {
+      Ok(cache) => cache,
+      Err(poisoned) => poisoned.into_inner(),
+   };
+   let is_fresh = cache.as_ref().is_some_and(|cached| {
+      cached.sources.first().map(|(path, _)| path) == Some(&config_path)
+         && cached
+            .sources
+            .iter()
+            .all(|(path, mtime)| modified_time(path) == *mtime)
+   });
+   if !is_fresh {
+      let mut sources = Vec::new();
+      let blocks = parse_config_file(&config_path, home, 0, &all_hosts(), &mut sources);
+      *cache = Some(CachedConfig { sources, blocks });
+   }
+
+   cache
+      .as_ref()
+      .map(|cached| resolve_host(&cached.blocks, host, home))
+      .unwrap_or_default()
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+   fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn all_hosts() -> Vec<String> {
+   vec!["*".to_string()]
+}
+
+fn parse_config_file(
+   path: &Path,
+   home: &Path,
+   depth: usize,
+   patterns: &[String],
+   sources: &mut Vec<(PathBuf, Option<SystemTime>)>,
+) -> Vec<HostBlock> {
+   sources.push((path.to_path_buf(), modified_time(path)));
+   match fs::read_to_string(path) {
+      Ok(content) => parse_config(&content, home, depth, patterns, sources),
+      Err(_) => Vec::new(),
+   }
+}
+
+/// Parse config text into host blocks in file order. Options before the first `Host` line
+/// belong to `patterns`: every host for the main file, the enclosing block for an included one.
+/// Included files are spliced in where the `Include` line appears.
+fn parse_config(
+   content: &str,
+   home: &Path,
+   depth: usize,
+   patterns: &[String],
+   sources: &mut Vec<(PathBuf, Option<SystemTime>)>,
+) -> Vec<HostBlock> {
+   let mut blocks = vec![HostBlock {
+      patterns: patterns.to_vec(),
+      options: Vec::new(),
+   }];
+
+   for line in content.lines() {
+      let Some((key, value)) = split_option(line) else {
+         continue;
+      };
+
+      match key.as_str() {
+         "host" => blocks.push(HostBlock {
+            patterns: value.split_whitespace().map(str::to_string).collect(),
+            options: Vec::new(),
+         }),
+         "include" if depth < MAX_INCLUDE_DEPTH => {
+            let enclosing = blocks
+               .last()
+               .map(|block| block.patterns.clone())
+               .unwrap_or_else(all_hosts);
+            for include in value.split_whitespace() {
+               for path in expand_include(include, home) {
+                  blocks.extend(parse_config_file(
+                     &path,
+                     home,
+                     depth + 1,
+                     &enclosing,
+                     sources,
+                  ));
+               }
+            }
+            // Lines after the Include still belong to the enclosing Host block.
+            blocks.push(HostBlock {
+               patterns: enclosing,
+               options: Vec::new(),
+            });
+         }
+         _ => {
+            if let Some(block) = blocks.last_mut() {
+               block.options.push((key, value.to_string()));
+            }
+         }
+      }
+   }
+
+   blocks
+}
+
+/// Split `Key value` or `Key=value` into a lowercase key and its value.
+fn split_option(line: &str) -> Option<(String, &str)> {
+   let line = line.trim();
+   if line.is_empty() || line.starts_with('#') {
+      return None;
+   }
+
+   let split_at = line.find(|c: char| c.is_whitespace() || c == '=')?;
+   let (key, rest) = line.split_at(split_at);
+   let value = rest
+      .trim_start()
+      .strip_prefix('=')
+      .unwrap_or(rest)
+      .trim()
+      .trim_matches('"');
+   (!value.is_empty()).then(|| (key.to_lowercase(), value))
+}
+
+/// Relative includes are resolved against `~/.ssh`. Wildcards may appear in any component.
+fn expand_include(include: &str, home: &Path) -> Vec<PathBuf> {
+   let path = expand_tilde(include, home);
+   let path = if path.is_absolute() {
+      path
+   } else {
+      home.join(".ssh").join(path)
+   };
+
+   let mut matches = vec![PathBuf::new()];
+   for component in path.components() {
+      let component = component.as_os_str().to_string_lossy();
+      if !component.contains(['*', '?']) {
+         for matched in &mut matches {
+            matched.push(component.as_ref());
+         }
+         continue;
+      }
+
+      let mut next = Vec::new();
+      for dir in &matches {
+         let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+         };
+         let mut names: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| wildcard_match(&component, name))
+            .collect();
+         names.sort();
+         next.extend(names.into_iter().map(|name| dir.join(name)));
+      }
+      matches = next;
+   }
+
+   matches.retain(|path| path.is_file());
+   matches
+}
+
+fn expand_tilde(value: &str, home: &Path) -> PathBuf {
+   match value.strip_prefix("~/") {
+      Some(stripped) => home.join(stripped),
+      None => PathBuf::from(value),
+   }
+}
+
+/// Apply matching blocks in order. As in OpenSSH, the first value seen for an option wins.
+fn resolve_host(blocks: &[HostBlock], host: &str, home: &Path) -> SshConfig {
+   let mut config = SshConfig::default();
+
+   for block in blocks
+      .iter()
+      .filter(|block| host_matches(&block.patterns, host))
+   {
+      for (key, value) in &block.options {
+         match key.as_str() {
+            "hostname" if config.hostname.is_none() => config.hostname = Some(value.clone()),
+            "user" if config.user.is_none() => config.user = Some(value.clone()),
+            "identityfile" if config.identity_file.is_none() => {
+               config.identity_file = Some(expand_tilde(value, home).to_string_lossy().to_string());
+            }
+            "port" if config.port.is_none() => config.port = value.parse().ok(),
+            _ => {}
+         }
+      }
+   }
+
+   config
+}
+
+/// A host matches when any pattern matches and no negated (`!pattern`) one does.
+fn host_matches(patterns: &[String], host: &str) -> bool {
+   let mut matched = false;
+   for pattern in patterns {
+      if let Some(negated) = pattern.strip_prefix('!') {
+         if wildcard_match(negated, host) {
+            return false;
+         }
+      } else if wildcard_match(pattern, host) {
+         matched = true;
+      }
+   }
+   matched
+}
+
+/// Glob match supporting `*` (any run of characters) and `?` (one character).
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+   let pattern: Vec<char> = pattern.chars().collect();
+   let text: Vec<char> = text.chars().collect();
+   let (mut p, mut t) = (0, 0);
+   let mut backtrack: Option<(usize, usize)> = None;
+
+   while t < text.len() {
+      if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+         p += 1;
+         t += 1;
+      } else if p < pattern.len() && pattern[p] == '*' {
+         backtrack = Some((p, t));
+         p += 1;
+      } else if let Some((star_p, star_t)) = backtrack {
+         p = star_p + 1;
+         t = star_t + 1;
+         backtrack = Some((star_p, star_t + 1));
+      } else {
+         return false;
+      }
+   }
+
+   pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn resolve(content: &str, host: &str) -> SshConfig {
+      let home = Path::new("/home/dev");
+      let blocks = parse_config(content, home, 0, &all_hosts(), &mut Vec::new());
+      resolve_host(&blocks, host, home)
+   }
+
+   #[test]
+   fn first_match_wins_over_later_wildcards() {
+      let config = resolve(
+         "Host box\n  HostName 10.0.0.5\n  User deploy\n\nHost *\n  User root\n  Port 2222\n",
+         "box",
+      );
+
+      assert_eq!(config.hostname.as_deref(), Some("10.0.0.5"));
+      assert_eq!(config.user.as_deref(), Some("deploy"));
+      assert_eq!(config.port, Some(2222));
+   }
+
+   #[test]
+   fn matches_patterns_and_negations() {
+      let content = "Host *.internal !db.internal\n  User ops\nHost=db.*\n  User=dba\n";
+
+      assert_eq!(
+         resolve(content, "web.internal").user.as_deref(),
+         Some("ops")
+      );
+      assert_eq!(resolve(content, "db.internal").user.as_deref(), Some("dba"));
+      assert_eq!(resolve(content, "example.com").user, None);
+   }
+
+   #[test]
+   fn expands_identity_file_home() {
+      let config = resolve("IdentityFile ~/.ssh/work_key\n", "anything");
+      assert_eq!(
+         config.identity_file.as_deref(),
+         Some("/home/dev/.ssh/work_key")
+      );
+   }
+
+   #[test]
+   fn resolves_globbed_includes_in_place() {
+      let home = tempfile::tempdir().unwrap();
+      let ssh_dir = home.path().join(".ssh");
+      fs::create_dir_all(ssh_dir.join("config.d")).unwrap();
+      fs::write(
+         ssh_dir.join("config.d/10-work"),
+         "Host work\n  HostName work.example.com\n  User alice\n",
+      )
+      .unwrap();
+      fs::write(
+         ssh_dir.join("config.d/20-personal"),
+         "Host pi\n  HostName 192.168.1.20\n",
+      )
+      .unwrap();
+
+      let mut sources = Vec::new();
+      let blocks = parse_config(
+         "Include config.d/*\n\nHost *\n  User fallback\n",
+         home.path(),
+         0,
+         &all_hosts(),
+         &mut sources,
+      );
+
+      let work = resolve_host(&blocks, "work", home.path());
+      assert_eq!(work.hostname.as_deref(), Some("work.example.com"));
+      assert_eq!(work.user.as_deref(), Some("alice"));
+
+      let pi = resolve_host(&blocks, "pi", home.path());
+      assert_eq!(pi.hostname.as_deref(), Some("192.168.1.20"));
+      assert_eq!(pi.user.as_deref(), Some("fallback"));
+      assert_eq!(sources.len(), 2);
+   }
+}
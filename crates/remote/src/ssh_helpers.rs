@@ -1,13 +1,6 @@
+use crate::{reconnect::KEEPALIVE_INTERVAL_SECS, ssh_config::get_ssh_config};
 use ssh2::Session;
-use std::{env, fs, io::Read, net::TcpStream, path::Path};
-
-#[derive(Debug, Clone)]
-struct SshConfig {
-   hostname: Option<String>,
-   user: Option<String>,
-   identity_file: Option<String>,
-   port: Option<u16>,
-}
+use std::{env, io::Read, net::TcpStream, path::Path};
 
 pub(super) fn shell_quote(value: &str) -> String {
    format!("'{}'", value.replace('\'', "'\\''"))
@@ -53,64 +46,6 @@ pub(super) fn exec_remote_command(session: &Session, command: &str) -> Result<St
    Ok(stdout)
 }
 
-fn get_ssh_config(host: &str) -> SshConfig {
-   let mut config = SshConfig {
-      hostname: None,
-      user: None,
-      identity_file: None,
-      port: None,
-   };
-
-   if let Ok(home_dir) = env::var("HOME") {
-      let ssh_config_path = format!("{}/.ssh/config", home_dir);
-      if let Ok(content) = fs::read_to_string(&ssh_config_path) {
-         let mut in_host_section = false;
-
-         for line in content.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-               continue;
-            }
-
-            if line.to_lowercase().starts_with("host ") {
-               let current_host_pattern = line[5..].trim();
-               in_host_section = current_host_pattern == host || current_host_pattern == "*";
-               continue;
-            }
-
-            if in_host_section {
-               let parts: Vec<&str> = line.splitn(2, ' ').collect();
-               if parts.len() == 2 {
-                  let key = parts[0].to_lowercase();
-                  let value = parts[1].trim();
-
-                  match key.as_str() {
-                     "hostname" => config.hostname = Some(value.to_string()),
-                     "user" => config.user = Some(value.to_string()),
-                     "identityfile" => {
-                        let expanded_path = if let Some(stripped) = value.strip_prefix("~/") {
-                           format!("{}/{}", home_dir, stripped)
-                        } else {
-                           value.to_string()
-                        };
-                        config.identity_file = Some(expanded_path);
-                     }
-                     "port" => {
-                        if let Ok(port) = value.parse::<u16>() {
-                           config.port = Some(port);
-                        }
-                     }
-                     _ => {}
-                  }
-               }
-            }
-         }
-      }
-   }
-
-   config
-}
-
 pub(super) fn create_ssh_session(
    host: &str,
    port: u16,
@@ -143,6 +78,7 @@ pub(super) fn create_ssh_session(
    sess
       .handshake()
       .map_err(|e| format!("Failed to handshake: {}", e))?;
+   sess.set_keepalive(true, KEEPALIVE_INTERVAL_SECS);
 
    let home_dir = env::var("HOME").unwrap_or_default();
    let default_key_paths = [
@@ -1,3 +1,4 @@
+use crate::port_forward::PortForward;
 use athas_terminal::TerminalReaderControl;
 use ssh2::{Channel, Session, Sftp};
 use std::{
@@ -7,10 +8,25 @@ use std::{
 
 pub(super) type ConnectionStorage = Arc<Mutex<HashMap<String, (Session, Option<Sftp>)>>>;
 pub(super) type RemoteTerminalStorage = Arc<Mutex<HashMap<String, RemoteTerminal>>>;
+pub(super) type ConnectionParamsStorage = Arc<Mutex<HashMap<String, ConnectionParams>>>;
+pub(super) type PortForwardStorage = Arc<Mutex<HashMap<u16, PortForward>>>;
 
 lazy_static::lazy_static! {
     pub(super) static ref CONNECTIONS: ConnectionStorage = Arc::new(Mutex::new(HashMap::new()));
     pub(super) static ref REMOTE_TERMINALS: RemoteTerminalStorage = Arc::new(Mutex::new(HashMap::new()));
+    pub(super) static ref CONNECTION_PARAMS: ConnectionParamsStorage = Arc::new(Mutex::new(HashMap::new()));
+    pub(super) static ref FORWARDS: PortForwardStorage = Arc::new(Mutex::new(HashMap::new()));
+}
+
+/// What `ssh_connect` was called with, kept so a dropped session can be re-established.
+#[derive(Clone)]
+pub(super) struct ConnectionParams {
+   pub host: String,
+   pub port: u16,
+   pub username: String,
+   pub password: Option<String>,
+   pub key_path: Option<String>,
+   pub use_sftp: bool,
 }
 
 pub(super) struct RemoteTerminal {
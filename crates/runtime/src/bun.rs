@@ -1,9 +1,13 @@
-use crate::{RuntimeError, RuntimeStatus, process::configure_background_command};
+use crate::{
+   RuntimeError, RuntimeStatus, parse_pinned_version, process::configure_background_command,
+   read_pinned_version, remove_managed_dir, write_pinned_version,
+};
 use std::{
    fs::{self, File},
    io::{self, Cursor},
    path::{Path, PathBuf},
    process::Command,
+   sync::OnceLock,
 };
 
 /// Bun version to download if system version is not available
@@ -15,16 +19,25 @@ pub const MIN_BUN_VERSION: (u32, u32, u32) = (1, 0, 0);
 /// Manages Bun runtime for running JS-based language servers
 pub struct BunRuntime {
    binary_path: PathBuf,
+   /// Version reported by `--version`, filled in on first use
+   version: OnceLock<(u32, u32, u32)>,
 }
 
 impl BunRuntime {
    /// Get Bun runtime, downloading if necessary
    ///
    /// Priority:
+   /// 0. Use an Athas-managed Bun pinned with `install`
    /// 1. Check system PATH for Bun >= 1.0.0
    /// 2. Check if Athas-managed Bun exists
    /// 3. Download Bun from GitHub releases
    pub async fn get_or_install(managed_root: Option<&Path>) -> Result<Self, RuntimeError> {
+      // 0. A pinned release wins over whatever is on PATH
+      if let Some(runtime) = Self::pinned_managed(managed_root) {
+         log::info!("Using pinned Bun at {:?}", runtime.binary_path);
+         return Ok(runtime);
+      }
+
       // 1. Check system PATH
       if let Ok(runtime) = Self::detect_system().await {
          log::info!("Using system Bun at {:?}", runtime.binary_path);
@@ -40,21 +53,31 @@ impl BunRuntime {
 
       // 3. Download and install
       log::info!("No suitable Bun found, downloading v{}", BUN_VERSION);
-      Self::download_and_install(managed_root).await
+      Self::download_and_install(managed_root, BUN_VERSION).await
    }
 
    /// Get runtime status without installing
    pub async fn get_status(managed_root: Option<&Path>) -> RuntimeStatus {
+      if let Some(runtime) = Self::pinned_managed(managed_root) {
+         return RuntimeStatus::ManagedInstalled {
+            version: runtime.version().await.ok(),
+         };
+      }
+
       // Check system first
-      if Self::detect_system().await.is_ok() {
-         return RuntimeStatus::SystemAvailable;
+      if let Ok(runtime) = Self::detect_system().await {
+         return RuntimeStatus::SystemAvailable {
+            version: runtime.version().await.ok(),
+         };
       }
 
       // Check managed installation
       if let Ok(managed_dir) = Self::get_managed_dir(managed_root)
-         && Self::from_managed_path(&managed_dir).is_ok()
+         && let Ok(runtime) = Self::from_managed_path(&managed_dir)
       {
-         return RuntimeStatus::ManagedInstalled;
+         return RuntimeStatus::ManagedInstalled {
+            version: runtime.version().await.ok(),
+         };
       }
 
       RuntimeStatus::NotInstalled
@@ -62,22 +85,65 @@ impl BunRuntime {
 
    /// Get the Bun version if installed
    pub async fn get_version(managed_root: Option<&Path>) -> Option<String> {
-      if let Ok(runtime) = Self::get_or_install(managed_root).await
-         && let Ok(version) = runtime.check_version().await
-      {
-         return Some(format!("{}.{}.{}", version.0, version.1, version.2));
+      let runtime = Self::get_or_install(managed_root).await.ok()?;
+      runtime.version().await.ok()
+   }
+
+   /// Download a Bun release into the managed directory, replacing any managed copy.
+   /// `version` pins a release such as `1.1.30`; without it `BUN_VERSION` is installed. A pinned
+   /// release is used ahead of the system PATH until it is removed or reinstalled unpinned.
+   pub async fn install(
+      managed_root: Option<&Path>,
+      version: Option<&str>,
+   ) -> Result<Self, RuntimeError> {
+      let pinned = version
+         .map(|version| parse_pinned_version(version, MIN_BUN_VERSION))
+         .transpose()?;
+      let version = pinned.as_deref().unwrap_or(BUN_VERSION);
+      log::info!("Installing Bun v{}", version);
+      let runtime = Self::download_and_install(managed_root, version).await?;
+      if let Some(pinned) = &pinned {
+         write_pinned_version(&Self::get_managed_dir(managed_root)?, pinned)?;
       }
-      None
+      Ok(runtime)
+   }
+
+   /// Bun version as reported by `--version`, cached after the first check
+   pub async fn version(&self) -> Result<String, RuntimeError> {
+      let (major, minor, patch) = self.version_parts().await?;
+      Ok(format!("{}.{}.{}", major, minor, patch))
+   }
+
+   async fn version_parts(&self) -> Result<(u32, u32, u32), RuntimeError> {
+      if let Some(version) = self.version.get() {
+         return Ok(*version);
+      }
+      let version = self.check_version().await?;
+      Ok(*self.version.get_or_init(|| version))
+   }
+
+   fn new(binary_path: PathBuf) -> Self {
+      Self {
+         binary_path,
+         version: OnceLock::new(),
+      }
+   }
+
+   /// Remove the Athas-managed Bun so the next use downloads it again. A Bun found on
+   /// the system PATH is never touched. Returns whether there was a managed copy to remove.
+   pub fn uninstall(managed_root: Option<&Path>) -> Result<bool, RuntimeError> {
+      let managed_dir = Self::get_managed_dir(managed_root)?;
+      remove_managed_dir(&managed_dir)
    }
 
    /// Detect Bun on system PATH
    async fn detect_system() -> Result<Self, RuntimeError> {
       let path = which::which("bun").map_err(|_| RuntimeError::NotFound("bun".to_string()))?;
 
-      let runtime = Self { binary_path: path };
+      let runtime = Self::new(path);
 
       // Check version
-      let version = runtime.check_version().await?;
+      let version = runtime.version_parts().await?;
       if version < MIN_BUN_VERSION {
          return Err(RuntimeError::VersionTooOld {
             found: format!("{}.{}.{}", version.0, version.1, version.2),
@@ -91,6 +157,13 @@ impl BunRuntime {
       Ok(runtime)
    }
 
+   /// The Athas-managed Bun, if it was installed as a pinned release
+   fn pinned_managed(managed_root: Option<&Path>) -> Option<Self> {
+      let managed_dir = Self::get_managed_dir(managed_root).ok()?;
+      read_pinned_version(&managed_dir)?;
+      Self::from_managed_path(&managed_dir).ok()
+   }
+
    /// Create runtime from managed installation path
    fn from_managed_path(managed_dir: &std::path::Path) -> Result<Self, RuntimeError> {
       let binary_path = get_bun_binary_path(managed_dir);
@@ -101,11 +174,14 @@ impl BunRuntime {
          ));
       }
 
-      Ok(Self { binary_path })
+      Ok(Self::new(binary_path))
    }
 
    /// Download Bun and install it
-   async fn download_and_install(managed_root: Option<&Path>) -> Result<Self, RuntimeError> {
+   async fn download_and_install(
+      managed_root: Option<&Path>,
+      version: &str,
+   ) -> Result<Self, RuntimeError> {
       let managed_dir = Self::get_managed_dir(managed_root)?;
 
       // Remove existing installation if present
@@ -114,7 +190,7 @@ impl BunRuntime {
       }
 
       // Download and extract
-      download_bun(BUN_VERSION, &managed_dir).await?;
+      download_bun(version, &managed_dir).await?;
 
       // Return the new runtime
       Self::from_managed_path(&managed_dir)
@@ -328,4 +404,19 @@ mod tests {
       assert_eq!(BunRuntime::parse_version("1.0.0").unwrap(), (1, 0, 0));
       assert_eq!(BunRuntime::parse_version("1.1.42\n").unwrap(), (1, 1, 42));
    }
+
+   #[test]
+   fn prefers_managed_bun_only_when_pinned() {
+      let temp = tempfile::tempdir().expect("tempdir");
+      let bun_dir = temp.path().join("bun");
+      fs::create_dir_all(&bun_dir).expect("bun dir");
+      fs::write(get_bun_binary_path(&bun_dir), "").expect("bun binary");
+
+      assert!(BunRuntime::pinned_managed(Some(temp.path())).is_none());
+
+      write_pinned_version(&bun_dir, "1.1.30").expect("pin");
+      let runtime = BunRuntime::pinned_managed(Some(temp.path())).expect("pinned bun");
+      assert_eq!(runtime.binary_path(), &get_bun_binary_path(&bun_dir));
+      assert_eq!(read_pinned_version(&bun_dir).as_deref(), Some("1.1.30"));
+   }
 }
@@ -2,12 +2,15 @@ mod bun;
 mod downloader;
 mod node;
 pub mod process;
+mod python;
+pub mod shell;
 
 pub use bun::BunRuntime;
 pub use node::NodeRuntime;
+pub use python::detect_project_python;
 use serde::{Deserialize, Serialize};
 use std::{
-   env, fmt,
+   env, fmt, fs,
    path::{Path, PathBuf},
 };
 
@@ -20,6 +23,7 @@ pub enum RuntimeType {
    Python,
    Go,
    Rust,
+   Zig,
 }
 
 /// Unified runtime manager that handles multiple runtime types
@@ -60,6 +64,7 @@ impl RuntimeManager {
          RuntimeType::Python => Self::detect_python(),
          RuntimeType::Go => Self::detect_go(),
          RuntimeType::Rust => Self::detect_rust(),
+         RuntimeType::Zig => Self::detect_zig(),
       }
    }
 
@@ -73,21 +78,28 @@ impl RuntimeManager {
          RuntimeType::Node => NodeRuntime::get_status(managed_root).await,
          RuntimeType::Python => {
             if Self::detect_python().is_ok() {
-               RuntimeStatus::SystemAvailable
+               RuntimeStatus::SystemAvailable { version: None }
             } else {
                RuntimeStatus::NotInstalled
             }
          }
          RuntimeType::Go => {
             if Self::detect_go().is_ok() {
-               RuntimeStatus::SystemAvailable
+               RuntimeStatus::SystemAvailable { version: None }
             } else {
                RuntimeStatus::NotInstalled
             }
          }
          RuntimeType::Rust => {
             if Self::detect_rust().is_ok() {
-               RuntimeStatus::SystemAvailable
+               RuntimeStatus::SystemAvailable { version: None }
+            } else {
+               RuntimeStatus::NotInstalled
+            }
+         }
+         RuntimeType::Zig => {
+            if Self::detect_zig().is_ok() {
+               RuntimeStatus::SystemAvailable { version: None }
             } else {
                RuntimeStatus::NotInstalled
             }
@@ -95,6 +107,49 @@ impl RuntimeManager {
       }
    }
 
+   /// Download a runtime into the managed directory, replacing any managed copy. `version`
+   /// pins a specific release; without it the default release is installed.
+   pub async fn install(
+      managed_root: Option<&Path>,
+      runtime_type: RuntimeType,
+      version: Option<&str>,
+   ) -> Result<PathBuf, RuntimeError> {
+      match runtime_type {
+         RuntimeType::Bun => {
+            let runtime = BunRuntime::install(managed_root, version).await?;
+            Ok(runtime.binary_path().clone())
+         }
+         RuntimeType::Node => {
+            let runtime = NodeRuntime::install(managed_root, version).await?;
+            Ok(runtime.binary_path().clone())
+         }
+         RuntimeType::Python | RuntimeType::Go | RuntimeType::Rust | RuntimeType::Zig => {
+            Err(RuntimeError::Other(format!(
+               "{:?} is provided by the system and is not managed by Athas",
+               runtime_type
+            )))
+         }
+      }
+   }
+
+   /// Remove the Athas-managed copy of a runtime so it is downloaded again on next use.
+   /// Python, Go, Rust and Zig are only ever detected on the system, so they are refused.
+   pub fn uninstall(
+      managed_root: Option<&Path>,
+      runtime_type: RuntimeType,
+   ) -> Result<bool, RuntimeError> {
+      match runtime_type {
+         RuntimeType::Bun => BunRuntime::uninstall(managed_root),
+         RuntimeType::Node => NodeRuntime::uninstall(managed_root),
+         RuntimeType::Python | RuntimeType::Go | RuntimeType::Rust | RuntimeType::Zig => {
+            Err(RuntimeError::Other(format!(
+               "{:?} is provided by the system and is not managed by Athas",
+               runtime_type
+            )))
+         }
+      }
+   }
+
    fn detect_python() -> Result<PathBuf, RuntimeError> {
       if let Ok(path) = which::which("python3") {
          return Ok(path);
@@ -163,6 +218,69 @@ impl RuntimeManager {
       }
       Err(RuntimeError::NotFound("cargo".to_string()))
    }
+
+   fn detect_zig() -> Result<PathBuf, RuntimeError> {
+      if let Ok(path) = which::which("zig") {
+         return Ok(path);
+      }
+      if let Some(path) = find_binary_in_dirs("zig", common_system_binary_dirs()) {
+         return Ok(path);
+      }
+      Err(RuntimeError::NotFound("zig".to_string()))
+   }
+}
+
+/// Validate a pinned release such as `1.1.30` or `v22.5.1`, returning it without the `v`.
+/// Only plain `major.minor.patch` versions at or above `minimum` are accepted, since the
+/// version ends up in the download URL.
+fn parse_pinned_version(version: &str, minimum: (u32, u32, u32)) -> Result<String, RuntimeError> {
+   let trimmed = version.trim().trim_start_matches('v');
+   let parts = trimmed
+      .split('.')
+      .map(|part| part.parse::<u32>().ok())
+      .collect::<Option<Vec<_>>>();
+
+   let Some([major, minor, patch]) = parts.as_deref() else {
+      return Err(RuntimeError::Other(format!(
+         "Invalid runtime version '{}', expected major.minor.patch",
+         version
+      )));
+   };
+   if (*major, *minor, *patch) < minimum {
+      return Err(RuntimeError::VersionTooOld {
+         found: trimmed.to_string(),
+         minimum: format!("{}.{}.{}", minimum.0, minimum.1, minimum.2),
+      });
+   }
+
+   Ok(format!("{}.{}.{}", major, minor, patch))
+}
+
+/// Marker in a managed runtime directory recording the release the user pinned
+const PINNED_VERSION_FILE: &str = ".athas-pinned-version";
+
+/// Remember that the runtime in `managed_dir` was installed as a pinned release
+fn write_pinned_version(managed_dir: &Path, version: &str) -> Result<(), RuntimeError> {
+   fs::write(managed_dir.join(PINNED_VERSION_FILE), version)?;
+   Ok(())
+}
+
+/// The release pinned for the runtime in `managed_dir`, if one was
+fn read_pinned_version(managed_dir: &Path) -> Option<String> {
+   fs::read_to_string(managed_dir.join(PINNED_VERSION_FILE))
+      .ok()
+      .map(|version| version.trim().to_string())
+      .filter(|version| !version.is_empty())
+}
+
+/// Delete a managed runtime directory, returning whether it existed
+fn remove_managed_dir(managed_dir: &Path) -> Result<bool, RuntimeError> {
+   if !managed_dir.exists() {
+      return Ok(false);
+   }
+   fs::remove_dir_all(managed_dir)?;
+   log::info!("Removed managed runtime at {:?}", managed_dir);
+   Ok(true)
 }
 
 fn find_binary_in_dirs(
@@ -228,6 +346,40 @@ mod tests {
       );
    }
 
+   #[test]
+   fn validates_pinned_versions() {
+      assert_eq!(
+         parse_pinned_version("v22.5.1", (22, 0, 0)).expect("version"),
+         "22.5.1"
+      );
+      assert_eq!(
+         parse_pinned_version(" 1.1.30 ", (1, 0, 0)).expect("version"),
+         "1.1.30"
+      );
+      assert!(matches!(
+         parse_pinned_version("20.11.0", (22, 0, 0)),
+         Err(RuntimeError::VersionTooOld { .. })
+      ));
+      assert!(parse_pinned_version("1.1", (1, 0, 0)).is_err());
+      assert!(parse_pinned_version("1.1.30/../x", (1, 0, 0)).is_err());
+      assert!(parse_pinned_version("latest", (1, 0, 0)).is_err());
+   }
+
+   #[test]
+   fn uninstalls_only_managed_runtimes() {
+      let temp = tempfile::tempdir().expect("tempdir");
+      let bun_dir = temp.path().join("bun");
+      std::fs::create_dir_all(&bun_dir).expect("bun dir");
+      std::fs::write(bun::get_bun_binary_path(&bun_dir), "").expect("bun binary");
+
+      assert!(RuntimeManager::uninstall(Some(temp.path()), RuntimeType::Bun).expect("uninstall"));
+      assert!(!bun_dir.exists());
+      assert!(!RuntimeManager::uninstall(Some(temp.path()), RuntimeType::Bun).expect("uninstall"));
+      assert!(!RuntimeManager::uninstall(Some(temp.path()), RuntimeType::Node).expect("uninstall"));
+      assert!(RuntimeManager::uninstall(Some(temp.path()), RuntimeType::Python).is_err());
+      assert!(RuntimeManager::uninstall(None, RuntimeType::Node).is_err());
+   }
+
    #[test]
    #[cfg(target_os = "macos")]
    fn common_system_dirs_include_homebrew_locations() {
@@ -269,14 +421,14 @@ mod tests {
 
 /// Status of a runtime installation
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "camelCase")]
+#[serde(tag = "status", rename_all = "camelCase")]
 pub enum RuntimeStatus {
    /// Runtime is not installed and not available
    NotInstalled,
-   /// Runtime is available on system PATH
-   SystemAvailable,
+   /// Runtime is available on system PATH. Only Bun and Node.js report a version.
+   SystemAvailable { version: Option<String> },
    /// Runtime was downloaded and managed by Athas
-   ManagedInstalled,
+   ManagedInstalled { version: Option<String> },
    /// Runtime path is configured by user in settings
    CustomConfigured,
 }
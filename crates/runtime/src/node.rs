@@ -1,7 +1,12 @@
-use crate::{RuntimeError, RuntimeStatus, downloader, process::configure_background_command};
+use crate::{
+   RuntimeError, RuntimeStatus, downloader, parse_pinned_version,
+   process::configure_background_command, read_pinned_version, remove_managed_dir,
+   write_pinned_version,
+};
 use std::{
    path::{Path, PathBuf},
    process::Command,
+   sync::OnceLock,
 };
 
 /// Node.js version to download if system version is not available
@@ -13,16 +18,25 @@ pub const MIN_NODE_VERSION: (u32, u32, u32) = (22, 0, 0);
 /// Manages Node.js runtime for running JS-based language servers
 pub struct NodeRuntime {
    binary_path: PathBuf,
+   /// Version reported by `--version`, filled in on first use
+   version: OnceLock<(u32, u32, u32)>,
 }
 
 impl NodeRuntime {
    /// Get Node.js runtime, downloading if necessary
    ///
    /// Priority:
+   /// 0. Use an Athas-managed Node.js pinned with `install`
    /// 1. Check system PATH for Node.js >= 22.0.0
    /// 2. Check if Athas-managed Node.js exists
    /// 3. Download Node.js from nodejs.org
    pub async fn get_or_install(managed_root: Option<&Path>) -> Result<Self, RuntimeError> {
+      // 0. A pinned release wins over whatever is on PATH
+      if let Some(runtime) = Self::pinned_managed(managed_root) {
+         log::info!("Using pinned Node.js at {:?}", runtime.binary_path);
+         return Ok(runtime);
+      }
+
       // 1. Check system PATH
       if let Ok(runtime) = Self::detect_system().await {
          log::info!("Using system Node.js at {:?}", runtime.binary_path);
@@ -38,7 +52,7 @@ impl NodeRuntime {
 
       // 3. Download and install
       log::info!("No suitable Node.js found, downloading v{}", NODE_VERSION);
-      Self::download_and_install(managed_root).await
+      Self::download_and_install(managed_root, NODE_VERSION).await
    }
 
    /// Get Node.js runtime, preferring an existing Athas-managed runtime over
@@ -62,21 +76,31 @@ impl NodeRuntime {
       }
 
       log::info!("No suitable Node.js found, downloading v{}", NODE_VERSION);
-      Self::download_and_install(managed_root).await
+      Self::download_and_install(managed_root, NODE_VERSION).await
    }
 
    /// Get runtime status without installing
    pub async fn get_status(managed_root: Option<&Path>) -> RuntimeStatus {
+      if let Some(runtime) = Self::pinned_managed(managed_root) {
+         return RuntimeStatus::ManagedInstalled {
+            version: runtime.version().await.ok(),
+         };
+      }
+
       // Check system first
-      if Self::detect_system().await.is_ok() {
-         return RuntimeStatus::SystemAvailable;
+      if let Ok(runtime) = Self::detect_system().await {
+         return RuntimeStatus::SystemAvailable {
+            version: runtime.version().await.ok(),
+         };
       }
 
       // Check managed installation
       if let Ok(managed_dir) = Self::get_managed_dir(managed_root)
-         && Self::from_managed_path(&managed_dir).is_ok()
+         && let Ok(runtime) = Self::from_managed_path(&managed_dir)
       {
-         return RuntimeStatus::ManagedInstalled;
+         return RuntimeStatus::ManagedInstalled {
+            version: runtime.version().await.ok(),
+         };
       }
 
       RuntimeStatus::NotInstalled
@@ -84,22 +108,65 @@ impl NodeRuntime {
 
    /// Get the Node.js version if installed
    pub async fn get_version(managed_root: Option<&Path>) -> Option<String> {
-      if let Ok(runtime) = Self::get_or_install(managed_root).await
-         && let Ok(version) = runtime.check_version().await
-      {
-         return Some(format!("{}.{}.{}", version.0, version.1, version.2));
+      let runtime = Self::get_or_install(managed_root).await.ok()?;
+      runtime.version().await.ok()
+   }
+
+   /// Download a Node.js release into the managed directory, replacing any managed copy.
+   /// `version` pins a release such as `22.5.1`; without it `NODE_VERSION` is installed. A pinned
+   /// release is used ahead of the system PATH until it is removed or reinstalled unpinned.
+   pub async fn install(
+      managed_root: Option<&Path>,
+      version: Option<&str>,
+   ) -> Result<Self, RuntimeError> {
+      let pinned = version
+         .map(|version| parse_pinned_version(version, MIN_NODE_VERSION))
+         .transpose()?;
+      let version = pinned.as_deref().unwrap_or(NODE_VERSION);
+      log::info!("Installing Node.js v{}", version);
+      let runtime = Self::download_and_install(managed_root, version).await?;
+      if let Some(pinned) = &pinned {
+         write_pinned_version(&Self::get_managed_dir(managed_root)?, pinned)?;
+      }
+      Ok(runtime)
+   }
+
+   /// Node.js version as reported by `--version`, cached after the first check
+   pub async fn version(&self) -> Result<String, RuntimeError> {
+      let (major, minor, patch) = self.version_parts().await?;
+      Ok(format!("{}.{}.{}", major, minor, patch))
+   }
+
+   async fn version_parts(&self) -> Result<(u32, u32, u32), RuntimeError> {
+      if let Some(version) = self.version.get() {
+         return Ok(*version);
+      }
+      let version = self.check_version().await?;
+      Ok(*self.version.get_or_init(|| version))
+   }
+
+   fn new(binary_path: PathBuf) -> Self {
+      Self {
+         binary_path,
+         version: OnceLock::new(),
       }
-      None
+   }
+
+   /// Remove the Athas-managed Node.js so the next use downloads it again. A Node.js found on
+   /// the system PATH is never touched. Returns whether there was a managed copy to remove.
+   pub fn uninstall(managed_root: Option<&Path>) -> Result<bool, RuntimeError> {
+      let managed_dir = Self::get_managed_dir(managed_root)?;
+      remove_managed_dir(&managed_dir)
    }
 
    /// Detect Node.js on system PATH
    async fn detect_system() -> Result<Self, RuntimeError> {
       let path = which::which("node").map_err(|_| RuntimeError::NotFound("node".to_string()))?;
 
-      let runtime = Self { binary_path: path };
+      let runtime = Self::new(path);
 
       // Check version
-      let version = runtime.check_version().await?;
+      let version = runtime.version_parts().await?;
       if version < MIN_NODE_VERSION {
          return Err(RuntimeError::VersionTooOld {
             found: format!("{}.{}.{}", version.0, version.1, version.2),
@@ -113,6 +180,13 @@ impl NodeRuntime {
       Ok(runtime)
    }
 
+   /// The Athas-managed Node.js, if it was installed as a pinned release
+   fn pinned_managed(managed_root: Option<&Path>) -> Option<Self> {
+      let managed_dir = Self::get_managed_dir(managed_root).ok()?;
+      read_pinned_version(&managed_dir)?;
+      Self::from_managed_path(&managed_dir).ok()
+   }
+
    /// Create runtime from managed installation path
    fn from_managed_path(managed_dir: &std::path::Path) -> Result<Self, RuntimeError> {
       let binary_path = downloader::get_node_binary_path(managed_dir);
@@ -123,11 +197,14 @@ impl NodeRuntime {
          ));
       }
 
-      Ok(Self { binary_path })
+      Ok(Self::new(binary_path))
    }
 
    /// Download Node.js and install it
-   async fn download_and_install(managed_root: Option<&Path>) -> Result<Self, RuntimeError> {
+   async fn download_and_install(
+      managed_root: Option<&Path>,
+      version: &str,
+   ) -> Result<Self, RuntimeError> {
       let managed_dir = Self::get_managed_dir(managed_root)?;
 
       // Remove existing installation if present
@@ -136,7 +213,7 @@ impl NodeRuntime {
       }
 
       // Download and extract
-      downloader::download_node(NODE_VERSION, &managed_dir).await?;
+      downloader::download_node(version, &managed_dir).await?;
 
       // Return the new runtime
       Self::from_managed_path(&managed_dir)
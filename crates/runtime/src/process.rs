@@ -1,4 +1,10 @@
-use std::process::Command;
+use std::{
+   io::{self, Read},
+   process::{Command, Output, Stdio},
+   sync::{Arc, Mutex, mpsc},
+   thread,
+   time::{Duration, Instant},
+};
 
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
@@ -12,3 +18,170 @@ pub fn configure_background_command(command: &mut Command) -> &mut Command {
 
    command
 }
+
+/// Start the command in a process group of its own, so `kill_process_tree` can stop it together
+/// with everything it spawns.
+pub fn configure_process_group(command: &mut Command) -> &mut Command {
+   #[cfg(unix)]
+   {
+      use std::os::unix::process::CommandExt;
+      command.process_group(0);
+   }
+
+   command
+}
+
+/// Kill a process started with `configure_process_group` along with its descendants. On Windows
+/// the process tree is ended with `taskkill`, which blocks until it is done.
+pub fn kill_process_tree(pid: u32) {
+   #[cfg(unix)]
+   if let Ok(pid) = libc::pid_t::try_from(pid)
+      && pid > 0
+   {
+      unsafe {
+         libc::kill(-pid, libc::SIGKILL);
+      }
+   }
+
+   #[cfg(target_os = "windows")]
+   {
+      let mut command = Command::new("taskkill");
+      configure_background_command(&mut command);
+      let _ = command
+         .args(["/T", "/F", "/PID", &pid.to_string()])
+         .stdout(Stdio::null())
+         .stderr(Stdio::null())
+         .status();
+   }
+}
+
+/// Prepend the user's login shell PATH so tools installed outside the system paths are found.
+pub fn apply_login_shell_path(command: &mut Command) -> &mut Command {
+   if let Some(path) = crate::shell::login_shell_path_env() {
+      command.env("PATH", path);
+   }
+
+   command
+}
+
+/// Run `command` like `Command::output`, but kill it and everything it spawned once `timeout`
+/// passes. A command that runs too long fails with `ErrorKind::TimedOut`. Output is read until
+/// the same deadline, so a background process still holding the pipes open cannot hang the
+/// caller; whatever was read by then is returned.
+pub fn output_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+   let mut child = configure_process_group(command)
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped())
+      .spawn()?;
+
+   let stdout = child.stdout.take().map(read_in_background);
+   let stderr = child.stderr.take().map(read_in_background);
+   let deadline = Instant::now() + timeout;
+   let status = loop {
+      if let Some(status) = child.try_wait()? {
+         break status;
+      }
+      if Instant::now() >= deadline {
+         kill_process_tree(child.id());
+         let _ = child.kill();
+         let _ = child.wait();
+         return Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("command did not finish within {:?}", timeout),
+         ));
+      }
+      thread::sleep(Duration::from_millis(20));
+   };
+
+   let collect = |reader: Option<BackgroundReader>| {
+      reader
+         .map(|reader| reader.finish(deadline))
+         .unwrap_or_default()
+   };
+   Ok(Output {
+      status,
+      stdout: collect(stdout),
+      stderr: collect(stderr),
+   })
+}
+
+/// A pipe drained on its own thread. The thread is left behind if the pipe is still open when
+/// the output is collected, and exits once the last writer closes it.
+struct BackgroundReader {
+   buffer: Arc<Mutex<Vec<u8>>>,
+   done: mpsc::Receiver<()>,
+}
+
+impl BackgroundReader {
+   /// Wait until the pipe closes or `deadline` passes, then return what was read
+   fn finish(self, deadline: Instant) -> Vec<u8> {
+      let _ = self
+         .done
+         .recv_timeout(deadline.saturating_duration_since(Instant::now()));
+      std::mem::take(&mut *self.buffer.lock().unwrap())
+   }
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> BackgroundReader {
+   let buffer = Arc::new(Mutex::new(Vec::new()));
+   let (done_tx, done) = mpsc::channel();
+   let thread_buffer = buffer.clone();
+   thread::spawn(move || {
+      let mut chunk = [0u8; 8192];
+      loop {
+         match pipe.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => thread_buffer.lock().unwrap().extend_from_slice(&chunk[..n]),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+         }
+      }
+      let _ = done_tx.send(());
+   });
+   BackgroundReader { buffer, done }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn returns_output_of_commands_that_finish_in_time() {
+      let output = output_with_timeout(
+         Command::new("sh").args(["-c", "echo ready"]),
+         Duration::from_secs(5),
+      )
+      .expect("output");
+
+      assert!(output.status.success());
+      assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ready");
+   }
+
+   #[test]
+   fn kills_commands_that_run_too_long() {
+      let started = Instant::now();
+      let error = output_with_timeout(
+         Command::new("sh").args(["-c", "sleep 30; echo late"]),
+         Duration::from_millis(200),
+      )
+      .expect_err("timeout");
+
+      assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+      assert!(started.elapsed() < Duration::from_secs(5));
+   }
+
+   #[test]
+   fn returns_once_the_command_exits_even_if_a_background_process_keeps_the_pipes() {
+      let started = Instant::now();
+      let output = output_with_timeout(
+         Command::new("sh").args(["-c", "sleep 30 & echo ready"]),
+         Duration::from_millis(500),
+      )
+      .expect("output");
+
+      assert!(output.status.success());
+      assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ready");
+      assert!(started.elapsed() < Duration::from_secs(5));
+   }
+}
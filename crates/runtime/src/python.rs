@@ -0,0 +1,172 @@
+use crate::process::{apply_login_shell_path, configure_background_command, output_with_timeout};
+use std::{
+   env, fs,
+   path::{Component, Path, PathBuf},
+   process::Command,
+   time::Duration,
+};
+
+/// Virtualenv directories looked for at the workspace root, in order
+const VENV_DIRS: &[&str] = &[".venv", "venv"];
+
+/// How long `poetry env info` may take before the Poetry environment is skipped
+const POETRY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Find the Python interpreter a workspace is set up to use, so language servers analyze
+/// against the project's packages instead of the global interpreter.
+///
+/// Priority:
+/// 1. A `.venv` or `venv` virtualenv at the workspace root
+/// 2. The pyenv version named in `.python-version`
+/// 3. The Poetry environment of a `pyproject.toml` with a `[tool.poetry]` table
+///
+/// Language servers run the interpreter they are given, so one that is a file inside the
+/// workspace is never returned: a cloned repository could ship any program as
+/// `.venv/bin/python`. Virtualenv interpreters are symlinks to a base interpreter on Unix and
+/// qualify. This may run `poetry`, so call it off the async runtime.
+pub fn detect_project_python(workspace: &Path) -> Option<PathBuf> {
+   let python = venv_python(workspace)
+      .or_else(|| pyenv_root().and_then(|root| pyenv_python(workspace, &root)))
+      .or_else(|| poetry_python(workspace))?;
+   resolves_outside(&python, workspace).then_some(python)
+}
+
+fn venv_python(workspace: &Path) -> Option<PathBuf> {
+   VENV_DIRS
+      .iter()
+      .map(|dir| interpreter_in_env(&workspace.join(dir)))
+      .find(|python| python.exists() && resolves_outside(python, workspace))
+}
+
+/// Whether `python` is, after following symlinks, a file outside `workspace`
+fn resolves_outside(python: &Path, workspace: &Path) -> bool {
+   match (fs::canonicalize(python), fs::canonicalize(workspace)) {
+      (Ok(python), Ok(workspace)) => !python.starts_with(workspace),
+      _ => false,
+   }
+}
+
+fn pyenv_root() -> Option<PathBuf> {
+   env::var_os("PYENV_ROOT")
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".pyenv")))
+}
+
+/// Interpreter of the first version in `.python-version` that pyenv has installed. Entries
+/// that are not a plain directory name, such as `../../tmp/x`, are ignored.
+fn pyenv_python(workspace: &Path, pyenv_root: &Path) -> Option<PathBuf> {
+   let contents = fs::read_to_string(workspace.join(".python-version")).ok()?;
+   contents
+      .lines()
+      .map(str::trim)
+      .filter(|version| !version.is_empty() && !version.starts_with('#'))
+      .filter(|version| is_plain_name(version))
+      .map(|version| interpreter_in_env(&pyenv_root.join("versions").join(version)))
+      .find(|python| python.exists())
+}
+
+fn is_plain_name(name: &str) -> bool {
+   let mut components = Path::new(name).components();
+   matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+}
+
+fn poetry_python(workspace: &Path) -> Option<PathBuf> {
+   let pyproject = fs::read_to_string(workspace.join("pyproject.toml")).ok()?;
+   if !pyproject.contains("[tool.poetry]") {
+      return None;
+   }
+
+   let poetry = which::which("poetry").ok()?;
+   let mut command = Command::new(poetry);
+   apply_login_shell_path(configure_background_command(&mut command))
+      .args(["env", "info", "--path"])
+      .current_dir(workspace);
+   let output = match output_with_timeout(&mut command, POETRY_TIMEOUT) {
+      Ok(output) => output,
+      Err(error) => {
+         log::warn!("Skipping Poetry environment of {:?}: {}", workspace, error);
+         return None;
+      }
+   };
+   if !output.status.success() {
+      return None;
+   }
+
+   let env_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+   if env_path.is_empty() {
+      return None;
+   }
+   let python = interpreter_in_env(Path::new(&env_path));
+   python.exists().then_some(python)
+}
+
+fn interpreter_in_env(env_dir: &Path) -> PathBuf {
+   if cfg!(windows) {
+      env_dir.join("Scripts").join("python.exe")
+   } else {
+      env_dir.join("bin").join("python")
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn create_interpreter(env_dir: &Path) -> PathBuf {
+      let python = interpreter_in_env(env_dir);
+      fs::create_dir_all(python.parent().expect("interpreter dir")).expect("env dir");
+      fs::write(&python, "").expect("interpreter");
+      python
+   }
+
+   /// A virtualenv whose interpreter links to `base`, the way `python -m venv` lays it out
+   #[cfg(unix)]
+   fn create_venv(env_dir: &Path, base: &Path) -> PathBuf {
+      let python = interpreter_in_env(env_dir);
+      fs::create_dir_all(python.parent().expect("interpreter dir")).expect("env dir");
+      std::os::unix::fs::symlink(base, &python).expect("interpreter link");
+      python
+   }
+
+   #[test]
+   #[cfg(unix)]
+   fn prefers_dot_venv_over_venv() {
+      let workspace = tempfile::tempdir().expect("tempdir");
+      let base = tempfile::tempdir().expect("tempdir");
+      let base_python = create_interpreter(base.path());
+      let venv = create_venv(&workspace.path().join("venv"), &base_python);
+
+      assert_eq!(venv_python(workspace.path()), Some(venv));
+
+      let dot_venv = create_venv(&workspace.path().join(".venv"), &base_python);
+      assert_eq!(detect_project_python(workspace.path()), Some(dot_venv));
+   }
+
+   #[test]
+   fn ignores_interpreters_shipped_inside_the_workspace() {
+      let workspace = tempfile::tempdir().expect("tempdir");
+      create_interpreter(&workspace.path().join(".venv"));
+
+      assert_eq!(venv_python(workspace.path()), None);
+      assert_eq!(detect_project_python(workspace.path()), None);
+   }
+
+   #[test]
+   fn resolves_installed_pyenv_versions() {
+      let workspace = tempfile::tempdir().expect("tempdir");
+      let pyenv_root = tempfile::tempdir().expect("tempdir");
+      let python = create_interpreter(&pyenv_root.path().join("versions").join("3.12.4"));
+      create_interpreter(&pyenv_root.path().join("escaped"));
+      fs::write(
+         workspace.path().join(".python-version"),
+         "# pinned\n../escaped\n/tmp/x\n3.13.0\n3.12.4\n",
+      )
+      .expect("python version");
+
+      assert_eq!(
+         pyenv_python(workspace.path(), pyenv_root.path()),
+         Some(python)
+      );
+      assert_eq!(venv_python(workspace.path()), None);
+   }
+}
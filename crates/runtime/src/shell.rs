@@ -0,0 +1,106 @@
+use crate::process::output_with_timeout;
+use std::{
+   collections::HashSet,
+   env,
+   ffi::{OsStr, OsString},
+   process::Command,
+   sync::OnceLock,
+   time::Duration,
+};
+
+static LOGIN_SHELL_PATH: OnceLock<Option<String>> = OnceLock::new();
+
+/// How long the login shell may take to print its PATH. A profile that waits on input or the
+/// network is killed after this, and tools get the inherited PATH instead.
+const LOGIN_SHELL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Get the PATH from the user's login shell, resolved once and cached.
+///
+/// Apps launched from Finder/Launchpad on macOS inherit a minimal PATH, so tools installed
+/// through Homebrew, rustup or version managers are not found unless the PATH is read from the
+/// shell. Always `None` on Windows.
+pub fn get_login_shell_path() -> Option<&'static str> {
+   LOGIN_SHELL_PATH
+      .get_or_init(load_login_shell_path)
+      .as_deref()
+}
+
+/// Resolve the login shell PATH in the background so the first spawned tool doesn't wait on it.
+pub fn warm_login_shell_path() {
+   std::thread::spawn(|| {
+      let _ = get_login_shell_path();
+   });
+}
+
+/// The PATH to give spawned tools: the login shell PATH followed by the process PATH.
+pub fn login_shell_path_env() -> Option<OsString> {
+   let login_path = get_login_shell_path()?;
+   merge_paths(login_path, env::var_os("PATH").as_deref())
+}
+
+fn load_login_shell_path() -> Option<String> {
+   if cfg!(target_os = "windows") {
+      return None;
+   }
+
+   let shell = env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+   let mut command = Command::new(&shell);
+   command.args(["-ilc", "echo $PATH"]);
+   let output = match output_with_timeout(&mut command, LOGIN_SHELL_TIMEOUT) {
+      Ok(output) => output,
+      Err(error) => {
+         log::warn!(
+            "Failed to read PATH from login shell {}, using the inherited PATH: {}",
+            shell,
+            error
+         );
+         return None;
+      }
+   };
+   if !output.status.success() {
+      log::warn!("Failed to read PATH from login shell {}", shell);
+      return None;
+   }
+
+   // Interactive profiles may print banners first, so the PATH is the last line.
+   let stdout = String::from_utf8(output.stdout).ok()?;
+   let path = stdout.lines().last()?.trim();
+   (!path.is_empty()).then(|| path.to_string())
+}
+
+/// Prepend `login_path` to `current`, dropping empty and duplicate entries.
+fn merge_paths(login_path: &str, current: Option<&OsStr>) -> Option<OsString> {
+   let mut seen = HashSet::new();
+   let entries = env::split_paths(login_path)
+      .chain(current.into_iter().flat_map(env::split_paths))
+      .filter(|entry| !entry.as_os_str().is_empty() && seen.insert(entry.clone()))
+      .collect::<Vec<_>>();
+
+   env::join_paths(entries).ok()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn merge_paths_prefers_login_entries_and_dedupes() {
+      let merged = merge_paths(
+         "/opt/homebrew/bin:/usr/bin",
+         Some(OsStr::new("/usr/bin:/bin::/usr/bin")),
+      );
+
+      assert_eq!(
+         merged,
+         Some(OsString::from("/opt/homebrew/bin:/usr/bin:/bin"))
+      );
+   }
+
+   #[test]
+   fn merge_paths_without_process_path() {
+      assert_eq!(
+         merge_paths("/usr/local/bin", None),
+         Some(OsString::from("/usr/local/bin"))
+      );
+   }
+}
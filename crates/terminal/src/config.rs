@@ -9,10 +9,20 @@ pub struct TerminalConfig {
    pub shell: Option<String>,
    pub wsl_distribution: Option<String>,
    pub wsl_working_directory: Option<String>,
+   /// Variables overlaid on the inherited environment. The process environment and the login
+   /// shell environment (including its PATH) are always inherited unless `clear_env` is set.
    pub environment: Option<HashMap<String, String>>,
+   /// Start from an empty environment so only `environment` and the terminal variables apply.
+   #[serde(default)]
+   pub clear_env: bool,
    pub command: Option<String>,
    pub args: Option<Vec<String>>,
    pub size: TerminalSize,
    #[serde(default)]
    pub term_program_version: Option<String>,
+   /// Longest time streaming PTY output is merged before it is sent as one event. Output is
+   /// sent sooner whenever no more is queued. Zero sends every read as it arrives. Defaults to
+   /// 16ms.
+   #[serde(default)]
+   pub output_batch_interval_ms: Option<u64>,
 }
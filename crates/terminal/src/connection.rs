@@ -1,5 +1,6 @@
 use crate::{
    config::TerminalConfig,
+   output_batch::{DEFAULT_OUTPUT_BATCH_INTERVAL, forward_batched_output},
    protocol::{TerminalEvent, TerminalEventHandler, TerminalReaderControl, TerminalSize},
    shell::get_shell_by_id,
 };
@@ -9,18 +10,27 @@ use std::{
    collections::HashMap,
    io::{Read, Write},
    path::Path,
-   sync::{Arc, Mutex, OnceLock},
+   sync::{Arc, Mutex, OnceLock, mpsc},
    thread,
+   time::Duration,
 };
 static USER_ENVIRONMENT_CACHE: OnceLock<HashMap<String, String>> = OnceLock::new();
 
+/// Largest single write to the PTY. Canonical mode line buffers hold about this much, and
+/// larger writes can drop characters while the program is still reading.
+const PTY_WRITE_CHUNK_SIZE: usize = 1024;
+
+/// Pause between chunks of a large write, giving the program time to drain its input
+const PTY_WRITE_CHUNK_PAUSE: Duration = Duration::from_millis(1);
+
 pub struct TerminalConnection {
    pub id: String,
    pub pty_pair: PtyPair,
    pub event_handler: TerminalEventHandler,
-   pub writer: Arc<Mutex<Option<Box<dyn Write + Send>>>>,
+   pub input: mpsc::Sender<Vec<u8>>,
    pub child: Arc<Mutex<Option<Box<dyn Child + Send + Sync>>>>,
    pub reader_control: Arc<TerminalReaderControl>,
+   pub output_batch_interval: Duration,
 }
 
 impl TerminalConnection {
@@ -32,6 +42,9 @@ impl TerminalConnection {
       let pty_system = portable_pty::native_pty_system();
 
       let size = config.size.normalized();
+      let output_batch_interval = config
+         .output_batch_interval_ms
+         .map_or(DEFAULT_OUTPUT_BATCH_INTERVAL, Duration::from_millis);
       let pty_pair = pty_system.openpty(PtySize {
          rows: size.rows,
          cols: size.cols,
@@ -41,16 +54,21 @@ impl TerminalConnection {
 
       let cmd = Self::build_command(&config)?;
       let child = pty_pair.slave.spawn_command(cmd)?;
-      let writer = Arc::new(Mutex::new(Some(pty_pair.master.take_writer()?)));
+      let input = Self::start_writer_thread(
+         id.clone(),
+         pty_pair.master.take_writer()?,
+         event_handler.clone(),
+      );
       let child = Arc::new(Mutex::new(Some(child)));
 
       Ok(Self {
          id,
          pty_pair,
          event_handler,
-         writer,
+         input,
          child,
          reader_control: Arc::new(TerminalReaderControl::default()),
+         output_batch_interval,
       })
    }
 
@@ -190,7 +208,12 @@ impl TerminalConnection {
 
       // First, inherit user's full shell environment
       // This ensures PATH, HOME, USER, LANG, and other critical vars are available
-      let user_env = Self::get_user_environment();
+      let user_env = if config.clear_env {
+         cmd.env_clear();
+         HashMap::new()
+      } else {
+         Self::get_user_environment()
+      };
       for (key, value) in &user_env {
          cmd.env(key, value);
       }
@@ -470,9 +493,31 @@ impl TerminalConnection {
       anyhow!("Failed to {operation}: {working_dir}: {err}")
    }
 
+   /// Start the thread that owns the PTY writer. Input is queued on the returned channel and
+   /// written in the order it was sent, so keystrokes never overtake each other or a paste.
+   fn start_writer_thread(
+      id: String,
+      mut writer: Box<dyn Write + Send>,
+      event_handler: TerminalEventHandler,
+   ) -> mpsc::Sender<Vec<u8>> {
+      let (sender, receiver) = mpsc::channel::<Vec<u8>>();
+      thread::spawn(move || {
+         for data in receiver {
+            if let Err(e) = write_in_chunks(&mut writer, &data, PTY_WRITE_CHUNK_PAUSE) {
+               event_handler(
+                  &id,
+                  TerminalEvent::Error {
+                     message: format!("Failed to write to terminal: {e}"),
+                  },
+               );
+               break;
+            }
+         }
+      });
+      sender
+   }
+
    pub fn start_reader_thread(&self) {
-      let id = self.id.clone();
-      let event_handler = self.event_handler.clone();
       let child = self.child.clone();
       let reader_control = self.reader_control.clone();
       let mut reader = self
@@ -481,6 +526,21 @@ impl TerminalConnection {
          .try_clone_reader()
          .expect("Failed to clone reader");
 
+      let emit: Box<dyn Fn(TerminalEvent) -> bool + Send> = {
+         let id = self.id.clone();
+         let event_handler = self.event_handler.clone();
+         if self.output_batch_interval.is_zero() {
+            Box::new(move |event| event_handler(&id, event))
+         } else {
+            let (sender, receiver) = mpsc::channel();
+            let interval = self.output_batch_interval;
+            thread::spawn(move || {
+               forward_batched_output(receiver, interval, |event| event_handler(&id, event))
+            });
+            Box::new(move |event| sender.send(event).is_ok())
+         }
+      };
+
       thread::spawn(move || {
          let mut buffer = vec![0u8; 65536]; // 64KB buffer for better performance
          loop {
@@ -491,17 +551,14 @@ impl TerminalConnection {
             match reader.read(&mut buffer) {
                Ok(0) => {
                   let (exit_code, signal) = Self::child_exit_status(&child, true);
-                  event_handler(&id, TerminalEvent::Exit { exit_code, signal });
-                  event_handler(&id, TerminalEvent::Closed);
+                  emit(TerminalEvent::Exit { exit_code, signal });
+                  emit(TerminalEvent::Closed);
                   break;
                }
                Ok(n) => {
-                  if !event_handler(
-                     &id,
-                     TerminalEvent::Output {
-                        data: buffer[..n].to_vec(),
-                     },
-                  ) {
+                  if !emit(TerminalEvent::Output {
+                     data: buffer[..n].to_vec(),
+                  }) {
                      break;
                   }
                }
@@ -513,17 +570,14 @@ impl TerminalConnection {
                      );
                   let (exit_code, signal) = Self::child_exit_status(&child, should_wait_for_status);
                   if exit_code.is_some() || signal.is_some() {
-                     event_handler(&id, TerminalEvent::Exit { exit_code, signal });
+                     emit(TerminalEvent::Exit { exit_code, signal });
                   } else {
                      eprintln!("Error reading from PTY: {}", e);
-                     event_handler(
-                        &id,
-                        TerminalEvent::Error {
-                           message: e.to_string(),
-                        },
-                     );
+                     emit(TerminalEvent::Error {
+                        message: e.to_string(),
+                     });
                   }
-                  event_handler(&id, TerminalEvent::Closed);
+                  emit(TerminalEvent::Closed);
                   break;
                }
             }
@@ -556,15 +610,12 @@ impl TerminalConnection {
       })
    }
 
-   pub fn write(&self, data: &[u8]) -> Result<()> {
-      let mut writer_guard = self.writer.lock().unwrap();
-      if let Some(writer) = writer_guard.as_mut() {
-         writer.write_all(data)?;
-         writer.flush()?;
-         Ok(())
-      } else {
-         Err(anyhow!("Terminal writer is not available"))
-      }
+   /// Queue `data` for the writer thread. Returns once it is queued, not once it is written.
+   pub fn write(&self, data: Vec<u8>) -> Result<()> {
+      self
+         .input
+         .send(data)
+         .map_err(|_| anyhow!("Terminal writer is not available"))
    }
 
    pub fn resize(&self, size: TerminalSize) -> Result<()> {
@@ -595,11 +646,97 @@ impl TerminalConnection {
    }
 }
 
+/// Write `data` in PTY-sized chunks, flushing after each one and pausing between them
+fn write_in_chunks(writer: &mut impl Write, data: &[u8], pause: Duration) -> std::io::Result<()> {
+   for (index, chunk) in data.chunks(PTY_WRITE_CHUNK_SIZE).enumerate() {
+      if index > 0 && !pause.is_zero() {
+         thread::sleep(pause);
+      }
+      writer.write_all(chunk)?;
+      writer.flush()?;
+   }
+   Ok(())
+}
+
 #[cfg(test)]
 mod tests {
    use super::*;
    use std::ffi::OsStr;
 
+   #[derive(Default)]
+   struct RecordingWriter {
+      writes: Vec<Vec<u8>>,
+      flushes: usize,
+   }
+
+   impl Write for RecordingWriter {
+      fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+         self.writes.push(buf.to_vec());
+         Ok(buf.len())
+      }
+
+      fn flush(&mut self) -> std::io::Result<()> {
+         self.flushes += 1;
+         Ok(())
+      }
+   }
+
+   #[test]
+   fn splits_large_writes_into_flushed_chunks() {
+      let data = vec![b'x'; PTY_WRITE_CHUNK_SIZE * 2 + 10];
+      let mut writer = RecordingWriter::default();
+
+      write_in_chunks(&mut writer, &data, Duration::ZERO).unwrap();
+
+      let sizes: Vec<_> = writer.writes.iter().map(Vec::len).collect();
+      assert_eq!(sizes, vec![PTY_WRITE_CHUNK_SIZE, PTY_WRITE_CHUNK_SIZE, 10]);
+      assert_eq!(writer.flushes, 3);
+      assert_eq!(writer.writes.concat(), data);
+   }
+
+   #[derive(Clone, Default)]
+   struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+   impl Write for SharedWriter {
+      fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+         self.0.lock().unwrap().extend_from_slice(buf);
+         Ok(buf.len())
+      }
+
+      fn flush(&mut self) -> std::io::Result<()> {
+         Ok(())
+      }
+   }
+
+   #[test]
+   fn writes_queued_input_in_order() {
+      let written = SharedWriter::default();
+      let event_handler: TerminalEventHandler = Arc::new(|_, _| true);
+      let input = TerminalConnection::start_writer_thread(
+         "test".to_string(),
+         Box::new(written.clone()),
+         event_handler,
+      );
+
+      let paste = vec![b'p'; PTY_WRITE_CHUNK_SIZE * 3];
+      let mut expected = Vec::new();
+      for data in [b"a".to_vec(), paste, b"b".to_vec(), b"c".to_vec()] {
+         expected.extend_from_slice(&data);
+         input.send(data).unwrap();
+      }
+      drop(input);
+
+      let deadline = std::time::Instant::now() + Duration::from_secs(5);
+      while written.0.lock().unwrap().len() < expected.len() {
+         assert!(
+            std::time::Instant::now() < deadline,
+            "writer thread stalled"
+         );
+         thread::sleep(Duration::from_millis(5));
+      }
+      assert_eq!(*written.0.lock().unwrap(), expected);
+   }
+
    fn config_with_env(environment: HashMap<String, String>) -> TerminalConfig {
       TerminalConfig {
          working_directory: None,
@@ -607,10 +744,12 @@ mod tests {
          wsl_distribution: None,
          wsl_working_directory: None,
          environment: Some(environment),
+         clear_env: false,
          command: Some("node".to_string()),
          args: None,
          size: TerminalSize::default(),
          term_program_version: Some("0.9.0-test".to_string()),
+         output_batch_interval_ms: None,
       }
    }
 
@@ -761,6 +900,32 @@ mod tests {
       assert!(cmd.get_env("CLICOLOR_FORCE").is_none());
    }
 
+   #[test]
+   fn overlays_custom_environment_on_inherited_path() {
+      let mut environment = HashMap::new();
+      environment.insert("ATHAS_TEST_VAR".to_string(), "1".to_string());
+
+      let cmd = TerminalConnection::build_command(&config_with_env(environment)).unwrap();
+
+      assert!(cmd.get_env("PATH").is_some());
+      assert_eq!(cmd.get_env("ATHAS_TEST_VAR"), Some(OsStr::new("1")));
+   }
+
+   #[test]
+   fn clear_env_keeps_only_custom_and_terminal_variables() {
+      let mut environment = HashMap::new();
+      environment.insert("ATHAS_TEST_VAR".to_string(), "1".to_string());
+      let mut config = config_with_env(environment);
+      config.clear_env = true;
+
+      let cmd = TerminalConnection::build_command(&config).unwrap();
+
+      assert!(cmd.get_env("PATH").is_none());
+      assert!(cmd.get_env("HOME").is_none());
+      assert_eq!(cmd.get_env("ATHAS_TEST_VAR"), Some(OsStr::new("1")));
+      assert_eq!(cmd.get_env("TERM"), Some(OsStr::new("xterm-256color")));
+   }
+
    #[test]
    fn removes_inherited_no_color_for_interactive_terminal_color() {
       let cmd = TerminalConnection::build_command(&config_with_env(HashMap::new())).unwrap();
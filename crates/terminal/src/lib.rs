@@ -1,6 +1,7 @@
 pub mod config;
 pub mod connection;
 pub mod manager;
+pub mod output_batch;
 pub mod protocol;
 pub mod shell;
 
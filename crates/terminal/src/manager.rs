@@ -47,10 +47,12 @@ impl TerminalManager {
       Ok(id)
    }
 
+   /// Queue input for a terminal. Each terminal has one writer thread that sends queued input
+   /// to the PTY in order, so this returns without waiting for large writes to go out.
    pub fn write_to_terminal(&self, id: &str, input: TerminalInput) -> Result<()> {
       let connections = self.connections.lock().unwrap();
       if let Some(connection) = connections.get(id) {
-         connection.write(&input.into_bytes())
+         connection.write(input.into_bytes())
       } else {
          Err(anyhow!("Terminal connection not found"))
       }
@@ -0,0 +1,137 @@
+use crate::protocol::TerminalEvent;
+use std::{
+   sync::mpsc::{Receiver, TryRecvError},
+   time::{Duration, Instant},
+};
+
+/// Longest time output keeps being merged while more arrives when the terminal config doesn't
+/// set one, about one frame
+pub const DEFAULT_OUTPUT_BATCH_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Size at which a batch is sent without waiting for the rest of the window
+pub const MAX_OUTPUT_BATCH_BYTES: usize = 256 * 1024;
+
+/// Forward events from the PTY reader, merging output already queued behind the first pending
+/// chunk into a single event. A batch is sent as soon as the queue runs dry, so an echoed
+/// keystroke goes out at once, and at the latest `interval` after its first chunk while output
+/// keeps streaming in. Other events flush pending output first, so exit and error events still
+/// follow the output before them. Returns once the reader hangs up or `emit` reports that
+/// nobody is listening.
+pub fn forward_batched_output(
+   events: Receiver<TerminalEvent>,
+   interval: Duration,
+   mut emit: impl FnMut(TerminalEvent) -> bool,
+) {
+   while let Ok(event) = events.recv() {
+      let TerminalEvent::Output { data: mut batch } = event else {
+         if !emit(event) {
+            return;
+         }
+         continue;
+      };
+
+      let deadline = Instant::now() + interval;
+      let mut next_event = None;
+      while batch.len() < MAX_OUTPUT_BATCH_BYTES && Instant::now() < deadline {
+         match events.try_recv() {
+            Ok(TerminalEvent::Output { data }) => batch.extend_from_slice(&data),
+            Ok(event) => {
+               next_event = Some(event);
+               break;
+            }
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+         }
+      }
+
+      if !emit(TerminalEvent::Output { data: batch }) {
+         return;
+      }
+      if let Some(event) = next_event
+         && !emit(event)
+      {
+         return;
+      }
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use std::{sync::mpsc, thread};
+
+   fn output(data: &[u8]) -> TerminalEvent {
+      TerminalEvent::Output {
+         data: data.to_vec(),
+      }
+   }
+
+   #[test]
+   fn merges_queued_output_and_keeps_event_order() {
+      let (sender, receiver) = mpsc::channel();
+      sender.send(output(b"a")).unwrap();
+      sender.send(output(b"b")).unwrap();
+      sender.send(output(b"c")).unwrap();
+      sender.send(TerminalEvent::Closed).unwrap();
+      sender.send(output(b"d")).unwrap();
+      drop(sender);
+
+      let mut emitted = Vec::new();
+      forward_batched_output(receiver, Duration::from_secs(1), |event| {
+         emitted.push(event);
+         true
+      });
+
+      let summary: Vec<_> = emitted
+         .iter()
+         .map(|event| match event {
+            TerminalEvent::Output { data } => String::from_utf8_lossy(data).to_string(),
+            TerminalEvent::Closed => "closed".to_string(),
+            _ => "other".to_string(),
+         })
+         .collect();
+      assert_eq!(summary, vec!["abc", "closed", "d"]);
+   }
+
+   #[test]
+   fn sends_full_batches_early_and_stops_when_unheard() {
+      let (sender, receiver) = mpsc::channel();
+      let chunk = vec![b'x'; MAX_OUTPUT_BATCH_BYTES / 2];
+      for _ in 0..5 {
+         sender
+            .send(TerminalEvent::Output {
+               data: chunk.clone(),
+            })
+            .unwrap();
+      }
+
+      let mut sizes = Vec::new();
+      forward_batched_output(receiver, Duration::from_secs(60), |event| {
+         if let TerminalEvent::Output { data } = event {
+            sizes.push(data.len());
+         }
+         sizes.len() < 2
+      });
+
+      assert_eq!(sizes, vec![MAX_OUTPUT_BATCH_BYTES, MAX_OUTPUT_BATCH_BYTES]);
+   }
+
+   #[test]
+   fn sends_output_as_soon_as_the_queue_is_idle() {
+      let (sender, receiver) = mpsc::channel();
+      let (emitted_sender, emitted) = mpsc::channel();
+      let forwarder = thread::spawn(move || {
+         forward_batched_output(receiver, Duration::from_secs(60), |event| {
+            emitted_sender.send(event).is_ok()
+         });
+      });
+
+      sender.send(output(b"k")).unwrap();
+      let event = emitted
+         .recv_timeout(Duration::from_secs(5))
+         .expect("output sent before the window ends");
+      assert!(matches!(event, TerminalEvent::Output { data } if data == b"k"));
+
+      drop(sender);
+      forwarder.join().unwrap();
+   }
+}
@@ -31,11 +31,24 @@ impl TerminalSize {
    }
 }
 
+const BRACKETED_PASTE_START: &str = "\x1b[200~";
+const BRACKETED_PASTE_END: &str = "\x1b[201~";
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum TerminalInput {
-   Text { data: String },
-   Binary { data: Vec<u8> },
+   Text {
+      data: String,
+   },
+   Binary {
+      data: Vec<u8>,
+   },
+   /// Pasted text, wrapped in bracketed paste markers when the running program enabled them
+   Paste {
+      data: String,
+      #[serde(default)]
+      bracketed: bool,
+   },
 }
 
 impl TerminalInput {
@@ -43,6 +56,22 @@ impl TerminalInput {
       match self {
          Self::Text { data } => data.into_bytes(),
          Self::Binary { data } => data,
+         Self::Paste {
+            data,
+            bracketed: false,
+         } => data.into_bytes(),
+         Self::Paste {
+            data,
+            bracketed: true,
+         } => {
+            // An end marker inside the paste would let pasted text run as typed input, and
+            // removing one can join the bytes around it into another
+            let mut data = data;
+            while data.contains(BRACKETED_PASTE_END) {
+               data = data.replace(BRACKETED_PASTE_END, "");
+            }
+            format!("{BRACKETED_PASTE_START}{data}{BRACKETED_PASTE_END}").into_bytes()
+         }
       }
    }
 }
@@ -134,6 +163,38 @@ mod tests {
       assert_eq!(input.into_bytes(), vec![255, 0, 27]);
    }
 
+   #[test]
+   fn wraps_bracketed_pastes_in_markers() {
+      let input: TerminalInput = serde_json::from_value(serde_json::json!({
+         "kind": "paste",
+         "data": "echo one\necho two\u{1b}[201~rm -rf ~\n",
+         "bracketed": true
+      }))
+      .unwrap();
+
+      assert_eq!(
+         input.into_bytes(),
+         b"\x1b[200~echo one\necho tworm -rf ~\n\x1b[201~".to_vec()
+      );
+
+      let plain: TerminalInput =
+         serde_json::from_value(serde_json::json!({ "kind": "paste", "data": "ls\n" })).unwrap();
+      assert_eq!(plain.into_bytes(), b"ls\n".to_vec());
+   }
+
+   #[test]
+   fn strips_end_markers_nested_inside_each_other() {
+      let input = TerminalInput::Paste {
+         data: "echo \u{1b}[20\u{1b}[201~1~rm -rf ~\n".to_string(),
+         bracketed: true,
+      };
+
+      assert_eq!(
+         input.into_bytes(),
+         b"\x1b[200~echo rm -rf ~\n\x1b[201~".to_vec()
+      );
+   }
+
    #[test]
    fn deserializes_pixel_aware_terminal_size() {
       let size: TerminalSize = serde_json::from_value(serde_json::json!({
@@ -1,15 +1,23 @@
-use crate::{ToolConfig, ToolError, ToolRuntime, platform, runtime::AthasAppHandle as AppHandle};
-use athas_runtime::{RuntimeManager, RuntimeType, process::configure_background_command};
+use crate::{
+   LanguageToolConfigSet, ToolConfig, ToolError, ToolInstallProgress, ToolInstallState,
+   ToolRegistry, ToolRuntime, ToolStatus, ToolType, platform, runtime::AthasAppHandle as AppHandle,
+};
+use athas_runtime::{
+   RuntimeManager, RuntimeType,
+   process::{apply_login_shell_path, configure_background_command},
+   shell,
+};
 use flate2::read::GzDecoder;
-use futures_util::StreamExt;
+use futures_util::{StreamExt, stream};
 use serde_json::Value;
 use std::{
+   collections::HashMap,
    env, fs,
    io::Cursor,
    path::{Component, Path, PathBuf},
    process::Command,
 };
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use url::Url;
 use walkdir::WalkDir;
 use xz2::read::XzDecoder;
@@ -19,6 +27,10 @@ use zip::ZipArchive;
 /// small, but SDK-backed language servers such as Dart include runtime assets.
 const MAX_BINARY_DOWNLOAD_BYTES: u64 = 512 * 1024 * 1024;
 
+/// Maximum number of tools installed at the same time by `install_all`. Package managers are
+/// network and disk heavy, so running every install at once tends to be slower, not faster.
+const MAX_CONCURRENT_INSTALLS: usize = 2;
+
 /// Validate that a binary download URL uses an acceptable scheme and host.
 ///
 /// Release builds require HTTPS. Debug builds additionally permit `http://`
@@ -144,6 +156,7 @@ impl ToolInstaller {
          .ok()
          .or_else(|| Self::find_binary_in_dirs(command_name, Self::common_system_tool_dirs()))?;
       let mut command = Command::new(command_path);
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args(args)
          .output()
@@ -175,6 +188,13 @@ impl ToolInstaller {
          return Ok(path);
       }
 
+      if let Some(login_path) = shell::login_shell_path_env()
+         && let Ok(cwd) = std::env::current_dir()
+         && let Ok(path) = which::which_in(command_name, Some(login_path), cwd)
+      {
+         return Ok(path);
+      }
+
       if command_name == "sourcekit-lsp"
          && let Some(path) = Self::find_sourcekit_lsp()
       {
@@ -532,6 +552,7 @@ impl ToolInstaller {
       let mut args = vec![install_command];
       let packages = Self::node_packages_to_install(package, companion_packages);
       args.extend(packages.iter().map(String::as_str));
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args(args)
          .current_dir(package_dir)
@@ -806,6 +827,54 @@ impl ToolInstaller {
       Ok(installed_binary)
    }
 
+   /// Install every tool configured for a language, running independent installs concurrently.
+   ///
+   /// Emits `tools://install-progress` as each tool finishes and returns the final status of
+   /// each tool type.
+   pub async fn install_all(
+      app_handle: &AppHandle,
+      language_id: &str,
+      manifest_tools: Option<LanguageToolConfigSet>,
+   ) -> HashMap<ToolType, ToolStatus> {
+      let Some(tools) = ToolRegistry::get_tools(language_id, manifest_tools) else {
+         return HashMap::new();
+      };
+
+      stream::iter(tools)
+         .map(|(tool_type, config)| {
+            let app_handle = app_handle.clone();
+            let language_id = language_id.to_string();
+            let task = tauri::async_runtime::spawn(async move {
+               let status = match Self::install(&app_handle, &config).await {
+                  Ok(_) => ToolStatus::Installed,
+                  Err(e) => ToolStatus::Failed(e.to_string()),
+               };
+
+               let _ = app_handle.emit(
+                  "tools://install-progress",
+                  ToolInstallProgress {
+                     language_id,
+                     tool_type,
+                     tool_name: config.name,
+                     status: status.clone(),
+                  },
+               );
+
+               status
+            });
+
+            async move {
+               let status = task
+                  .await
+                  .unwrap_or_else(|e| ToolStatus::Failed(format!("Install task failed: {}", e)));
+               (tool_type, status)
+            }
+         })
+         .buffer_unordered(MAX_CONCURRENT_INSTALLS)
+         .collect()
+         .await
+   }
+
    /// Install a tool based on its configuration
    pub async fn install(app_handle: &AppHandle, config: &ToolConfig) -> Result<PathBuf, ToolError> {
       match config.runtime {
@@ -1025,6 +1094,7 @@ impl ToolInstaller {
 
       // Create virtual environment
       let mut command = Command::new(&python_path);
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args(["-m", "venv", venv_dir.to_string_lossy().as_ref()])
          .output()
@@ -1046,6 +1116,7 @@ impl ToolInstaller {
       };
 
       let mut command = Command::new(&pip_path);
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args(["install", package])
          .output()
@@ -1089,6 +1160,7 @@ impl ToolInstaller {
       log::info!("Installing {} via go install", package);
 
       let mut command = Command::new(&go_path);
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args(["install", &format!("{}@latest", package)])
          .env("GOPATH", &gopath)
@@ -1130,6 +1202,7 @@ impl ToolInstaller {
       log::info!("Installing {} via cargo install", package);
 
       let mut command = Command::new(&cargo_path);
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args(["install", package])
          .env("CARGO_HOME", &cargo_home)
@@ -1254,6 +1327,7 @@ impl ToolInstaller {
       log::info!("Installing {} via RubyGems to {:?}", package, gem_home);
 
       let mut command = Command::new(&gem_path);
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args([
             "install",
@@ -1349,6 +1423,7 @@ impl ToolInstaller {
       );
 
       let mut command = Command::new(&rscript_path);
+      apply_login_shell_path(&mut command);
       let output = configure_background_command(&mut command)
          .args(["--vanilla", "-e", install_expr.as_str()])
          .env("R_LIBS_USER", &r_library_dir)
@@ -1435,6 +1510,29 @@ impl ToolInstaller {
       Ok(true)
    }
 
+   /// Report whether a tool is installed and, if so, the path it is launched from.
+   pub fn install_state(
+      app_handle: &AppHandle,
+      tool_type: ToolType,
+      config: &ToolConfig,
+   ) -> ToolInstallState {
+      let installed = Self::is_installed(app_handle, config).unwrap_or(false);
+      let path = installed
+         .then(|| match tool_type {
+            ToolType::Lsp => Self::get_lsp_launch_path(app_handle, config),
+            _ => Self::get_tool_path(app_handle, config),
+         })
+         .and_then(Result::ok)
+         .map(|path| path.to_string_lossy().to_string());
+
+      ToolInstallState {
+         tool_type,
+         name: config.name.clone(),
+         installed,
+         path,
+      }
+   }
+
    /// Get the path where a tool would be/is installed
    pub fn get_tool_path(app_handle: &AppHandle, config: &ToolConfig) -> Result<PathBuf, ToolError> {
       let tools_dir = Self::get_tools_dir(app_handle)?;
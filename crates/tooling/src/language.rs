@@ -0,0 +1,245 @@
+use std::path::Path;
+
+/// Well-known file names, lowercase, that say more than their extension.
+const FILENAME_LANGUAGES: &[(&str, &str)] = &[
+   (".bash_profile", "bash"),
+   (".bashrc", "bash"),
+   (".profile", "bash"),
+   (".zshrc", "bash"),
+   (".dockerignore", "gitignore"),
+   (".eslintignore", "gitignore"),
+   (".fdignore", "gitignore"),
+   (".gitignore", "gitignore"),
+   (".ignore", "gitignore"),
+   (".npmignore", "gitignore"),
+   (".prettierignore", "gitignore"),
+   (".rgignore", "gitignore"),
+   (".stylelintignore", "gitignore"),
+   (".vscodeignore", "gitignore"),
+   (".gitattributes", "gitattributes"),
+   (".rprofile", "r"),
+   ("cmakelists.txt", "cmake"),
+   ("containerfile", "dockerfile"),
+   ("dockerfile", "dockerfile"),
+   ("gnumakefile", "makefile"),
+   ("makefile", "makefile"),
+   ("go.mod", "go"),
+   ("go.sum", "go"),
+   ("go.work", "go"),
+   ("gemfile", "ruby"),
+   ("rakefile", "ruby"),
+];
+
+/// Extensions, lowercase and without the dot, matching the editor's language ids.
+const EXTENSION_LANGUAGES: &[(&str, &str)] = &[
+   ("js", "javascript"),
+   ("mjs", "javascript"),
+   ("cjs", "javascript"),
+   ("jsx", "javascriptreact"),
+   ("ts", "typescript"),
+   ("mts", "typescript"),
+   ("cts", "typescript"),
+   ("tsx", "typescriptreact"),
+   ("py", "python"),
+   ("pyi", "python"),
+   ("ipy", "python"),
+   ("ipynb", "jupyter-notebook"),
+   ("r", "r"),
+   ("rmd", "rmarkdown"),
+   ("rs", "rust"),
+   ("go", "go"),
+   ("java", "java"),
+   ("c", "c"),
+   ("h", "c"),
+   ("cpp", "cpp"),
+   ("cc", "cpp"),
+   ("cxx", "cpp"),
+   ("hpp", "cpp"),
+   ("hh", "cpp"),
+   ("hxx", "cpp"),
+   ("cs", "csharp"),
+   ("rb", "ruby"),
+   ("php", "php"),
+   ("html", "html"),
+   ("htm", "html"),
+   ("xml", "xml"),
+   ("svg", "xml"),
+   ("plist", "xml"),
+   ("css", "css"),
+   ("scss", "scss"),
+   ("sass", "sass"),
+   ("less", "less"),
+   ("dockerfile", "dockerfile"),
+   ("mk", "makefile"),
+   ("cmake", "cmake"),
+   ("diff", "diff"),
+   ("patch", "diff"),
+   ("json", "json"),
+   ("jsonc", "json"),
+   ("yaml", "yaml"),
+   ("yml", "yaml"),
+   ("toml", "toml"),
+   ("md", "markdown"),
+   ("mdx", "markdown"),
+   ("markdown", "markdown"),
+   ("sh", "bash"),
+   ("bash", "bash"),
+   ("zsh", "bash"),
+   ("swift", "swift"),
+   ("kt", "kotlin"),
+   ("kts", "kotlin"),
+   ("scala", "scala"),
+   ("lua", "lua"),
+   ("nix", "nix"),
+   ("dart", "dart"),
+   ("ex", "elixir"),
+   ("exs", "elixir"),
+   ("elm", "elm"),
+   ("graphql", "graphql"),
+   ("gql", "graphql"),
+   ("ml", "ocaml"),
+   ("mli", "ocaml"),
+   ("proto", "protobuf"),
+   ("sql", "sql"),
+   ("sol", "solidity"),
+   ("tf", "terraform"),
+   ("tfvars", "terraform"),
+   ("zig", "zig"),
+   ("astro", "astro"),
+   ("vue", "vue"),
+   ("svelte", "svelte"),
+   ("erb", "embedded_template"),
+];
+
+/// Interpreters named in shebang lines, without version suffixes.
+const INTERPRETER_LANGUAGES: &[(&str, &str)] = &[
+   ("bash", "bash"),
+   ("sh", "bash"),
+   ("zsh", "bash"),
+   ("dash", "bash"),
+   ("ksh", "bash"),
+   ("python", "python"),
+   ("pypy", "python"),
+   ("node", "javascript"),
+   ("nodejs", "javascript"),
+   ("deno", "typescript"),
+   ("bun", "javascript"),
+   ("ts-node", "typescript"),
+   ("tsx", "typescript"),
+   ("ruby", "ruby"),
+   ("php", "php"),
+   ("lua", "lua"),
+   ("luajit", "lua"),
+   ("rscript", "r"),
+   ("elixir", "elixir"),
+   ("make", "makefile"),
+];
+
+/// Detect the language id of a file from its name, its extension, and for files whose
+/// extension says nothing, a shebang in `first_bytes`. The ids are the ones language tools are
+/// registered under.
+pub fn detect_language(path: &Path, first_bytes: &[u8]) -> Option<String> {
+   let file_name = path.file_name()?.to_string_lossy().to_lowercase();
+
+   if file_name == ".env" || file_name.starts_with(".env.") {
+      return Some("dotenv".to_string());
+   }
+   if let Some(language) = lookup(FILENAME_LANGUAGES, &file_name) {
+      return Some(language.to_string());
+   }
+   // Variants such as `Dockerfile.dev` or `Makefile.linux`
+   if let Some((stem, _)) = file_name.split_once('.')
+      && matches!(stem, "dockerfile" | "containerfile" | "makefile")
+      && let Some(language) = lookup(FILENAME_LANGUAGES, stem)
+   {
+      return Some(language.to_string());
+   }
+
+   let extension = path
+      .extension()
+      .map(|extension| extension.to_string_lossy().to_lowercase());
+   if let Some(language) = extension
+      .as_deref()
+      .and_then(|extension| lookup(EXTENSION_LANGUAGES, extension))
+   {
+      return Some(language.to_string());
+   }
+
+   shebang_language(first_bytes).map(str::to_string)
+}
+
+fn lookup(table: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+   table
+      .iter()
+      .find(|(candidate, _)| *candidate == key)
+      .map(|(_, language)| *language)
+}
+
+/// Language of the interpreter in a `#!` line, looking through `/usr/bin/env` and its flags.
+fn shebang_language(first_bytes: &[u8]) -> Option<&'static str> {
+   let first_line = first_bytes.split(|byte| *byte == b'\n').next()?;
+   let first_line = String::from_utf8_lossy(first_line);
+   let command = first_line.strip_prefix("#!")?;
+
+   let mut words = command.split_whitespace();
+   let mut program = words.next()?.rsplit('/').next()?;
+   if program == "env" {
+      program = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+   }
+
+   let interpreter = program
+      .to_lowercase()
+      .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+      .to_string();
+   lookup(INTERPRETER_LANGUAGES, &interpreter)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn detect(path: &str, first_bytes: &str) -> Option<String> {
+      detect_language(Path::new(path), first_bytes.as_bytes())
+   }
+
+   #[test]
+   fn detects_well_known_file_names() {
+      assert_eq!(detect("/app/Dockerfile", "").as_deref(), Some("dockerfile"));
+      assert_eq!(
+         detect("/app/Dockerfile.dev", "").as_deref(),
+         Some("dockerfile")
+      );
+      assert_eq!(detect("/app/GNUmakefile", "").as_deref(), Some("makefile"));
+      assert_eq!(detect("/app/.gitignore", "").as_deref(), Some("gitignore"));
+      assert_eq!(detect("/app/.env.local", "").as_deref(), Some("dotenv"));
+      assert_eq!(detect("/app/CMakeLists.txt", "").as_deref(), Some("cmake"));
+   }
+
+   #[test]
+   fn prefers_extension_over_shebang() {
+      assert_eq!(
+         detect("/app/main.TS", "#!/usr/bin/env node\n").as_deref(),
+         Some("typescript")
+      );
+      assert_eq!(detect("/app/notes.txt", "hello"), None);
+   }
+
+   #[test]
+   fn reads_shebangs_of_extensionless_scripts() {
+      assert_eq!(
+         detect("/bin/tool", "#!/usr/bin/env python3\nimport os\n").as_deref(),
+         Some("python")
+      );
+      assert_eq!(
+         detect(
+            "/bin/tool",
+            "#!/usr/bin/env -S PYTHONPATH=. python3.12 -u\n"
+         )
+         .as_deref(),
+         Some("python")
+      );
+      assert_eq!(detect("/bin/tool", "#!/bin/sh\n").as_deref(), Some("bash"));
+      assert_eq!(detect("/bin/tool", "#!/usr/bin/perl\n"), None);
+      assert_eq!(detect("/bin/tool", "echo hi\n"), None);
+   }
+}
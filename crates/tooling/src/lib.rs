@@ -1,12 +1,14 @@
 mod installer;
+mod language;
 mod platform;
 mod registry;
 mod runtime;
 mod types;
 
 pub use installer::ToolInstaller;
+pub use language::detect_language;
 pub use registry::ToolRegistry;
 pub use types::{
-   LanguageToolConfigSet, LanguageToolStatus, ToolConfig, ToolError, ToolRuntime, ToolStatus,
-   ToolType,
+   LanguageToolConfigSet, LanguageToolStatus, ToolConfig, ToolError, ToolInstallProgress,
+   ToolInstallState, ToolRuntime, ToolStatus, ToolType,
 };
@@ -1,41 +1,94 @@
 use super::{
    platform,
-   types::{LanguageToolConfigSet, ToolConfig, ToolType},
+   types::{LanguageToolConfigSet, ToolConfig, ToolError, ToolType},
+};
+use std::{
+   collections::HashMap,
+   fs,
+   path::Path,
+   sync::{LazyLock, RwLock},
 };
-use std::collections::HashMap;
 
 const CLANGD_VERSION: &str = "22.1.0";
 const ELIXIR_LS_VERSION: &str = "v0.30.0";
 const TERRAFORM_LS_VERSION: &str = "0.38.6";
 const ZIG_VERSION: &str = "0.16.0";
 
+/// File in the app config directory holding user-defined tools.
+const USER_TOOLS_FILE_NAME: &str = "tools.json";
+
+/// User-defined tools keyed by language id, loaded from `tools.json`.
+type UserToolMap = HashMap<String, HashMap<ToolType, ToolConfig>>;
+
+static USER_TOOLS: LazyLock<RwLock<UserToolMap>> = LazyLock::new(Default::default);
+
 /// Tool configurations resolved from extension manifests.
 pub struct ToolRegistry;
 
 impl ToolRegistry {
-   /// Get tool configurations for a language from manifest-provided configs.
+   /// Get tool configurations for a language from manifest-provided configs, with user-defined
+   /// tools from `tools.json` taking precedence.
    pub fn get_tools(
-      _language_id: &str,
+      language_id: &str,
       manifest_tools: Option<LanguageToolConfigSet>,
    ) -> Option<HashMap<ToolType, ToolConfig>> {
       let mut tools = HashMap::new();
-      let manifest_tools = manifest_tools?;
 
-      if let Some(config) = manifest_tools.lsp {
-         tools.insert(ToolType::Lsp, Self::normalize_tool_config(config));
-      }
+      if let Some(manifest_tools) = manifest_tools {
+         if let Some(config) = manifest_tools.lsp {
+            tools.insert(
+               ToolType::Lsp,
+               Self::normalize_tool_config(ToolType::Lsp, config),
+            );
+         }
+
+         if let Some(config) = manifest_tools.formatter {
+            tools.insert(
+               ToolType::Formatter,
+               Self::normalize_tool_config(ToolType::Formatter, config),
+            );
+         }
 
-      if let Some(config) = manifest_tools.formatter {
-         tools.insert(ToolType::Formatter, Self::normalize_tool_config(config));
+         if let Some(config) = manifest_tools.linter {
+            tools.insert(
+               ToolType::Linter,
+               Self::normalize_tool_config(ToolType::Linter, config),
+            );
+         }
       }
 
-      if let Some(config) = manifest_tools.linter {
-         tools.insert(ToolType::Linter, Self::normalize_tool_config(config));
+      if let Some(user_tools) = USER_TOOLS.read().unwrap().get(language_id) {
+         for (tool_type, config) in user_tools {
+            tools.insert(
+               *tool_type,
+               Self::normalize_tool_config(*tool_type, config.clone()),
+            );
+         }
       }
 
       if tools.is_empty() { None } else { Some(tools) }
    }
 
+   /// Load user-defined tools from `tools.json` in `config_dir`, replacing any previously loaded
+   /// ones. A missing file clears them. Returns the number of languages with user tools.
+   pub fn load_user_tools(config_dir: &Path) -> Result<usize, ToolError> {
+      let path = config_dir.join(USER_TOOLS_FILE_NAME);
+      let user_tools = if path.exists() {
+         Self::parse_user_tools(&fs::read_to_string(&path)?)?
+      } else {
+         HashMap::new()
+      };
+
+      let count = user_tools.len();
+      *USER_TOOLS.write().unwrap() = user_tools;
+      Ok(count)
+   }
+
+   fn parse_user_tools(contents: &str) -> Result<UserToolMap, ToolError> {
+      serde_json::from_str(contents)
+         .map_err(|e| ToolError::ConfigError(format!("Invalid {}: {}", USER_TOOLS_FILE_NAME, e)))
+   }
+
    /// Get a single tool configuration from manifest-provided configs.
    pub fn get_tool(
       language_id: &str,
@@ -45,9 +98,12 @@ impl ToolRegistry {
       Self::get_tools(language_id, manifest_tools).and_then(|tools| tools.get(&tool_type).cloned())
    }
 
-   fn normalize_tool_config(mut config: ToolConfig) -> ToolConfig {
+   fn normalize_tool_config(tool_type: ToolType, mut config: ToolConfig) -> ToolConfig {
       Self::apply_known_package_tool(&mut config);
       Self::apply_known_ruby_tool(&mut config);
+      if config.args.is_empty() {
+         config.args = Self::known_tool_args(tool_type, &config);
+      }
       if config.command.is_none() {
          config.command = Self::known_tool_command(&config);
       }
@@ -65,6 +121,7 @@ impl ToolRegistry {
       }
 
       match config.name.as_str() {
+         "biome" => Some(Self::biome_download_url()),
          "clangd" => Some(Self::clangd_download_url()),
          "dart" => Some(Self::dart_sdk_download_url()),
          "elixir-ls" => Some(Self::elixir_ls_download_url()),
@@ -129,6 +186,41 @@ impl ToolRegistry {
       }
    }
 
+   /// Default arguments for tools that need a subcommand, such as Biome's `format` and `lint`.
+   fn known_tool_args(tool_type: ToolType, config: &ToolConfig) -> Vec<String> {
+      let args: &[&str] = match (config.name.as_str(), tool_type) {
+         ("biome", ToolType::Formatter) => &["format", "--stdin-file-path=${file}"],
+         ("biome", ToolType::Linter) => &["lint", "--stdin-file-path=${file}", "--reporter=json"],
+         _ => &[],
+      };
+
+      args.iter().map(|arg| arg.to_string()).collect()
+   }
+
+   fn biome_download_url() -> String {
+      let arch = match std::env::consts::ARCH {
+         "aarch64" => "arm64",
+         _ => "x64",
+      };
+
+      let asset = match std::env::consts::OS {
+         "macos" => format!("biome-darwin-{}", arch),
+         "windows" => format!("biome-win32-{}.exe", arch),
+         _ => {
+            let libc_suffix = match platform::detect_linux_libc() {
+               platform::LinuxLibc::Musl => "-musl",
+               platform::LinuxLibc::Gnu | platform::LinuxLibc::Unknown => "",
+            };
+            format!("biome-linux-{}{}", arch, libc_suffix)
+         }
+      };
+
+      format!(
+         "https://github.com/biomejs/biome/releases/latest/download/{}",
+         asset
+      )
+   }
+
    fn clangd_download_url() -> String {
       let platform = match std::env::consts::OS {
          "macos" => "mac",
@@ -549,6 +641,42 @@ mod tests {
       assert!(resolved.download_url.is_none());
    }
 
+   #[test]
+   fn supplies_biome_download_url_and_subcommand_args() {
+      let biome = || ToolConfig {
+         name: "biome".to_string(),
+         command: None,
+         runtime: crate::ToolRuntime::Binary,
+         package: None,
+         packages: Vec::new(),
+         download_url: None,
+         args: Vec::new(),
+         env: std::collections::HashMap::new(),
+      };
+
+      let language_tools = LanguageToolConfigSet {
+         lsp: None,
+         formatter: Some(biome()),
+         linter: Some(biome()),
+      };
+
+      let tools = ToolRegistry::get_tools("typescript", Some(language_tools)).unwrap();
+      let formatter = tools.get(&ToolType::Formatter).unwrap();
+      let linter = tools.get(&ToolType::Linter).unwrap();
+
+      assert!(formatter.download_url.as_ref().is_some_and(|url| {
+         url.starts_with("https://github.com/biomejs/biome/releases/latest/download/biome-")
+      }));
+      assert_eq!(formatter.args, ["format", "--stdin-file-path=${file}"]);
+      assert_eq!(
+         linter.args,
+         ["lint", "--stdin-file-path=${file}", "--reporter=json"]
+      );
+
+      // Biome is only used where it is configured
+      assert!(ToolRegistry::get_tools("json", None).is_none());
+   }
+
    #[test]
    fn supplies_known_download_urls_for_standalone_language_servers() {
       for name in [
@@ -640,4 +768,25 @@ mod tests {
       assert_eq!(resolved.runtime, crate::ToolRuntime::System);
       assert!(resolved.download_url.is_none());
    }
+
+   #[test]
+   fn parses_user_tools_keyed_by_language_and_tool_type() {
+      let user_tools = ToolRegistry::parse_user_tools(
+         r#"{
+            "elixir": {
+               "lsp": { "name": "elixir-ls", "runtime": "binary" },
+               "formatter": { "name": "mix", "runtime": "system", "args": ["format", "-"] }
+            }
+         }"#,
+      )
+      .unwrap();
+
+      let elixir = user_tools.get("elixir").unwrap();
+      assert_eq!(elixir.get(&ToolType::Lsp).unwrap().name, "elixir-ls");
+      assert_eq!(
+         elixir.get(&ToolType::Formatter).unwrap().args,
+         vec!["format", "-"]
+      );
+      assert!(ToolRegistry::parse_user_tools("{ not json").is_err());
+   }
 }
@@ -67,7 +67,7 @@ pub struct ToolConfig {
 }
 
 /// Tool types that can be installed
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
 pub enum ToolType {
    Lsp,
@@ -108,6 +108,27 @@ impl LanguageToolStatus {
    }
 }
 
+/// Install state of a single tool, with the resolved binary path when it is installed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolInstallState {
+   pub tool_type: ToolType,
+   pub name: String,
+   pub installed: bool,
+   pub path: Option<String>,
+}
+
+/// Payload of the `tools://install-progress` event, emitted as each tool of a language finishes
+/// installing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolInstallProgress {
+   pub language_id: String,
+   pub tool_type: ToolType,
+   pub tool_name: String,
+   pub status: ToolStatus,
+}
+
 /// Errors that can occur during tool operations
 #[derive(Debug)]
 pub enum ToolError {
@@ -125,38 +125,16 @@ pub fn git_blame_file(root_path: &str, file_path: &str, content: &str) -> Result
 #[cfg(test)]
 mod tests {
    use super::*;
-   use git2::{IndexAddOption, Signature};
-   use std::fs;
-
-   fn commit_file(repo: &Repository, relative_path: &str, content: &str) {
-      let workdir = repo.workdir().expect("repository workdir");
-      fs::write(workdir.join(relative_path), content).expect("write file");
-
-      let mut index = repo.index().expect("repository index");
-      index
-         .add_all([relative_path], IndexAddOption::DEFAULT, None)
-         .expect("add file");
-      index.write().expect("write index");
-      let tree_id = index.write_tree().expect("write tree");
-      let tree = repo.find_tree(tree_id).expect("find tree");
-      let signature = Signature::now("Athas Test", "test@athas.dev").expect("signature");
-      repo
-         .commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            "Initial commit",
-            &tree,
-            &[],
-         )
-         .expect("commit file");
-   }
+   use crate::git::test_support::{commit_files, init_repo};
 
    #[test]
    fn aligns_blame_with_inserted_editor_content() {
-      let temp_dir = tempfile::tempdir().expect("temp dir");
-      let repo = Repository::init(temp_dir.path()).expect("repo init");
-      commit_file(&repo, "example.txt", "first\nsecond\n");
+      let (temp_dir, repo) = init_repo();
+      commit_files(
+         &repo,
+         &[("example.txt", "first\nsecond\n")],
+         "Initial commit",
+      );
 
       let blame = git_blame_file(
          temp_dir.path().to_str().expect("repo path"),
@@ -184,9 +162,12 @@ mod tests {
 
    #[test]
    fn aligns_blame_after_deleting_a_committed_line() {
-      let temp_dir = tempfile::tempdir().expect("temp dir");
-      let repo = Repository::init(temp_dir.path()).expect("repo init");
-      commit_file(&repo, "example.txt", "first\nsecond\n");
+      let (temp_dir, repo) = init_repo();
+      commit_files(
+         &repo,
+         &[("example.txt", "first\nsecond\n")],
+         "Initial commit",
+      );
 
       let blame = git_blame_file(
          temp_dir.path().to_str().expect("repo path"),
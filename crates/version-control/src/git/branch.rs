@@ -1,4 +1,4 @@
-use crate::git::IntoStringError;
+use crate::git::{IntoStringError, current_branch_name};
 use anyhow::{Context, Result};
 use git2::{BranchType, Repository, Status};
 use serde::Serialize;
@@ -32,6 +32,52 @@ fn _git_branches(repo_path: String) -> Result<Vec<String>> {
    Ok(branch_names)
 }
 
+/// The branch the repository treats as its trunk: what `origin/HEAD` points at, else the
+/// `init.defaultBranch`, `main` or `master` branch if one exists, else the current branch.
+pub fn git_default_branch(repo_path: String) -> Result<String, String> {
+   _git_default_branch(repo_path).into_string_error()
+}
+
+fn _git_default_branch(repo_path: String) -> Result<String> {
+   let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   Ok(default_branch_name(&repo))
+}
+
+pub(crate) fn default_branch_name(repo: &Repository) -> String {
+   let remote_head = repo
+      .find_reference("refs/remotes/origin/HEAD")
+      .ok()
+      .and_then(|reference| reference.symbolic_target().map(str::to_string))
+      .and_then(|target| {
+         target
+            .strip_prefix("refs/remotes/origin/")
+            .map(str::to_string)
+      });
+   if let Some(branch) = remote_head {
+      return branch;
+   }
+
+   let configured = repo
+      .config()
+      .ok()
+      .and_then(|config| config.get_string("init.defaultBranch").ok());
+   let branch_exists = |name: &str| {
+      repo.find_branch(name, BranchType::Local).is_ok()
+         || repo
+            .find_branch(&format!("origin/{name}"), BranchType::Remote)
+            .is_ok()
+   };
+   if let Some(branch) = configured
+      .into_iter()
+      .chain(["main".to_string(), "master".to_string()])
+      .find(|name| branch_exists(name))
+   {
+      return branch;
+   }
+
+   current_branch_name(repo)
+}
+
 pub fn git_checkout(repo_path: String, branch_name: String) -> Result<CheckoutResult, String> {
    _git_checkout(repo_path, branch_name).into_string_error()
 }
@@ -145,3 +191,44 @@ fn _git_delete_branch(repo_path: String, branch_name: String) -> Result<()> {
 
    Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::commit_empty;
+
+   fn git_init_with_head(repo_path: &str, branch: &str) {
+      let mut options = git2::RepositoryInitOptions::new();
+      options.initial_head(branch);
+      Repository::init_opts(repo_path, &options).expect("repo init");
+   }
+
+   #[test]
+   fn default_branch_prefers_origin_head() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+      git_init_with_head(&repo_path, "feature");
+      let repo = Repository::open(&repo_path).expect("open repo");
+      let head = commit_empty(&repo, Some("HEAD"), "Initial", &[]);
+
+      assert_eq!(git_default_branch(repo_path.clone()).unwrap(), "feature");
+
+      repo
+         .reference("refs/heads/master", head, false, "test")
+         .expect("create master");
+      assert_eq!(git_default_branch(repo_path.clone()).unwrap(), "master");
+
+      repo
+         .reference("refs/remotes/origin/trunk", head, false, "test")
+         .expect("create remote branch");
+      repo
+         .reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/trunk",
+            false,
+            "test",
+         )
+         .expect("set origin HEAD");
+      assert_eq!(git_default_branch(repo_path).unwrap(), "trunk");
+   }
+}
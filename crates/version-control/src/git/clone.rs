@@ -0,0 +1,90 @@
+use crate::git::{GitCloneProgress, IntoStringError, credentials::credential_callbacks};
+use anyhow::{Context, Result};
+use git2::{FetchOptions, build::RepoBuilder};
+use std::{
+   path::Path,
+   time::{Duration, Instant},
+};
+
+/// Minimum time between progress reports, except for the final one
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Clone `url` into `dest_path`, reporting transfer progress as objects arrive. Returns the
+/// working directory of the new repository.
+pub fn git_clone(
+   url: String,
+   dest_path: String,
+   github_token: Option<String>,
+   on_progress: impl FnMut(GitCloneProgress),
+) -> Result<String, String> {
+   _git_clone(url, dest_path, github_token, on_progress).into_string_error()
+}
+
+fn _git_clone(
+   url: String,
+   dest_path: String,
+   github_token: Option<String>,
+   mut on_progress: impl FnMut(GitCloneProgress),
+) -> Result<String> {
+   let mut callbacks = credential_callbacks(github_token.as_deref());
+   let mut last_report: Option<Instant> = None;
+   callbacks.transfer_progress(|progress| {
+      let done = progress.received_objects() == progress.total_objects()
+         && progress.indexed_deltas() == progress.total_deltas();
+      if done || last_report.is_none_or(|reported| reported.elapsed() >= PROGRESS_INTERVAL) {
+         last_report = Some(Instant::now());
+         on_progress(GitCloneProgress {
+            received_objects: progress.received_objects(),
+            indexed_objects: progress.indexed_objects(),
+            total_objects: progress.total_objects(),
+            received_bytes: progress.received_bytes(),
+         });
+      }
+      true
+   });
+
+   let mut fetch_options = FetchOptions::new();
+   fetch_options.remote_callbacks(callbacks);
+   let repo = RepoBuilder::new()
+      .fetch_options(fetch_options)
+      .clone(&url, Path::new(&dest_path))
+      .with_context(|| format!("Failed to clone {url}"))?;
+
+   let workdir = repo.workdir().unwrap_or_else(|| repo.path());
+   Ok(workdir
+      .to_string_lossy()
+      .trim_end_matches(['/', '\\'])
+      .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::{commit_files, init_repo};
+   use std::fs;
+
+   #[test]
+   fn clones_local_repository() {
+      let (source_dir, source) = init_repo();
+      commit_files(&source, &[("README.md", "hello\n")], "Initial");
+
+      let dest_dir = tempfile::tempdir().expect("temp dir");
+      let dest = dest_dir.path().join("clone");
+      let mut reports = Vec::new();
+      let cloned = git_clone(
+         format!("file://{}", source_dir.path().display()),
+         dest.to_string_lossy().to_string(),
+         None,
+         |progress| reports.push(progress),
+      )
+      .expect("clone");
+
+      assert_eq!(Path::new(&cloned), dest);
+      assert_eq!(
+         fs::read_to_string(dest.join("README.md")).unwrap(),
+         "hello\n"
+      );
+      let last = reports.last().expect("progress reported");
+      assert_eq!(last.received_objects, last.total_objects);
+   }
+}
@@ -1,6 +1,19 @@
-use crate::git::{GitCommit, IntoStringError};
-use anyhow::{Context, Result};
-use git2::{Repository, Sort};
+use crate::git::{GitCommit, IntoStringError, ResetMode};
+use anyhow::{Context, Result, bail};
+use git2::{Repository, ResetType, Sort, build::CheckoutBuilder};
+use serde::Serialize;
+use std::{
+   path::Path,
+   process::{Command, Stdio},
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitOperationResult {
+   pub success: bool,
+   pub conflicted: bool,
+   pub message: String,
+}
 
 pub fn git_commit(repo_path: String, message: String) -> Result<(), String> {
    _git_commit(repo_path, message).into_string_error()
@@ -71,3 +84,193 @@ fn _git_log(repo_path: String, limit: Option<u32>, skip: Option<u32>) -> Result<
 
    Ok(commits)
 }
+
+pub fn git_revert(
+   repo_path: String,
+   commit_hash: String,
+   no_commit: bool,
+) -> Result<CommitOperationResult, String> {
+   _git_revert(repo_path, commit_hash, no_commit).into_string_error()
+}
+
+fn _git_revert(
+   repo_path: String,
+   commit_hash: String,
+   no_commit: bool,
+) -> Result<CommitOperationResult> {
+   let mut args = vec!["revert", "--no-edit"];
+   if no_commit {
+      args.push("-n");
+   }
+   // A hash that starts with a dash must not be read as an option
+   args.extend(["--end-of-options", &commit_hash]);
+
+   run_commit_operation(Path::new(&repo_path), &args, "revert")
+}
+
+pub fn git_cherry_pick(
+   repo_path: String,
+   commit_hash: String,
+   no_commit: bool,
+) -> Result<CommitOperationResult, String> {
+   _git_cherry_pick(repo_path, commit_hash, no_commit).into_string_error()
+}
+
+fn _git_cherry_pick(
+   repo_path: String,
+   commit_hash: String,
+   no_commit: bool,
+) -> Result<CommitOperationResult> {
+   let mut args = vec!["cherry-pick"];
+   if no_commit {
+      args.push("-n");
+   }
+   // A hash that starts with a dash must not be read as an option
+   args.extend(["--end-of-options", &commit_hash]);
+
+   run_commit_operation(Path::new(&repo_path), &args, "cherry-pick")
+}
+
+/// Move the current branch to `target_ref`. A hard reset discards working tree changes, so it
+/// is refused unless `confirm` is set.
+pub fn git_reset_to(
+   repo_path: String,
+   target_ref: String,
+   mode: ResetMode,
+   confirm: bool,
+) -> Result<(), String> {
+   _git_reset_to(repo_path, target_ref, mode, confirm).into_string_error()
+}
+
+fn _git_reset_to(
+   repo_path: String,
+   target_ref: String,
+   mode: ResetMode,
+   confirm: bool,
+) -> Result<()> {
+   if mode == ResetMode::Hard && !confirm {
+      bail!("Hard reset discards uncommitted changes and must be confirmed");
+   }
+
+   let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   let target = repo
+      .revparse_single(&target_ref)
+      .and_then(|object| object.peel(git2::ObjectType::Commit))
+      .with_context(|| format!("Failed to resolve {target_ref}"))?;
+
+   let (reset_type, mut checkout) = match mode {
+      ResetMode::Soft => (ResetType::Soft, None),
+      ResetMode::Mixed => (ResetType::Mixed, None),
+      ResetMode::Hard => {
+         let mut checkout = CheckoutBuilder::new();
+         checkout.force();
+         (ResetType::Hard, Some(checkout))
+      }
+   };
+   repo
+      .reset(&target, reset_type, checkout.as_mut())
+      .context("Failed to reset")?;
+
+   Ok(())
+}
+
+/// Runs a commit-applying git command, reporting merge conflicts as a result instead of an error
+/// so the caller can offer to resolve them.
+pub(super) fn run_commit_operation(
+   repo_dir: &Path,
+   args: &[&str],
+   operation: &str,
+) -> Result<CommitOperationResult> {
+   let output = Command::new("git")
+      .current_dir(repo_dir)
+      .env("GIT_EDITOR", "true")
+      .stdin(Stdio::null())
+      .args(args)
+      .output()
+      .with_context(|| format!("Failed to execute git {operation}"))?;
+
+   let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+   let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+   if output.status.success() {
+      return Ok(CommitOperationResult {
+         success: true,
+         conflicted: false,
+         message: stdout,
+      });
+   }
+
+   let repo = Repository::open(repo_dir).context("Failed to open repository")?;
+   let conflicted = repo
+      .index()
+      .map(|index| index.has_conflicts())
+      .unwrap_or(false);
+
+   if !conflicted {
+      bail!(
+         "Git {operation} failed: {}",
+         if stderr.is_empty() { stdout } else { stderr }
+      );
+   }
+
+   Ok(CommitOperationResult {
+      success: false,
+      conflicted: true,
+      message: if stderr.is_empty() { stdout } else { stderr },
+   })
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::{commit_files, init_repo};
+   use std::fs;
+
+   fn commit_file(repo: &Repository, content: &str) -> git2::Oid {
+      commit_files(repo, &[("file.txt", content)], content)
+   }
+
+   #[test]
+   fn resets_branch_by_mode() {
+      let (temp_dir, repo) = init_repo();
+      let first = commit_file(&repo, "first\n");
+      commit_file(&repo, "second\n");
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+      let file = temp_dir.path().join("file.txt");
+
+      assert!(git_reset_to(repo_path.clone(), first.to_string(), ResetMode::Hard, false).is_err());
+
+      git_reset_to(
+         repo_path.clone(),
+         "HEAD~1".to_string(),
+         ResetMode::Soft,
+         false,
+      )
+      .expect("soft reset");
+      assert_eq!(repo.head().unwrap().target(), Some(first));
+      assert!(!repo.statuses(None).unwrap().is_empty());
+      assert_eq!(fs::read_to_string(&file).unwrap(), "second\n");
+
+      git_reset_to(repo_path, first.to_string(), ResetMode::Hard, true).expect("hard reset");
+      assert!(repo.statuses(None).unwrap().is_empty());
+      assert_eq!(fs::read_to_string(&file).unwrap(), "first\n");
+   }
+
+   #[test]
+   fn never_reads_the_commit_as_an_option() {
+      let (temp_dir, repo) = init_repo();
+      commit_file(&repo, "first\n");
+      let second = commit_file(&repo, "second\n");
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+
+      assert!(git_revert(repo_path.clone(), "--quit".to_string(), true).is_err());
+      assert!(git_cherry_pick(repo_path.clone(), "--quit".to_string(), true).is_err());
+
+      let result = git_revert(repo_path, second.to_string(), true).expect("revert");
+      assert!(result.success);
+      assert_eq!(
+         fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+         "first\n"
+      );
+   }
+}
@@ -0,0 +1,82 @@
+use crate::git::IntoStringError;
+use anyhow::{Context, Result};
+use git2::{Config, ConfigLevel, ErrorCode, Repository};
+use std::{env, path::PathBuf};
+
+/// Read a config value as git would resolve it for the repository, falling back through the
+/// global and system config files.
+pub fn git_get_config(repo_path: String, key: String) -> Result<Option<String>, String> {
+   _git_get_config(repo_path, key).into_string_error()
+}
+
+fn _git_get_config(repo_path: String, key: String) -> Result<Option<String>> {
+   let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   let config = repo
+      .config()
+      .and_then(|mut config| config.snapshot())
+      .context("Failed to read repository config")?;
+
+   match config.get_string(&key) {
+      Ok(value) => Ok(Some(value)),
+      Err(error) if error.code() == ErrorCode::NotFound => Ok(None),
+      Err(error) => Err(error).with_context(|| format!("Failed to read {key}")),
+   }
+}
+
+/// Write a config value to the repository's own config, or to the user's global config.
+pub fn git_set_config(
+   repo_path: String,
+   key: String,
+   value: String,
+   global: bool,
+) -> Result<(), String> {
+   _git_set_config(repo_path, key, value, global).into_string_error()
+}
+
+fn _git_set_config(repo_path: String, key: String, value: String, global: bool) -> Result<()> {
+   let mut config = if global {
+      Config::open(&global_config_path()?).context("Failed to open global git config")?
+   } else {
+      let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+      repo
+         .config()
+         .and_then(|config| config.open_level(ConfigLevel::Local))
+         .context("Failed to open repository config")?
+   };
+
+   config
+      .set_str(&key, &value)
+      .with_context(|| format!("Failed to set {key}"))
+}
+
+/// The existing global config file, or `~/.gitconfig` when the user has none yet
+fn global_config_path() -> Result<PathBuf> {
+   if let Ok(path) = Config::find_global() {
+      return Ok(path);
+   }
+   env::home_dir()
+      .map(|home| home.join(".gitconfig"))
+      .context("Failed to locate home directory")
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::init_repo;
+
+   #[test]
+   fn reads_and_writes_repository_config() {
+      let (temp_dir, _repo) = init_repo();
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+      let key = "athas.testvalue".to_string();
+
+      assert_eq!(git_get_config(repo_path.clone(), key.clone()), Ok(None));
+
+      git_set_config(repo_path.clone(), key.clone(), "enabled".to_string(), false)
+         .expect("set config");
+      assert_eq!(
+         git_get_config(repo_path, key),
+         Ok(Some("enabled".to_string()))
+      );
+   }
+}
@@ -0,0 +1,253 @@
+use anyhow::{Context, Result};
+use git2::{Cred, CredentialType, RemoteCallbacks};
+use std::{
+   env,
+   io::Write,
+   path::{Path, PathBuf},
+   process::Command,
+};
+use tempfile::TempPath;
+
+/// Credential requests answered before giving up, so a rejected credential can't loop forever
+const MAX_CREDENTIAL_ATTEMPTS: usize = 8;
+
+/// Config key of the credential helper scoped to HTTPS github.com remotes
+const GITHUB_CREDENTIAL_HELPER_KEY: &str = "credential.https://github.com.helper";
+
+/// Private keys tried after the ssh-agent, in `~/.ssh`
+const SSH_KEY_FILES: &[&str] = &["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// Whether `url` is an HTTPS remote on github.com, the only host the stored GitHub token is
+/// sent to.
+pub fn is_github_https_url(url: &str) -> bool {
+   let Some(rest) = url.strip_prefix("https://") else {
+      return false;
+   };
+   let authority = rest.split('/').next().unwrap_or_default();
+   let host = authority.rsplit('@').next().unwrap_or_default();
+   let host = host.split(':').next().unwrap_or_default();
+   host.eq_ignore_ascii_case("github.com")
+}
+
+/// Callbacks that authenticate libgit2 network operations: the GitHub token for HTTPS
+/// github.com remotes, and the ssh-agent followed by the default key files for SSH remotes.
+pub(crate) fn credential_callbacks<'a>(github_token: Option<&'a str>) -> RemoteCallbacks<'a> {
+   let mut callbacks = RemoteCallbacks::new();
+   let mut attempts = 0;
+   let mut ssh_attempts = 0;
+
+   callbacks.credentials(move |url, username, allowed| {
+      attempts += 1;
+      if attempts > MAX_CREDENTIAL_ATTEMPTS {
+         return Err(git2::Error::from_str(&format!(
+            "Authentication failed for {url}"
+         )));
+      }
+
+      if allowed.contains(CredentialType::USER_PASS_PLAINTEXT)
+         && let Some(token) = github_token.filter(|_| is_github_https_url(url))
+      {
+         return Cred::userpass_plaintext("x-access-token", token);
+      }
+
+      if allowed.contains(CredentialType::SSH_KEY) {
+         let username = username.unwrap_or("git");
+         ssh_attempts += 1;
+         if ssh_attempts == 1 {
+            return Cred::ssh_key_from_agent(username);
+         }
+         if let Some(key) = ssh_key_files().into_iter().nth(ssh_attempts - 2) {
+            return Cred::ssh_key(username, None, &key, None);
+         }
+      }
+
+      if allowed.contains(CredentialType::DEFAULT) {
+         return Cred::default();
+      }
+
+      Err(git2::Error::from_str(&format!(
+         "No credentials available for {url}"
+      )))
+   });
+
+   callbacks
+}
+
+/// Make a git subprocess authenticate to HTTPS github.com remotes with the stored token. The
+/// token goes to a private temporary file that the credential helper prints, so it never shows
+/// up in the arguments or the environment that git passes on to hooks and other subprocesses.
+/// The empty helper entry clears helpers from the user's config, so a stale keychain entry can't
+/// shadow the token. The entries are appended after any `GIT_CONFIG_COUNT` entries the command
+/// already carries or inherits, so those keep applying. The returned file is removed when it is
+/// dropped, so keep it until the command has finished.
+pub(crate) fn apply_github_token(
+   command: &mut Command,
+   github_token: Option<&str>,
+) -> Result<Option<TempPath>> {
+   let Some(token) = github_token else {
+      return Ok(None);
+   };
+
+   let mut file = tempfile::Builder::new()
+      .prefix("athas-git-credentials-")
+      .tempfile()
+      .context("Failed to create the GitHub credential file")?;
+   writeln!(file, "username=x-access-token\npassword={}", token.trim())
+      .and_then(|()| file.flush())
+      .context("Failed to write the GitHub credential file")?;
+   let path = file.into_temp_path();
+
+   let first = config_env_count(command);
+   command
+      .env("GIT_CONFIG_COUNT", (first + 2).to_string())
+      .env(
+         format!("GIT_CONFIG_KEY_{first}"),
+         GITHUB_CREDENTIAL_HELPER_KEY,
+      )
+      .env(format!("GIT_CONFIG_VALUE_{first}"), "")
+      .env(
+         format!("GIT_CONFIG_KEY_{}", first + 1),
+         GITHUB_CREDENTIAL_HELPER_KEY,
+      )
+      .env(
+         format!("GIT_CONFIG_VALUE_{}", first + 1),
+         credential_helper(&path),
+      );
+   Ok(Some(path))
+}
+
+/// A helper that answers `get` requests with the contents of the credential file at `path`
+fn credential_helper(path: &Path) -> String {
+   let path = path.to_string_lossy().replace('\'', r"'\''");
+   format!("!f() {{ test \"$1\" = get || return 0; cat '{path}'; }}; f")
+}
+
+/// Number of `GIT_CONFIG_KEY_<n>` entries `command` will pass to git, from its own environment
+/// or the one it inherits.
+fn config_env_count(command: &Command) -> usize {
+   let count = match command
+      .get_envs()
+      .find(|(key, _)| *key == "GIT_CONFIG_COUNT")
+   {
+      Some((_, value)) => value.map(|value| value.to_os_string()),
+      None => env::var_os("GIT_CONFIG_COUNT"),
+   };
+   count
+      .and_then(|count| count.to_str()?.trim().parse().ok())
+      .unwrap_or(0)
+}
+
+fn ssh_key_files() -> Vec<PathBuf> {
+   let Some(ssh_dir) = env::home_dir().map(|home| home.join(".ssh")) else {
+      return Vec::new();
+   };
+   SSH_KEY_FILES
+      .iter()
+      .map(|name| ssh_dir.join(name))
+      .filter(|path| path.exists())
+      .collect()
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use std::process::{Command, Stdio};
+
+   fn credential_fill(dir: &std::path::Path, host: &str, github_token: Option<&str>) -> String {
+      let mut command = Command::new("git");
+      command
+         .current_dir(dir)
+         .env("GIT_TERMINAL_PROMPT", "0")
+         .args(["credential", "fill"])
+         .stdin(Stdio::piped())
+         .stdout(Stdio::piped())
+         .stderr(Stdio::null());
+      let credentials = apply_github_token(&mut command, github_token).expect("credentials");
+      let mut child = command.spawn().expect("spawn git");
+      child
+         .stdin
+         .take()
+         .expect("stdin")
+         .write_all(format!("protocol=https\nhost={host}\n\n").as_bytes())
+         .expect("write request");
+      let output = child.wait_with_output().expect("git output");
+      drop(credentials);
+      String::from_utf8_lossy(&output.stdout).to_string()
+   }
+
+   #[test]
+   fn matches_only_github_https_urls() {
+      assert!(is_github_https_url("https://github.com/athasdev/athas.git"));
+      assert!(is_github_https_url(
+         "https://user@GitHub.com:443/athasdev/athas"
+      ));
+      assert!(!is_github_https_url("http://github.com/athasdev/athas"));
+      assert!(!is_github_https_url("git@github.com:athasdev/athas.git"));
+      assert!(!is_github_https_url("https://github.com.evil.dev/athas"));
+      assert!(!is_github_https_url("https://gitlab.com/athasdev/athas"));
+   }
+
+   #[test]
+   fn credential_helper_answers_only_for_github() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+
+      let github = credential_fill(temp_dir.path(), "github.com", Some("secret-token"));
+      assert!(github.contains("username=x-access-token"));
+      assert!(github.contains("password=secret-token"));
+
+      let other = credential_fill(temp_dir.path(), "gitlab.com", Some("secret-token"));
+      assert!(!other.contains("secret-token"));
+   }
+
+   #[test]
+   fn keeps_the_token_out_of_the_environment() {
+      let mut command = Command::new("git");
+      let credentials = apply_github_token(&mut command, Some("secret-token"))
+         .expect("credentials")
+         .expect("credential file");
+      let path = credentials.to_path_buf();
+
+      assert!(command.get_envs().all(|(_, value)| {
+         !value.is_some_and(|value| value.to_string_lossy().contains("secret-token"))
+      }));
+      #[cfg(unix)]
+      {
+         use std::os::unix::fs::PermissionsExt;
+         let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+         assert_eq!(mode & 0o077, 0);
+      }
+
+      drop(credentials);
+      assert!(!path.exists());
+   }
+
+   #[test]
+   fn keeps_config_entries_the_command_already_has() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let mut command = Command::new("git");
+      command
+         .current_dir(temp_dir.path())
+         .env("GIT_CONFIG_COUNT", "1")
+         .env("GIT_CONFIG_KEY_0", "user.name")
+         .env("GIT_CONFIG_VALUE_0", "Athas Test")
+         .args(["config", "--get-all", "user.name"]);
+      let credentials =
+         apply_github_token(&mut command, Some("secret-token")).expect("credentials");
+      let output = command.output().expect("git config");
+      drop(credentials);
+      assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "Athas Test");
+
+      let mut command = Command::new("git");
+      command.env("GIT_CONFIG_COUNT", "1");
+      let credentials = apply_github_token(&mut command, Some("secret-token"))
+         .expect("credentials")
+         .expect("credential file");
+      assert_eq!(config_env_count(&command), 3);
+      let helper = credential_helper(&credentials);
+      assert!(
+         command
+            .get_envs()
+            .any(|(key, value)| key == "GIT_CONFIG_VALUE_2" && value == Some(helper.as_ref()))
+      );
+   }
+}
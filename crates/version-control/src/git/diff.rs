@@ -1,7 +1,10 @@
-use crate::git::{DiffLineType, GitDiff, GitDiffLine, GitDiffStat, get_blob_base64, is_image_file};
+use crate::git::{
+   DiffLineType, GitDiff, GitDiffLine, GitDiffStat, GitLineRange, default_branch_name,
+   get_blob_base64, is_image_file,
+};
 use anyhow::Result;
 use base64::{Engine as _, engine::general_purpose};
-use git2::{Diff, DiffFormat, Oid, Repository, Tree};
+use git2::{BranchType, Commit, Diff, DiffFormat, Oid, Repository, Tree};
 use std::{collections::HashMap, path::Path};
 
 const LARGE_DIFF_LINE_THRESHOLD: usize = 20_000;
@@ -298,6 +301,7 @@ fn collect_diff_stats(
                staged,
                additions: 0,
                deletions: 0,
+               binary: false,
             });
 
          if origin == '+' {
@@ -342,6 +346,155 @@ pub fn git_status_diff_stats(repo_path: String) -> Result<Vec<GitDiffStat>, Stri
    Ok(stats.into_values().collect())
 }
 
+/// Added and removed line counts for every changed file, like `git diff --numstat`. `staged`
+/// compares HEAD with the index; otherwise the index is compared with the working tree,
+/// untracked files included. Binary files are listed with no line counts.
+pub fn git_diff_stat(repo_path: String, staged: bool) -> Result<Vec<GitDiffStat>, String> {
+   let repo =
+      Repository::open(&repo_path).map_err(|e| format!("Failed to open repository: {e}"))?;
+   let index = repo
+      .index()
+      .map_err(|e| format!("Failed to get index: {e}"))?;
+
+   let diff = if staged {
+      let head_tree = repo
+         .head()
+         .ok()
+         .and_then(|head| head.peel_to_commit().ok())
+         .and_then(|commit| commit.tree().ok());
+      repo
+         .diff_tree_to_index(head_tree.as_ref(), Some(&index), None)
+         .map_err(|e| format!("Failed to create staged diff: {e}"))?
+   } else {
+      let mut options = git2::DiffOptions::new();
+      options
+         .include_untracked(true)
+         .recurse_untracked_dirs(true)
+         .show_untracked_content(true);
+      repo
+         .diff_index_to_workdir(Some(&index), Some(&mut options))
+         .map_err(|e| format!("Failed to create unstaged diff: {e}"))?
+   };
+
+   let mut stats = Vec::with_capacity(diff.deltas().len());
+   for delta_index in 0..diff.deltas().len() {
+      let patch = git2::Patch::from_diff(&diff, delta_index)
+         .map_err(|e| format!("Failed to create patch: {e}"))?;
+      let (additions, deletions) = match &patch {
+         Some(patch) => {
+            let (_, additions, deletions) = patch
+               .line_stats()
+               .map_err(|e| format!("Failed to count changed lines: {e}"))?;
+            (additions, deletions)
+         }
+         None => (0, 0),
+      };
+      let Some(delta) = diff.get_delta(delta_index) else {
+         continue;
+      };
+      let file_path = diff_delta_file_path(&delta);
+      if file_path.is_empty() {
+         continue;
+      }
+
+      stats.push(GitDiffStat {
+         file_path,
+         staged,
+         additions,
+         deletions,
+         binary: delta.flags().is_binary(),
+      });
+   }
+
+   stats.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+   Ok(stats)
+}
+
+/// Diffs for every changed file on one side of the index, from a single diff of the
+/// repository. `staged` compares HEAD with the index; otherwise the index is compared with the
+/// working tree. Saves opening the repository once per file with `git_diff_file`.
+pub fn git_diff_all(repo_path: String, staged: bool) -> Result<Vec<GitDiff>, String> {
+   let repo =
+      Repository::open(&repo_path).map_err(|e| format!("Failed to open repository: {e}"))?;
+   let index = repo
+      .index()
+      .map_err(|e| format!("Failed to get index: {e}"))?;
+
+   let diff_result = if staged {
+      let head_tree = repo
+         .head()
+         .ok()
+         .and_then(|head| head.peel_to_commit().ok())
+         .and_then(|commit| commit.tree().ok());
+      repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), None)
+   } else {
+      repo.diff_index_to_workdir(Some(&index), None)
+   };
+   let mut diff = diff_result.map_err(|e| format!("Failed to create diff: {e}"))?;
+   let mut diff_entries_by_file = parse_diff_to_file_entries(&mut diff)?;
+   let mut results: Vec<GitDiff> = Vec::new();
+
+   for delta in diff.deltas() {
+      let old_path = delta
+         .old_file()
+         .path()
+         .map(|p| p.to_string_lossy().into_owned());
+      let new_path = delta
+         .new_file()
+         .path()
+         .map(|p| p.to_string_lossy().into_owned());
+      let file_path = diff_delta_file_path(&delta);
+      let is_image = is_image_file(&file_path);
+      let is_new = delta.status() == git2::Delta::Added;
+      let is_deleted = delta.status() == git2::Delta::Deleted;
+      let is_renamed = delta.status() == git2::Delta::Renamed;
+      let mut old_blob_base64 = None;
+      let mut new_blob_base64 = None;
+      let parsed = if is_image {
+         if !is_new {
+            old_blob_base64 = get_blob_base64(
+               &repo,
+               Some(delta.old_file().id()),
+               old_path.as_deref().unwrap_or(&file_path),
+            );
+         }
+         if !is_deleted {
+            let new_file_path = new_path.as_deref().unwrap_or(&file_path);
+            new_blob_base64 = if staged {
+               get_blob_base64(&repo, Some(delta.new_file().id()), new_file_path)
+            } else {
+               std::fs::read(Path::new(&repo_path).join(new_file_path))
+                  .ok()
+                  .map(|data| general_purpose::STANDARD.encode(data))
+            };
+         }
+         ParsedDiffFile::default()
+      } else {
+         diff_entries_by_file.remove(&file_path).unwrap_or_default()
+      };
+
+      results.push(GitDiff {
+         file_path,
+         old_path,
+         new_path,
+         is_new,
+         is_deleted,
+         is_renamed,
+         is_binary: is_image,
+         is_image,
+         old_blob_base64,
+         new_blob_base64,
+         lines: parsed.lines,
+         raw_patch: parsed.raw_patch,
+         additions: Some(parsed.additions),
+         deletions: Some(parsed.deletions),
+         is_truncated: parsed.is_truncated.then_some(true),
+      });
+   }
+
+   Ok(results)
+}
+
 pub fn git_diff_file(
    repo_path: String,
    file_path: String,
@@ -859,6 +1012,65 @@ pub fn git_diff_file_with_content(
    })
 }
 
+/// Lines of `content` that were added or changed compared with the file at HEAD. Pure deletions
+/// leave nothing behind to format and are skipped. A file missing from HEAD is one range
+/// covering all of it.
+pub fn git_changed_line_ranges(
+   repo_path: String,
+   file_path: String,
+   content: String,
+) -> Result<Vec<GitLineRange>, String> {
+   let repo =
+      Repository::open(&repo_path).map_err(|e| format!("Failed to open repository: {e}"))?;
+   let head_blob = repo
+      .head()
+      .and_then(|head| head.peel_to_tree())
+      .ok()
+      .and_then(|tree| tree.get_path(Path::new(&file_path)).ok())
+      .map(|entry| repo.find_blob(entry.id()))
+      .transpose()
+      .map_err(|e| format!("Failed to read '{file_path}' at HEAD: {e}"))?;
+
+   let Some(head_blob) = head_blob else {
+      let line_count = content.lines().count() as u32;
+      return Ok(if line_count == 0 {
+         Vec::new()
+      } else {
+         vec![GitLineRange {
+            start_line: 0,
+            end_line: line_count - 1,
+         }]
+      });
+   };
+
+   let mut options = git2::DiffOptions::new();
+   options.context_lines(0);
+   let patch = git2::Patch::from_blob_and_buffer(
+      &head_blob,
+      Some(Path::new(&file_path)),
+      content.as_bytes(),
+      Some(Path::new(&file_path)),
+      Some(&mut options),
+   )
+   .map_err(|e| format!("Failed to diff '{file_path}' against HEAD: {e}"))?;
+
+   let mut ranges = Vec::new();
+   for hunk_index in 0..patch.num_hunks() {
+      let (hunk, _) = patch
+         .hunk(hunk_index)
+         .map_err(|e| format!("Failed to read diff hunk: {e}"))?;
+      if hunk.new_lines() == 0 {
+         continue;
+      }
+      let start_line = hunk.new_start() - 1;
+      ranges.push(GitLineRange {
+         start_line,
+         end_line: start_line + hunk.new_lines() - 1,
+      });
+   }
+   Ok(ranges)
+}
+
 pub fn git_commit_diff(
    repo_path: String,
    commit_hash: String,
@@ -870,6 +1082,16 @@ pub fn git_commit_diff(
    let commit = repo
       .find_commit(oid)
       .map_err(|e| format!("Commit not found: {e}"))?;
+   commit_diff(&repo, &commit, file_path.as_deref())
+}
+
+/// Diff `commit` against its first parent, or against nothing for a root commit. Used for both
+/// commits and stash entries, which are commits on top of the commit they were created from.
+pub(crate) fn commit_diff(
+   repo: &Repository,
+   commit: &Commit,
+   file_path: Option<&str>,
+) -> Result<Vec<GitDiff>, String> {
    let commit_tree = commit
       .tree()
       .map_err(|e| format!("Failed to get commit tree: {e}"))?;
@@ -891,7 +1113,7 @@ pub fn git_commit_diff(
       None
    };
    let mut diff_opts = git2::DiffOptions::new();
-   if let Some(path) = &file_path {
+   if let Some(path) = file_path {
       diff_opts.pathspec(path);
    }
    let diff = repo
@@ -937,17 +1159,17 @@ pub fn git_commit_diff(
          let new_oid = delta.new_file().id();
          if is_new {
             new_blob_base64 =
-               get_blob_base64(&repo, Some(new_oid), new_path.as_deref().unwrap_or(""));
+               get_blob_base64(repo, Some(new_oid), new_path.as_deref().unwrap_or(""));
          } else if is_deleted {
             if let Some(parent_tree) = &parent_tree {
                let old_blob_oid = old_path
                   .as_ref()
                   .and_then(|p| parent_tree.get_path(Path::new(p)).ok().map(|e| e.id()));
                old_blob_base64 =
-                  get_blob_base64(&repo, old_blob_oid, old_path.as_deref().unwrap_or(""));
+                  get_blob_base64(repo, old_blob_oid, old_path.as_deref().unwrap_or(""));
             } else {
                old_blob_base64 =
-                  get_blob_base64(&repo, Some(old_oid), old_path.as_deref().unwrap_or(""));
+                  get_blob_base64(repo, Some(old_oid), old_path.as_deref().unwrap_or(""));
             }
          } else if is_renamed {
             if let Some(parent_tree) = &parent_tree {
@@ -955,26 +1177,26 @@ pub fn git_commit_diff(
                   .as_ref()
                   .and_then(|p| parent_tree.get_path(Path::new(p)).ok().map(|e| e.id()));
                old_blob_base64 =
-                  get_blob_base64(&repo, old_blob_oid, old_path.as_deref().unwrap_or(""));
+                  get_blob_base64(repo, old_blob_oid, old_path.as_deref().unwrap_or(""));
             } else {
                old_blob_base64 =
-                  get_blob_base64(&repo, Some(old_oid), old_path.as_deref().unwrap_or(""));
+                  get_blob_base64(repo, Some(old_oid), old_path.as_deref().unwrap_or(""));
             }
             new_blob_base64 =
-               get_blob_base64(&repo, Some(new_oid), new_path.as_deref().unwrap_or(""));
+               get_blob_base64(repo, Some(new_oid), new_path.as_deref().unwrap_or(""));
          } else {
             if let Some(parent_tree) = &parent_tree {
                let old_blob_oid = old_path
                   .as_ref()
                   .and_then(|p| parent_tree.get_path(Path::new(p)).ok().map(|e| e.id()));
                old_blob_base64 =
-                  get_blob_base64(&repo, old_blob_oid, old_path.as_deref().unwrap_or(""));
+                  get_blob_base64(repo, old_blob_oid, old_path.as_deref().unwrap_or(""));
             } else {
                old_blob_base64 =
-                  get_blob_base64(&repo, Some(old_oid), old_path.as_deref().unwrap_or(""));
+                  get_blob_base64(repo, Some(old_oid), old_path.as_deref().unwrap_or(""));
             }
             new_blob_base64 =
-               get_blob_base64(&repo, Some(new_oid), new_path.as_deref().unwrap_or(""));
+               get_blob_base64(repo, Some(new_oid), new_path.as_deref().unwrap_or(""));
          }
          Vec::new()
       } else {
@@ -1033,6 +1255,75 @@ pub fn git_ref_diff(
    git_diff_between_trees(&repo, Some(&base_tree), Some(&target_tree))
 }
 
+/// Changes the current branch made since it forked from `base`, defaulting to the repository's
+/// default branch. Diffs their merge base against HEAD, so commits that landed on `base`
+/// afterwards don't show up.
+pub fn git_branch_diff(repo_path: String, base: Option<String>) -> Result<Vec<GitDiff>, String> {
+   let repo =
+      Repository::open(&repo_path).map_err(|e| format!("Failed to open repository: {e}"))?;
+   let (base, base_commit) = match base.filter(|base| !base.trim().is_empty()) {
+      Some(base) => {
+         let commit = repo
+            .revparse_single(&base)
+            .or_else(|_| repo.revparse_single(&format!("origin/{base}")))
+            .and_then(|object| object.peel_to_commit());
+         (base, commit)
+      }
+      None => {
+         let base = default_branch_name(&repo);
+         let commit = default_branch_commit(&repo, &base);
+         (base, commit)
+      }
+   };
+   let base_commit =
+      base_commit.map_err(|e| format!("Failed to find base branch '{base}': {e}"))?;
+   let head_commit = repo
+      .head()
+      .and_then(|head| head.peel_to_commit())
+      .map_err(|e| format!("Failed to get HEAD commit: {e}"))?;
+   let merge_base = repo
+      .merge_base(base_commit.id(), head_commit.id())
+      .map_err(|e| format!("Failed to find merge base with '{base}': {e}"))?;
+   let base_tree = repo
+      .find_commit(merge_base)
+      .and_then(|commit| commit.tree())
+      .map_err(|e| format!("Failed to get merge base tree: {e}"))?;
+   let head_tree = head_commit
+      .tree()
+      .map_err(|e| format!("Failed to get HEAD tree: {e}"))?;
+
+   git_diff_between_trees(&repo, Some(&base_tree), Some(&head_tree))
+}
+
+/// Tip of the default branch `name`, taking whichever of the local branch and `origin/name` is
+/// ahead, since the local copy is often behind the remote one it came from. When they have
+/// diverged, the remote branch wins.
+fn default_branch_commit<'repo>(
+   repo: &'repo Repository,
+   name: &str,
+) -> Result<Commit<'repo>, git2::Error> {
+   let tip = |name: &str, branch_type| {
+      repo
+         .find_branch(name, branch_type)
+         .and_then(|branch| branch.get().peel_to_commit())
+         .ok()
+   };
+   match (
+      tip(name, BranchType::Local),
+      tip(&format!("origin/{name}"), BranchType::Remote),
+   ) {
+      (Some(local), Some(remote)) => {
+         if repo.graph_descendant_of(local.id(), remote.id())? {
+            Ok(local)
+         } else {
+            Ok(remote)
+         }
+      }
+      (Some(commit), None) | (None, Some(commit)) => Ok(commit),
+      (None, None) => repo.revparse_single(name)?.peel_to_commit(),
+   }
+}
+
 fn git_diff_between_trees(
    repo: &Repository,
    base_tree: Option<&Tree<'_>>,
@@ -1147,3 +1438,207 @@ fn git_diff_between_trees(
 
    Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::{commit_files, init_repo};
+   use std::fs;
+
+   #[test]
+   fn counts_staged_and_unstaged_lines() {
+      let (temp_dir, repo) = init_repo();
+      let root = temp_dir.path();
+      fs::write(root.join("image.bin"), [0u8, 1, 2, 3]).expect("write binary");
+      commit_files(&repo, &[("notes.txt", "one\ntwo\nthree\n")], "Initial");
+
+      fs::write(root.join("notes.txt"), "one\n2\nthree\nfour\n").expect("edit file");
+      fs::write(root.join("image.bin"), [0u8, 9, 9, 9]).expect("edit binary");
+      let mut index = repo.index().expect("index");
+      index.add_path(Path::new("notes.txt")).expect("stage file");
+      index.write().expect("write index");
+      fs::write(root.join("new.txt"), "a\nb\n").expect("write untracked");
+
+      let repo_path = root.to_string_lossy().to_string();
+      let staged = git_diff_stat(repo_path.clone(), true).expect("staged stats");
+      assert_eq!(staged.len(), 1);
+      assert_eq!(staged[0].file_path, "notes.txt");
+      assert_eq!((staged[0].additions, staged[0].deletions), (2, 1));
+      assert!(staged[0].staged);
+
+      let unstaged = git_diff_stat(repo_path, false).expect("unstaged stats");
+      let summary: Vec<_> = unstaged
+         .iter()
+         .map(|stat| {
+            (
+               stat.file_path.as_str(),
+               stat.additions,
+               stat.deletions,
+               stat.binary,
+            )
+         })
+         .collect();
+      assert_eq!(
+         summary,
+         vec![("image.bin", 0, 0, true), ("new.txt", 2, 0, false)]
+      );
+   }
+
+   #[test]
+   fn diffs_every_changed_file_at_once() {
+      let (temp_dir, repo) = init_repo();
+      let root = temp_dir.path();
+      commit_files(&repo, &[("a.txt", "one\n"), ("b.txt", "two\n")], "Initial");
+
+      fs::write(root.join("a.txt"), "one\nmore\n").expect("edit a");
+      fs::remove_file(root.join("b.txt")).expect("delete b");
+
+      let repo_path = root.to_string_lossy().to_string();
+      let diffs = git_diff_all(repo_path.clone(), false).expect("unstaged diffs");
+      assert_eq!(diffs.len(), 2);
+      let edited = diffs
+         .iter()
+         .find(|d| d.file_path == "a.txt")
+         .expect("a.txt");
+      assert_eq!((edited.additions, edited.deletions), (Some(1), Some(0)));
+      assert!(
+         edited
+            .lines
+            .iter()
+            .any(|line| matches!(line.line_type, DiffLineType::Added) && line.content == "more")
+      );
+      let deleted = diffs
+         .iter()
+         .find(|d| d.file_path == "b.txt")
+         .expect("b.txt");
+      assert!(deleted.is_deleted);
+      assert_eq!(deleted.deletions, Some(1));
+
+      assert!(
+         git_diff_all(repo_path, true)
+            .expect("staged diffs")
+            .is_empty()
+      );
+   }
+
+   #[test]
+   fn changed_line_ranges_cover_edits_since_head() {
+      let (temp_dir, repo) = init_repo();
+      let root = temp_dir.path();
+      commit_files(&repo, &[("lib.rs", "a\nb\nc\nd\ne\n")], "Initial");
+      let repo_path = root.to_string_lossy().to_string();
+
+      let ranges = git_changed_line_ranges(
+         repo_path.clone(),
+         "lib.rs".to_string(),
+         "a\nB\nc\ne\nf\ng\n".to_string(),
+      )
+      .expect("changed ranges");
+      assert_eq!(
+         ranges,
+         vec![
+            GitLineRange {
+               start_line: 1,
+               end_line: 1,
+            },
+            GitLineRange {
+               start_line: 4,
+               end_line: 5,
+            },
+         ]
+      );
+
+      let untracked =
+         git_changed_line_ranges(repo_path, "new.rs".to_string(), "x\ny\n".to_string())
+            .expect("untracked ranges");
+      assert_eq!(
+         untracked,
+         vec![GitLineRange {
+            start_line: 0,
+            end_line: 1,
+         }]
+      );
+   }
+
+   #[test]
+   fn branch_diff_excludes_changes_made_on_base() {
+      let (temp_dir, repo) = init_repo();
+      let root = temp_dir.path();
+      let commit_file =
+         |name: &str, message: &str| commit_files(&repo, &[(name, "content\n")], message);
+
+      let root_commit = commit_file("shared.txt", "Initial");
+      let base_branch = repo.head().expect("head").shorthand().unwrap().to_string();
+      repo
+         .branch("feature", &repo.find_commit(root_commit).unwrap(), false)
+         .expect("create branch");
+      commit_file("base-only.txt", "Base work");
+
+      repo.set_head("refs/heads/feature").expect("switch branch");
+      repo
+         .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+         .expect("checkout");
+      commit_file("feature.txt", "Feature work");
+
+      let diffs = git_branch_diff(root.to_string_lossy().to_string(), Some(base_branch))
+         .expect("branch diff");
+      let paths: Vec<_> = diffs.iter().map(|diff| diff.file_path.as_str()).collect();
+      assert_eq!(paths, vec!["feature.txt"]);
+      assert!(diffs[0].is_new);
+   }
+
+   #[test]
+   fn branch_diff_defaults_to_the_newer_remote_base() {
+      let (temp_dir, repo) = init_repo();
+      let root = temp_dir.path();
+      let commit_file =
+         |name: &str, message: &str| commit_files(&repo, &[(name, "content\n")], message);
+
+      // The local base branch stays at the first commit while origin has moved on, and the
+      // feature branch starts from origin's newer tip
+      let root_commit = commit_file("shared.txt", "Initial");
+      let base_branch = repo.head().expect("head").shorthand().unwrap().to_string();
+      let upstream_commit = commit_file("upstream.txt", "Upstream work");
+      repo
+         .reference(
+            &format!("refs/remotes/origin/{base_branch}"),
+            upstream_commit,
+            true,
+            "fetch",
+         )
+         .expect("remote branch");
+      repo
+         .reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            &format!("refs/remotes/origin/{base_branch}"),
+            true,
+            "remote head",
+         )
+         .expect("remote head");
+      repo
+         .branch(
+            "feature",
+            &repo.find_commit(upstream_commit).unwrap(),
+            false,
+         )
+         .expect("create branch");
+      repo
+         .reference(
+            &format!("refs/heads/{base_branch}"),
+            root_commit,
+            true,
+            "stale",
+         )
+         .expect("stale local base");
+
+      repo.set_head("refs/heads/feature").expect("switch branch");
+      repo
+         .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+         .expect("checkout");
+      commit_file("feature.txt", "Feature work");
+
+      let diffs = git_branch_diff(root.to_string_lossy().to_string(), None).expect("branch diff");
+      let paths: Vec<_> = diffs.iter().map(|diff| diff.file_path.as_str()).collect();
+      assert_eq!(paths, vec!["feature.txt"]);
+   }
+}
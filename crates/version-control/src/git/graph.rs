@@ -0,0 +1,125 @@
+use crate::git::{GitGraphNode, IntoStringError};
+use anyhow::{Context, Result};
+use git2::{Oid, Repository, Sort};
+use std::collections::HashMap;
+
+/// Walk commit ancestry for a graph view. Starts from HEAD when no branches are given.
+pub fn git_commit_graph(
+   repo_path: String,
+   limit: Option<u32>,
+   branches: Option<Vec<String>>,
+) -> Result<Vec<GitGraphNode>, String> {
+   _git_commit_graph(repo_path, limit, branches).into_string_error()
+}
+
+fn _git_commit_graph(
+   repo_path: String,
+   limit: Option<u32>,
+   branches: Option<Vec<String>>,
+) -> Result<Vec<GitGraphNode>> {
+   let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   let mut revwalk = repo.revwalk().context("Failed to create revwalk")?;
+
+   match branches.filter(|branches| !branches.is_empty()) {
+      Some(branches) => {
+         for branch in branches {
+            let commit = repo
+               .revparse_single(&branch)
+               .and_then(|object| object.peel_to_commit())
+               .with_context(|| format!("Failed to resolve branch {branch}"))?;
+            revwalk.push(commit.id()).context("Failed to push branch")?;
+         }
+      }
+      None => revwalk.push_head().context("Failed to push HEAD")?,
+   }
+   revwalk
+      .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+      .context("Failed to set sorting")?;
+
+   let mut refs = refs_by_commit(&repo)?;
+   let limit = limit.unwrap_or(200) as usize;
+   let mut nodes = Vec::new();
+
+   for oid in revwalk.take(limit) {
+      let oid = oid.context("Failed to get commit oid")?;
+      let commit = repo.find_commit(oid).context("Failed to find commit")?;
+      let author = commit.author();
+      let date = chrono::DateTime::<chrono::Utc>::from_timestamp(author.when().seconds(), 0)
+         .map(|dt| dt.format("%Y-%m-%d").to_string())
+         .unwrap_or_default();
+
+      nodes.push(GitGraphNode {
+         hash: oid.to_string(),
+         parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+         refs: refs.remove(&oid).unwrap_or_default(),
+         author: author.name().unwrap_or("Unknown").to_string(),
+         email: author.email().unwrap_or("").to_string(),
+         date,
+         summary: commit.summary().unwrap_or("").to_string(),
+      });
+   }
+
+   Ok(nodes)
+}
+
+/// Short names of branches, remote branches and tags, keyed by the commit they point at
+fn refs_by_commit(repo: &Repository) -> Result<HashMap<Oid, Vec<String>>> {
+   let mut refs: HashMap<Oid, Vec<String>> = HashMap::new();
+   for reference in repo.references().context("Failed to list references")? {
+      let Ok(reference) = reference else {
+         continue;
+      };
+      let Some(name) = reference.shorthand() else {
+         continue;
+      };
+      if reference.name() == Some("refs/stash") {
+         continue;
+      }
+      let Ok(commit) = reference.peel_to_commit() else {
+         continue;
+      };
+      refs.entry(commit.id()).or_default().push(name.to_string());
+   }
+   for names in refs.values_mut() {
+      names.sort();
+   }
+   Ok(refs)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::{commit_empty, init_repo};
+
+   #[test]
+   fn reports_merge_parents_and_refs() {
+      let (temp_dir, repo) = init_repo();
+      let root = commit_empty(&repo, Some("HEAD"), "Root", &[]);
+      let head_branch = repo.head().expect("head").shorthand().unwrap().to_string();
+      let feature = commit_empty(&repo, None, "Feature", &[root]);
+      repo
+         .branch("feature", &repo.find_commit(feature).unwrap(), false)
+         .expect("create branch");
+      let main = commit_empty(&repo, Some("HEAD"), "Main", &[root]);
+      let merge = commit_empty(&repo, Some("HEAD"), "Merge feature", &[main, feature]);
+
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+      let nodes = git_commit_graph(repo_path.clone(), None, None).expect("graph");
+
+      assert_eq!(nodes.len(), 4);
+      assert_eq!(nodes[0].hash, merge.to_string());
+      assert_eq!(
+         nodes[0].parents,
+         vec![main.to_string(), feature.to_string()]
+      );
+      assert_eq!(nodes[0].refs, vec![head_branch]);
+      assert_eq!(nodes[3].hash, root.to_string());
+      assert!(nodes[3].parents.is_empty());
+
+      let feature_nodes =
+         git_commit_graph(repo_path, Some(10), Some(vec!["feature".to_string()])).expect("graph");
+      let hashes: Vec<_> = feature_nodes.iter().map(|node| node.hash.clone()).collect();
+      assert_eq!(hashes, vec![feature.to_string(), root.to_string()]);
+      assert_eq!(feature_nodes[0].refs, vec!["feature".to_string()]);
+   }
+}
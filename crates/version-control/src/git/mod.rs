@@ -1,12 +1,18 @@
 mod blame;
 mod branch;
+mod clone;
 mod commit;
+mod config;
+mod credentials;
 mod diff;
+mod graph;
 mod hunk;
+mod operation;
 mod remote;
 mod staging;
 mod stash;
 mod status;
+mod submodule;
 mod tag;
 mod types;
 mod utils;
@@ -14,14 +20,97 @@ mod worktree;
 
 pub use blame::*;
 pub use branch::*;
+pub use clone::*;
 pub use commit::*;
+pub use config::*;
+pub use credentials::is_github_https_url;
 pub use diff::*;
+pub use graph::*;
 pub use hunk::*;
+pub use operation::*;
 pub use remote::*;
 pub use staging::*;
 pub use stash::*;
 pub use status::*;
+pub use submodule::*;
 pub use tag::*;
 pub use types::*;
 pub use utils::*;
 pub use worktree::*;
+
+/// Repository fixtures shared by the tests of the git modules
+#[cfg(test)]
+mod test_support {
+   use git2::{IndexAddOption, Oid, Repository, Signature};
+   use std::fs;
+   use tempfile::TempDir;
+
+   /// A new repository in a temporary directory, which is removed when the `TempDir` drops
+   pub(super) fn init_repo() -> (TempDir, Repository) {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let repo = Repository::init(temp_dir.path()).expect("repo init");
+      (temp_dir, repo)
+   }
+
+   pub(super) fn signature() -> Signature<'static> {
+      Signature::now("Athas Test", "test@athas.dev").expect("signature")
+   }
+
+   /// Write `files` into the work tree, stage everything in it and commit on top of HEAD
+   pub(super) fn commit_files(repo: &Repository, files: &[(&str, &str)], message: &str) -> Oid {
+      let workdir = repo.workdir().expect("repository workdir");
+      for (name, content) in files {
+         let path = workdir.join(name);
+         if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("create parent dir");
+         }
+         fs::write(path, content).expect("write file");
+      }
+
+      let mut index = repo.index().expect("index");
+      index
+         .add_all(["*"], IndexAddOption::DEFAULT, None)
+         .expect("add files");
+      index.write().expect("write index");
+      let tree = repo
+         .find_tree(index.write_tree().expect("write tree"))
+         .expect("find tree");
+      let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+      let parents: Vec<_> = parent.iter().collect();
+      let signature = signature();
+      repo
+         .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+         )
+         .expect("commit")
+   }
+
+   /// Commit an empty tree with explicit `parents`, moving `update_ref` if given
+   pub(super) fn commit_empty(
+      repo: &Repository,
+      update_ref: Option<&str>,
+      message: &str,
+      parents: &[Oid],
+   ) -> Oid {
+      let tree_id = repo
+         .treebuilder(None)
+         .expect("tree builder")
+         .write()
+         .expect("write tree");
+      let tree = repo.find_tree(tree_id).expect("find tree");
+      let parents: Vec<_> = parents
+         .iter()
+         .map(|id| repo.find_commit(*id).expect("find parent"))
+         .collect();
+      let parents: Vec<_> = parents.iter().collect();
+      let signature = signature();
+      repo
+         .commit(update_ref, &signature, &signature, message, &tree, &parents)
+         .expect("commit")
+   }
+}
@@ -0,0 +1,150 @@
+use crate::git::{CommitOperationResult, GitOperationState, IntoStringError, run_commit_operation};
+use anyhow::{Context, Result, bail};
+use git2::Repository;
+use std::{path::Path, process::Command};
+
+pub fn git_operation_state(repo_path: String) -> Result<GitOperationState, String> {
+   _git_operation_state(repo_path).into_string_error()
+}
+
+fn _git_operation_state(repo_path: String) -> Result<GitOperationState> {
+   let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   Ok(operation_state_in(repo.path()))
+}
+
+/// Reads the marker files git leaves in the git directory while an operation is paused.
+fn operation_state_in(git_dir: &Path) -> GitOperationState {
+   if git_dir.join("rebase-merge").is_dir() || git_dir.join("rebase-apply").is_dir() {
+      GitOperationState::Rebase
+   } else if git_dir.join("MERGE_HEAD").is_file() {
+      GitOperationState::Merge
+   } else if git_dir.join("CHERRY_PICK_HEAD").is_file() {
+      GitOperationState::CherryPick
+   } else if git_dir.join("REVERT_HEAD").is_file() {
+      GitOperationState::Revert
+   } else {
+      GitOperationState::None
+   }
+}
+
+fn operation_command(state: GitOperationState) -> Option<&'static str> {
+   match state {
+      GitOperationState::None => None,
+      GitOperationState::Merge => Some("merge"),
+      GitOperationState::Rebase => Some("rebase"),
+      GitOperationState::CherryPick => Some("cherry-pick"),
+      GitOperationState::Revert => Some("revert"),
+   }
+}
+
+pub fn git_abort_operation(repo_path: String) -> Result<GitOperationState, String> {
+   _git_abort_operation(repo_path).into_string_error()
+}
+
+fn _git_abort_operation(repo_path: String) -> Result<GitOperationState> {
+   let state = _git_operation_state(repo_path.clone())?;
+   let Some(command) = operation_command(state) else {
+      bail!("No merge, rebase, cherry-pick or revert is in progress");
+   };
+
+   let output = Command::new("git")
+      .current_dir(Path::new(&repo_path))
+      .args([command, "--abort"])
+      .output()
+      .with_context(|| format!("Failed to execute git {command} --abort"))?;
+
+   if !output.status.success() {
+      bail!(
+         "Git {command} --abort failed: {}",
+         String::from_utf8_lossy(&output.stderr)
+      );
+   }
+
+   Ok(state)
+}
+
+/// Continue the paused operation. Conflicts hit by the next step (e.g. the next commit of a
+/// rebase) are reported in the result rather than as an error.
+pub fn git_continue_operation(repo_path: String) -> Result<CommitOperationResult, String> {
+   _git_continue_operation(repo_path).into_string_error()
+}
+
+fn _git_continue_operation(repo_path: String) -> Result<CommitOperationResult> {
+   let state = _git_operation_state(repo_path.clone())?;
+   let Some(command) = operation_command(state) else {
+      bail!("No merge, rebase, cherry-pick or revert is in progress");
+   };
+
+   run_commit_operation(Path::new(&repo_path), &[command, "--continue"], command)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use std::fs;
+
+   fn git(dir: &Path, args: &[&str]) -> bool {
+      Command::new("git")
+         .current_dir(dir)
+         .args([
+            "-c",
+            "user.name=Athas",
+            "-c",
+            "user.email=athas@example.com",
+         ])
+         .args(args)
+         .output()
+         .expect("run git")
+         .status
+         .success()
+   }
+
+   #[test]
+   fn detects_markers_in_git_dir() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let git_dir = temp_dir.path();
+      assert_eq!(operation_state_in(git_dir), GitOperationState::None);
+
+      fs::write(git_dir.join("CHERRY_PICK_HEAD"), "abc").expect("write marker");
+      assert_eq!(operation_state_in(git_dir), GitOperationState::CherryPick);
+
+      fs::write(git_dir.join("MERGE_HEAD"), "abc").expect("write marker");
+      assert_eq!(operation_state_in(git_dir), GitOperationState::Merge);
+
+      fs::create_dir(git_dir.join("rebase-merge")).expect("create marker");
+      assert_eq!(operation_state_in(git_dir), GitOperationState::Rebase);
+   }
+
+   #[test]
+   fn aborts_conflicted_merge() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let repo = temp_dir.path();
+      let file = repo.join("notes.txt");
+      assert!(git(repo, &["init", "-q", "-b", "main"]));
+      fs::write(&file, "base\n").expect("write file");
+      assert!(git(repo, &["add", "."]));
+      assert!(git(repo, &["commit", "-q", "-m", "base"]));
+      assert!(git(repo, &["checkout", "-q", "-b", "feature"]));
+      fs::write(&file, "feature\n").expect("write file");
+      assert!(git(repo, &["commit", "-q", "-am", "feature"]));
+      assert!(git(repo, &["checkout", "-q", "main"]));
+      fs::write(&file, "main\n").expect("write file");
+      assert!(git(repo, &["commit", "-q", "-am", "main"]));
+      assert!(!git(repo, &["merge", "-q", "feature"]));
+
+      let repo_path = repo.to_string_lossy().to_string();
+      assert_eq!(
+         git_operation_state(repo_path.clone()),
+         Ok(GitOperationState::Merge)
+      );
+      assert_eq!(
+         git_abort_operation(repo_path.clone()),
+         Ok(GitOperationState::Merge)
+      );
+      assert_eq!(
+         git_operation_state(repo_path.clone()),
+         Ok(GitOperationState::None)
+      );
+      assert!(git_continue_operation(repo_path).is_err());
+   }
+}
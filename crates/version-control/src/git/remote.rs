@@ -1,4 +1,4 @@
-use crate::git::{GitRemote, IntoStringError};
+use crate::git::{GitRemote, IntoStringError, credentials::apply_github_token};
 use anyhow::{Context, Result, bail};
 use git2::Repository;
 use std::{
@@ -6,16 +6,37 @@ use std::{
    process::{Command, Stdio},
 };
 
-pub fn git_push(repo_path: String, branch: Option<String>, remote: String) -> Result<(), String> {
-   _git_push(repo_path, branch, remote).into_string_error()
+pub fn git_push(
+   repo_path: String,
+   branch: Option<String>,
+   remote: String,
+   github_token: Option<String>,
+) -> Result<(), String> {
+   _git_push(repo_path, branch, remote, github_token).into_string_error()
 }
 
 pub(crate) fn execute_remote_git_command(
    repo_dir: &Path,
    args: &[&str],
    operation: &str,
+   github_token: Option<&str>,
 ) -> Result<()> {
-   let output = Command::new("git")
+   run_remote_git_command(repo_dir, args, operation, github_token).map(|_| ())
+}
+
+/// Runs a non-interactive git network command and returns its combined output.
+///
+/// Git reports progress and ref update summaries for fetch/push on stderr, so both streams are
+/// returned on success. `github_token` authenticates HTTPS github.com remotes.
+pub(crate) fn run_remote_git_command(
+   repo_dir: &Path,
+   args: &[&str],
+   operation: &str,
+   github_token: Option<&str>,
+) -> Result<String> {
+   let mut command = Command::new("git");
+   let credentials = apply_github_token(&mut command, github_token)?;
+   let output = command
       .current_dir(repo_dir)
       .env("GIT_TERMINAL_PROMPT", "0")
       .env("GCM_INTERACTIVE", "never")
@@ -25,13 +46,19 @@ pub(crate) fn execute_remote_git_command(
       .args(args)
       .output()
       .with_context(|| format!("Failed to execute git {operation}"))?;
+   drop(credentials);
+
+   let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+   let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
 
    if output.status.success() {
-      return Ok(());
+      return Ok([stdout, stderr]
+         .into_iter()
+         .filter(|text| !text.is_empty())
+         .collect::<Vec<_>>()
+         .join("\n"));
    }
 
-   let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-   let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
    let details = if !stderr.is_empty() {
       stderr
    } else if !stdout.is_empty() {
@@ -43,7 +70,12 @@ pub(crate) fn execute_remote_git_command(
    bail!("Git {operation} failed: {details}");
 }
 
-fn _git_push(repo_path: String, branch: Option<String>, remote: String) -> Result<()> {
+fn _git_push(
+   repo_path: String,
+   branch: Option<String>,
+   remote: String,
+   github_token: Option<String>,
+) -> Result<()> {
    let repo_dir = Path::new(&repo_path);
    let mut args = vec!["push", &remote];
    let branch_str;
@@ -52,14 +84,24 @@ fn _git_push(repo_path: String, branch: Option<String>, remote: String) -> Resul
       args.push(&branch_str);
    }
 
-   execute_remote_git_command(repo_dir, &args, "push")
+   execute_remote_git_command(repo_dir, &args, "push", github_token.as_deref())
 }
 
-pub fn git_pull(repo_path: String, branch: Option<String>, remote: String) -> Result<(), String> {
-   _git_pull(repo_path, branch, remote).into_string_error()
+pub fn git_pull(
+   repo_path: String,
+   branch: Option<String>,
+   remote: String,
+   github_token: Option<String>,
+) -> Result<(), String> {
+   _git_pull(repo_path, branch, remote, github_token).into_string_error()
 }
 
-fn _git_pull(repo_path: String, branch: Option<String>, remote: String) -> Result<()> {
+fn _git_pull(
+   repo_path: String,
+   branch: Option<String>,
+   remote: String,
+   github_token: Option<String>,
+) -> Result<()> {
    let repo_dir = Path::new(&repo_path);
    let mut args = vec!["pull", &remote];
    let branch_str;
@@ -68,23 +110,36 @@ fn _git_pull(repo_path: String, branch: Option<String>, remote: String) -> Resul
       args.push(&branch_str);
    }
 
-   execute_remote_git_command(repo_dir, &args, "pull")
+   execute_remote_git_command(repo_dir, &args, "pull", github_token.as_deref())
 }
 
-pub fn git_fetch(repo_path: String, remote: Option<String>) -> Result<(), String> {
-   _git_fetch(repo_path, remote).into_string_error()
+pub fn git_fetch(
+   repo_path: String,
+   remote: Option<String>,
+   prune: bool,
+   github_token: Option<String>,
+) -> Result<String, String> {
+   _git_fetch(repo_path, remote, prune, github_token).into_string_error()
 }
 
-fn _git_fetch(repo_path: String, remote: Option<String>) -> Result<()> {
+fn _git_fetch(
+   repo_path: String,
+   remote: Option<String>,
+   prune: bool,
+   github_token: Option<String>,
+) -> Result<String> {
    let repo_dir = Path::new(&repo_path);
    let mut args = vec!["fetch"];
+   if prune {
+      args.push("--prune");
+   }
    let remote_str;
    if let Some(r) = remote {
       remote_str = r;
       args.push(&remote_str);
    }
 
-   execute_remote_git_command(repo_dir, &args, "fetch")
+   run_remote_git_command(repo_dir, &args, "fetch", github_token.as_deref())
 }
 
 pub fn git_get_remotes(repo_path: String) -> Result<Vec<GitRemote>, String> {
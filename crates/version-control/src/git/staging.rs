@@ -1,7 +1,7 @@
 use crate::git::IntoStringError;
-use anyhow::{Context, Result};
-use git2::{ErrorCode, Repository};
-use std::path::Path;
+use anyhow::{Context, Result, bail};
+use git2::{ErrorCode, Repository, Status};
+use std::{fs, path::Path};
 
 pub fn git_add(repo_path: String, file_path: String) -> Result<(), String> {
    _git_add(repo_path, file_path).into_string_error()
@@ -128,12 +128,64 @@ fn _git_reset_all(repo_path: String) -> Result<()> {
    Ok(())
 }
 
-pub fn git_discard_file_changes(repo_path: String, file_path: String) -> Result<(), String> {
-   _git_discard_file_changes(repo_path, file_path).into_string_error()
+pub fn git_discard_file_changes(
+   repo_path: String,
+   file_path: String,
+   discard_staged: bool,
+) -> Result<(), String> {
+   _git_discard_file_changes(repo_path, file_path, discard_staged).into_string_error()
 }
 
-fn _git_discard_file_changes(repo_path: String, file_path: String) -> Result<()> {
+fn _git_discard_file_changes(
+   repo_path: String,
+   file_path: String,
+   discard_staged: bool,
+) -> Result<()> {
    let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   let status = path_status(&repo, &file_path)?;
+
+   if status.is_empty() {
+      return Ok(());
+   }
+
+   if status == Status::WT_NEW {
+      return remove_untracked_path(&Path::new(&repo_path).join(&file_path));
+   }
+
+   let index_flags = Status::INDEX_NEW
+      | Status::INDEX_MODIFIED
+      | Status::INDEX_DELETED
+      | Status::INDEX_RENAMED
+      | Status::INDEX_TYPECHANGE;
+
+   if !status.intersects(index_flags) {
+      let mut checkout_opts = git2::build::CheckoutBuilder::new();
+      checkout_opts.force().path(&file_path);
+
+      repo
+         .checkout_index(None, Some(&mut checkout_opts))
+         .context("Failed to restore file from index")?;
+
+      return Ok(());
+   }
+
+   if !discard_staged {
+      bail!(
+         "{} has staged changes that would also be lost. Unstage them first or discard staged \
+          changes too.",
+         file_path
+      );
+   }
+
+   if status.contains(Status::INDEX_NEW) {
+      let mut index = repo.index().context("Failed to get index")?;
+      index
+         .remove_path(Path::new(&file_path))
+         .context("Failed to remove from index")?;
+      index.write().context("Failed to write index")?;
+
+      return remove_untracked_path(&Path::new(&repo_path).join(&file_path));
+   }
 
    let head = repo
       .head()
@@ -151,6 +203,35 @@ fn _git_discard_file_changes(repo_path: String, file_path: String) -> Result<()>
    Ok(())
 }
 
+/// Combined status flags for every entry under `file_path`, so untracked directories reported as
+/// `dir/` are handled the same way as single files.
+fn path_status(repo: &Repository, file_path: &str) -> Result<Status> {
+   let mut status_opts = git2::StatusOptions::new();
+   status_opts
+      .pathspec(file_path)
+      .include_untracked(true)
+      .recurse_untracked_dirs(true)
+      .include_ignored(false);
+
+   let statuses = repo
+      .statuses(Some(&mut status_opts))
+      .context("Failed to get file status")?;
+
+   Ok(statuses
+      .iter()
+      .fold(Status::empty(), |flags, entry| flags | entry.status()))
+}
+
+fn remove_untracked_path(path: &Path) -> Result<()> {
+   if path.is_dir() {
+      fs::remove_dir_all(path)
+         .with_context(|| format!("Failed to delete untracked directory {}", path.display()))
+   } else {
+      fs::remove_file(path)
+         .with_context(|| format!("Failed to delete untracked file {}", path.display()))
+   }
+}
+
 pub fn git_discard_all_changes(repo_path: String) -> Result<(), String> {
    _git_discard_all_changes(repo_path).into_string_error()
 }
@@ -170,3 +251,50 @@ fn _git_discard_all_changes(repo_path: String) -> Result<()> {
 
    Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::{commit_files, init_repo};
+
+   #[test]
+   fn discard_restores_unstaged_changes_and_deletes_untracked_files() {
+      let (temp_dir, repo) = init_repo();
+      commit_files(&repo, &[("tracked.txt", "original\n")], "initial");
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+
+      fs::write(temp_dir.path().join("tracked.txt"), "edited\n").expect("edit file");
+      fs::write(temp_dir.path().join("scratch.txt"), "new\n").expect("write untracked");
+
+      git_discard_file_changes(repo_path.clone(), "tracked.txt".to_string(), false)
+         .expect("discard tracked");
+      git_discard_file_changes(repo_path, "scratch.txt".to_string(), false)
+         .expect("discard untracked");
+
+      assert_eq!(
+         fs::read_to_string(temp_dir.path().join("tracked.txt")).expect("read file"),
+         "original\n"
+      );
+      assert!(!temp_dir.path().join("scratch.txt").exists());
+   }
+
+   #[test]
+   fn discard_refuses_staged_changes_unless_requested() {
+      let (temp_dir, repo) = init_repo();
+      commit_files(&repo, &[("tracked.txt", "original\n")], "initial");
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+
+      fs::write(temp_dir.path().join("tracked.txt"), "staged\n").expect("edit file");
+      git_add(repo_path.clone(), "tracked.txt".to_string()).expect("stage file");
+
+      assert!(
+         git_discard_file_changes(repo_path.clone(), "tracked.txt".to_string(), false).is_err()
+      );
+
+      git_discard_file_changes(repo_path, "tracked.txt".to_string(), true).expect("discard staged");
+      assert_eq!(
+         fs::read_to_string(temp_dir.path().join("tracked.txt")).expect("read file"),
+         "original\n"
+      );
+   }
+}
@@ -1,4 +1,4 @@
-use crate::git::{GitDiff, GitStash, IntoStringError, diff::parse_diff_to_lines, is_image_file};
+use crate::git::{GitDiff, GitStash, IntoStringError, diff::commit_diff};
 use anyhow::{Context, Result, bail};
 use git2::Repository;
 use std::{path::Path, process::Command};
@@ -192,127 +192,55 @@ fn _git_drop_stash(repo_path: String, stash_index: usize) -> Result<()> {
    Ok(())
 }
 
-pub fn git_stash_diff(repo_path: String, stash_index: usize) -> Result<Vec<GitDiff>, String> {
-   _git_stash_diff(repo_path, stash_index).map_err(|e| e.to_string())
+pub fn git_stash_diff(
+   repo_path: String,
+   stash_index: usize,
+   file_path: Option<String>,
+) -> Result<Vec<GitDiff>, String> {
+   _git_stash_diff(repo_path, stash_index, file_path).into_string_error()
 }
 
-fn _git_stash_diff(repo_path: String, stash_index: usize) -> Result<Vec<GitDiff>> {
-   let repo_dir = Path::new(&repo_path);
-   let stash_ref = format!("stash@{{{stash_index}}}");
-
-   // Get the list of files changed in the stash using git stash show
-   let output = Command::new("git")
-      .current_dir(repo_dir)
-      .args(["stash", "show", "--name-status", &stash_ref])
-      .output()
-      .context("Failed to execute git stash show")?;
-
-   if !output.status.success() {
-      bail!(
-         "Git stash show failed: {}",
-         String::from_utf8_lossy(&output.stderr)
-      );
-   }
-
-   let file_list = String::from_utf8_lossy(&output.stdout);
-   let mut results: Vec<GitDiff> = Vec::new();
-
-   // Open repo with git2 for getting the actual diffs
+/// Diff a stash entry against the commit it was created on, optionally limited to one file.
+fn _git_stash_diff(
+   repo_path: String,
+   stash_index: usize,
+   file_path: Option<String>,
+) -> Result<Vec<GitDiff>> {
    let repo = Repository::open(&repo_path).context("Failed to open repository")?;
-
-   // Get stash commit hash
+   let stash_ref = format!("stash@{{{stash_index}}}");
    let stash_commit = repo
       .revparse_single(&stash_ref)
-      .context("Failed to find stash")?
+      .with_context(|| format!("Failed to find {stash_ref}"))?
       .peel_to_commit()
       .context("Failed to peel stash to commit")?;
 
-   let stash_tree = stash_commit.tree().context("Failed to get stash tree")?;
-
-   // Get parent tree
-   let parent_tree = if stash_commit.parent_count() > 0 {
-      Some(
-         stash_commit
-            .parent(0)
-            .context("Failed to get parent")?
-            .tree()
-            .context("Failed to get parent tree")?,
-      )
-   } else {
-      None
-   };
-
-   for line in file_list.lines() {
-      let parts: Vec<&str> = line.split('\t').collect();
-      if parts.len() < 2 {
-         continue;
-      }
-
-      let status_char = parts[0].chars().next().unwrap_or(' ');
-      let file_path = parts.last().unwrap_or(&"").to_string();
-
-      if file_path.is_empty() {
-         continue;
-      }
-
-      let is_new = status_char == 'A';
-      let is_deleted = status_char == 'D';
-      let is_renamed = status_char == 'R';
-      let is_image = is_image_file(&file_path);
-
-      let old_path = if is_renamed && parts.len() >= 3 {
-         Some(parts[1].to_string())
-      } else if !is_new {
-         Some(file_path.clone())
-      } else {
-         None
-      };
-
-      let new_path = if !is_deleted {
-         Some(file_path.clone())
-      } else {
-         None
-      };
+   commit_diff(&repo, &stash_commit, file_path.as_deref()).map_err(anyhow::Error::msg)
+}
 
-      let (lines, is_binary, old_blob_base64, new_blob_base64, is_truncated) = if is_image {
-         (Vec::new(), true, None, None, false)
-      } else {
-         // Get diff for this specific file
-         let mut diff_opts = git2::DiffOptions::new();
-         diff_opts.pathspec(&file_path);
-
-         let mut diff = repo
-            .diff_tree_to_tree(
-               parent_tree.as_ref(),
-               Some(&stash_tree),
-               Some(&mut diff_opts),
-            )
-            .context("Failed to create diff")?;
-
-         let parsed = parse_diff_to_lines(&mut diff).unwrap_or_default();
-         let is_truncated = parsed.is_truncated;
-         let lines = parsed.lines;
-         (lines, false, None, None, is_truncated)
-      };
-
-      results.push(GitDiff {
-         file_path,
-         old_path,
-         new_path,
-         is_new,
-         is_deleted,
-         is_renamed,
-         is_binary,
-         is_image,
-         old_blob_base64,
-         new_blob_base64,
-         lines,
-         raw_patch: None,
-         additions: None,
-         deletions: None,
-         is_truncated: is_truncated.then_some(true),
-      });
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::git::test_support::{commit_files, init_repo, signature};
+   use std::fs;
+
+   #[test]
+   fn stash_diff_compares_the_stash_with_its_base_commit() {
+      let (temp_dir, mut repo) = init_repo();
+      commit_files(&repo, &[("a.txt", "one\n")], "commit");
+      commit_files(&repo, &[("b.txt", "two\n")], "commit");
+      fs::write(temp_dir.path().join("a.txt"), "one\nmore\n").expect("edit a");
+      fs::write(temp_dir.path().join("b.txt"), "changed\n").expect("edit b");
+      repo.stash_save(&signature(), "wip", None).expect("stash");
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+
+      let all = git_stash_diff(repo_path.clone(), 0, None).expect("stash diff");
+      let only_a = git_stash_diff(repo_path.clone(), 0, Some("a.txt".to_string())).expect("diff");
+
+      assert_eq!(all.len(), 2);
+      assert_eq!(only_a.len(), 1);
+      assert_eq!(only_a[0].file_path, "a.txt");
+      assert_eq!(only_a[0].additions, Some(1));
+      assert_eq!(only_a[0].deletions, Some(0));
+      assert!(git_stash_diff(repo_path, 1, None).is_err());
    }
-
-   Ok(results)
 }
@@ -1,7 +1,7 @@
 use crate::git::{FileStatus, GitFile, GitStatus, IntoStringError, get_ahead_behind_counts};
 use anyhow::{Context, Result};
-use git2::{ErrorCode, Repository};
-use std::fs;
+use git2::{ErrorCode, Repository, RepositoryInitOptions, StatusEntry};
+use std::{fs, path::Path};
 
 pub fn git_status(repo_path: String) -> Result<GitStatus, String> {
    _git_status(repo_path).into_string_error()
@@ -20,7 +20,7 @@ fn _git_status(repo_path: String) -> Result<GitStatus> {
       .recurse_untracked_dirs(false)
       .include_ignored(false)
       .include_unmodified(false)
-      .renames_head_to_index(false)
+      .renames_head_to_index(true)
       .renames_index_to_workdir(false);
 
    let statuses = repo
@@ -35,7 +35,7 @@ fn _git_status(repo_path: String) -> Result<GitStatus> {
          continue;
       }
 
-      let path = entry.path().context("Invalid path")?.to_string();
+      let path = status_entry_path(&entry).context("Invalid path")?;
 
       let has_staged = status_flags.intersects(
          git2::Status::INDEX_NEW
@@ -100,7 +100,18 @@ fn _git_status(repo_path: String) -> Result<GitStatus> {
    })
 }
 
-fn current_branch_name(repo: &Repository) -> String {
+/// Path of a status entry, using the new name for staged renames.
+fn status_entry_path(entry: &StatusEntry) -> Option<String> {
+   let renamed_to = entry
+      .head_to_index()
+      .filter(|_| entry.status().contains(git2::Status::INDEX_RENAMED))
+      .and_then(|delta| delta.new_file().path())
+      .and_then(|path| path.to_str());
+
+   renamed_to.or_else(|| entry.path()).map(str::to_string)
+}
+
+pub(crate) fn current_branch_name(repo: &Repository) -> String {
    match repo.head() {
       Ok(head) => {
          if head.is_branch() {
@@ -135,12 +146,22 @@ fn unborn_head_branch_name(repo: &Repository) -> String {
       .unwrap_or_else(|| "main".to_string())
 }
 
-pub fn git_init(repo_path: String) -> Result<(), String> {
-   _git_init(repo_path).into_string_error()
+/// Create a repository at `repo_path`. Without `initial_branch`, the unborn branch follows
+/// `init.defaultBranch` like `git init` does.
+pub fn git_init(repo_path: String, initial_branch: Option<String>) -> Result<(), String> {
+   _git_init(repo_path, initial_branch).into_string_error()
 }
 
-fn _git_init(repo_path: String) -> Result<()> {
-   Repository::init(&repo_path).context("Failed to initialize repository")?;
+fn _git_init(repo_path: String, initial_branch: Option<String>) -> Result<()> {
+   let mut options = RepositoryInitOptions::new();
+   if let Some(branch) = initial_branch
+      .as_deref()
+      .map(str::trim)
+      .filter(|branch| !branch.is_empty())
+   {
+      options.initial_head(branch);
+   }
+   Repository::init_opts(&repo_path, &options).context("Failed to initialize repository")?;
    Ok(())
 }
 
@@ -162,18 +183,120 @@ pub fn git_discover_repo(path: String) -> Result<Option<String>, String> {
    Ok(discovered)
 }
 
+/// Whether each of `paths` would be ignored by the repository's ignore rules. Paths may be
+/// relative to the working directory or absolute paths inside it, and need not exist yet.
+pub fn git_check_ignore(repo_path: String, paths: Vec<String>) -> Result<Vec<bool>, String> {
+   _git_check_ignore(repo_path, paths).into_string_error()
+}
+
+fn _git_check_ignore(repo_path: String, paths: Vec<String>) -> Result<Vec<bool>> {
+   let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   let workdir = repo
+      .workdir()
+      .context("Repository has no working directory")?;
+
+   paths
+      .iter()
+      .map(|path| {
+         let path = Path::new(path);
+         let relative = path.strip_prefix(workdir).unwrap_or(path);
+         repo
+            .status_should_ignore(relative)
+            .with_context(|| format!("Failed to check ignore rules for {}", path.display()))
+      })
+      .collect()
+}
+
 #[cfg(test)]
 mod tests {
    use super::*;
+   use crate::git::test_support::{commit_files, init_repo};
 
    #[test]
    fn current_branch_name_uses_unborn_head_name_for_empty_repositories() {
-      let temp_dir = tempfile::tempdir().expect("temp dir");
-      let repo = Repository::init(temp_dir.path()).expect("repo init");
+      let (_temp_dir, repo) = init_repo();
 
       let branch = current_branch_name(&repo);
 
       assert_ne!(branch, "unknown");
       assert!(!branch.is_empty());
    }
+
+   #[test]
+   fn git_status_reports_untracked_files_in_empty_repositories() {
+      let (temp_dir, _repo) = init_repo();
+      fs::write(temp_dir.path().join("README.md"), "hello").expect("write file");
+
+      let status = git_status(temp_dir.path().to_string_lossy().to_string()).expect("status");
+
+      assert_ne!(status.branch, "unknown");
+      assert_ne!(status.branch, "HEAD");
+      assert_eq!((status.ahead, status.behind), (0, 0));
+      assert_eq!(status.files.len(), 1);
+      assert_eq!(status.files[0].path, "README.md");
+      assert!(!status.files[0].staged);
+   }
+
+   #[test]
+   fn git_init_uses_the_requested_initial_branch() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let repo_path = temp_dir.path().to_string_lossy().to_string();
+
+      git_init(repo_path.clone(), Some("trunk".to_string())).expect("init");
+      let status = git_status(repo_path).expect("status");
+
+      assert_eq!(status.branch, "trunk");
+      assert!(status.files.is_empty());
+   }
+
+   #[test]
+   fn git_check_ignore_matches_relative_and_absolute_paths() {
+      let (temp_dir, _repo) = init_repo();
+      fs::write(temp_dir.path().join(".gitignore"), "target/\n*.log\n").expect("gitignore");
+      let root = temp_dir.path().to_string_lossy().to_string();
+
+      let ignored = git_check_ignore(
+         root.clone(),
+         vec![
+            "target/debug/app".to_string(),
+            "src/main.rs".to_string(),
+            temp_dir
+               .path()
+               .join("logs/build.log")
+               .to_string_lossy()
+               .to_string(),
+         ],
+      )
+      .expect("check ignore");
+
+      assert_eq!(ignored, vec![true, false, true]);
+   }
+
+   #[test]
+   fn git_status_reports_staged_renames_under_the_new_path() {
+      let (temp_dir, repo) = init_repo();
+      let contents = "a file with enough content to be detected as a rename\n".repeat(4);
+      commit_files(&repo, &[("old.txt", &contents)], "initial");
+
+      fs::rename(
+         temp_dir.path().join("old.txt"),
+         temp_dir.path().join("new.txt"),
+      )
+      .expect("rename file");
+      let mut index = repo.index().expect("index");
+      index
+         .remove_path(std::path::Path::new("old.txt"))
+         .expect("remove old path");
+      index
+         .add_path(std::path::Path::new("new.txt"))
+         .expect("add new path");
+      index.write().expect("write index");
+
+      let status = git_status(temp_dir.path().to_string_lossy().to_string()).expect("status");
+
+      assert_eq!(status.files.len(), 1);
+      assert_eq!(status.files[0].path, "new.txt");
+      assert!(matches!(status.files[0].status, FileStatus::Renamed));
+      assert!(status.files[0].staged);
+   }
 }
@@ -0,0 +1,175 @@
+use crate::git::{GitSubmodule, IntoStringError};
+use anyhow::{Context, Result, bail};
+use git2::{Repository, SubmoduleIgnore, SubmoduleStatus};
+use std::{path::Path, process::Command};
+
+pub fn git_submodules(repo_path: String) -> Result<Vec<GitSubmodule>, String> {
+   _git_submodules(repo_path).into_string_error()
+}
+
+fn _git_submodules(repo_path: String) -> Result<Vec<GitSubmodule>> {
+   let repo = Repository::open(&repo_path).context("Failed to open repository")?;
+   let submodules = repo.submodules().context("Failed to list submodules")?;
+
+   let mut result = Vec::with_capacity(submodules.len());
+   for submodule in submodules {
+      let name = submodule.name().unwrap_or_default().to_string();
+      let status = repo
+         .submodule_status(&name, SubmoduleIgnore::None)
+         .with_context(|| format!("Failed to get status of submodule {}", name))?;
+      let is_initialized = !status.contains(SubmoduleStatus::WD_UNINITIALIZED);
+
+      result.push(GitSubmodule {
+         path: submodule.path().to_string_lossy().to_string(),
+         url: submodule.url().map(str::to_string),
+         recorded_commit: submodule
+            .index_id()
+            .or_else(|| submodule.head_id())
+            .map(|id| id.to_string()),
+         checked_out_commit: submodule.workdir_id().map(|id| id.to_string()),
+         is_initialized,
+         is_modified: status.intersects(
+            SubmoduleStatus::WD_INDEX_MODIFIED
+               | SubmoduleStatus::WD_WD_MODIFIED
+               | SubmoduleStatus::WD_UNTRACKED,
+         ),
+         is_out_of_date: is_initialized && status.contains(SubmoduleStatus::WD_MODIFIED),
+         name,
+      });
+   }
+
+   result.sort_by(|a, b| a.path.cmp(&b.path));
+   Ok(result)
+}
+
+pub fn git_submodule_update(
+   repo_path: String,
+   path: Option<String>,
+   init: bool,
+   recursive: bool,
+) -> Result<(), String> {
+   _git_submodule_update(repo_path, path, init, recursive).into_string_error()
+}
+
+fn _git_submodule_update(
+   repo_path: String,
+   path: Option<String>,
+   init: bool,
+   recursive: bool,
+) -> Result<()> {
+   let mut args: Vec<String> = vec!["submodule".into(), "update".into()];
+   if init {
+      args.push("--init".into());
+   }
+   if recursive {
+      args.push("--recursive".into());
+   }
+   if let Some(path) = path
+      .map(|value| value.trim().to_string())
+      .filter(|value| !value.is_empty())
+   {
+      args.push("--".into());
+      args.push(path);
+   }
+
+   let output = Command::new("git")
+      .current_dir(Path::new(&repo_path))
+      .args(&args)
+      .output()
+      .context("Failed to execute git submodule update")?;
+
+   if !output.status.success() {
+      bail!(
+         "Git submodule update failed: {}",
+         String::from_utf8_lossy(&output.stderr)
+      );
+   }
+
+   Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use std::fs;
+
+   fn git(dir: &Path, args: &[&str]) {
+      let output = Command::new("git")
+         .current_dir(dir)
+         .args([
+            "-c",
+            "user.name=Athas",
+            "-c",
+            "user.email=athas@example.com",
+            "-c",
+            "protocol.file.allow=always",
+         ])
+         .args(args)
+         .output()
+         .expect("run git");
+      assert!(
+         output.status.success(),
+         "git {:?} failed: {}",
+         args,
+         String::from_utf8_lossy(&output.stderr)
+      );
+   }
+
+   #[test]
+   fn reports_submodule_state() {
+      let temp_dir = tempfile::tempdir().expect("temp dir");
+      let library = temp_dir.path().join("library");
+      let app = temp_dir.path().join("app");
+      fs::create_dir_all(&library).expect("create library");
+      fs::create_dir_all(&app).expect("create app");
+
+      git(&library, &["init", "-q"]);
+      fs::write(library.join("lib.txt"), "v1").expect("write file");
+      git(&library, &["add", "."]);
+      git(&library, &["commit", "-q", "-m", "v1"]);
+
+      git(&app, &["init", "-q"]);
+      git(
+         &app,
+         &[
+            "submodule",
+            "add",
+            "-q",
+            library.to_str().unwrap(),
+            "vendor/library",
+         ],
+      );
+      git(&app, &["commit", "-q", "-m", "add submodule"]);
+
+      let repo_path = app.to_string_lossy().to_string();
+      let submodules = git_submodules(repo_path.clone()).expect("submodules");
+      assert_eq!(submodules.len(), 1);
+      let submodule = &submodules[0];
+      assert_eq!(submodule.path, "vendor/library");
+      assert!(submodule.is_initialized);
+      assert!(!submodule.is_modified);
+      assert!(!submodule.is_out_of_date);
+      assert_eq!(submodule.recorded_commit, submodule.checked_out_commit);
+
+      let checkout = app.join("vendor/library");
+      fs::write(checkout.join("scratch.txt"), "wip").expect("write file");
+      fs::write(checkout.join("lib.txt"), "v2").expect("write file");
+      git(&checkout, &["commit", "-q", "-am", "v2"]);
+
+      let submodule = git_submodules(repo_path.clone())
+         .expect("submodules")
+         .remove(0);
+      assert!(submodule.is_modified);
+      assert!(submodule.is_out_of_date);
+
+      git_submodule_update(
+         repo_path.clone(),
+         Some("vendor/library".into()),
+         false,
+         false,
+      )
+      .expect("update");
+      let submodule = git_submodules(repo_path).expect("submodules").remove(0);
+      assert!(!submodule.is_out_of_date);
+   }
+}
@@ -162,16 +162,27 @@ fn _git_delete_tag(repo_path: String, name: String) -> Result<()> {
    Ok(())
 }
 
-pub fn git_push_tag(repo_path: String, name: String, remote: String) -> Result<(), String> {
-   _git_push_tag(repo_path, name, remote).into_string_error()
+pub fn git_push_tag(
+   repo_path: String,
+   name: String,
+   remote: String,
+   github_token: Option<String>,
+) -> Result<(), String> {
+   _git_push_tag(repo_path, name, remote, github_token).into_string_error()
 }
 
-fn _git_push_tag(repo_path: String, name: String, remote: String) -> Result<()> {
+fn _git_push_tag(
+   repo_path: String,
+   name: String,
+   remote: String,
+   github_token: Option<String>,
+) -> Result<()> {
    let repo_dir = Path::new(&repo_path);
    execute_remote_git_command(
       repo_dir,
       &["push", &remote, &format!("refs/tags/{name}")],
       "push tag",
+      github_token.as_deref(),
    )
 }
 
@@ -179,16 +190,23 @@ pub fn git_delete_remote_tag(
    repo_path: String,
    name: String,
    remote: String,
+   github_token: Option<String>,
 ) -> Result<(), String> {
-   _git_delete_remote_tag(repo_path, name, remote).into_string_error()
+   _git_delete_remote_tag(repo_path, name, remote, github_token).into_string_error()
 }
 
-fn _git_delete_remote_tag(repo_path: String, name: String, remote: String) -> Result<()> {
+fn _git_delete_remote_tag(
+   repo_path: String,
+   name: String,
+   remote: String,
+   github_token: Option<String>,
+) -> Result<()> {
    let repo_dir = Path::new(&repo_path);
    execute_remote_git_command(
       repo_dir,
       &["push", &remote, &format!(":refs/tags/{name}")],
       "delete remote tag",
+      github_token.as_deref(),
    )
 }
 
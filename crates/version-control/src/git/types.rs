@@ -35,6 +35,18 @@ pub struct GitCommit {
    pub date: String,
 }
 
+/// A commit with its ancestry, for drawing the commit graph
+#[derive(Serialize)]
+pub struct GitGraphNode {
+   pub hash: String,
+   pub parents: Vec<String>,
+   pub refs: Vec<String>,
+   pub author: String,
+   pub email: String,
+   pub date: String,
+   pub summary: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum DiffLineType {
@@ -81,6 +93,14 @@ pub struct GitDiffStat {
    pub staged: bool,
    pub additions: usize,
    pub deletions: usize,
+   pub binary: bool,
+}
+
+/// Zero-based, inclusive span of lines in the working copy of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GitLineRange {
+   pub start_line: u32,
+   pub end_line: u32,
 }
 
 #[derive(Serialize)]
@@ -107,6 +127,16 @@ pub struct GitRemote {
    pub url: String,
 }
 
+/// Payload of the `git-clone-progress` event
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCloneProgress {
+   pub received_objects: usize,
+   pub indexed_objects: usize,
+   pub total_objects: usize,
+   pub received_bytes: usize,
+}
+
 #[derive(Serialize)]
 pub struct GitStash {
    pub index: usize,
@@ -135,8 +165,44 @@ pub struct GitWorktree {
    pub is_current: bool,
 }
 
+/// Multi-step operation paused in the repository, usually waiting on conflict resolution.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GitOperationState {
+   None,
+   Merge,
+   Rebase,
+   CherryPick,
+   Revert,
+}
+
+#[derive(Serialize)]
+pub struct GitSubmodule {
+   pub name: String,
+   pub path: String,
+   pub url: Option<String>,
+   /// Commit the superproject records for the submodule.
+   pub recorded_commit: Option<String>,
+   /// Commit checked out in the submodule's working tree.
+   pub checked_out_commit: Option<String>,
+   pub is_initialized: bool,
+   /// The submodule's working tree has uncommitted or untracked changes.
+   pub is_modified: bool,
+   /// The checked out commit differs from the recorded one.
+   pub is_out_of_date: bool,
+}
+
 #[derive(Deserialize)]
 pub struct GitHunk {
    pub file_path: String,
    pub lines: Vec<GitDiffLine>,
 }
+
+/// How far `git_reset_to` rewinds: the branch only, the index too, or the working tree as well.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ResetMode {
+   Soft,
+   Mixed,
+   Hard,
+}
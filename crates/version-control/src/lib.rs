@@ -1,3 +1,4 @@
 pub mod git;
+pub mod text_diff;
 
 pub use git::*;
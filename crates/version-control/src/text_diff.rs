@@ -0,0 +1,146 @@
+use crate::git::{DiffLineType, GitDiffLine};
+use similar::{ChangeTag, TextDiff};
+use std::{fs, path::Path};
+
+/// Unchanged lines kept around each change, matching git's default
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Diff the unsaved `buffer_content` of `path` against the file on disk. Unlike
+/// `git_diff_file`, this works for files outside a repository. A file that does not exist
+/// yet diffs as empty, so every buffer line shows as added.
+pub fn diff_buffer_against_disk(
+   path: &Path,
+   buffer_content: &str,
+) -> Result<Vec<GitDiffLine>, String> {
+   let disk_content = match fs::read(path) {
+      Ok(bytes) => String::from_utf8(bytes)
+         .map_err(|_| format!("{} is not a UTF-8 text file", path.display()))?,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+      Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+   };
+
+   Ok(diff_texts(
+      &disk_content,
+      buffer_content,
+      DEFAULT_CONTEXT_LINES,
+   ))
+}
+
+/// Line diff of `old` and `new` in the shape git diffs are parsed into: a header per hunk,
+/// followed by its context, removed and added lines with 1-based line numbers.
+pub fn diff_texts(old: &str, new: &str, context_lines: usize) -> Vec<GitDiffLine> {
+   let diff = TextDiff::from_lines(old, new);
+   let mut lines = Vec::new();
+
+   for hunk in diff
+      .unified_diff()
+      .context_radius(context_lines)
+      .iter_hunks()
+   {
+      lines.push(GitDiffLine {
+         line_type: DiffLineType::Header,
+         content: hunk.header().to_string().trim_end().to_string(),
+         old_line_number: None,
+         new_line_number: None,
+      });
+
+      for change in hunk.iter_changes() {
+         let (line_type, old_line_number, new_line_number) = match change.tag() {
+            ChangeTag::Delete => (DiffLineType::Removed, change.old_index(), None),
+            ChangeTag::Insert => (DiffLineType::Added, None, change.new_index()),
+            ChangeTag::Equal => (
+               DiffLineType::Context,
+               change.old_index(),
+               change.new_index(),
+            ),
+         };
+         lines.push(GitDiffLine {
+            line_type,
+            content: change.value().trim_end_matches(['\n', '\r']).to_string(),
+            old_line_number: old_line_number.map(|index| index as u32 + 1),
+            new_line_number: new_line_number.map(|index| index as u32 + 1),
+         });
+      }
+   }
+
+   lines
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn summarize(lines: &[GitDiffLine]) -> Vec<String> {
+      lines
+         .iter()
+         .map(|line| {
+            let prefix = match line.line_type {
+               DiffLineType::Added => "+",
+               DiffLineType::Removed => "-",
+               DiffLineType::Context => " ",
+               DiffLineType::Header => "",
+            };
+            format!(
+               "{}{} {:?} {:?}",
+               prefix, line.content, line.old_line_number, line.new_line_number
+            )
+         })
+         .collect()
+   }
+
+   #[test]
+   fn diffs_buffer_against_file_on_disk() {
+      let dir = tempfile::tempdir().unwrap();
+      let path = dir.path().join("notes.txt");
+      fs::write(&path, "one\ntwo\nthree\n").unwrap();
+
+      let lines = diff_buffer_against_disk(&path, "one\n2\nthree\nfour\n").unwrap();
+
+      assert_eq!(
+         summarize(&lines),
+         vec![
+            "@@ -1,3 +1,4 @@ None None",
+            " one Some(1) Some(1)",
+            "-two Some(2) None",
+            "+2 None Some(2)",
+            " three Some(3) Some(3)",
+            "+four None Some(4)",
+         ]
+      );
+      assert!(
+         diff_buffer_against_disk(&path, "one\ntwo\nthree\n")
+            .unwrap()
+            .is_empty()
+      );
+   }
+
+   #[test]
+   fn limits_context_around_changes() {
+      let old = "a\nb\nc\nd\ne\nf\ng\n";
+      let new = "a\nb\nc\nD\ne\nf\ng\n";
+
+      assert_eq!(
+         summarize(&diff_texts(old, new, 1)),
+         vec![
+            "@@ -3,3 +3,3 @@ None None",
+            " c Some(3) Some(3)",
+            "-d Some(4) None",
+            "+D None Some(4)",
+            " e Some(5) Some(5)",
+         ]
+      );
+      assert_eq!(diff_texts(old, new, 0).len(), 3);
+      assert!(diff_texts(old, old, 3).is_empty());
+   }
+
+   #[test]
+   fn treats_missing_files_as_empty() {
+      let dir = tempfile::tempdir().unwrap();
+      let lines = diff_buffer_against_disk(&dir.path().join("new.txt"), "hello").unwrap();
+
+      assert_eq!(
+         summarize(&lines),
+         vec!["@@ -0,0 +1 @@ None None", "+hello None Some(1)"]
+      );
+   }
+}
@@ -1,14 +1,15 @@
 use crate::{
    app_runtime::AthasRuntime,
-   commands::{self, FffSearchState, FileClipboard, ThemeCache},
+   commands::{self, FffSearchState, FileClipboard, ThemeCache, UserThemeWatcher},
    file_events::TauriFileChangeEmitter,
-   menu,
+   menu, menu_commands,
    terminal::ManagedTerminalManager as TerminalManager,
 };
 use athas_ai::AcpAgentBridge;
 use athas_debugger::DebugManager;
 use athas_lsp::LspManager;
 use athas_project::FileWatcher;
+use athas_tooling::ToolRegistry;
 use log::{debug, info};
 use serde::Serialize;
 use std::{path::PathBuf, sync::Arc, time::Instant};
@@ -23,6 +24,9 @@ pub fn configure_app(app: &mut tauri::App<AthasRuntime>) -> Result<(), Box<dyn s
    register_managed_state(app);
    emit_cli_open_requests(app);
    configure_initial_window(app);
+   load_user_tool_registry(app);
+   cleanup_scratch_files();
+   athas_runtime::shell::warm_login_shell_path();
 
    #[cfg(all(unix, not(target_os = "macos")))]
    commands::development::cli::auto_fix_cli_on_startup();
@@ -33,6 +37,7 @@ pub fn configure_app(app: &mut tauri::App<AthasRuntime>) -> Result<(), Box<dyn s
 }
 
 fn configure_menu(app: &mut tauri::App<AthasRuntime>) -> Result<(), Box<dyn std::error::Error>> {
+   menu_commands::load_keybindings(app.handle());
    let store = app.store("settings.json")?;
 
    #[cfg(any(target_os = "windows", target_os = "linux"))]
@@ -81,12 +86,34 @@ fn register_managed_state(app: &mut tauri::App<AthasRuntime>) {
    app.manage(LspManager::new(app.handle().clone()));
    app.manage(DebugManager::new(app.handle().clone()));
    app.manage(ThemeCache::new(std::collections::HashMap::new()));
+   app.manage(UserThemeWatcher::new(app.handle().clone()));
    app.manage(FileClipboard::new(None));
    app.manage(FffSearchState::new());
+   app.manage(commands::editor::search::SearchCancellations::default());
+   app.manage(commands::editor::lint::CargoChecks::default());
    app.manage(commands::development::docker::DockerLogStreams::default());
+   app.manage(commands::development::tasks::ProjectTasks::default());
    app.manage(commands::development::cli_args::PendingCliOpenRequests::default());
 }
 
+fn load_user_tool_registry(app: &tauri::App<AthasRuntime>) {
+   let Ok(config_dir) = app.path().app_config_dir() else {
+      return;
+   };
+
+   if let Err(e) = ToolRegistry::load_user_tools(&config_dir) {
+      log::warn!("Failed to load user tool registry: {}", e);
+   }
+}
+
+fn cleanup_scratch_files() {
+   std::thread::spawn(|| match commands::cleanup_scratch_files() {
+      Ok(0) => {}
+      Ok(removed) => debug!("Removed {} stale scratch files", removed),
+      Err(e) => log::warn!("Failed to clean up scratch files: {}", e),
+   });
+}
+
 fn emit_cli_open_requests(app: &tauri::App<AthasRuntime>) {
    let cwd = std::env::current_dir().unwrap_or_default();
    let args: Vec<String> = std::env::args().collect();
@@ -418,7 +445,7 @@ fn handle_menu_event(app_handle: &tauri::AppHandle<AthasRuntime>, event: tauri::
                      log::error!("Failed to toggle fullscreen: {}", e);
                   }
                }
-               theme_id if theme_id.contains('-') => {
+               theme_id if theme_id.contains('-') || menu::is_menu_theme(theme_id) => {
                   emit_menu_event(&window, "menu_theme_change", theme_id);
                }
                _ => {}
@@ -1,5 +1,8 @@
 use crate::{app_runtime::AppHandle, service_urls};
-use athas_ai::{AcpAgentBridge, AcpAgentStatus, AcpSessionList, AgentConfig, AgentRuntime};
+use athas_ai::{
+   AcpAgentBridge, AcpAgentStatus, AcpSessionList, AgentConfig, AgentRuntime, McpServerConfig,
+   SlashCommand,
+};
 use athas_runtime::{RuntimeManager, RuntimeType};
 use athas_tooling::{ToolConfig, ToolInstaller, ToolRuntime};
 use serde::Deserialize;
@@ -37,6 +40,19 @@ pub async fn get_available_agents(
    Ok(bridge.detect_agents())
 }
 
+#[tauri::command]
+pub async fn set_acp_agent_mcp_servers(
+   bridge: State<'_, AcpBridgeState>,
+   agent_id: String,
+   mcp_servers: Vec<McpServerConfig>,
+) -> Result<AgentConfig, String> {
+   let mut bridge = bridge.lock().await;
+   refresh_registered_agents(&mut bridge).await;
+   bridge
+      .set_agent_mcp_servers(&agent_id, mcp_servers)
+      .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn start_acp_agent(
    bridge: State<'_, AcpBridgeState>,
@@ -56,6 +72,24 @@ pub async fn start_acp_agent(
       .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn new_acp_session(
+   bridge: State<'_, AcpBridgeState>,
+   agent_id: String,
+   workspace_path: Option<String>,
+) -> Result<AcpAgentStatus, String> {
+   let bridge = {
+      let mut bridge = bridge.lock().await;
+      refresh_registered_agents(&mut bridge).await;
+      bridge.detect_agents();
+      bridge.clone()
+   };
+   bridge
+      .new_session(&agent_id, workspace_path)
+      .await
+      .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn install_acp_agent(
    app_handle: AppHandle,
@@ -197,6 +231,7 @@ fn to_agent_config(contribution: MarketplaceAgentContribution) -> AgentConfig {
       install_download_url: None,
       install_command: None,
       can_install: false,
+      mcp_servers: Vec::new(),
    };
 
    if let Some(install) = contribution.install {
@@ -389,6 +424,18 @@ pub async fn logout_acp_agent(bridge: State<'_, AcpBridgeState>) -> Result<(), S
    bridge.logout().await.map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn list_acp_slash_commands(
+   bridge: State<'_, AcpBridgeState>,
+   agent_id: String,
+) -> Result<Vec<SlashCommand>, String> {
+   let bridge = { bridge.lock().await.clone() };
+   bridge
+      .list_slash_commands(&agent_id)
+      .await
+      .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn cancel_acp_prompt(bridge: State<'_, AcpBridgeState>) -> Result<(), String> {
    let bridge = { bridge.lock().await.clone() };
@@ -0,0 +1,4 @@
+#[tauri::command]
+pub fn format_sql(query: String) -> String {
+   athas_database::sql_format::format_sql(&query)
+}
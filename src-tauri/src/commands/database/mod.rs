@@ -1,7 +1,9 @@
 pub mod connection_manager;
 pub mod credentials;
+pub mod format;
 pub mod sidecar;
 
 pub use connection_manager::{connect_database, disconnect_database, test_connection};
 pub use credentials::*;
+pub use format::format_sql;
 pub use sidecar::*;
@@ -4,17 +4,21 @@ use super::{
       flatten_workspace_symbol_response, symbol_kind_label,
    },
    types::{
-      FlatCodeLens, FlatInlayHint, FlatSemanticToken, FlatSymbol, FlatTextEdit,
-      FlatTextEditPosition, FlatTextEditRange, FlatWorkspaceSymbol, LspApplyCodeActionResult,
-      LspCodeActionItem, LspDiagnosticContext,
+      FlatCodeLens, FlatDocumentHighlight, FlatFoldingRange, FlatInlayHint, FlatSemanticToken,
+      FlatSymbol, FlatTextEdit, FlatTextEditPosition, FlatTextEditRange, FlatWorkspaceSymbol,
+      LspApplyCodeActionResult, LspCodeActionItem, LspDiagnosticContext,
    },
 };
-use crate::app_runtime::AppHandle;
-use athas_lsp::{LspError, LspManager, LspResult};
+use crate::{app_runtime::AppHandle, commands::project::path_guard::require_path_under_home};
+use athas_lsp::{
+   LspCapabilities, LspError, LspManager, LspResult, LspServerStatus,
+   workspace_edit::{AppliedWorkspaceEdit, apply_workspace_edit},
+};
 use athas_tooling::{LanguageToolConfigSet, ToolInstaller, ToolRegistry, ToolType};
 use lsp_types::{
-   CodeActionOrCommand, CompletionItem, DocumentSymbolResponse, GotoDefinitionResponse, Hover,
-   Location, PrepareRenameResponse, SemanticTokensResult, SignatureHelp, WorkspaceEdit,
+   CodeActionOrCommand, CompletionItem, Diagnostic, DocumentHighlightKind, DocumentSymbolResponse,
+   FoldingRangeKind, FormattingOptions, GotoDefinitionResponse, Hover, Location,
+   PrepareRenameResponse, SemanticTokensResult, SignatureHelp, WorkspaceEdit,
 };
 use serde_json::Value;
 use std::{collections::HashMap, path::PathBuf};
@@ -122,12 +126,61 @@ pub fn lsp_stop(lsp_manager: State<'_, LspManager>, workspace_path: String) -> L
       })
 }
 
+/// Restart every running server, e.g. after a branch switch leaves their state stale. Returns
+/// how many servers were restarted.
+#[tauri::command]
+pub async fn lsp_restart_all(lsp_manager: State<'_, LspManager>) -> LspResult<usize> {
+   log::info!("lsp_restart_all command called");
+   lsp_manager.restart_all().await.map_err(|e| {
+      log::error!("Failed to restart LSP servers: {}", e);
+      e.into()
+   })
+}
+
+/// Shut down every running server. Returns how many servers were stopped.
+#[tauri::command]
+pub async fn lsp_stop_all(lsp_manager: State<'_, LspManager>) -> LspResult<usize> {
+   log::info!("lsp_stop_all command called");
+   Ok(lsp_manager.stop_all().await)
+}
+
+#[tauri::command]
+pub fn lsp_add_workspace_root(
+   lsp_manager: State<'_, LspManager>,
+   workspace_path: String,
+) -> LspResult<()> {
+   lsp_manager.add_workspace_root(PathBuf::from(workspace_path));
+   Ok(())
+}
+
+#[tauri::command]
+pub fn lsp_remove_workspace_root(
+   lsp_manager: State<'_, LspManager>,
+   workspace_path: String,
+) -> LspResult<()> {
+   lsp_manager
+      .remove_workspace_root(&PathBuf::from(workspace_path))
+      .map_err(|e| {
+         log::error!("Failed to remove LSP workspace root: {}", e);
+         e.into()
+      })
+}
+
+#[tauri::command]
+pub fn lsp_get_workspace_roots(lsp_manager: State<'_, LspManager>) -> Vec<String> {
+   lsp_manager
+      .workspace_roots()
+      .into_iter()
+      .map(|root| root.to_string_lossy().to_string())
+      .collect()
+}
+
 #[tauri::command]
 pub async fn lsp_start_for_file(
    app_handle: AppHandle,
    lsp_manager: State<'_, LspManager>,
    file_path: String,
-   workspace_path: String,
+   workspace_path: Option<String>,
    server_path: Option<String>,
    server_args: Option<Vec<String>>,
    language_id: Option<String>,
@@ -141,7 +194,7 @@ pub async fn lsp_start_for_file(
    lsp_manager
       .start_lsp_for_file(
          PathBuf::from(file_path),
-         PathBuf::from(workspace_path),
+         workspace_path.map(PathBuf::from),
          server_path,
          server_args,
          server_env,
@@ -191,6 +244,23 @@ pub async fn lsp_get_completions(
    result
 }
 
+/// Cancel the in-flight requests of an LSP method such as `textDocument/hover` on one file,
+/// returning how many were cancelled. Completion requests cancel their stale predecessors
+/// themselves.
+#[tauri::command]
+pub fn lsp_cancel_request(
+   lsp_manager: State<'_, LspManager>,
+   file_path: String,
+   operation: String,
+) -> LspResult<usize> {
+   lsp_manager
+      .cancel_requests(&file_path, &operation)
+      .map_err(|e| {
+         log::error!("Failed to cancel LSP request: {}", e);
+         e.into()
+      })
+}
+
 #[tauri::command]
 pub async fn lsp_get_hover(
    lsp_manager: State<'_, LspManager>,
@@ -398,15 +468,47 @@ pub async fn lsp_get_code_lens(
       .collect())
 }
 
+#[tauri::command]
+pub async fn lsp_folding_ranges(
+   lsp_manager: State<'_, LspManager>,
+   file_path: String,
+) -> LspResult<Vec<FlatFoldingRange>> {
+   let response = lsp_manager
+      .get_folding_ranges(&file_path)
+      .await
+      .map_err(|e| {
+         log::error!("Failed to get folding ranges: {}", e);
+         LspError::from(e)
+      })?;
+
+   Ok(response
+      .unwrap_or_default()
+      .into_iter()
+      .map(|range| FlatFoldingRange {
+         start_line: range.start_line,
+         end_line: range.end_line,
+         kind: range.kind.map(|kind| match kind {
+            FoldingRangeKind::Comment => "comment".to_string(),
+            FoldingRangeKind::Imports => "imports".to_string(),
+            FoldingRangeKind::Region => "region".to_string(),
+         }),
+      })
+      .collect())
+}
+
 #[tauri::command]
 pub async fn lsp_format_document(
    lsp_manager: State<'_, LspManager>,
    file_path: String,
+   options: Option<FormattingOptions>,
 ) -> LspResult<Vec<FlatTextEdit>> {
-   let response = lsp_manager.format_document(&file_path).await.map_err(|e| {
-      log::error!("Failed to format document with LSP: {}", e);
-      LspError::from(e)
-   })?;
+   let response = lsp_manager
+      .format_document(&file_path, options)
+      .await
+      .map_err(|e| {
+         log::error!("Failed to format document with LSP: {}", e);
+         LspError::from(e)
+      })?;
 
    Ok(response
       .unwrap_or_default()
@@ -435,6 +537,7 @@ pub async fn lsp_format_range(
    start_character: u32,
    end_line: u32,
    end_character: u32,
+   options: Option<FormattingOptions>,
 ) -> LspResult<Vec<FlatTextEdit>> {
    let response = lsp_manager
       .format_range(
@@ -443,6 +546,7 @@ pub async fn lsp_format_range(
          start_character,
          end_line,
          end_character,
+         options,
       )
       .await
       .map_err(|e| {
@@ -545,6 +649,23 @@ pub async fn lsp_get_workspace_symbols(
    Ok(flatten_workspace_symbol_response(responses))
 }
 
+/// Search symbols across every running server, merging and deduplicating the results.
+#[tauri::command]
+pub async fn lsp_workspace_symbols(
+   lsp_manager: State<'_, LspManager>,
+   query: String,
+) -> LspResult<Vec<FlatWorkspaceSymbol>> {
+   let responses = lsp_manager
+      .get_all_workspace_symbols(&query)
+      .await
+      .map_err(|e| {
+         log::error!("Failed to search workspace symbols: {}", e);
+         LspError::from(e)
+      })?;
+
+   Ok(flatten_workspace_symbol_response(responses))
+}
+
 #[tauri::command]
 pub async fn lsp_get_signature_help(
    lsp_manager: State<'_, LspManager>,
@@ -585,6 +706,45 @@ pub async fn lsp_get_references(
       })
 }
 
+#[tauri::command]
+pub async fn lsp_document_highlights(
+   lsp_manager: State<'_, LspManager>,
+   file_path: String,
+   line: u32,
+   character: u32,
+) -> LspResult<Vec<FlatDocumentHighlight>> {
+   let response = lsp_manager
+      .get_document_highlights(&file_path, line, character)
+      .await
+      .map_err(|e| {
+         log::error!("Failed to get document highlights: {}", e);
+         LspError::from(e)
+      })?;
+
+   Ok(response
+      .unwrap_or_default()
+      .into_iter()
+      .map(|highlight| FlatDocumentHighlight {
+         range: FlatTextEditRange {
+            start: FlatTextEditPosition {
+               line: highlight.range.start.line,
+               character: highlight.range.start.character,
+            },
+            end: FlatTextEditPosition {
+               line: highlight.range.end.line,
+               character: highlight.range.end.character,
+            },
+         },
+         kind: match highlight.kind {
+            Some(DocumentHighlightKind::READ) => "read",
+            Some(DocumentHighlightKind::WRITE) => "write",
+            _ => "text",
+         }
+         .to_string(),
+      })
+      .collect())
+}
+
 #[tauri::command]
 pub async fn lsp_rename(
    lsp_manager: State<'_, LspManager>,
@@ -618,6 +778,40 @@ pub async fn lsp_prepare_rename(
       })
 }
 
+/// Apply a workspace edit on disk. The frontend applies edits to open buffers itself and sends
+/// the rest here, such as the closed files and file operations of a project-wide rename.
+#[tauri::command]
+pub async fn lsp_apply_workspace_edit(
+   edit: WorkspaceEdit,
+   workspace_root: Option<String>,
+) -> LspResult<AppliedWorkspaceEdit> {
+   let workspace_root = workspace_root
+      .map(|root| require_path_under_home(&root))
+      .transpose()
+      .map_err(|e| LspError::from(anyhow::anyhow!(e)))?;
+   tauri::async_runtime::spawn_blocking(move || {
+      apply_workspace_edit(&edit, workspace_root.as_deref())
+   })
+   .await
+   .map_err(|e| anyhow::anyhow!("Workspace edit task failed: {}", e))?
+   .map_err(|e| {
+      log::error!("Failed to apply workspace edit: {:#}", e);
+      e.into()
+   })
+}
+
+/// Refresh a file's diagnostics on demand. `None` means the server only pushes diagnostics.
+#[tauri::command]
+pub async fn lsp_pull_diagnostics(
+   lsp_manager: State<'_, LspManager>,
+   file_path: String,
+) -> LspResult<Option<Vec<Diagnostic>>> {
+   lsp_manager.pull_diagnostics(&file_path).await.map_err(|e| {
+      log::error!("Failed to pull diagnostics: {}", e);
+      e.into()
+   })
+}
+
 #[tauri::command]
 pub fn lsp_document_open(
    lsp_manager: State<'_, LspManager>,
@@ -660,6 +854,26 @@ pub fn lsp_document_close(lsp_manager: State<'_, LspManager>, file_path: String)
       .map_err(Into::into)
 }
 
+#[tauri::command]
+pub fn lsp_get_server_logs(lsp_manager: State<'_, LspManager>, server_name: String) -> Vec<String> {
+   lsp_manager.get_server_logs(&server_name)
+}
+
+#[tauri::command]
+pub fn lsp_server_status(lsp_manager: State<'_, LspManager>) -> Vec<LspServerStatus> {
+   lsp_manager.get_server_status()
+}
+
+/// Capabilities of the running server for `file_path`, so the editor can hide features the
+/// server doesn't implement instead of sending requests that fail
+#[tauri::command]
+pub fn lsp_get_capabilities(
+   lsp_manager: State<'_, LspManager>,
+   file_path: String,
+) -> Option<LspCapabilities> {
+   lsp_manager.get_capabilities(&file_path)
+}
+
 #[tauri::command]
 pub fn lsp_is_language_supported(lsp_manager: State<'_, LspManager>, file_path: String) -> bool {
    lsp_manager.get_client_for_file(&file_path).is_some()
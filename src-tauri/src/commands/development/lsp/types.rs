@@ -113,3 +113,18 @@ pub struct FlatTextEdit {
    pub range: FlatTextEditRange,
    pub new_text: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatDocumentHighlight {
+   pub range: FlatTextEditRange,
+   pub kind: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatFoldingRange {
+   pub start_line: u32,
+   pub end_line: u32,
+   pub kind: Option<String>,
+}
@@ -5,6 +5,7 @@ pub mod docker;
 pub mod ide_recents;
 pub mod lsp;
 pub mod runtime;
+pub mod tasks;
 pub mod tools;
 
 pub use cli::*;
@@ -14,4 +15,5 @@ pub use docker::*;
 pub use ide_recents::*;
 pub use lsp::*;
 pub use runtime::*;
+pub use tasks::*;
 pub use tools::*;
@@ -13,7 +13,7 @@ fn managed_runtime_root(app_handle: &AppHandle) -> Result<PathBuf, String> {
 
 /// Ensure a runtime is available, downloading if necessary
 ///
-/// Supports: "bun", "node", "python", "go", "rust"
+/// Supports: "bun", "node", "python", "go", "rust", "zig"
 #[tauri::command]
 pub async fn ensure_runtime(app_handle: AppHandle, runtime_type: String) -> Result<String, String> {
    let rt = parse_runtime_type(&runtime_type)?;
@@ -46,11 +46,40 @@ pub async fn get_runtime_version(
       "bun" => Ok(BunRuntime::get_version(Some(&managed_root)).await),
       "node" => Ok(NodeRuntime::get_version(Some(&managed_root)).await),
       // For other runtimes, we don't track versions (system-provided)
-      "python" | "go" | "rust" => Ok(None),
+      "python" | "go" | "rust" | "zig" => Ok(None),
       _ => Err(format!("Unknown runtime type: {}", runtime_type)),
    }
 }
 
+/// Download a managed runtime, optionally pinned to a specific release such as "1.1.30"
+///
+/// Supports: "bun", "node"
+#[tauri::command]
+pub async fn install_runtime(
+   app_handle: AppHandle,
+   runtime_type: String,
+   version: Option<String>,
+) -> Result<String, String> {
+   let rt = parse_runtime_type(&runtime_type)?;
+   let managed_root = managed_runtime_root(&app_handle)?;
+   let path = RuntimeManager::install(Some(&managed_root), rt, version.as_deref())
+      .await
+      .map_err(|e| e.to_string())?;
+   Ok(path.to_string_lossy().into())
+}
+
+/// Remove an Athas-managed runtime so a broken install is downloaded again on next use.
+/// Returns whether a managed copy was removed; system runtimes are refused.
+#[tauri::command]
+pub async fn uninstall_runtime(
+   app_handle: AppHandle,
+   runtime_type: String,
+) -> Result<bool, String> {
+   let rt = parse_runtime_type(&runtime_type)?;
+   let managed_root = managed_runtime_root(&app_handle)?;
+   RuntimeManager::uninstall(Some(&managed_root), rt).map_err(|e| e.to_string())
+}
+
 /// Get a JavaScript runtime (prefers Bun, falls back to Node)
 #[tauri::command]
 pub async fn get_js_runtime(app_handle: AppHandle) -> Result<String, String> {
@@ -89,6 +118,10 @@ pub async fn get_all_runtime_statuses(
       "rust".to_string(),
       RuntimeManager::get_status(Some(&managed_root), RuntimeType::Rust).await,
    );
+   statuses.insert(
+      "zig".to_string(),
+      RuntimeManager::get_status(Some(&managed_root), RuntimeType::Zig).await,
+   );
 
    Ok(statuses)
 }
@@ -100,6 +133,7 @@ fn parse_runtime_type(s: &str) -> Result<RuntimeType, String> {
       "python" => Ok(RuntimeType::Python),
       "go" => Ok(RuntimeType::Go),
       "rust" => Ok(RuntimeType::Rust),
+      "zig" => Ok(RuntimeType::Zig),
       _ => Err(format!("Unknown runtime type: {}", s)),
    }
 }
@@ -0,0 +1,239 @@
+use crate::app_runtime::AthasRuntime;
+use athas_runtime::process::{
+   configure_background_command, configure_process_group, kill_process_tree,
+};
+use serde::Serialize;
+use std::{collections::HashMap, path::Path, process::Stdio, sync::Arc, time::Duration};
+use tauri::{Emitter, State, WebviewWindow};
+use tokio::{
+   io::{AsyncBufReadExt, AsyncRead, BufReader},
+   process::Command,
+   sync::{Mutex, oneshot},
+   task::JoinHandle,
+};
+use uuid::Uuid;
+
+type TaskWindow = WebviewWindow<AthasRuntime>;
+
+type CancelSender = oneshot::Sender<()>;
+
+/// How long output is still forwarded after the task exits. A daemon the task started can hold
+/// the pipes open indefinitely, so the readers are dropped after this.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Running project tasks keyed by task id, holding the sender that cancels each one. Dropping a
+/// sender cancels its task too, so closing the app stops every task it started.
+#[derive(Default)]
+pub struct ProjectTasks {
+   tasks: Arc<Mutex<HashMap<String, CancelSender>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTaskOutputEvent {
+   pub task_id: String,
+   pub stream: String,
+   pub line: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTaskExitEvent {
+   pub task_id: String,
+   pub code: Option<i32>,
+   pub cancelled: bool,
+   pub error: Option<String>,
+}
+
+/// Run a project script (npm, cargo, make, ...) in the workspace directory.
+///
+/// Output is streamed line by line on `task-output-{id}` and the exit code is reported on
+/// `task-exit-{id}`. Returns the task id, which can be passed to `cancel_task`.
+#[tauri::command]
+pub async fn run_project_task(
+   workspace: String,
+   command: String,
+   args: Option<Vec<String>>,
+   window: TaskWindow,
+   tasks: State<'_, ProjectTasks>,
+) -> Result<String, String> {
+   if command.trim().is_empty() {
+      return Err("Task command is required.".to_string());
+   }
+   if !Path::new(&workspace).is_dir() {
+      return Err(format!("Workspace directory not found: {}", workspace));
+   }
+
+   let mut std_command = std::process::Command::new(command.trim());
+   configure_background_command(&mut std_command);
+   configure_process_group(&mut std_command);
+   std_command
+      .args(args.unwrap_or_default())
+      .current_dir(&workspace)
+      .stdin(Stdio::null())
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped());
+
+   let mut task_command = Command::from(std_command);
+   task_command.kill_on_drop(true);
+
+   let task_id = Uuid::new_v4().to_string();
+   let task_id_for_task = task_id.clone();
+   let running = tasks.tasks.clone();
+
+   let (cancel_tx, cancel_rx) = oneshot::channel();
+
+   // Hold the lock until the sender is stored so a fast-exiting task can't remove itself first.
+   let mut guard = tasks.tasks.lock().await;
+   tokio::spawn(async move {
+      run_task(
+         window,
+         running,
+         task_id_for_task,
+         command,
+         task_command,
+         cancel_rx,
+      )
+      .await;
+   });
+   guard.insert(task_id.clone(), cancel_tx);
+
+   Ok(task_id)
+}
+
+/// Cancel a running project task, killing its process and everything it started. The task
+/// reports the cancellation on `task-exit-{id}`.
+#[tauri::command]
+pub async fn cancel_task(task_id: String, tasks: State<'_, ProjectTasks>) -> Result<(), String> {
+   if let Some(cancel) = tasks.tasks.lock().await.remove(&task_id) {
+      let _ = cancel.send(());
+   }
+
+   Ok(())
+}
+
+async fn run_task(
+   window: TaskWindow,
+   tasks: Arc<Mutex<HashMap<String, CancelSender>>>,
+   task_id: String,
+   command: String,
+   mut task_command: Command,
+   cancel: oneshot::Receiver<()>,
+) {
+   let mut child = match task_command.spawn() {
+      Ok(child) => child,
+      Err(error) => {
+         emit_task_exit(
+            &window,
+            &task_id,
+            None,
+            false,
+            Some(format!("Failed to start {}: {}", command, error)),
+         );
+         tasks.lock().await.remove(&task_id);
+         return;
+      }
+   };
+
+   let stdout_task = child
+      .stdout
+      .take()
+      .map(|stdout| spawn_output_reader(window.clone(), task_id.clone(), "stdout", stdout));
+   let stderr_task = child
+      .stderr
+      .take()
+      .map(|stderr| spawn_output_reader(window.clone(), task_id.clone(), "stderr", stderr));
+
+   let status = tokio::select! {
+      status = child.wait() => status,
+      _ = cancel => {
+         // Scripts like npm run their work in child processes, so the whole group is killed.
+         if let Some(pid) = child.id() {
+            let _ = tokio::task::spawn_blocking(move || kill_process_tree(pid)).await;
+         }
+         let _ = child.kill().await;
+         // A process that escaped the group may still hold the pipes open.
+         for reader in [stdout_task, stderr_task].into_iter().flatten() {
+            reader.abort();
+         }
+         emit_task_exit(&window, &task_id, None, true, None);
+         return;
+      }
+   };
+
+   // Let the readers drain what the process wrote before it exited.
+   let deadline = tokio::time::Instant::now() + OUTPUT_DRAIN_TIMEOUT;
+   for mut reader in [stdout_task, stderr_task].into_iter().flatten() {
+      if tokio::time::timeout_at(deadline, &mut reader)
+         .await
+         .is_err()
+      {
+         reader.abort();
+      }
+   }
+
+   match status {
+      Ok(status) => emit_task_exit(&window, &task_id, status.code(), false, None),
+      Err(error) => emit_task_exit(
+         &window,
+         &task_id,
+         None,
+         false,
+         Some(format!("Task failed: {}", error)),
+      ),
+   }
+
+   tasks.lock().await.remove(&task_id);
+}
+
+fn spawn_output_reader<R>(
+   window: TaskWindow,
+   task_id: String,
+   stream: &'static str,
+   reader: R,
+) -> JoinHandle<()>
+where
+   R: AsyncRead + Unpin + Send + 'static,
+{
+   tokio::spawn(async move {
+      let event = format!("task-output-{}", task_id);
+      let mut reader = BufReader::new(reader);
+      let mut buffer = Vec::new();
+      // Read raw bytes so non-UTF-8 output doesn't stop the pipe from being drained.
+      while let Ok(read) = reader.read_until(b'\n', &mut buffer).await {
+         if read == 0 {
+            break;
+         }
+         let line = String::from_utf8_lossy(&buffer)
+            .trim_end_matches(['\r', '\n'])
+            .to_string();
+         buffer.clear();
+         let _ = window.emit(
+            &event,
+            ProjectTaskOutputEvent {
+               task_id: task_id.clone(),
+               stream: stream.to_string(),
+               line,
+            },
+         );
+      }
+   })
+}
+
+fn emit_task_exit(
+   window: &TaskWindow,
+   task_id: &str,
+   code: Option<i32>,
+   cancelled: bool,
+   error: Option<String>,
+) {
+   let _ = window.emit(
+      &format!("task-exit-{}", task_id),
+      ProjectTaskExitEvent {
+         task_id: task_id.to_string(),
+         code,
+         cancelled,
+         error,
+      },
+   );
+}
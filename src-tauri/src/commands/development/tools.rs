@@ -1,14 +1,17 @@
 use crate::app_runtime::AppHandle;
 use athas_tooling::{
-   LanguageToolConfigSet, LanguageToolStatus, ToolInstaller, ToolRegistry, ToolStatus, ToolType,
+   LanguageToolConfigSet, LanguageToolStatus, ToolInstallState, ToolInstaller, ToolRegistry,
+   ToolStatus, ToolType, detect_language as detect_file_language,
 };
 use serde_json::Value;
+use std::{fs::File, io::Read, path::Path};
 #[cfg(debug_assertions)]
 use std::{
    fs::OpenOptions,
    io::Write,
    time::{SystemTime, UNIX_EPOCH},
 };
+use tauri::Manager;
 
 #[tauri::command]
 pub fn frontend_trace(level: String, scope: String, message: String, payload: Option<Value>) {
@@ -134,6 +137,16 @@ fn format_value(value: &Value) -> String {
    }
 }
 
+/// Reload user-defined tools from `tools.json` in the app config directory
+#[tauri::command]
+pub fn reload_tool_registry(app_handle: AppHandle) -> Result<usize, String> {
+   let config_dir = app_handle
+      .path()
+      .app_config_dir()
+      .map_err(|e| format!("Failed to resolve config directory: {}", e))?;
+   ToolRegistry::load_user_tools(&config_dir).map_err(Into::into)
+}
+
 /// Install all tools for a language
 #[tauri::command]
 pub async fn install_language_tools(
@@ -142,34 +155,11 @@ pub async fn install_language_tools(
    tools: Option<LanguageToolConfigSet>,
 ) -> Result<LanguageToolStatus, String> {
    let mut status = LanguageToolStatus::new(&language_id);
+   let mut results = ToolInstaller::install_all(&app_handle, &language_id, tools).await;
 
-   let Some(resolved_tools) = ToolRegistry::get_tools(&language_id, tools) else {
-      return Ok(status);
-   };
-
-   // Install LSP
-   if let Some(config) = resolved_tools.get(&ToolType::Lsp) {
-      status.lsp = Some(match ToolInstaller::install(&app_handle, config).await {
-         Ok(_) => ToolStatus::Installed,
-         Err(e) => ToolStatus::Failed(e.to_string()),
-      });
-   }
-
-   // Install formatter
-   if let Some(config) = resolved_tools.get(&ToolType::Formatter) {
-      status.formatter = Some(match ToolInstaller::install(&app_handle, config).await {
-         Ok(_) => ToolStatus::Installed,
-         Err(e) => ToolStatus::Failed(e.to_string()),
-      });
-   }
-
-   // Install linter
-   if let Some(config) = resolved_tools.get(&ToolType::Linter) {
-      status.linter = Some(match ToolInstaller::install(&app_handle, config).await {
-         Ok(_) => ToolStatus::Installed,
-         Err(e) => ToolStatus::Failed(e.to_string()),
-      });
-   }
+   status.lsp = results.remove(&ToolType::Lsp);
+   status.formatter = results.remove(&ToolType::Formatter);
+   status.linter = results.remove(&ToolType::Linter);
 
    Ok(status)
 }
@@ -203,53 +193,24 @@ pub async fn install_tool(
    }
 }
 
-/// Get the status of all tools for a language
+/// Get the install state of every tool configured for a language
 #[tauri::command]
 pub async fn get_language_tool_status(
    app_handle: AppHandle,
    language_id: String,
    tools: Option<LanguageToolConfigSet>,
-) -> Result<LanguageToolStatus, String> {
-   let mut status = LanguageToolStatus::new(&language_id);
-
+) -> Result<Vec<ToolInstallState>, String> {
    let Some(resolved_tools) = ToolRegistry::get_tools(&language_id, tools) else {
-      return Ok(status);
+      return Ok(Vec::new());
    };
 
-   // Check LSP
-   if let Some(config) = resolved_tools.get(&ToolType::Lsp) {
-      status.lsp = Some(
-         if ToolInstaller::is_installed(&app_handle, config).unwrap_or(false) {
-            ToolStatus::Installed
-         } else {
-            ToolStatus::NotInstalled
-         },
-      );
-   }
-
-   // Check formatter
-   if let Some(config) = resolved_tools.get(&ToolType::Formatter) {
-      status.formatter = Some(
-         if ToolInstaller::is_installed(&app_handle, config).unwrap_or(false) {
-            ToolStatus::Installed
-         } else {
-            ToolStatus::NotInstalled
-         },
-      );
-   }
+   let mut states: Vec<ToolInstallState> = resolved_tools
+      .iter()
+      .map(|(tool_type, config)| ToolInstaller::install_state(&app_handle, *tool_type, config))
+      .collect();
+   states.sort_by_key(|state| state.tool_type);
 
-   // Check linter
-   if let Some(config) = resolved_tools.get(&ToolType::Linter) {
-      status.linter = Some(
-         if ToolInstaller::is_installed(&app_handle, config).unwrap_or(false) {
-            ToolStatus::Installed
-         } else {
-            ToolStatus::NotInstalled
-         },
-      );
-   }
-
-   Ok(status)
+   Ok(states)
 }
 
 /// Get the path to a tool's binary
@@ -299,6 +260,23 @@ pub fn get_available_tools(
    }
 }
 
+/// Detect the tool registry language id of a file. When `first_bytes` is not given, the start of
+/// the file is read so shebang scripts without an extension are recognized.
+#[tauri::command]
+pub fn detect_language(path: String, first_bytes: Option<Vec<u8>>) -> Option<String> {
+   let path = Path::new(&path);
+   let first_bytes = first_bytes.unwrap_or_else(|| read_file_head(path));
+   detect_file_language(path, &first_bytes)
+}
+
+fn read_file_head(path: &Path) -> Vec<u8> {
+   let mut head = Vec::new();
+   if let Ok(file) = File::open(path) {
+      let _ = file.take(256).read_to_end(&mut head);
+   }
+   head
+}
+
 fn tool_type_str(t: &ToolType) -> &'static str {
    match t {
       ToolType::Lsp => "lsp",
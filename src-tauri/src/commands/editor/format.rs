@@ -1,12 +1,16 @@
 use super::exec_guard::{validate_exec_command, validate_exec_env};
-use athas_runtime::process::configure_background_command;
+use athas_lsp::{LspManager, workspace_edit::apply_text_edits};
+use athas_runtime::process::{apply_login_shell_path, configure_background_command};
+use athas_version_control::git::git_changed_line_ranges;
+use lsp_types::{FormattingOptions, Position, Range};
 use serde::{Deserialize, Serialize};
 use std::{
    collections::HashMap,
    io::Write,
+   path::Path,
    process::{Command, Stdio},
 };
-use tauri::command;
+use tauri::{State, command};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FormatRequest {
@@ -58,6 +62,92 @@ pub async fn format_code(request: FormatRequest) -> Result<FormatResponse, Strin
    }
 }
 
+/// Format only the lines that differ from HEAD, through the language server's range formatting,
+/// so saving a legacy file doesn't reformat code nobody touched. `content` is synced to the
+/// server as document `version` before formatting.
+#[command]
+pub async fn format_changed_ranges(
+   lsp_manager: State<'_, LspManager>,
+   repo_path: String,
+   file_path: String,
+   content: String,
+   version: i32,
+   options: Option<FormattingOptions>,
+) -> Result<FormatResponse, String> {
+   let relative_path = Path::new(&file_path)
+      .strip_prefix(&repo_path)
+      .map_err(|_| format!("{} is not inside {}", file_path, repo_path))?
+      .to_string_lossy()
+      .replace('\\', "/");
+   let changed = {
+      let content = content.clone();
+      tauri::async_runtime::spawn_blocking(move || {
+         git_changed_line_ranges(repo_path, relative_path, content)
+      })
+      .await
+      .map_err(|e| format!("Failed to compute changed lines: {}", e))??
+   };
+
+   if changed.is_empty() {
+      return Ok(FormatResponse {
+         formatted_content: content,
+         success: true,
+         error: None,
+      });
+   }
+
+   // Whole lines, ending before the line break so a range never reaches into the next line
+   let lines: Vec<&str> = content.split('\n').collect();
+   let ranges: Vec<Range> = changed
+      .iter()
+      .map(|range| {
+         let end_line = lines
+            .get(range.end_line as usize)
+            .copied()
+            .unwrap_or_default();
+         let end_character = end_line.trim_end_matches('\r').encode_utf16().count() as u32;
+         Range::new(
+            Position::new(range.start_line, 0),
+            Position::new(range.end_line, end_character),
+         )
+      })
+      .collect();
+
+   let edits = match lsp_manager
+      .format_ranges(&file_path, content.clone(), version, &ranges, options)
+      .await
+   {
+      Ok(Some(edits)) => edits,
+      Ok(None) => {
+         return Ok(FormatResponse {
+            formatted_content: content,
+            success: false,
+            error: Some("No range formatter available for this file".to_string()),
+         });
+      }
+      Err(e) => {
+         return Ok(FormatResponse {
+            formatted_content: content,
+            success: false,
+            error: Some(format!("Range formatting failed: {}", e)),
+         });
+      }
+   };
+
+   match apply_text_edits(&content, &edits) {
+      Ok(formatted) => Ok(FormatResponse {
+         formatted_content: formatted,
+         success: true,
+         error: None,
+      }),
+      Err(e) => Ok(FormatResponse {
+         formatted_content: content,
+         success: false,
+         error: Some(format!("Failed to apply formatting edits: {}", e)),
+      }),
+   }
+}
+
 /// Format code using generic formatter configuration from extension
 async fn format_with_generic(
    content: &str,
@@ -92,6 +182,7 @@ async fn format_with_generic(
    // Build command
    let mut cmd = Command::new(&command);
    configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
    cmd.args(&args);
 
    // Add environment variables if specified
@@ -230,6 +321,7 @@ async fn format_with_prettier(content: &str, language: &str) -> Result<FormatRes
 
    let mut cmd = Command::new("npx");
    configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
    cmd.args([
       "prettier",
       "--parser",
@@ -297,6 +389,7 @@ async fn format_with_prettier(content: &str, language: &str) -> Result<FormatRes
 async fn format_with_rustfmt(content: &str) -> Result<FormatResponse, String> {
    let mut cmd = Command::new("rustfmt");
    configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
    cmd.args(["--emit", "stdout"])
       .stdin(std::process::Stdio::piped())
       .stdout(std::process::Stdio::piped())
@@ -354,6 +447,7 @@ async fn format_with_rustfmt(content: &str) -> Result<FormatResponse, String> {
 async fn format_with_gofmt(content: &str) -> Result<FormatResponse, String> {
    let mut cmd = Command::new("gofmt");
    configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
    cmd.stdin(std::process::Stdio::piped())
       .stdout(std::process::Stdio::piped())
       .stderr(std::process::Stdio::piped());
@@ -1,12 +1,18 @@
 use super::exec_guard::{validate_exec_command, validate_exec_env};
-use athas_runtime::process::configure_background_command;
+use crate::app_runtime::AthasRuntime;
+use athas_runtime::process::{
+   apply_login_shell_path, configure_background_command, configure_process_group, kill_process_tree,
+};
 use serde::{Deserialize, Serialize};
 use std::{
    collections::HashMap,
-   io::Write,
+   io::{BufRead, BufReader, Read, Write},
+   path::{Path, PathBuf},
    process::{Command, Stdio},
+   sync::{Arc, Mutex},
+   thread,
 };
-use tauri::command;
+use tauri::{Emitter, State, command};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LintRequest {
@@ -47,6 +53,43 @@ pub struct LintResponse {
    pub error: Option<String>,
 }
 
+/// A diagnostic from a whole-project check, with the file it belongs to
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectDiagnostic {
+   pub file_path: String,
+   #[serde(flatten)]
+   pub diagnostic: Diagnostic,
+}
+
+/// Payload of the `cargo-check-progress` event
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CargoCheckProgress {
+   /// Crates checked so far, including ones cargo found up to date
+   pub checked_crates: usize,
+   /// Crate most recently finished
+   pub current_crate: Option<String>,
+   pub diagnostics: usize,
+}
+
+/// Running cargo checks, by the process id of each, keyed by cargo workspace root. Only one
+/// check runs per workspace, since a second cargo would just wait on the first one's build lock.
+#[derive(Clone, Default)]
+pub struct CargoChecks {
+   running: Arc<Mutex<HashMap<PathBuf, u32>>>,
+}
+
+impl CargoChecks {
+   /// Kill the check running in `workspace_root` along with its rustc processes, returning
+   /// whether there was one
+   fn stop(&self, workspace_root: &Path) -> bool {
+      let pid = self.running.lock().unwrap().remove(workspace_root);
+      if let Some(pid) = pid {
+         kill_process_tree(pid);
+      }
+      pid.is_some()
+   }
+}
+
 /// Lint code content using the specified linter
 ///
 /// The linter configuration must be provided by the frontend via the extension registry.
@@ -117,6 +160,7 @@ async fn lint_with_generic(
    // Build command
    let mut cmd = Command::new(&command);
    configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
    cmd.args(&args);
 
    // Add environment variables if specified
@@ -161,7 +205,7 @@ async fn lint_with_generic(
                let diagnostic_format = config.diagnostic_format.as_deref().unwrap_or("json");
 
                let diagnostics = match diagnostic_format {
-                  "json" | "lsp" => parse_json_diagnostics(&stdout),
+                  "json" | "lsp" => parse_json_diagnostics(&stdout, content),
                   "regex" => {
                      if let Some(pattern) = &config.diagnostic_pattern {
                         parse_regex_diagnostics(&stdout, pattern)
@@ -202,15 +246,398 @@ async fn lint_with_generic(
    }
 }
 
+/// Run `cargo check`, or `cargo clippy`, over every target in the workspace and collect the
+/// compiler diagnostics with their files. Emits `cargo-check-progress` as crates finish, since a
+/// cold build can take minutes. A check already running in the same cargo workspace is cancelled
+/// first.
+#[command]
+pub async fn run_cargo_check(
+   window: tauri::WebviewWindow<AthasRuntime>,
+   checks: State<'_, CargoChecks>,
+   workspace: String,
+   clippy: bool,
+) -> Result<Vec<ProjectDiagnostic>, String> {
+   let checks = checks.inner().clone();
+   tauri::async_runtime::spawn_blocking(move || {
+      cargo_check(&workspace, clippy, &checks, |progress| {
+         let _ = window.emit("cargo-check-progress", progress);
+      })
+   })
+   .await
+   .map_err(|e| format!("Cargo check task failed: {}", e))?
+}
+
+/// Cancel the cargo check running in the cargo workspace containing `workspace`, returning
+/// whether one was running. The cancelled `run_cargo_check` call fails.
+#[command]
+pub async fn cancel_cargo_check(
+   checks: State<'_, CargoChecks>,
+   workspace: String,
+) -> Result<bool, String> {
+   let checks = checks.inner().clone();
+   tauri::async_runtime::spawn_blocking(move || checks.stop(&cargo_workspace_root(&workspace)))
+      .await
+      .map_err(|e| format!("Cargo check task failed: {}", e))
+}
+
+fn cargo_check(
+   workspace: &str,
+   clippy: bool,
+   checks: &CargoChecks,
+   mut on_progress: impl FnMut(CargoCheckProgress),
+) -> Result<Vec<ProjectDiagnostic>, String> {
+   let subcommand = if clippy { "clippy" } else { "check" };
+   let workspace_root = cargo_workspace_root(workspace);
+
+   let mut cmd = Command::new("cargo");
+   configure_background_command(&mut cmd);
+   configure_process_group(&mut cmd);
+   apply_login_shell_path(&mut cmd);
+   cmd.args([
+      subcommand,
+      "--workspace",
+      "--all-targets",
+      "--message-format=json",
+   ])
+   .current_dir(workspace)
+   .stdin(Stdio::null())
+   .stdout(Stdio::piped())
+   .stderr(Stdio::piped());
+
+   // Replace the running check under the lock, so two new checks can't both start
+   let mut child = {
+      let mut running = checks.running.lock().unwrap();
+      if let Some(pid) = running.remove(&workspace_root) {
+         log::info!("Cancelling the running cargo check in {:?}", workspace_root);
+         kill_process_tree(pid);
+      }
+      let child = cmd
+         .spawn()
+         .map_err(|e| format!("Cargo not available: {}", e))?;
+      running.insert(workspace_root.clone(), child.id());
+      child
+   };
+   let pid = child.id();
+   let stdout = child
+      .stdout
+      .take()
+      .ok_or_else(|| "Failed to read cargo output".to_string())?;
+
+   // Cargo writes build progress to stderr. Drain it on the side so a full pipe can't stall the
+   // build, and keep it for the error message.
+   let stderr = child.stderr.take();
+   let stderr_reader = thread::spawn(move || {
+      let mut output = String::new();
+      if let Some(mut stderr) = stderr {
+         let _ = stderr.read_to_string(&mut output);
+      }
+      output
+   });
+
+   let mut collector = CargoCheckCollector::new(clippy, workspace_root.clone());
+   for line in BufReader::new(stdout).lines() {
+      let Ok(line) = line else {
+         break;
+      };
+      if collector.handle_line(&line) {
+         on_progress(collector.progress.clone());
+      }
+   }
+
+   let status = child
+      .wait()
+      .map_err(|e| format!("Failed to run cargo {}: {}", subcommand, e))?;
+   let stderr = stderr_reader.join().unwrap_or_default();
+
+   // A check that is no longer registered was stopped by a cancel or a newer check
+   let cancelled = {
+      let mut running = checks.running.lock().unwrap();
+      if running.get(&workspace_root) == Some(&pid) {
+         running.remove(&workspace_root);
+         false
+      } else {
+         true
+      }
+   };
+   if cancelled {
+      return Err(format!("cargo {} was cancelled", subcommand));
+   }
+
+   cargo_check_result(subcommand, status.success(), collector.diagnostics, &stderr)
+}
+
+/// Diagnostics and progress gathered from the JSON messages of a cargo check
+struct CargoCheckCollector {
+   clippy: bool,
+   workspace_root: PathBuf,
+   diagnostics: Vec<ProjectDiagnostic>,
+   progress: CargoCheckProgress,
+}
+
+impl CargoCheckCollector {
+   fn new(clippy: bool, workspace_root: PathBuf) -> Self {
+      Self {
+         clippy,
+         workspace_root,
+         diagnostics: Vec::new(),
+         progress: CargoCheckProgress::default(),
+      }
+   }
+
+   /// Take in one line of cargo output, returning whether the progress changed
+   fn handle_line(&mut self, line: &str) -> bool {
+      let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+         return false;
+      };
+
+      match message.get("reason").and_then(|r| r.as_str()) {
+         Some("compiler-artifact") => {
+            self.progress.checked_crates += 1;
+            self.progress.current_crate = message
+               .pointer("/target/name")
+               .and_then(|n| n.as_str())
+               .map(|n| n.to_string());
+            true
+         }
+         Some("compiler-message") => {
+            let Some(mut diagnostic) = message
+               .get("message")
+               .and_then(|msg| parse_cargo_project_diagnostic(msg, &self.workspace_root))
+            else {
+               return false;
+            };
+            if !self.clippy {
+               diagnostic.diagnostic.source = Some("rustc".to_string());
+            }
+            // Code shared by several targets, such as a library and its tests, is reported
+            // once per target
+            let duplicate = self.diagnostics.iter().any(|existing| {
+               existing.file_path == diagnostic.file_path
+                  && existing.diagnostic.line == diagnostic.diagnostic.line
+                  && existing.diagnostic.column == diagnostic.diagnostic.column
+                  && existing.diagnostic.message == diagnostic.diagnostic.message
+            });
+            if duplicate {
+               return false;
+            }
+            self.diagnostics.push(diagnostic);
+            self.progress.diagnostics = self.diagnostics.len();
+            true
+         }
+         _ => false,
+      }
+   }
+}
+
+/// Cargo exits with an error whenever the code has errors, which is a successful check. Only a
+/// failure that produced no diagnostics means cargo itself could not run.
+fn cargo_check_result(
+   subcommand: &str,
+   success: bool,
+   diagnostics: Vec<ProjectDiagnostic>,
+   stderr: &str,
+) -> Result<Vec<ProjectDiagnostic>, String> {
+   if !success && diagnostics.is_empty() {
+      return Err(format!("cargo {} failed: {}", subcommand, stderr.trim()));
+   }
+
+   Ok(diagnostics)
+}
+
+/// Root of the cargo workspace containing `workspace`, which compiler paths are relative to
+fn cargo_workspace_root(workspace: &str) -> PathBuf {
+   let mut cmd = Command::new("cargo");
+   configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
+   cmd.args(["locate-project", "--workspace", "--message-format", "plain"])
+      .current_dir(workspace);
+
+   cmd.output()
+      .ok()
+      .filter(|output| output.status.success())
+      .and_then(|output| {
+         let manifest = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+         manifest.parent().map(Path::to_path_buf)
+      })
+      .unwrap_or_else(|| PathBuf::from(workspace))
+}
+
+/// Parse a Cargo/Clippy JSON message along with the absolute path of its primary span's file
+fn parse_cargo_project_diagnostic(
+   msg: &serde_json::Value,
+   workspace_root: &Path,
+) -> Option<ProjectDiagnostic> {
+   let file_name = cargo_primary_span(msg)?.get("file_name")?.as_str()?;
+   let diagnostic = parse_cargo_diagnostic(msg)?;
+
+   Some(ProjectDiagnostic {
+      file_path: workspace_root.join(file_name).to_string_lossy().to_string(),
+      diagnostic,
+   })
+}
+
+/// Run golangci-lint over `package` (every package by default) in the Go module at `workspace`
+/// and collect its issues with their files
+#[command]
+pub async fn run_go_lint(
+   workspace: String,
+   package: Option<String>,
+) -> Result<Vec<ProjectDiagnostic>, String> {
+   tauri::async_runtime::spawn_blocking(move || go_lint(&workspace, package.as_deref()))
+      .await
+      .map_err(|e| format!("Go lint task failed: {}", e))?
+}
+
+fn go_lint(workspace: &str, package: Option<&str>) -> Result<Vec<ProjectDiagnostic>, String> {
+   let package = package
+      .map(str::trim)
+      .filter(|package| !package.is_empty())
+      .unwrap_or("./...");
+
+   // golangci-lint 2 replaced `--out-format` with per-format output flags
+   let json_output_args: &[&str] = if golangci_lint_major_version() >= Some(2) {
+      &["--output.json.path", "stdout", "--show-stats=false"]
+   } else {
+      &["--out-format", "json"]
+   };
+
+   let mut cmd = Command::new("golangci-lint");
+   configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
+   // `--` keeps a package that starts with a dash from being read as a flag
+   cmd.arg("run")
+      .args(json_output_args)
+      .arg("--")
+      .arg(package)
+      .current_dir(workspace)
+      .stdin(Stdio::null());
+
+   let output = cmd
+      .output()
+      .map_err(|e| format!("golangci-lint not available: {}", e))?;
+   let stdout = String::from_utf8_lossy(&output.stdout);
+
+   // Exit code 1 means issues were found. Any other failure, or output without a report, means
+   // the lint itself could not run.
+   match parse_golangci_lint_report(&stdout, Path::new(workspace)) {
+      Some(diagnostics) if output.status.success() || output.status.code() == Some(1) => {
+         Ok(diagnostics)
+      }
+      _ => Err(format!(
+         "golangci-lint failed: {}",
+         String::from_utf8_lossy(&output.stderr).trim()
+      )),
+   }
+}
+
+fn golangci_lint_major_version() -> Option<u32> {
+   let mut cmd = Command::new("golangci-lint");
+   configure_background_command(&mut cmd);
+   apply_login_shell_path(&mut cmd);
+   let output = cmd.arg("--version").output().ok()?;
+
+   // "golangci-lint has version v1.64.8 built with ..." or "... has version 2.1.6 ..."
+   let version = String::from_utf8_lossy(&output.stdout)
+      .split_whitespace()
+      .skip_while(|word| *word != "version")
+      .nth(1)?
+      .trim_start_matches('v')
+      .to_string();
+   version.split('.').next()?.parse().ok()
+}
+
+/// Parse the `Issues` of a golangci-lint JSON report. The report is a single line, which may be
+/// followed by plain-text summaries.
+fn parse_golangci_lint_report(output: &str, workspace: &Path) -> Option<Vec<ProjectDiagnostic>> {
+   let report = output
+      .lines()
+      .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+      .find(|value| value.is_object())?;
+   let issues = match report.get("Issues") {
+      Some(serde_json::Value::Array(issues)) => issues.as_slice(),
+      // Older releases write null when nothing was found
+      Some(serde_json::Value::Null) | None => &[],
+      Some(_) => return None,
+   };
+
+   Some(
+      issues
+         .iter()
+         .filter_map(|issue| parse_golangci_lint_issue(issue, workspace))
+         .collect(),
+   )
+}
+
+/// Parse individual golangci-lint issue
+fn parse_golangci_lint_issue(
+   issue: &serde_json::Value,
+   workspace: &Path,
+) -> Option<ProjectDiagnostic> {
+   let position = issue.get("Pos")?;
+   let file_name = position.get("Filename")?.as_str()?;
+   let line = position.get("Line")?.as_u64()? as u32;
+   // Linters that only know the line report column 0
+   let column = position
+      .get("Column")
+      .and_then(|c| c.as_u64())
+      .map(|c| c.max(1) as u32)
+      .unwrap_or(1);
+   let message = issue.get("Text")?.as_str()?.to_string();
+
+   let end_line = issue
+      .get("LineRange")
+      .and_then(|range| range.get("To"))
+      .and_then(|to| to.as_u64())
+      .map(|to| to as u32);
+
+   let severity = match issue.get("Severity").and_then(|s| s.as_str()) {
+      Some("error") => "error",
+      Some("info") => "info",
+      Some("hint") => "hint",
+      _ => "warning",
+   }
+   .to_string();
+
+   let code = issue
+      .get("FromLinter")
+      .and_then(|l| l.as_str())
+      .map(|s| s.to_string());
+
+   Some(ProjectDiagnostic {
+      file_path: workspace.join(file_name).to_string_lossy().to_string(),
+      diagnostic: Diagnostic {
+         line,
+         column,
+         end_line,
+         end_column: None,
+         severity,
+         message,
+         code,
+         source: Some("golangci-lint".to_string()),
+      },
+   })
+}
+
 /// Parse JSON diagnostics from various linters
 ///
 /// Supports multiple formats:
 /// - ESLint/TSLint format (array with "messages")
 /// - Clippy/Cargo format (single message or array of messages)
+/// - Biome format (object with "diagnostics")
 /// - Generic LSP diagnostic format
-fn parse_json_diagnostics(output: &str) -> Vec<Diagnostic> {
+fn parse_json_diagnostics(output: &str, content: &str) -> Vec<Diagnostic> {
    let mut diagnostics = vec![];
 
+   // Biome format: report object with "diagnostics"
+   if let Ok(json_obj) = serde_json::from_str::<serde_json::Value>(output)
+      && let Some(items) = json_obj.get("diagnostics").and_then(|d| d.as_array())
+   {
+      return items
+         .iter()
+         .filter_map(|item| parse_biome_diagnostic(item, content))
+         .collect();
+   }
+
    // Try to parse as ESLint format (array with "messages")
    if let Ok(json_array) = serde_json::from_str::<Vec<serde_json::Value>>(output) {
       for item in json_array {
@@ -278,19 +705,92 @@ fn parse_eslint_diagnostic(msg: &serde_json::Value) -> Option<Diagnostic> {
    })
 }
 
+/// Parse individual Biome diagnostic
+///
+/// Locations are either `start`/`end` line and column objects or a byte `span` into the linted
+/// content.
+fn parse_biome_diagnostic(item: &serde_json::Value, content: &str) -> Option<Diagnostic> {
+   let message = item
+      .get("description")
+      .and_then(|d| d.as_str())
+      .map(|d| d.to_string())
+      .or_else(|| {
+         let parts = item.get("message")?.as_array()?;
+         Some(
+            parts
+               .iter()
+               .filter_map(|part| part.get("content").and_then(|c| c.as_str()))
+               .collect::<String>(),
+         )
+      })?;
+
+   let severity = match item.get("severity").and_then(|s| s.as_str()) {
+      Some("error" | "fatal") => "error",
+      Some("warning") => "warning",
+      Some("hint") => "hint",
+      _ => "info",
+   }
+   .to_string();
+
+   let location = item.get("location")?;
+   let position = |key: &str| -> Option<(u32, u32)> {
+      let point = location.get(key)?;
+      Some((
+         point.get("line")?.as_u64()? as u32,
+         point.get("column")?.as_u64()? as u32,
+      ))
+   };
+
+   let (start, end) = if let Some(start) = position("start") {
+      (start, position("end"))
+   } else {
+      let span = location.get("span")?.as_array()?;
+      let start = span.first()?.as_u64()? as usize;
+      let end = span.get(1).and_then(|e| e.as_u64()).map(|e| e as usize);
+      (
+         offset_to_line_column(content, start),
+         end.map(|end| offset_to_line_column(content, end)),
+      )
+   };
+
+   let code = item
+      .get("category")
+      .and_then(|c| c.as_str())
+      .map(|s| s.to_string());
+
+   Some(Diagnostic {
+      line: start.0,
+      column: start.1,
+      end_line: end.map(|(line, _)| line),
+      end_column: end.map(|(_, column)| column),
+      severity,
+      message,
+      code,
+      source: Some("biome".to_string()),
+   })
+}
+
+/// Convert a byte offset into a 1-based line and column. Columns count UTF-16 code units like
+/// the editor and ESLint do.
+fn offset_to_line_column(content: &str, offset: usize) -> (u32, u32) {
+   let mut offset = offset.min(content.len());
+   while !content.is_char_boundary(offset) {
+      offset -= 1;
+   }
+   let before = &content[..offset];
+   let line = before.matches('\n').count() as u32 + 1;
+   let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+   let column = before[line_start..].encode_utf16().count() as u32 + 1;
+   (line, column)
+}
+
 /// Parse individual Cargo/Clippy JSON message
 fn parse_cargo_diagnostic(msg: &serde_json::Value) -> Option<Diagnostic> {
    // Cargo format has "message", "level", and "spans"
    let message_text = msg.get("message")?.as_str()?.to_string();
    let level = msg.get("level")?.as_str()?;
 
-   // Get the first span (primary location)
-   let spans = msg.get("spans")?.as_array()?;
-   if spans.is_empty() {
-      return None;
-   }
-
-   let span = &spans[0];
+   let span = cargo_primary_span(msg)?;
    let line = span.get("line_start")?.as_u64()? as u32;
    let column = span.get("column_start")?.as_u64()? as u32;
    let end_line = span
@@ -330,6 +830,15 @@ fn parse_cargo_diagnostic(msg: &serde_json::Value) -> Option<Diagnostic> {
    })
 }
 
+/// The span a Cargo/Clippy message points at, falling back to the first one
+fn cargo_primary_span(msg: &serde_json::Value) -> Option<&serde_json::Value> {
+   let spans = msg.get("spans")?.as_array()?;
+   spans
+      .iter()
+      .find(|span| span.get("is_primary").and_then(|p| p.as_bool()) == Some(true))
+      .or_else(|| spans.first())
+}
+
 /// Parse diagnostics using regex pattern
 ///
 /// Pattern should contain named capture groups:
@@ -452,3 +961,227 @@ fn substitute_variables(
 
    result
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use serde_json::json;
+
+   fn compiler_message(target: &str, spans: serde_json::Value) -> String {
+      json!({
+         "reason": "compiler-message",
+         "target": { "name": target },
+         "message": {
+            "message": "unused variable: `count`",
+            "level": "warning",
+            "code": { "code": "unused_variables" },
+            "spans": spans,
+         },
+      })
+      .to_string()
+   }
+
+   fn span(file_name: &str, line: u32, column: u32, is_primary: bool) -> serde_json::Value {
+      json!({
+         "file_name": file_name,
+         "line_start": line,
+         "line_end": line,
+         "column_start": column,
+         "column_end": column + 5,
+         "is_primary": is_primary,
+      })
+   }
+
+   #[test]
+   fn uses_the_primary_span_of_cargo_messages() {
+      let mut collector = CargoCheckCollector::new(false, PathBuf::from("/work"));
+      let line = compiler_message(
+         "app",
+         json!([
+            span("src/macros.rs", 3, 1, false),
+            span("crates/app/src/lib.rs", 12, 9, true),
+         ]),
+      );
+
+      assert!(collector.handle_line(&line));
+      let diagnostic = &collector.diagnostics[0];
+      assert_eq!(
+         PathBuf::from(&diagnostic.file_path),
+         Path::new("/work").join("crates/app/src/lib.rs")
+      );
+      assert_eq!(
+         (diagnostic.diagnostic.line, diagnostic.diagnostic.column),
+         (12, 9)
+      );
+      assert_eq!(diagnostic.diagnostic.source.as_deref(), Some("rustc"));
+      assert_eq!(
+         diagnostic.diagnostic.code.as_deref(),
+         Some("unused_variables")
+      );
+   }
+
+   #[test]
+   fn reports_diagnostics_shared_by_targets_once() {
+      let mut collector = CargoCheckCollector::new(true, PathBuf::from("/work"));
+      let spans = json!([span("src/lib.rs", 4, 5, true)]);
+      let artifact = json!({ "reason": "compiler-artifact", "target": { "name": "app" } });
+
+      assert!(collector.handle_line(&compiler_message("app", spans.clone())));
+      assert!(!collector.handle_line(&compiler_message("app-tests", spans)));
+      assert!(collector.handle_line(&artifact.to_string()));
+      assert!(!collector.handle_line("   Compiling app v0.1.0"));
+
+      assert_eq!(collector.diagnostics.len(), 1);
+      assert_eq!(
+         collector.diagnostics[0].diagnostic.source.as_deref(),
+         Some("clippy")
+      );
+      assert_eq!(collector.progress.checked_crates, 1);
+      assert_eq!(collector.progress.current_crate.as_deref(), Some("app"));
+      assert_eq!(collector.progress.diagnostics, 1);
+   }
+
+   #[test]
+   fn treats_a_failed_run_with_diagnostics_as_a_check() {
+      let mut collector = CargoCheckCollector::new(false, PathBuf::from("/work"));
+      collector.handle_line(&compiler_message(
+         "app",
+         json!([span("src/lib.rs", 1, 1, true)]),
+      ));
+
+      let diagnostics = cargo_check_result("check", false, collector.diagnostics, "")
+         .expect("diagnostics from a failing build");
+      assert_eq!(diagnostics.len(), 1);
+
+      let error = cargo_check_result(
+         "check",
+         false,
+         Vec::new(),
+         "error: could not find Cargo.toml\n",
+      )
+      .expect_err("cargo failed to run");
+      assert_eq!(
+         error,
+         "cargo check failed: error: could not find Cargo.toml"
+      );
+   }
+
+   #[test]
+   fn parses_golangci_lint_1_reports_without_issues() {
+      let output = "{\"Issues\":null,\"Report\":{\"Linters\":[]}}\n";
+
+      let diagnostics = parse_golangci_lint_report(output, Path::new("/work")).expect("report");
+      assert!(diagnostics.is_empty());
+      assert!(
+         parse_golangci_lint_report("level=error msg=\"timeout\"", Path::new("/work")).is_none()
+      );
+   }
+
+   #[test]
+   fn parses_golangci_lint_2_reports_followed_by_a_summary() {
+      let report = json!({
+         "Issues": [{
+            "FromLinter": "errcheck",
+            "Text": "Error return value of `f.Close` is not checked",
+            "Severity": "",
+            "Pos": { "Filename": "cmd/main.go", "Line": 14, "Column": 0 },
+            "LineRange": { "From": 14, "To": 15 },
+         }],
+      });
+      let output = format!("{}\n1 issues:\n* errcheck: 1\n", report);
+
+      let diagnostics = parse_golangci_lint_report(&output, Path::new("/work")).expect("report");
+      assert_eq!(diagnostics.len(), 1);
+      let diagnostic = &diagnostics[0];
+      assert_eq!(
+         PathBuf::from(&diagnostic.file_path),
+         Path::new("/work").join("cmd/main.go")
+      );
+      assert_eq!(
+         (diagnostic.diagnostic.line, diagnostic.diagnostic.column),
+         (14, 1)
+      );
+      assert_eq!(diagnostic.diagnostic.end_line, Some(15));
+      assert_eq!(diagnostic.diagnostic.severity, "warning");
+      assert_eq!(diagnostic.diagnostic.code.as_deref(), Some("errcheck"));
+   }
+
+   #[test]
+   fn keeps_golangci_lint_severity_and_skips_issues_without_a_position() {
+      let workspace = Path::new("/work");
+      let issue = json!({
+         "FromLinter": "govet",
+         "Text": "printf: wrong verb",
+         "Severity": "error",
+         "Pos": { "Filename": "main.go", "Line": 3, "Column": 7 },
+      });
+
+      let diagnostic = parse_golangci_lint_issue(&issue, workspace).expect("issue");
+      assert_eq!(diagnostic.diagnostic.severity, "error");
+      assert_eq!(diagnostic.diagnostic.column, 7);
+      assert!(parse_golangci_lint_issue(&json!({ "Text": "no position" }), workspace).is_none());
+   }
+
+   #[test]
+   fn parses_biome_diagnostics_with_line_and_column_locations() {
+      let item = json!({
+         "category": "lint/suspicious/noDebugger",
+         "severity": "error",
+         "message": [{ "content": "This is an unexpected use of the " }, { "content": "debugger" }],
+         "location": {
+            "start": { "line": 2, "column": 3 },
+            "end": { "line": 2, "column": 12 },
+         },
+      });
+
+      let diagnostic = parse_biome_diagnostic(&item, "").expect("diagnostic");
+      assert_eq!(
+         diagnostic.message,
+         "This is an unexpected use of the debugger"
+      );
+      assert_eq!((diagnostic.line, diagnostic.column), (2, 3));
+      assert_eq!(
+         (diagnostic.end_line, diagnostic.end_column),
+         (Some(2), Some(12))
+      );
+      assert_eq!(diagnostic.severity, "error");
+      assert_eq!(
+         diagnostic.code.as_deref(),
+         Some("lint/suspicious/noDebugger")
+      );
+      assert!(parse_biome_diagnostic(&json!({ "description": "no location" }), "").is_none());
+   }
+
+   #[test]
+   fn maps_biome_byte_spans_onto_the_linted_content() {
+      let content = "let a = 1;\nlet é = \"日本\"; debugger;\n";
+      let start = content.find("debugger").unwrap();
+      let item = json!({
+         "description": "This is an unexpected use of the debugger statement.",
+         "severity": "warning",
+         "location": { "span": [start, start + "debugger;".len()] },
+      });
+
+      let diagnostic = parse_biome_diagnostic(&item, content).expect("diagnostic");
+      assert_eq!((diagnostic.line, diagnostic.column), (2, 15));
+      assert_eq!(
+         (diagnostic.end_line, diagnostic.end_column),
+         (Some(2), Some(24))
+      );
+      assert_eq!(diagnostic.severity, "warning");
+   }
+
+   #[test]
+   fn converts_byte_offsets_to_character_columns() {
+      let content = "ab\nçd😀e\n";
+
+      assert_eq!(offset_to_line_column(content, 0), (1, 1));
+      assert_eq!(offset_to_line_column(content, 3), (2, 1));
+      // `ç` is two bytes and one UTF-16 unit, the emoji four bytes and two units
+      assert_eq!(offset_to_line_column(content, 6), (2, 3));
+      assert_eq!(offset_to_line_column(content, 10), (2, 5));
+      // Offsets inside a character or past the end are clamped
+      assert_eq!(offset_to_line_column(content, 8), (2, 3));
+      assert_eq!(offset_to_line_column(content, 100), (3, 1));
+   }
+}
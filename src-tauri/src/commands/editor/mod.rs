@@ -3,10 +3,12 @@ mod exec_guard;
 pub mod format;
 pub mod lint;
 pub mod notebook;
+pub mod position;
 pub mod search;
 
 pub use editorconfig::*;
 pub use format::*;
 pub use lint::*;
 pub use notebook::*;
+pub use position::*;
 pub use search::*;
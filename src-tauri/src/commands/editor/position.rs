@@ -0,0 +1,37 @@
+use athas_lsp::position::{self, PositionEncoding};
+use lsp_types::Position;
+use std::fs;
+
+fn read_text(file_path: &str) -> Result<String, String> {
+   fs::read_to_string(file_path).map_err(|e| format!("Failed to read {}: {}", file_path, e))
+}
+
+/// Byte offset of an LSP position in a file. `encoding` defaults to UTF-16, which is what
+/// language servers use unless they negotiate otherwise.
+#[tauri::command]
+pub fn resolve_position(
+   file_path: String,
+   line: u32,
+   character: u32,
+   encoding: Option<PositionEncoding>,
+) -> Result<usize, String> {
+   let text = read_text(&file_path)?;
+   position::position_to_offset(
+      &text,
+      Position::new(line, character),
+      encoding.unwrap_or_default(),
+   )
+   .ok_or_else(|| format!("Line {} is past the end of {}", line, file_path))
+}
+
+/// LSP position of a byte offset in a file, the inverse of `resolve_position`.
+#[tauri::command]
+pub fn offset_to_position(
+   file_path: String,
+   offset: usize,
+   encoding: Option<PositionEncoding>,
+) -> Result<Position, String> {
+   let text = read_text(&file_path)?;
+   position::offset_to_position(&text, offset, encoding.unwrap_or_default())
+      .ok_or_else(|| format!("Offset {} is past the end of {}", offset, file_path))
+}
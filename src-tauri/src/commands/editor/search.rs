@@ -1,10 +1,22 @@
 use crate::{
    app_runtime::AppHandle,
-   commands::fuzzy::{FffSearchState, local_workspace_paths},
+   commands::{
+      fuzzy::{FffSearchState, local_workspace_paths},
+      project::path_guard::require_path_under_home,
+   },
 };
-use athas_fff_search::{FffGrepOptions, GrepMode};
+use athas_fff_search::{FffGrepMatch, FffGrepOptions, GrepMode};
+use athas_project::{FileReplaceResult, ReplaceOptions};
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use std::{
+   collections::HashMap,
+   path::PathBuf,
+   sync::{
+      Arc, Mutex,
+      atomic::{AtomicBool, Ordering},
+   },
+};
+use tauri::{Emitter, Manager, State};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SearchMatchRange {
@@ -23,7 +35,7 @@ pub struct SearchMatch {
    pub context_after: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FileSearchResult {
    pub file_path: String,
    pub matches: Vec<SearchMatch>,
@@ -49,17 +61,52 @@ pub struct SearchFilesRequest {
    pub root_paths: Vec<String>,
    pub query: String,
    pub case_sensitive: Option<bool>,
+   /// Match case only when the query contains an uppercase letter. Overrides `case_sensitive`.
+   pub smart_case: Option<bool>,
    pub whole_word: Option<bool>,
    pub use_regex: Option<bool>,
+   /// Match the query literally even when `use_regex` is set.
+   pub fixed_string: Option<bool>,
    pub max_results: Option<usize>,
    pub file_offset: Option<usize>,
    pub context_lines: Option<usize>,
 }
 
+/// A page of results from a streaming search, emitted as `search-result-{search_id}`.
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchResultBatch {
+   pub search_id: String,
+   pub results: Vec<FileSearchResult>,
+}
+
+/// Emitted as `search-complete` once a streaming search finishes, fails or is cancelled.
+#[derive(Debug, Serialize, Clone)]
+pub struct SearchComplete {
+   pub search_id: String,
+   pub searched_files: usize,
+   pub searchable_files: usize,
+   pub files_with_matches: usize,
+   pub total_matches: usize,
+   pub is_indexing: bool,
+   pub cancelled: bool,
+   pub regex_fallback_error: Option<String>,
+   pub error: Option<String>,
+}
+
+/// Cancellation flags for the streaming searches that are still running.
+#[derive(Default)]
+pub struct SearchCancellations {
+   flags: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
 fn build_fff_grep_pattern(request: &SearchFilesRequest) -> (String, GrepMode) {
-   let case_sensitive = request.case_sensitive.unwrap_or(false);
    let whole_word = request.whole_word.unwrap_or(false);
-   let use_regex = request.use_regex.unwrap_or(false);
+   let use_regex = request.use_regex.unwrap_or(false) && !request.fixed_string.unwrap_or(false);
+   let case_sensitive = if request.smart_case.unwrap_or(false) {
+      has_uppercase_literal(&request.query, use_regex)
+   } else {
+      request.case_sensitive.unwrap_or(false)
+   };
 
    let base_pattern = if use_regex {
       request.query.clone()
@@ -88,6 +135,20 @@ fn build_fff_grep_pattern(request: &SearchFilesRequest) -> (String, GrepMode) {
    (final_pattern, mode)
 }
 
+/// Whether the query has an uppercase letter that is matched literally. In a regex, letters
+/// that follow a backslash are escapes like `\W` or `\S` and do not count, as in ripgrep.
+fn has_uppercase_literal(query: &str, is_regex: bool) -> bool {
+   let mut chars = query.chars();
+   while let Some(c) = chars.next() {
+      if is_regex && c == '\\' {
+         chars.next();
+      } else if c.is_uppercase() {
+         return true;
+      }
+   }
+   false
+}
+
 fn empty_search_response(is_indexing: bool, indexed_files: usize) -> SearchFilesResponse {
    SearchFilesResponse {
       results: Vec::new(),
@@ -120,52 +181,18 @@ fn byte_range_to_char_range(text: &str, start: usize, end: usize) -> (usize, usi
    (char_start, char_end.max(char_start + 1))
 }
 
-#[tauri::command]
-pub fn search_files_content(
-   app: AppHandle,
-   state: State<'_, FffSearchState>,
-   request: SearchFilesRequest,
-) -> Result<SearchFilesResponse, String> {
-   let root_paths = local_workspace_paths(request.root_paths.clone());
-   if request.query.trim().is_empty() || root_paths.is_empty() {
-      return Ok(empty_search_response(false, 0));
-   }
-
-   state.ensure_workspaces(&app, &root_paths)?;
-   let fff = state.get_or_init(&app)?;
-
-   let (pattern, mode) = build_fff_grep_pattern(&request);
-   let context_lines = request.context_lines.unwrap_or(0).min(10);
-   let grep_result = fff
-      .grep(
-         root_paths.iter().map(std::path::PathBuf::as_path),
-         &FffGrepOptions {
-            pattern,
-            mode,
-            file_offset: request.file_offset.unwrap_or(0),
-            page_limit: request.max_results.unwrap_or(100).max(1),
-            time_budget_ms: 120,
-            before_context: context_lines,
-            after_context: context_lines,
-         },
-      )
-      .map_err(|error| format!("fff grep: {error}"))?;
-
-   if grep_result.is_indexing {
-      return Ok(empty_search_response(true, grep_result.indexed_files));
-   }
-
+/// Group line matches by file, keeping the order in which files were first matched.
+fn group_grep_matches(matches: Vec<FffGrepMatch>, query: &str) -> Vec<FileSearchResult> {
    let mut grouped_results: Vec<FileSearchResult> = Vec::new();
-   let mut file_index_map: std::collections::HashMap<String, usize> =
-      std::collections::HashMap::new();
+   let mut file_index_map: HashMap<String, usize> = HashMap::new();
 
-   for grep_match in grep_result.matches {
+   for grep_match in matches {
       let line_content = grep_match.line_content;
       let start_end_bytes = grep_match
          .match_byte_offsets
          .first()
          .map(|(start, end)| (*start as usize, *end as usize))
-         .unwrap_or((grep_match.column, grep_match.column + request.query.len()));
+         .unwrap_or((grep_match.column, grep_match.column + query.len()));
       let start_end = byte_range_to_char_range(&line_content, start_end_bytes.0, start_end_bytes.1);
       let match_ranges = grep_match
          .match_byte_offsets
@@ -205,6 +232,46 @@ pub fn search_files_content(
       grouped.total_matches += 1;
    }
 
+   grouped_results
+}
+
+#[tauri::command]
+pub fn search_files_content(
+   app: AppHandle,
+   state: State<'_, FffSearchState>,
+   request: SearchFilesRequest,
+) -> Result<SearchFilesResponse, String> {
+   let root_paths = local_workspace_paths(request.root_paths.clone());
+   if request.query.trim().is_empty() || root_paths.is_empty() {
+      return Ok(empty_search_response(false, 0));
+   }
+
+   state.ensure_workspaces(&app, &root_paths)?;
+   let fff = state.get_or_init(&app)?;
+
+   let (pattern, mode) = build_fff_grep_pattern(&request);
+   let context_lines = request.context_lines.unwrap_or(0).min(10);
+   let grep_result = fff
+      .grep(
+         root_paths.iter().map(PathBuf::as_path),
+         &FffGrepOptions {
+            pattern,
+            mode,
+            file_offset: request.file_offset.unwrap_or(0),
+            page_limit: request.max_results.unwrap_or(100).max(1),
+            time_budget_ms: 120,
+            before_context: context_lines,
+            after_context: context_lines,
+         },
+      )
+      .map_err(|error| format!("fff grep: {error}"))?;
+
+   if grep_result.is_indexing {
+      return Ok(empty_search_response(true, grep_result.indexed_files));
+   }
+
+   let grouped_results = group_grep_matches(grep_result.matches, &request.query);
+
    Ok(SearchFilesResponse {
       results: grouped_results,
       total_files: grep_result.total_files,
@@ -219,6 +286,177 @@ pub fn search_files_content(
    })
 }
 
+/// Search like `search_files_content`, but page through the whole workspace in the background.
+/// Each page of matches is emitted as `search-result-{search_id}` as soon as it is found, and
+/// `search-complete` reports the totals at the end. `max_results` sets the page size.
+#[tauri::command]
+pub fn search_files_stream(
+   app: AppHandle,
+   cancellations: State<'_, SearchCancellations>,
+   search_id: String,
+   request: SearchFilesRequest,
+) -> Result<(), String> {
+   let cancelled = Arc::new(AtomicBool::new(false));
+   cancellations
+      .flags
+      .lock()
+      .map_err(|e| format!("Failed to lock search cancellations: {}", e))?
+      .insert(search_id.clone(), cancelled.clone());
+
+   tauri::async_runtime::spawn_blocking(move || {
+      let complete = run_streaming_search(&app, &search_id, &request, &cancelled);
+      if let Ok(mut flags) = app.state::<SearchCancellations>().flags.lock() {
+         flags.remove(&search_id);
+      }
+      let _ = app.emit("search-complete", complete);
+   });
+
+   Ok(())
+}
+
+fn run_streaming_search(
+   app: &AppHandle,
+   search_id: &str,
+   request: &SearchFilesRequest,
+   cancelled: &AtomicBool,
+) -> SearchComplete {
+   let mut complete = SearchComplete {
+      search_id: search_id.to_string(),
+      searched_files: 0,
+      searchable_files: 0,
+      files_with_matches: 0,
+      total_matches: 0,
+      is_indexing: false,
+      cancelled: false,
+      regex_fallback_error: None,
+      error: None,
+   };
+
+   let root_paths = local_workspace_paths(request.root_paths.clone());
+   if request.query.trim().is_empty() || root_paths.is_empty() {
+      return complete;
+   }
+
+   let state = app.state::<FffSearchState>();
+   let fff = match state
+      .ensure_workspaces(app, &root_paths)
+      .and_then(|_| state.get_or_init(app))
+   {
+      Ok(fff) => fff,
+      Err(error) => {
+         complete.error = Some(error);
+         return complete;
+      }
+   };
+
+   let (pattern, mode) = build_fff_grep_pattern(request);
+   let context_lines = request.context_lines.unwrap_or(0).min(10);
+   let mut file_offset = request.file_offset.unwrap_or(0);
+
+   loop {
+      if cancelled.load(Ordering::Relaxed) {
+         complete.cancelled = true;
+         break;
+      }
+
+      let grep_result = match fff.grep(
+         root_paths.iter().map(PathBuf::as_path),
+         &FffGrepOptions {
+            pattern: pattern.clone(),
+            mode,
+            file_offset,
+            page_limit: request.max_results.unwrap_or(100).max(1),
+            time_budget_ms: 120,
+            before_context: context_lines,
+            after_context: context_lines,
+         },
+      ) {
+         Ok(grep_result) => grep_result,
+         Err(error) => {
+            complete.error = Some(format!("fff grep: {error}"));
+            break;
+         }
+      };
+
+      if grep_result.is_indexing {
+         complete.is_indexing = true;
+         break;
+      }
+
+      complete.searched_files += grep_result.searched_files;
+      complete.searchable_files = grep_result.searchable_files;
+      complete.files_with_matches += grep_result.files_with_matches;
+      complete.total_matches += grep_result.matches.len();
+      if complete.regex_fallback_error.is_none() {
+         complete.regex_fallback_error = grep_result.regex_fallback_error;
+      }
+
+      let results = group_grep_matches(grep_result.matches, &request.query);
+      if !results.is_empty() {
+         let _ = app.emit(
+            &format!("search-result-{}", search_id),
+            SearchResultBatch {
+               search_id: search_id.to_string(),
+               results,
+            },
+         );
+      }
+
+      if grep_result.next_file_offset == 0 {
+         break;
+      }
+      file_offset = grep_result.next_file_offset;
+   }
+
+   complete
+}
+
+/// Stop a streaming search. Unknown ids are ignored since the search may already be done.
+#[tauri::command]
+pub fn search_cancel(
+   cancellations: State<'_, SearchCancellations>,
+   search_id: String,
+) -> Result<(), String> {
+   if let Some(flag) = cancellations
+      .flags
+      .lock()
+      .map_err(|e| format!("Failed to lock search cancellations: {}", e))?
+      .get(&search_id)
+   {
+      flag.store(true, Ordering::Relaxed);
+   }
+   Ok(())
+}
+
+/// Replace matches in every text file under `root`. With `dry_run` nothing is written and each
+/// result carries a unified diff of the would-be change for preview.
+#[tauri::command]
+pub async fn replace_in_files(
+   root: String,
+   search: String,
+   replace: String,
+   regex: bool,
+   respect_gitignore: bool,
+   dry_run: bool,
+) -> Result<Vec<FileReplaceResult>, String> {
+   tauri::async_runtime::spawn_blocking(move || {
+      let resolved = require_path_under_home(&root)?;
+      athas_project::replace_in_files(
+         &resolved,
+         &ReplaceOptions {
+            search,
+            replace,
+            regex,
+            respect_gitignore,
+            dry_run,
+         },
+      )
+      .map_err(|error| format!("{error:#}"))
+   })
+   .await
+   .map_err(|error| format!("Replace task failed: {error}"))?
+}
+
 #[cfg(test)]
 mod tests {
    use super::*;
@@ -228,8 +466,10 @@ mod tests {
          root_paths: vec!["/project".to_string()],
          query: query.to_string(),
          case_sensitive: Some(true),
+         smart_case: None,
          whole_word: Some(false),
          use_regex: Some(false),
+         fixed_string: None,
          max_results: None,
          file_offset: None,
          context_lines: None,
@@ -254,6 +494,33 @@ mod tests {
       assert!(matches!(mode, GrepMode::Regex));
    }
 
+   #[test]
+   fn smart_case_matches_case_only_for_uppercase_queries() {
+      let mut search_request = request("needle");
+      search_request.case_sensitive = Some(false);
+      search_request.smart_case = Some(true);
+      assert_eq!(build_fff_grep_pattern(&search_request).0, "(?i:needle)");
+
+      search_request.query = "Needle".to_string();
+      assert_eq!(build_fff_grep_pattern(&search_request).0, "Needle");
+
+      search_request.query = r"\Wneedle".to_string();
+      search_request.use_regex = Some(true);
+      assert_eq!(build_fff_grep_pattern(&search_request).0, r"(?i:\Wneedle)");
+   }
+
+   #[test]
+   fn fixed_string_escapes_regex_queries() {
+      let mut search_request = request("a.b");
+      search_request.use_regex = Some(true);
+      search_request.fixed_string = Some(true);
+      search_request.whole_word = Some(true);
+      let (pattern, mode) = build_fff_grep_pattern(&search_request);
+
+      assert_eq!(pattern, r"\b(?:a\.b)\b");
+      assert!(matches!(mode, GrepMode::Regex));
+   }
+
    #[test]
    fn converts_utf8_byte_ranges_to_character_ranges() {
       assert_eq!(byte_range_to_char_range("aé日z", 1, 6), (1, 3));
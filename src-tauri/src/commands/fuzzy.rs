@@ -11,8 +11,11 @@ use std::{
 };
 use tauri::{Manager, State};
 
+/// A candidate that matched, ranked by `score`. `index` points into the request items and
+/// `indices` are the matched character positions, for highlighting.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FuzzyMatchItem {
+   pub index: usize,
    pub text: String,
    pub score: i64,
    pub indices: Vec<u32>,
@@ -24,6 +27,8 @@ pub struct FuzzyMatchRequest {
    pub items: Vec<String>,
    pub case_sensitive: Option<bool>,
    pub normalize: Option<bool>,
+   /// Score items as file paths, favoring matches at the start of path segments
+   pub match_paths: Option<bool>,
 }
 
 #[tauri::command]
@@ -32,7 +37,9 @@ pub fn fuzzy_match(request: FuzzyMatchRequest) -> Vec<FuzzyMatchItem> {
       return request
          .items
          .into_iter()
-         .map(|text| FuzzyMatchItem {
+         .enumerate()
+         .map(|(index, text)| FuzzyMatchItem {
+            index,
             text,
             score: 0,
             indices: vec![],
@@ -60,16 +67,24 @@ pub fn fuzzy_match(request: FuzzyMatchRequest) -> Vec<FuzzyMatchItem> {
       false,
    );
 
-   let mut matcher = Matcher::new(Config::DEFAULT);
+   let config = if request.match_paths.unwrap_or(false) {
+      Config::DEFAULT.match_paths()
+   } else {
+      Config::DEFAULT
+   };
+   let mut matcher = Matcher::new(config);
    let mut matches: Vec<FuzzyMatchItem> = Vec::new();
 
-   for item in request.items {
+   for (index, item) in request.items.into_iter().enumerate() {
       let mut indices = Vec::new();
       let mut buf = Vec::new();
       let utf32_str = Utf32Str::new(&item, &mut buf);
 
       if let Some(score) = atom.indices(utf32_str, &mut matcher, &mut indices) {
+         indices.sort_unstable();
+         indices.dedup();
          matches.push(FuzzyMatchItem {
+            index,
             text: item,
             score: score as i64,
             indices,
@@ -77,7 +92,7 @@ pub fn fuzzy_match(request: FuzzyMatchRequest) -> Vec<FuzzyMatchItem> {
       }
    }
 
-   // Sort by score in descending order
+   // Sort by score in descending order, keeping the input order for ties
    matches.sort_by_key(|item| std::cmp::Reverse(item.score));
 
    matches
@@ -175,6 +190,22 @@ pub fn fff_ensure_workspaces(
    state.ensure_workspaces(&app, &root_paths)
 }
 
+#[tauri::command]
+pub fn fff_refresh_workspaces(
+   app: AppHandle,
+   state: State<'_, FffSearchState>,
+   root_paths: Vec<String>,
+) -> Result<(), String> {
+   let root_paths = local_workspace_paths(root_paths);
+   if root_paths.is_empty() {
+      return Ok(());
+   }
+   state
+      .get_or_init(&app)?
+      .refresh_workspaces(root_paths.iter().map(PathBuf::as_path))
+      .map_err(|e| format!("fff refresh_workspaces: {e}"))
+}
+
 #[tauri::command]
 pub fn fff_search_files(
    app: AppHandle,
@@ -1,8 +1,10 @@
 use super::{copy_dir_all, remove_dir_all};
 use crate::app_runtime::AppHandle;
+use base64::{Engine as _, engine::general_purpose};
 use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 use tauri::{Emitter, State, command};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tokio::sync::RwLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -33,6 +35,12 @@ pub struct PastedEntry {
    pub is_dir: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardImage {
+   pub base64: String,
+   pub mime_type: String,
+}
+
 fn generate_unique_path(base: &Path) -> std::path::PathBuf {
    if !base.exists() {
       return base.to_path_buf();
@@ -209,3 +217,48 @@ pub async fn clipboard_paste(
 
    Ok(pasted)
 }
+
+/// Read an image from the system clipboard as base64-encoded PNG. Returns `None` when the
+/// clipboard holds no image.
+#[command]
+pub fn read_clipboard_image(app: AppHandle) -> Result<Option<ClipboardImage>, String> {
+   let image = match app.clipboard().read_image() {
+      Ok(image) => image,
+      Err(e) => {
+         log::debug!("No image on clipboard: {}", e);
+         return Ok(None);
+      }
+   };
+
+   let png = encode_png(image.rgba(), image.width(), image.height())?;
+   Ok(Some(ClipboardImage {
+      base64: general_purpose::STANDARD.encode(png),
+      mime_type: "image/png".to_string(),
+   }))
+}
+
+#[command]
+pub fn write_clipboard_text(app: AppHandle, text: String) -> Result<(), String> {
+   app.clipboard()
+      .write_text(text)
+      .map_err(|e| format!("Failed to write to clipboard: {}", e))
+}
+
+fn encode_png(rgba: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+   let mut png = Vec::new();
+   let mut encoder = png::Encoder::new(&mut png, width, height);
+   encoder.set_color(png::ColorType::Rgba);
+   encoder.set_depth(png::BitDepth::Eight);
+
+   let mut writer = encoder
+      .write_header()
+      .map_err(|e| format!("Failed to encode clipboard image: {}", e))?;
+   writer
+      .write_image_data(rgba)
+      .map_err(|e| format!("Failed to encode clipboard image: {}", e))?;
+   writer
+      .finish()
+      .map_err(|e| format!("Failed to encode clipboard image: {}", e))?;
+
+   Ok(png)
+}
@@ -1,9 +1,15 @@
 use super::path_guard::{require_path_under_home, require_symlink_container_under_home};
 use crate::app_runtime::AppHandle;
+use athas_project::{
+   DirectoryEntry, DirectoryTreeNode, FileCategory, FileStats, LineEnding, SCRATCH_FILE_MAX_AGE,
+   cleanup_scratch_files as cleanup_stale_scratch_files,
+   create_scratch_file as create_scratch_file_in, scratch_dir, write_text_file_atomic,
+};
 use serde::Serialize;
 use std::{fs, path::Path, time::Instant};
 use tauri::command;
 use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_opener::OpenerExt;
 use walkdir::WalkDir;
 
 #[command]
@@ -59,38 +65,188 @@ pub async fn read_local_file(path: String) -> Result<tauri::ipc::Response, Strin
    Ok(tauri::ipc::Response::new(bytes))
 }
 
+/// Save a file atomically, keeping its permissions and line endings. `normalize_eol`
+/// (`lf`, `crlf` or `cr`) overrides the line ending detected in the existing file.
 #[command]
-pub fn open_file_external(path: String) -> Result<(), String> {
-   // Canonicalize and confine to $HOME so the platform opener cannot be
-   // invoked on system locations or on a scheme-like string that would be
-   // interpreted as a URL by xdg-open.
+pub async fn write_local_file(
+   path: String,
+   content: String,
+   backup: bool,
+   normalize_eol: Option<String>,
+) -> Result<(), String> {
+   tauri::async_runtime::spawn_blocking(move || {
+      let resolved = require_path_under_home(&path)?;
+      let line_ending = normalize_eol
+         .as_deref()
+         .map(LineEnding::parse)
+         .transpose()
+         .map_err(|error| error.to_string())?;
+      write_text_file_atomic(&resolved, &content, line_ending, backup)
+         .map_err(|error| format!("{error:#}"))
+   })
+   .await
+   .map_err(|error| format!("File write task failed: {error}"))?
+}
+
+#[command]
+pub async fn read_directory_filtered(
+   path: String,
+   respect_gitignore: bool,
+   show_hidden: bool,
+) -> Result<Vec<DirectoryEntry>, String> {
+   tauri::async_runtime::spawn_blocking(move || {
+      let resolved = require_path_under_home(&path)?;
+      athas_project::read_directory_filtered(&resolved, respect_gitignore, show_hidden)
+         .map_err(|error| format!("{error:#}"))
+   })
+   .await
+   .map_err(|error| format!("Directory read task failed: {error}"))?
+}
+
+#[command]
+pub async fn read_directory_recursive(
+   path: String,
+   max_depth: usize,
+) -> Result<Vec<DirectoryTreeNode>, String> {
+   tauri::async_runtime::spawn_blocking(move || {
+      let resolved = require_path_under_home(&path)?;
+      athas_project::read_directory_recursive(&resolved, max_depth)
+         .map_err(|error| format!("{error:#}"))
+   })
+   .await
+   .map_err(|error| format!("Directory read task failed: {error}"))?
+}
+
+/// Line, word and character counts of a file, or of the text files under a folder
+#[command]
+pub async fn count_file_stats(
+   path: String,
+   recursive: bool,
+   respect_gitignore: Option<bool>,
+) -> Result<FileStats, String> {
+   tauri::async_runtime::spawn_blocking(move || {
+      let resolved = require_path_under_home(&path)?;
+      athas_project::count_file_stats(&resolved, recursive, respect_gitignore.unwrap_or(true))
+         .map_err(|error| format!("{error:#}"))
+   })
+   .await
+   .map_err(|error| format!("File stats task failed: {error}"))?
+}
+
+/// Hand a file to the OS default application, e.g. for PDFs or archives the editor cannot show
+#[command]
+pub fn open_externally(app: AppHandle, path: String) -> Result<(), String> {
+   // Canonicalize to an absolute local path so the platform opener is never
+   // handed a relative or scheme-like string that xdg-open would interpret
+   // as a URL.
    let resolved = require_path_under_home(&path)?;
-   let resolved_str = resolved.to_string_lossy().to_string();
+   app.opener()
+      .open_path(resolved.to_string_lossy(), None::<&str>)
+      .map_err(|e| format!("Failed to open {}: {}", resolved.display(), e))
+}
+
+/// Whether a file is text, an image, an archive or another binary, so the frontend can decide
+/// between opening it in the editor and handing it to the OS
+#[command]
+pub async fn get_file_category(path: String) -> Result<FileCategory, String> {
+   tauri::async_runtime::spawn_blocking(move || {
+      let resolved = require_path_under_home(&path)?;
+      athas_project::file_category(&resolved)
+         .map_err(|e| format!("Failed to read {}: {}", resolved.display(), e))
+   })
+   .await
+   .map_err(|error| format!("File read task failed: {error}"))?
+}
+
+/// How long a file manager gets to answer the D-Bus request to show an item
+#[cfg(target_os = "linux")]
+const FILE_MANAGER_DBUS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Open the platform file manager with `path` selected.
+#[command]
+pub async fn reveal_in_file_manager(path: String) -> Result<(), String> {
+   tauri::async_runtime::spawn_blocking(move || reveal_path(&path))
+      .await
+      .map_err(|e| format!("Reveal task failed: {}", e))?
+}
+
+fn reveal_path(path: &str) -> Result<(), String> {
+   let resolved = require_path_under_home(path)?;
 
    #[cfg(target_os = "macos")]
    {
       std::process::Command::new("open")
-         .arg(&resolved_str)
+         .arg("-R")
+         .arg(&resolved)
          .spawn()
          .map_err(|e| e.to_string())?;
    }
    #[cfg(target_os = "windows")]
    {
-      std::process::Command::new("cmd")
-         .args(["/C", "start", "", &resolved_str])
+      let mut select_arg = std::ffi::OsString::from("/select,");
+      select_arg.push(&resolved);
+      std::process::Command::new("explorer")
+         .arg(select_arg)
          .spawn()
          .map_err(|e| e.to_string())?;
    }
    #[cfg(target_os = "linux")]
    {
-      std::process::Command::new("xdg-open")
-         .arg(&resolved_str)
-         .spawn()
-         .map_err(|e| e.to_string())?;
+      if !show_item_via_file_manager_dbus(&resolved) {
+         // No file manager implements org.freedesktop.FileManager1, so open the parent
+         // directory without a selection.
+         let parent = resolved.parent().unwrap_or(&resolved);
+         std::process::Command::new("xdg-open")
+            .arg(parent)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+      }
    }
    Ok(())
 }
 
+#[cfg(target_os = "linux")]
+fn show_item_via_file_manager_dbus(path: &Path) -> bool {
+   use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+
+   // dbus-send splits array arguments on commas, so those are escaped along with the
+   // characters that are not valid in a file URI path.
+   const FILE_URI_PATH: &AsciiSet = &CONTROLS
+      .add(b' ')
+      .add(b'"')
+      .add(b'#')
+      .add(b'%')
+      .add(b',')
+      .add(b'<')
+      .add(b'>')
+      .add(b'?')
+      .add(b'`')
+      .add(b'{')
+      .add(b'}');
+
+   let uri = format!(
+      "file://{}",
+      utf8_percent_encode(&path.to_string_lossy(), FILE_URI_PATH)
+   );
+   // A file manager that is activated but never answers would otherwise leave dbus-send
+   // waiting for its default reply timeout
+   let mut command = std::process::Command::new("dbus-send");
+   command
+      .args([
+         "--session",
+         "--print-reply",
+         "--reply-timeout=2000",
+         "--dest=org.freedesktop.FileManager1",
+         "--type=method_call",
+         "/org/freedesktop/FileManager1",
+         "org.freedesktop.FileManager1.ShowItems",
+      ])
+      .arg(format!("array:string:{}", uri))
+      .arg("string:");
+   athas_runtime::process::output_with_timeout(&mut command, FILE_MANAGER_DBUS_TIMEOUT)
+      .is_ok_and(|output| output.status.success())
+}
+
 #[command]
 pub async fn open_folder_dialog(app: AppHandle) -> Result<Option<String>, String> {
    tauri::async_runtime::spawn_blocking(move || {
@@ -176,6 +332,22 @@ pub fn get_symlink_info(
    })
 }
 
+/// Create an empty file in the scratch directory for a buffer that has no location yet, so
+/// language servers and formatters get a real path. Returns the new file's path.
+#[command]
+pub fn create_scratch_file(extension: Option<String>) -> Result<String, String> {
+   create_scratch_file_in(&scratch_dir(), extension.as_deref())
+      .map(|path| path.to_string_lossy().to_string())
+      .map_err(|error| format!("{error:#}"))
+}
+
+/// Remove scratch files that haven't been modified for a week. Returns how many were removed.
+#[command]
+pub fn cleanup_scratch_files() -> Result<usize, String> {
+   cleanup_stale_scratch_files(&scratch_dir(), SCRATCH_FILE_MAX_AGE)
+      .map_err(|error| format!("{error:#}"))
+}
+
 #[command]
 pub fn rename_file(source_path: String, target_path: String) -> Result<(), String> {
    let source_buf = require_path_under_home(&source_path)?;
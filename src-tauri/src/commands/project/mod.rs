@@ -1,7 +1,7 @@
 pub mod clipboard;
 pub mod fs;
 pub mod local_history;
-mod path_guard;
+pub(crate) mod path_guard;
 pub mod remote;
 pub mod remote_credentials;
 pub mod watcher;
@@ -13,6 +13,9 @@ use std::path::{Path, PathBuf};
 /// targets (e.g. the destination of a rename) the parent directory is
 /// canonicalized and the final file name is joined back on, mirroring how
 /// path_guard already works for create-like operations elsewhere in the code.
+///
+/// Despite the name, the result is not confined to the home directory:
+/// projects and scratch files can live anywhere on the local filesystem.
 pub fn require_path_under_home(path: &str) -> Result<PathBuf, String> {
    if path.trim().is_empty() {
       return Err("path must not be empty".to_string());
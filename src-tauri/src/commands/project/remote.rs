@@ -1,15 +1,18 @@
 use athas_remote::{
-   RemoteFileEntry, SshConnection, close_remote_terminal as remote_close_terminal,
+   PortForwardInfo, RemoteFileEntry, SshConnection, close_remote_terminal as remote_close_terminal,
    create_remote_terminal as remote_create_terminal,
    remote_terminal_resize as remote_terminal_resize_impl,
    remote_terminal_set_paused as remote_terminal_set_paused_impl,
-   remote_terminal_write as remote_terminal_write_impl, ssh_connect as remote_ssh_connect,
+   remote_terminal_write as remote_terminal_write_impl,
+   ssh_close_forward as remote_ssh_close_forward, ssh_connect as remote_ssh_connect,
    ssh_copy_path as remote_ssh_copy_path, ssh_create_directory as remote_ssh_create_directory,
    ssh_create_file as remote_ssh_create_file, ssh_delete_path as remote_ssh_delete_path,
    ssh_disconnect as remote_ssh_disconnect, ssh_disconnect_only as remote_ssh_disconnect_only,
+   ssh_forward_local_port as remote_ssh_forward_local_port,
    ssh_get_connected_ids as remote_ssh_get_connected_ids,
-   ssh_read_directory as remote_ssh_read_directory, ssh_read_file as remote_ssh_read_file,
-   ssh_rename_path as remote_ssh_rename_path, ssh_write_file as remote_ssh_write_file,
+   ssh_list_forwards as remote_ssh_list_forwards, ssh_read_directory as remote_ssh_read_directory,
+   ssh_read_file as remote_ssh_read_file, ssh_rename_path as remote_ssh_rename_path,
+   ssh_write_file as remote_ssh_write_file,
 };
 use athas_terminal::{TerminalEvent, TerminalInput, TerminalSize};
 use tauri::{Emitter, ipc::Channel};
@@ -86,24 +89,30 @@ pub async fn ssh_disconnect_only(
 
 #[tauri::command]
 pub async fn ssh_write_file(
+   app: crate::app_runtime::AppHandle,
    connection_id: String,
    file_path: String,
    content: String,
 ) -> Result<(), String> {
-   remote_ssh_write_file(connection_id, file_path, content).await
+   remote_ssh_write_file(app, connection_id, file_path, content).await
 }
 
 #[tauri::command]
 pub async fn ssh_read_directory(
+   app: crate::app_runtime::AppHandle,
    connection_id: String,
    path: String,
 ) -> Result<Vec<RemoteFileEntry>, String> {
-   remote_ssh_read_directory(connection_id, path).await
+   remote_ssh_read_directory(app, connection_id, path).await
 }
 
 #[tauri::command]
-pub async fn ssh_read_file(connection_id: String, file_path: String) -> Result<String, String> {
-   remote_ssh_read_file(connection_id, file_path).await
+pub async fn ssh_read_file(
+   app: crate::app_runtime::AppHandle,
+   connection_id: String,
+   file_path: String,
+) -> Result<String, String> {
+   remote_ssh_read_file(app, connection_id, file_path).await
 }
 
 #[tauri::command]
@@ -112,44 +121,72 @@ pub async fn ssh_get_connected_ids() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub async fn ssh_create_file(connection_id: String, file_path: String) -> Result<(), String> {
-   remote_ssh_create_file(connection_id, file_path).await
+pub async fn ssh_forward_local_port(
+   connection_id: String,
+   local_port: u16,
+   remote_host: String,
+   remote_port: u16,
+) -> Result<PortForwardInfo, String> {
+   remote_ssh_forward_local_port(connection_id, local_port, remote_host, remote_port).await
+}
+
+#[tauri::command]
+pub async fn ssh_close_forward(local_port: u16) -> Result<(), String> {
+   remote_ssh_close_forward(local_port).await
+}
+
+#[tauri::command]
+pub async fn ssh_list_forwards() -> Result<Vec<PortForwardInfo>, String> {
+   remote_ssh_list_forwards().await
+}
+
+#[tauri::command]
+pub async fn ssh_create_file(
+   app: crate::app_runtime::AppHandle,
+   connection_id: String,
+   file_path: String,
+) -> Result<(), String> {
+   remote_ssh_create_file(app, connection_id, file_path).await
 }
 
 #[tauri::command]
 pub async fn ssh_create_directory(
+   app: crate::app_runtime::AppHandle,
    connection_id: String,
    directory_path: String,
 ) -> Result<(), String> {
-   remote_ssh_create_directory(connection_id, directory_path).await
+   remote_ssh_create_directory(app, connection_id, directory_path).await
 }
 
 #[tauri::command]
 pub async fn ssh_delete_path(
+   app: crate::app_runtime::AppHandle,
    connection_id: String,
    target_path: String,
    is_directory: bool,
 ) -> Result<(), String> {
-   remote_ssh_delete_path(connection_id, target_path, is_directory).await
+   remote_ssh_delete_path(app, connection_id, target_path, is_directory).await
 }
 
 #[tauri::command]
 pub async fn ssh_rename_path(
+   app: crate::app_runtime::AppHandle,
    connection_id: String,
    source_path: String,
    target_path: String,
 ) -> Result<(), String> {
-   remote_ssh_rename_path(connection_id, source_path, target_path).await
+   remote_ssh_rename_path(app, connection_id, source_path, target_path).await
 }
 
 #[tauri::command]
 pub async fn ssh_copy_path(
+   app: crate::app_runtime::AppHandle,
    connection_id: String,
    source_path: String,
    target_path: String,
    is_directory: bool,
 ) -> Result<(), String> {
-   remote_ssh_copy_path(connection_id, source_path, target_path, is_directory).await
+   remote_ssh_copy_path(app, connection_id, source_path, target_path, is_directory).await
 }
 
 #[tauri::command]
@@ -79,3 +79,23 @@ pub async fn set_project_root(
          e.to_string()
       })
 }
+
+#[command]
+pub async fn watch_workspace(
+   path: String,
+   file_watcher: tauri::State<'_, Arc<FileWatcher>>,
+) -> Result<(), String> {
+   log::info!("[watcher] watch_workspace {}", short_path(&path));
+   file_watcher
+      .watch_workspace(path)
+      .await
+      .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn unwatch_workspace(
+   path: String,
+   file_watcher: tauri::State<'_, Arc<FileWatcher>>,
+) -> Result<(), String> {
+   file_watcher.stop_watching(path).map_err(|e| e.to_string())
+}
@@ -1,5 +1,9 @@
-use fontdb::Database;
+use fontdb::{Database, Style};
 use serde::{Deserialize, Serialize};
+use std::{
+   collections::{BTreeSet, HashMap},
+   sync::{Arc, Mutex},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub struct FontInfo {
@@ -7,58 +11,164 @@ pub struct FontInfo {
    pub family: String,
    pub style: String,
    pub is_monospace: bool,
+   pub weights: Vec<u16>,
+   pub styles: Vec<String>,
 }
 
-fn get_system_fonts_sync() -> Vec<FontInfo> {
+#[derive(Default)]
+struct FamilyFaces {
+   is_monospace: bool,
+   weights: BTreeSet<u16>,
+   styles: BTreeSet<&'static str>,
+   /// Weight and style of the face closest to an upright 400, which the family shows by default
+   default_face: Option<(u16, Style)>,
+}
+
+/// Scanning system fonts takes noticeable time on machines with many installed, so the result
+/// is kept until a caller asks for a fresh scan, e.g. after installing a font.
+static SYSTEM_FONTS: Mutex<Option<Arc<Vec<FontInfo>>>> = Mutex::new(None);
+
+fn system_fonts(refresh: bool) -> Arc<Vec<FontInfo>> {
+   let mut cached = SYSTEM_FONTS.lock().unwrap();
+   if refresh || cached.is_none() {
+      *cached = Some(Arc::new(load_system_fonts()));
+   }
+   cached.clone().unwrap_or_default()
+}
+
+fn load_system_fonts() -> Vec<FontInfo> {
    let mut db = Database::new();
    db.load_system_fonts();
 
    // Group faces by family to detect monospace properly
-   let mut font_map: std::collections::HashMap<String, bool> = std::collections::HashMap::new();
+   let mut families: HashMap<String, FamilyFaces> = HashMap::new();
 
    for face in db.faces() {
       if let Some(family) = face.families.first() {
-         let family_name = &family.0;
+         let entry = families.entry(family.0.clone()).or_default();
          // A font family is considered monospace if ANY of its variants are monospace
-         font_map
-            .entry(family_name.clone())
-            .and_modify(|is_mono| *is_mono = *is_mono || face.monospaced)
-            .or_insert(face.monospaced);
+         entry.is_monospace |= face.monospaced;
+         entry.weights.insert(face.weight.0);
+         entry.styles.insert(style_name(face.style));
+         let face_key = (face.weight.0, face.style);
+         if entry
+            .default_face
+            .is_none_or(|current| default_face_rank(face_key) < default_face_rank(current))
+         {
+            entry.default_face = Some(face_key);
+         }
       }
    }
 
-   let mut fonts: Vec<FontInfo> = font_map
+   let mut fonts: Vec<FontInfo> = families
       .into_iter()
-      .map(|(family, is_monospace)| FontInfo {
+      .map(|(family, faces)| FontInfo {
          name: family.clone(),
-         family: family.clone(),
-         style: "Regular".to_string(),
-         is_monospace,
+         family,
+         style: faces.default_face.map_or_else(
+            || "Regular".to_string(),
+            |(weight, style)| describe_face(weight, style),
+         ),
+         is_monospace: faces.is_monospace,
+         weights: faces.weights.into_iter().collect(),
+         styles: faces.styles.into_iter().map(str::to_string).collect(),
       })
       .collect();
 
-   fonts.sort_by_key(|font| font.family.clone());
+   fonts.sort_by(|a, b| a.family.cmp(&b.family));
    fonts
 }
 
+/// Upright faces first, then the weight closest to 400
+fn default_face_rank((weight, style): (u16, Style)) -> (bool, u16) {
+   (style != Style::Normal, weight.abs_diff(400))
+}
+
+fn style_name(style: Style) -> &'static str {
+   match style {
+      Style::Normal => "normal",
+      Style::Italic => "italic",
+      Style::Oblique => "oblique",
+   }
+}
+
+/// The conventional name of a face, such as "Regular", "Light" or "Bold Italic"
+fn describe_face(weight: u16, style: Style) -> String {
+   let weight_name = match weight {
+      0..=149 => "Thin",
+      150..=249 => "ExtraLight",
+      250..=349 => "Light",
+      350..=449 => "Regular",
+      450..=549 => "Medium",
+      550..=649 => "SemiBold",
+      650..=749 => "Bold",
+      750..=849 => "ExtraBold",
+      _ => "Black",
+   };
+   let slant = match style {
+      Style::Normal => return weight_name.to_string(),
+      Style::Italic => "Italic",
+      Style::Oblique => "Oblique",
+   };
+   if weight_name == "Regular" {
+      slant.to_string()
+   } else {
+      format!("{weight_name} {slant}")
+   }
+}
+
+/// List installed font families with their monospace flag, default style and available weights
+/// and styles. The scan is cached; pass `refresh` to pick up fonts installed since.
 #[tauri::command]
-pub async fn get_system_fonts() -> Result<Vec<FontInfo>, String> {
-   Ok(get_system_fonts_sync())
+pub async fn list_system_fonts(refresh: Option<bool>) -> Result<Vec<FontInfo>, String> {
+   let refresh = refresh.unwrap_or(false);
+   tauri::async_runtime::spawn_blocking(move || system_fonts(refresh).to_vec())
+      .await
+      .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn get_monospace_fonts() -> Result<Vec<FontInfo>, String> {
-   let all_fonts = get_system_fonts_sync();
-   let monospace_fonts: Vec<FontInfo> = all_fonts
-      .into_iter()
+   let monospace_fonts: Vec<FontInfo> = system_fonts(false)
+      .iter()
       .filter(|font| font.is_monospace)
+      .cloned()
       .collect();
    Ok(monospace_fonts)
 }
 
 #[tauri::command]
 pub async fn validate_font(font_family: String) -> Result<bool, String> {
-   let fonts = get_system_fonts_sync();
-   let is_valid = fonts.iter().any(|font| font.family == font_family);
+   let is_valid = system_fonts(false)
+      .iter()
+      .any(|font| font.family == font_family);
    Ok(is_valid)
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn names_faces_by_weight_and_slant() {
+      assert_eq!(describe_face(400, Style::Normal), "Regular");
+      assert_eq!(describe_face(400, Style::Italic), "Italic");
+      assert_eq!(describe_face(300, Style::Normal), "Light");
+      assert_eq!(describe_face(700, Style::Italic), "Bold Italic");
+      assert_eq!(describe_face(900, Style::Oblique), "Black Oblique");
+   }
+
+   #[test]
+   fn prefers_upright_faces_near_regular_weight_as_the_default() {
+      let faces = [
+         (700, Style::Normal),
+         (400, Style::Italic),
+         (300, Style::Normal),
+      ];
+      let default_face = faces
+         .into_iter()
+         .min_by_key(|face| default_face_rank(*face));
+
+      assert_eq!(default_face, Some((300, Style::Normal)));
+   }
+}
@@ -0,0 +1,35 @@
+use crate::app_runtime::AppHandle;
+use serde_json::Value;
+use tauri_plugin_store::StoreExt;
+
+/// Store holding one layout per workspace, keyed by the workspace path
+const WORKSPACE_LAYOUT_STORE: &str = "workspace-layouts.json";
+
+/// Remember the layout of `workspace_path`, such as its window geometry. The layout is opaque
+/// to the backend; the frontend decides what goes in it.
+#[tauri::command]
+pub fn save_workspace_layout(
+   app: AppHandle,
+   workspace_path: String,
+   layout: Value,
+) -> Result<(), String> {
+   let store = app
+      .store(WORKSPACE_LAYOUT_STORE)
+      .map_err(|e| format!("Failed to open workspace layout store: {e}"))?;
+   store.set(workspace_path, layout);
+   store
+      .save()
+      .map_err(|e| format!("Failed to save workspace layout: {e}"))
+}
+
+/// The layout last saved for `workspace_path`, if any
+#[tauri::command]
+pub fn load_workspace_layout(
+   app: AppHandle,
+   workspace_path: String,
+) -> Result<Option<Value>, String> {
+   let store = app
+      .store(WORKSPACE_LAYOUT_STORE)
+      .map_err(|e| format!("Failed to open workspace layout store: {e}"))?;
+   Ok(store.get(&workspace_path))
+}
@@ -1,7 +1,9 @@
 pub mod font;
+pub mod layout;
 pub mod theme;
 pub mod window;
 
 pub use font::*;
+pub use layout::*;
 pub use theme::*;
 pub use window::*;
@@ -1,8 +1,29 @@
+use crate::app_runtime::AppHandle;
+use athas_project::{FileChangeEmitter, FileChangeEvent, FileChangeType, FileWatcher};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::Path, process::Command};
-use tauri::State;
+use std::{
+   collections::{HashMap, HashSet},
+   fs,
+   path::{Path, PathBuf},
+   process::Command,
+   sync::Arc,
+};
+use tauri::{Emitter, Manager, State};
 use tokio::sync::RwLock;
 
+/// Color keys every user theme must define, matching the frontend theme file schema.
+const REQUIRED_THEME_COLOR_KEYS: [&str; 9] = [
+   "primary-bg",
+   "secondary-bg",
+   "text",
+   "text-light",
+   "text-lighter",
+   "border",
+   "hover",
+   "selected",
+   "accent",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TomlTheme {
    pub id: String,
@@ -24,6 +45,62 @@ pub struct TomlThemeFile {
 
 pub type ThemeCache = RwLock<HashMap<String, TomlTheme>>;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserTheme {
+   pub id: String,
+   pub name: String,
+   #[serde(default, skip_serializing_if = "Option::is_none")]
+   pub description: Option<String>,
+   pub appearance: String, // "dark" | "light"
+   pub colors: HashMap<String, String>,
+   #[serde(default, skip_serializing_if = "Option::is_none")]
+   pub syntax: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserThemeFile {
+   pub name: String,
+   pub themes: Vec<UserTheme>,
+}
+
+/// Watches the user themes folder and emits `themes-changed` with the reloaded themes.
+pub struct UserThemeWatcher {
+   watcher: FileWatcher,
+}
+
+impl UserThemeWatcher {
+   pub fn new(app: AppHandle) -> Self {
+      Self {
+         watcher: FileWatcher::new(Arc::new(UserThemeChangeEmitter { app })),
+      }
+   }
+}
+
+struct UserThemeChangeEmitter {
+   app: AppHandle,
+}
+
+impl FileChangeEmitter for UserThemeChangeEmitter {
+   fn emit_file_change(&self, event: &FileChangeEvent) {
+      if matches!(event.event_type, FileChangeType::Opened) || !is_json_file(Path::new(&event.path))
+      {
+         return;
+      }
+
+      match user_themes_dir(&self.app) {
+         Ok(themes_dir) => {
+            let _ = self
+               .app
+               .emit("themes-changed", load_user_themes_from_dir(&themes_dir));
+         }
+         Err(e) => log::warn!("Failed to reload user themes: {}", e),
+      }
+      if let Err(e) = crate::menu::rebuild_native_menu(&self.app) {
+         log::warn!("Failed to rebuild menu after theme change: {}", e);
+      }
+   }
+}
+
 fn get_system_theme_sync() -> String {
    #[cfg(target_os = "linux")]
    {
@@ -180,6 +257,121 @@ pub fn load_themes_from_directory(themes_dir: &Path) -> Result<Vec<TomlTheme>, S
    Ok(all_themes)
 }
 
+pub fn user_themes_dir<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+   app.path()
+      .app_config_dir()
+      .map(|config_dir| config_dir.join("themes"))
+      .map_err(|e| format!("Failed to resolve config directory: {}", e))
+}
+
+fn is_json_file(path: &Path) -> bool {
+   path.extension().and_then(|s| s.to_str()) == Some("json")
+}
+
+/// Load every valid theme from the `*.json` files in `themes_dir`, in file name order. Invalid
+/// files and themes are skipped with a warning; when two themes share an id the first wins.
+pub fn load_user_themes_from_dir(themes_dir: &Path) -> Vec<UserTheme> {
+   let Ok(entries) = fs::read_dir(themes_dir) else {
+      return Vec::new();
+   };
+
+   let mut paths: Vec<PathBuf> = entries
+      .flatten()
+      .map(|entry| entry.path())
+      .filter(|path| path.is_file() && is_json_file(path))
+      .collect();
+   paths.sort();
+
+   let mut seen_ids = HashSet::new();
+   let mut themes = Vec::new();
+   for path in paths {
+      let theme_file = match load_user_theme_file(&path) {
+         Ok(theme_file) => theme_file,
+         Err(e) => {
+            log::warn!("Skipping user theme file {}: {}", path.display(), e);
+            continue;
+         }
+      };
+
+      for theme in theme_file.themes {
+         if let Err(e) = validate_user_theme(&theme) {
+            log::warn!(
+               "Skipping theme \"{}\" in {}: {}",
+               theme.id,
+               path.display(),
+               e
+            );
+            continue;
+         }
+         if seen_ids.insert(theme.id.clone()) {
+            themes.push(theme);
+         }
+      }
+   }
+
+   themes
+}
+
+fn load_user_theme_file(path: &Path) -> Result<UserThemeFile, String> {
+   let content = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+   serde_json::from_str(&content).map_err(|e| format!("Invalid theme file: {}", e))
+}
+
+fn validate_user_theme(theme: &UserTheme) -> Result<(), String> {
+   if theme.id.trim().is_empty() || theme.name.trim().is_empty() {
+      return Err("id and name must not be empty".to_string());
+   }
+   if theme.appearance != "dark" && theme.appearance != "light" {
+      return Err(format!(
+         "appearance must be \"dark\" or \"light\", got \"{}\"",
+         theme.appearance
+      ));
+   }
+
+   let missing: Vec<&str> = REQUIRED_THEME_COLOR_KEYS
+      .into_iter()
+      .filter(|key| {
+         theme
+            .colors
+            .get(*key)
+            .is_none_or(|value| value.trim().is_empty())
+      })
+      .collect();
+   if !missing.is_empty() {
+      return Err(format!("missing colors: {}", missing.join(", ")));
+   }
+
+   Ok(())
+}
+
+/// Load themes from the `themes/` folder in the app config directory
+#[tauri::command]
+pub async fn load_user_themes(app: AppHandle) -> Result<Vec<UserTheme>, String> {
+   let themes_dir = user_themes_dir(&app)?;
+   let themes = load_user_themes_from_dir(&themes_dir);
+   if let Err(e) = crate::menu::rebuild_native_menu(&app) {
+      log::warn!("Failed to rebuild menu after loading user themes: {}", e);
+   }
+   Ok(themes)
+}
+
+/// Start watching the user themes folder, creating it if needed
+#[tauri::command]
+pub async fn watch_user_themes(
+   app: AppHandle,
+   watcher: State<'_, UserThemeWatcher>,
+) -> Result<(), String> {
+   let themes_dir = user_themes_dir(&app)?;
+   fs::create_dir_all(&themes_dir)
+      .map_err(|e| format!("Failed to create themes directory: {}", e))?;
+
+   watcher
+      .watcher
+      .watch_path(themes_dir.to_string_lossy().to_string())
+      .await
+      .map_err(|e| format!("Failed to watch themes directory: {}", e))
+}
+
 #[tauri::command]
 pub async fn load_toml_themes(themes_dir: String) -> Result<Vec<TomlTheme>, String> {
    let themes_path = Path::new(&themes_dir);
@@ -242,3 +434,55 @@ pub async fn delete_temp_file(file_name: String) -> Result<(), String> {
 
    Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn theme_json(id: &str, colors: &[&str]) -> String {
+      let colors = colors
+         .iter()
+         .map(|key| format!("\"{}\": \"#000000\"", key))
+         .collect::<Vec<_>>()
+         .join(", ");
+      format!(
+         r#"{{"name": "Pack", "themes": [{{"id": "{}", "name": "{}", "appearance": "dark", "colors": {{{}}}}}]}}"#,
+         id, id, colors
+      )
+   }
+
+   #[test]
+   fn loads_valid_user_themes_and_skips_invalid_ones() {
+      let dir = tempfile::tempdir().unwrap();
+      fs::write(
+         dir.path().join("a.json"),
+         theme_json("ocean", &REQUIRED_THEME_COLOR_KEYS),
+      )
+      .unwrap();
+      fs::write(
+         dir.path().join("b.json"),
+         theme_json("ocean", &REQUIRED_THEME_COLOR_KEYS),
+      )
+      .unwrap();
+      fs::write(dir.path().join("c.json"), theme_json("partial", &["text"])).unwrap();
+      fs::write(dir.path().join("d.json"), "not json").unwrap();
+      fs::write(
+         dir.path().join("e.txt"),
+         theme_json("ignored", &REQUIRED_THEME_COLOR_KEYS),
+      )
+      .unwrap();
+
+      let themes = load_user_themes_from_dir(dir.path());
+
+      assert_eq!(themes.len(), 1);
+      assert_eq!(themes[0].id, "ocean");
+   }
+
+   #[test]
+   fn reports_missing_color_keys() {
+      let theme: UserThemeFile = serde_json::from_str(&theme_json("partial", &["text"])).unwrap();
+      let error = validate_user_theme(&theme.themes[0]).unwrap_err();
+
+      assert!(error.starts_with("missing colors: primary-bg, secondary-bg, text-light,"));
+   }
+}
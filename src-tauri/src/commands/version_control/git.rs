@@ -1,5 +1,8 @@
+use super::github::get_stored_github_token;
+use crate::app_runtime::{AppHandle, AthasRuntime};
 use athas_version_control::git as git_backend;
 use std::{path::Path, time::Instant};
+use tauri::Emitter;
 
 async fn run_blocking<T, F>(operation: F) -> Result<T, String>
 where
@@ -62,8 +65,28 @@ pub async fn git_status(repo_path: String) -> Result<git_backend::GitStatus, Str
 }
 
 #[tauri::command]
-pub fn git_init(repo_path: String) -> Result<(), String> {
-   git_backend::git_init(resolve_backend_path(repo_path))
+pub fn git_init(repo_path: String, initial_branch: Option<String>) -> Result<(), String> {
+   git_backend::git_init(resolve_backend_path(repo_path), initial_branch)
+}
+
+/// Clone a repository, emitting `git-clone-progress` while objects arrive. HTTPS github.com
+/// remotes authenticate with the stored GitHub token.
+#[tauri::command]
+pub async fn git_clone(
+   app: AppHandle,
+   window: tauri::WebviewWindow<AthasRuntime>,
+   url: String,
+   dest_path: String,
+) -> Result<String, String> {
+   let github_token = get_stored_github_token(&app);
+   let backend_dest_path = resolve_backend_path(dest_path.clone());
+   let cloned_path = run_blocking(move || {
+      git_backend::git_clone(url, backend_dest_path, github_token, |progress| {
+         let _ = window.emit("git-clone-progress", progress);
+      })
+   })
+   .await?;
+   Ok(restore_provider_path(&dest_path, cloned_path))
 }
 
 #[tauri::command]
@@ -73,11 +96,38 @@ pub fn git_discover_repo(path: String) -> Result<Option<String>, String> {
       .map(|path_opt| path_opt.map(|repo_path| restore_provider_path(&path, repo_path)))
 }
 
+#[tauri::command]
+pub async fn git_check_ignore(repo_path: String, paths: Vec<String>) -> Result<Vec<bool>, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   let paths = paths.into_iter().map(resolve_backend_path).collect();
+   run_blocking(move || git_backend::git_check_ignore(repo_path, paths)).await
+}
+
 #[tauri::command]
 pub fn git_commit(repo_path: String, message: String) -> Result<(), String> {
    git_backend::git_commit(resolve_backend_path(repo_path), message)
 }
 
+#[tauri::command]
+pub async fn git_revert(
+   repo_path: String,
+   commit_hash: String,
+   no_commit: bool,
+) -> Result<git_backend::CommitOperationResult, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_revert(repo_path, commit_hash, no_commit)).await
+}
+
+#[tauri::command]
+pub async fn git_cherry_pick(
+   repo_path: String,
+   commit_hash: String,
+   no_commit: bool,
+) -> Result<git_backend::CommitOperationResult, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_cherry_pick(repo_path, commit_hash, no_commit)).await
+}
+
 #[tauri::command]
 pub fn git_log(
    repo_path: String,
@@ -87,6 +137,16 @@ pub fn git_log(
    git_backend::git_log(resolve_backend_path(repo_path), limit, skip)
 }
 
+#[tauri::command]
+pub async fn git_commit_graph(
+   repo_path: String,
+   limit: Option<u32>,
+   branches: Option<Vec<String>>,
+) -> Result<Vec<git_backend::GitGraphNode>, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_commit_graph(repo_path, limit, branches)).await
+}
+
 #[tauri::command]
 pub async fn git_diff_file(
    repo_path: String,
@@ -119,6 +179,24 @@ pub async fn git_status_diff_stats(
    run_blocking(move || git_backend::git_status_diff_stats(repo_path)).await
 }
 
+#[tauri::command]
+pub async fn git_diff_all(
+   repo_path: String,
+   staged: bool,
+) -> Result<Vec<git_backend::GitDiff>, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_diff_all(repo_path, staged)).await
+}
+
+#[tauri::command]
+pub async fn git_diff_stat(
+   repo_path: String,
+   staged: bool,
+) -> Result<Vec<git_backend::GitDiffStat>, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_diff_stat(repo_path, staged)).await
+}
+
 #[tauri::command]
 pub async fn git_commit_diff(
    repo_path: String,
@@ -139,6 +217,15 @@ pub async fn git_ref_diff(
    run_blocking(move || git_backend::git_ref_diff(repo_path, base_ref, target_ref)).await
 }
 
+#[tauri::command]
+pub async fn git_branch_diff(
+   repo_path: String,
+   base: Option<String>,
+) -> Result<Vec<git_backend::GitDiff>, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_branch_diff(repo_path, base)).await
+}
+
 #[tauri::command]
 pub async fn git_blame_file(
    root_path: String,
@@ -154,6 +241,11 @@ pub fn git_branches(repo_path: String) -> Result<Vec<String>, String> {
    git_backend::git_branches(resolve_backend_path(repo_path))
 }
 
+#[tauri::command]
+pub fn git_default_branch(repo_path: String) -> Result<String, String> {
+   git_backend::git_default_branch(resolve_backend_path(repo_path))
+}
+
 #[tauri::command]
 pub fn git_checkout(
    repo_path: String,
@@ -178,28 +270,38 @@ pub fn git_delete_branch(repo_path: String, branch_name: String) -> Result<(), S
 
 #[tauri::command]
 pub async fn git_push(
+   app: AppHandle,
    repo_path: String,
    branch: Option<String>,
    remote: String,
 ) -> Result<(), String> {
    let repo_path = resolve_backend_path(repo_path);
-   run_blocking(move || git_backend::git_push(repo_path, branch, remote)).await
+   let github_token = get_stored_github_token(&app);
+   run_blocking(move || git_backend::git_push(repo_path, branch, remote, github_token)).await
 }
 
 #[tauri::command]
 pub async fn git_pull(
+   app: AppHandle,
    repo_path: String,
    branch: Option<String>,
    remote: String,
 ) -> Result<(), String> {
    let repo_path = resolve_backend_path(repo_path);
-   run_blocking(move || git_backend::git_pull(repo_path, branch, remote)).await
+   let github_token = get_stored_github_token(&app);
+   run_blocking(move || git_backend::git_pull(repo_path, branch, remote, github_token)).await
 }
 
 #[tauri::command]
-pub async fn git_fetch(repo_path: String, remote: Option<String>) -> Result<(), String> {
+pub async fn git_fetch(
+   app: AppHandle,
+   repo_path: String,
+   remote: Option<String>,
+   prune: bool,
+) -> Result<String, String> {
    let repo_path = resolve_backend_path(repo_path);
-   run_blocking(move || git_backend::git_fetch(repo_path, remote)).await
+   let github_token = get_stored_github_token(&app);
+   run_blocking(move || git_backend::git_fetch(repo_path, remote, prune, github_token)).await
 }
 
 #[tauri::command]
@@ -217,6 +319,23 @@ pub fn git_remove_remote(repo_path: String, name: String) -> Result<(), String>
    git_backend::git_remove_remote(resolve_backend_path(repo_path), name)
 }
 
+#[tauri::command]
+pub async fn git_get_config(repo_path: String, key: String) -> Result<Option<String>, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_get_config(repo_path, key)).await
+}
+
+#[tauri::command]
+pub async fn git_set_config(
+   repo_path: String,
+   key: String,
+   value: String,
+   global: bool,
+) -> Result<(), String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_set_config(repo_path, key, value, global)).await
+}
+
 #[tauri::command]
 pub fn git_add(repo_path: String, file_path: String) -> Result<(), String> {
    git_backend::git_add(resolve_backend_path(repo_path), file_path)
@@ -227,6 +346,17 @@ pub fn git_reset(repo_path: String, file_path: String) -> Result<(), String> {
    git_backend::git_reset(resolve_backend_path(repo_path), file_path)
 }
 
+#[tauri::command]
+pub async fn git_reset_to(
+   repo_path: String,
+   target_ref: String,
+   mode: git_backend::ResetMode,
+   confirm: bool,
+) -> Result<(), String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_reset_to(repo_path, target_ref, mode, confirm)).await
+}
+
 #[tauri::command]
 pub fn git_add_all(repo_path: String) -> Result<(), String> {
    git_backend::git_add_all(resolve_backend_path(repo_path))
@@ -238,8 +368,12 @@ pub fn git_reset_all(repo_path: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn git_discard_file_changes(repo_path: String, file_path: String) -> Result<(), String> {
-   git_backend::git_discard_file_changes(resolve_backend_path(repo_path), file_path)
+pub fn git_discard_file_changes(
+   repo_path: String,
+   file_path: String,
+   discard_staged: bool,
+) -> Result<(), String> {
+   git_backend::git_discard_file_changes(resolve_backend_path(repo_path), file_path, discard_staged)
 }
 
 #[tauri::command]
@@ -286,9 +420,10 @@ pub fn git_drop_stash(repo_path: String, stash_index: usize) -> Result<(), Strin
 pub async fn git_stash_diff(
    repo_path: String,
    stash_index: usize,
+   file_path: Option<String>,
 ) -> Result<Vec<git_backend::GitDiff>, String> {
    let repo_path = resolve_backend_path(repo_path);
-   run_blocking(move || git_backend::git_stash_diff(repo_path, stash_index)).await
+   run_blocking(move || git_backend::git_stash_diff(repo_path, stash_index, file_path)).await
 }
 
 #[tauri::command]
@@ -319,19 +454,28 @@ pub fn git_delete_tag(repo_path: String, name: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn git_push_tag(repo_path: String, name: String, remote: String) -> Result<(), String> {
+pub async fn git_push_tag(
+   app: AppHandle,
+   repo_path: String,
+   name: String,
+   remote: String,
+) -> Result<(), String> {
    let repo_path = resolve_backend_path(repo_path);
-   run_blocking(move || git_backend::git_push_tag(repo_path, name, remote)).await
+   let github_token = get_stored_github_token(&app);
+   run_blocking(move || git_backend::git_push_tag(repo_path, name, remote, github_token)).await
 }
 
 #[tauri::command]
 pub async fn git_delete_remote_tag(
+   app: AppHandle,
    repo_path: String,
    name: String,
    remote: String,
 ) -> Result<(), String> {
    let repo_path = resolve_backend_path(repo_path);
-   run_blocking(move || git_backend::git_delete_remote_tag(repo_path, name, remote)).await
+   let github_token = get_stored_github_token(&app);
+   run_blocking(move || git_backend::git_delete_remote_tag(repo_path, name, remote, github_token))
+      .await
 }
 
 #[tauri::command]
@@ -367,6 +511,43 @@ pub fn git_prune_worktrees(repo_path: String) -> Result<(), String> {
    git_backend::git_prune_worktrees(resolve_backend_path(repo_path))
 }
 
+#[tauri::command]
+pub fn git_operation_state(repo_path: String) -> Result<git_backend::GitOperationState, String> {
+   git_backend::git_operation_state(resolve_backend_path(repo_path))
+}
+
+#[tauri::command]
+pub async fn git_abort_operation(
+   repo_path: String,
+) -> Result<git_backend::GitOperationState, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_abort_operation(repo_path)).await
+}
+
+#[tauri::command]
+pub async fn git_continue_operation(
+   repo_path: String,
+) -> Result<git_backend::CommitOperationResult, String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_continue_operation(repo_path)).await
+}
+
+#[tauri::command]
+pub fn git_submodules(repo_path: String) -> Result<Vec<git_backend::GitSubmodule>, String> {
+   git_backend::git_submodules(resolve_backend_path(repo_path))
+}
+
+#[tauri::command]
+pub async fn git_submodule_update(
+   repo_path: String,
+   path: Option<String>,
+   init: bool,
+   recursive: bool,
+) -> Result<(), String> {
+   let repo_path = resolve_backend_path(repo_path);
+   run_blocking(move || git_backend::git_submodule_update(repo_path, path, init, recursive)).await
+}
+
 #[tauri::command]
 pub fn git_stage_hunk(repo_path: String, hunk: git_backend::GitHunk) -> Result<(), String> {
    git_backend::git_stage_hunk(resolve_backend_path(repo_path), hunk)
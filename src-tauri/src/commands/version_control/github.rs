@@ -14,7 +14,7 @@ where
       .map_err(|error| format!("GitHub command task failed: {}", error))?
 }
 
-fn get_stored_github_token(app: &crate::app_runtime::AppHandle) -> Option<String> {
+pub(super) fn get_stored_github_token(app: &crate::app_runtime::AppHandle) -> Option<String> {
    get_secret(app, "github_token")
       .ok()
       .flatten()
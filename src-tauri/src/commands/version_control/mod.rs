@@ -1,5 +1,7 @@
 pub mod git;
 pub mod github;
+pub mod text_diff;
 
 pub use git::*;
 pub use github::*;
+pub use text_diff::*;
@@ -0,0 +1,30 @@
+use athas_version_control::{GitDiffLine, text_diff};
+use std::path::Path;
+
+/// Diff an editor buffer against the saved file, for files inside or outside a repository
+#[tauri::command]
+pub async fn diff_buffer_against_disk(
+   path: String,
+   buffer_content: String,
+) -> Result<Vec<GitDiffLine>, String> {
+   let path = athas_wsl::resolve_windows_path(&path).unwrap_or(path);
+   tauri::async_runtime::spawn_blocking(move || {
+      text_diff::diff_buffer_against_disk(Path::new(&path), &buffer_content)
+   })
+   .await
+   .map_err(|e| format!("Diff task failed: {}", e))?
+}
+
+/// Diff two arbitrary texts, such as pasted snippets, in the shape of a git file diff. Large
+/// texts take a while to diff, so this runs off the async runtime.
+#[tauri::command]
+pub async fn diff_texts(
+   old: String,
+   new: String,
+   context_lines: Option<usize>,
+) -> Result<Vec<GitDiffLine>, String> {
+   let context_lines = context_lines.unwrap_or(text_diff::DEFAULT_CONTEXT_LINES);
+   tauri::async_runtime::spawn_blocking(move || text_diff::diff_texts(&old, &new, context_lines))
+      .await
+      .map_err(|e| format!("Diff task failed: {}", e))
+}
@@ -1,4 +1,4 @@
-use athas_project::{FileChangeEmitter, FileChangeEvent};
+use athas_project::{FileChangeEmitter, FileChangeEvent, FileChangeType};
 use tauri::{AppHandle, Emitter, Runtime};
 
 pub struct TauriFileChangeEmitter<R: Runtime> {
@@ -14,5 +14,12 @@ impl<R: Runtime> TauriFileChangeEmitter<R> {
 impl<R: Runtime> FileChangeEmitter for TauriFileChangeEmitter<R> {
    fn emit_file_change(&self, event: &FileChangeEvent) {
       let _ = self.app_handle.emit("file-changed", event);
+
+      let specific_event = match event.event_type {
+         FileChangeType::Created => "file-created",
+         FileChangeType::Deleted => "file-deleted",
+         FileChangeType::Opened | FileChangeType::Reloaded => return,
+      };
+      let _ = self.app_handle.emit(specific_event, &event.path);
    }
 }
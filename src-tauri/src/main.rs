@@ -19,6 +19,7 @@ mod commands;
 mod file_events;
 mod logger;
 mod menu;
+mod menu_commands;
 mod secure_storage;
 mod service_urls;
 mod terminal;
@@ -60,10 +61,18 @@ fn main() {
          // File system commands
          read_athas_log,
          read_local_file,
-         open_file_external,
+         write_local_file,
+         read_directory_filtered,
+         read_directory_recursive,
+         count_file_stats,
+         open_externally,
+         get_file_category,
+         reveal_in_file_manager,
          open_folder_dialog,
          move_file,
          rename_file,
+         create_scratch_file,
+         cleanup_scratch_files,
          get_symlink_info,
          local_history_record_file,
          local_history_list_file,
@@ -75,21 +84,34 @@ fn main() {
          clipboard_get,
          clipboard_clear,
          clipboard_paste,
+         read_clipboard_image,
+         write_clipboard_text,
          // Git commands
          git_status,
          git_discover_repo,
+         git_check_ignore,
          git_add,
          git_reset,
+         git_reset_to,
          git_commit,
+         git_revert,
+         git_cherry_pick,
          git_add_all,
          git_reset_all,
          git_log,
+         git_commit_graph,
          git_diff_file,
          git_diff_file_with_content,
+         diff_buffer_against_disk,
+         diff_texts,
          git_status_diff_stats,
+         git_diff_stat,
+         git_diff_all,
          git_commit_diff,
          git_ref_diff,
+         git_branch_diff,
          git_branches,
+         git_default_branch,
          git_checkout,
          git_create_branch,
          git_delete_branch,
@@ -99,9 +121,12 @@ fn main() {
          git_pull,
          git_fetch,
          git_init,
+         git_clone,
          git_get_remotes,
          git_add_remote,
          git_remove_remote,
+         git_get_config,
+         git_set_config,
          git_get_stashes,
          git_create_stash,
          git_apply_stash,
@@ -118,6 +143,11 @@ fn main() {
          git_add_worktree,
          git_remove_worktree,
          git_prune_worktrees,
+         git_submodules,
+         git_submodule_update,
+         git_operation_state,
+         git_abort_operation,
+         git_continue_operation,
          git_stage_hunk,
          git_unstage_hunk,
          git_blame_file,
@@ -170,6 +200,8 @@ fn main() {
          uses_native_window_chrome,
          set_macos_window_appearance,
          set_window_transparency_enabled,
+         save_workspace_layout,
+         load_workspace_layout,
          create_embedded_webview,
          close_embedded_webview,
          close_all_embedded_webviews,
@@ -184,6 +216,8 @@ fn main() {
          start_watching,
          stop_watching,
          set_project_root,
+         watch_workspace,
+         unwatch_workspace,
          store_remote_credential,
          get_remote_credential,
          remove_remote_credential,
@@ -208,6 +242,9 @@ fn main() {
          ssh_read_directory,
          ssh_read_file,
          ssh_get_connected_ids,
+         ssh_forward_local_port,
+         ssh_close_forward,
+         ssh_list_forwards,
          create_remote_terminal,
          remote_terminal_write,
          remote_terminal_resize,
@@ -232,6 +269,8 @@ fn main() {
          install_acp_agent,
          uninstall_acp_agent,
          start_acp_agent,
+         set_acp_agent_mcp_servers,
+         new_acp_session,
          stop_acp_agent,
          send_acp_prompt,
          get_acp_status,
@@ -242,17 +281,20 @@ fn main() {
          delete_acp_session,
          logout_acp_agent,
          cancel_acp_prompt,
+         list_acp_slash_commands,
          // Theme commands
          get_system_theme,
          load_toml_themes,
          load_single_toml_theme,
+         load_user_themes,
+         watch_user_themes,
          get_cached_themes,
          cache_themes,
          get_temp_dir,
          write_temp_file,
          delete_temp_file,
          // Font commands
-         get_system_fonts,
+         list_system_fonts,
          get_monospace_fonts,
          validate_font,
          // Database provider sidecar commands
@@ -268,28 +310,41 @@ fn main() {
          save_connection,
          list_saved_connections,
          delete_saved_connection,
+         // SQL formatting
+         format_sql,
          // LSP commands
          lsp_start,
          lsp_stop,
+         lsp_restart_all,
+         lsp_stop_all,
+         lsp_add_workspace_root,
+         lsp_remove_workspace_root,
+         lsp_get_workspace_roots,
          lsp_start_for_file,
          lsp_stop_for_file,
          lsp_get_completions,
+         lsp_cancel_request,
          lsp_get_hover,
          lsp_get_definition,
          lsp_get_implementation,
          lsp_get_type_definition,
          lsp_get_semantic_tokens,
          lsp_get_code_lens,
+         lsp_folding_ranges,
          lsp_format_document,
          lsp_format_range,
          lsp_get_inlay_hints,
          lsp_get_document_symbols,
          lsp_get_workspace_symbols,
+         lsp_workspace_symbols,
          lsp_get_signature_help,
          lsp_get_signature_trigger_characters,
          lsp_get_references,
+         lsp_document_highlights,
          lsp_rename,
          lsp_prepare_rename,
+         lsp_apply_workspace_edit,
+         lsp_pull_diagnostics,
          lsp_get_code_actions,
          lsp_apply_code_action,
          lsp_document_open,
@@ -297,6 +352,9 @@ fn main() {
          lsp_document_save,
          lsp_document_close,
          lsp_is_language_supported,
+         lsp_get_server_logs,
+         lsp_server_status,
+         lsp_get_capabilities,
          // Debugger commands
          debug_start_session,
          debug_send_request,
@@ -316,18 +374,28 @@ fn main() {
          // Fuzzy matching commands
          fuzzy_match,
          fff_ensure_workspaces,
+         fff_refresh_workspaces,
          fff_search_files,
          fff_scan_status,
          fff_list_files,
          fff_track_access,
          // Search commands
          search_files_content,
+         search_files_stream,
+         search_cancel,
+         replace_in_files,
+         resolve_position,
+         offset_to_position,
          // EditorConfig commands
          get_editorconfig_properties,
          // Format commands
          format_code,
+         format_changed_ranges,
          // Lint commands
          lint_code,
+         run_cargo_check,
+         cancel_cargo_check,
+         run_go_lint,
          // Notebook commands
          notebook_run_python_cell,
          notebook_run_r_cell,
@@ -342,6 +410,8 @@ fn main() {
          ensure_runtime,
          get_runtime_status,
          get_runtime_version,
+         install_runtime,
+         uninstall_runtime,
          get_js_runtime,
          get_all_runtime_statuses,
          // Docker commands
@@ -350,6 +420,8 @@ fn main() {
          docker_get_container_logs,
          docker_start_container_log_stream,
          docker_stop_container_log_stream,
+         run_project_task,
+         cancel_task,
          docker_get_compose_project,
          docker_compose_action,
          docker_build_image,
@@ -372,15 +444,20 @@ fn main() {
          docker_delete_env_file,
          docker_open_dev_container,
          // Tool commands
+         reload_tool_registry,
          install_language_tools,
          install_tool,
          get_language_tool_status,
          get_tool_path,
          get_available_tools,
+         detect_language,
          frontend_trace,
          // Menu commands
          menu::toggle_menu_bar,
          menu::rebuild_menu_themes,
+         menu::rebuild_menu,
+         menu_commands::list_menu_commands,
+         menu_commands::reload_keybindings,
       ])
       .build(tauri::generate_context!())
       .expect("error while building tauri application")
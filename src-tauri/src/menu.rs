@@ -1,26 +1,45 @@
+use crate::menu_commands::{accelerator, menu_command};
 use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
 use tauri::menu::{
    AboutMetadata, HELP_SUBMENU_ID, MenuBuilder, MenuItem, Submenu, SubmenuBuilder,
    WINDOW_SUBMENU_ID,
 };
 use tauri_plugin_store::StoreExt;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeData {
    pub id: String,
    pub name: String,
    pub category: String,
 }
 
+/// Themes last sent by the frontend, reused whenever the menu is rebuilt from the backend.
+static MENU_THEMES: Mutex<Option<Vec<ThemeData>>> = Mutex::new(None);
+/// Ids of the entries currently in the theme submenu. Menu event ids are the theme ids.
+static MENU_THEME_IDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
 #[tauri::command]
 pub async fn rebuild_menu_themes(
    app: crate::app_runtime::AppHandle,
    themes: Vec<ThemeData>,
 ) -> Result<(), String> {
+   if let Ok(mut menu_themes) = MENU_THEMES.lock() {
+      *menu_themes = Some(themes);
+   }
+   rebuild_native_menu(&app)
+}
+
+/// Regenerate the native menu, picking up themes added since it was last built
+#[tauri::command]
+pub async fn rebuild_menu(app: crate::app_runtime::AppHandle) -> Result<(), String> {
+   rebuild_native_menu(&app)
+}
+
+pub fn rebuild_native_menu<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<(), String> {
    // Only rebuild menu if native menu bar is enabled
    if app.menu().is_some() {
-      let new_menu = create_menu_with_themes(&app, Some(themes))
-         .map_err(|e| format!("Failed to create menu: {}", e))?;
+      let new_menu = create_menu(app).map_err(|e| format!("Failed to create menu: {}", e))?;
       app.set_menu(new_menu)
          .map_err(|e| format!("Failed to set menu: {}", e))?;
    } else {
@@ -29,6 +48,13 @@ pub async fn rebuild_menu_themes(
    Ok(())
 }
 
+/// Whether a menu event id belongs to an entry in the theme submenu.
+pub fn is_menu_theme(id: &str) -> bool {
+   MENU_THEME_IDS
+      .lock()
+      .is_ok_and(|theme_ids| theme_ids.iter().any(|theme_id| theme_id == id))
+}
+
 #[tauri::command]
 pub async fn toggle_menu_bar(
    app: crate::app_runtime::AppHandle,
@@ -62,8 +88,7 @@ pub async fn toggle_menu_bar(
 
       if should_show_menu {
          // Show menu by recreating it
-         let new_menu = create_menu_with_themes(&app, None)
-            .map_err(|e| format!("Failed to create menu: {}", e))?;
+         let new_menu = create_menu(&app).map_err(|e| format!("Failed to create menu: {}", e))?;
          app.set_menu(new_menu)
             .map_err(|e| format!("Failed to show menu: {}", e))?;
          log::info!("Menu bar shown via command");
@@ -95,16 +120,33 @@ fn build_theme_submenu<R: tauri::Runtime>(
 ) -> Result<Submenu<R>, tauri::Error> {
    let mut theme_builder = SubmenuBuilder::new(app, "Theme");
 
+   let mut listed_ids = Vec::new();
    if let Some(theme_list) = themes {
       // Add all themes without grouping
       for theme in &theme_list {
          theme_builder = theme_builder.text(&theme.id, &theme.name);
+         listed_ids.push(theme.id.clone());
       }
    } else {
       // Fallback to hardcoded themes if none provided
       theme_builder = theme_builder
          .text("one-light", "One Light")
-         .text("one-dark", "One Dark")
+         .text("one-dark", "One Dark");
+      listed_ids.extend(["one-light".to_string(), "one-dark".to_string()]);
+   }
+
+   // Themes dropped into the user themes folder that the caller did not already list
+   if let Ok(themes_dir) = crate::commands::user_themes_dir(app) {
+      for theme in crate::commands::load_user_themes_from_dir(&themes_dir) {
+         if !listed_ids.contains(&theme.id) {
+            theme_builder = theme_builder.text(&theme.id, &theme.name);
+            listed_ids.push(theme.id);
+         }
+      }
+   }
+
+   if let Ok(mut theme_ids) = MENU_THEME_IDS.lock() {
+      *theme_ids = listed_ids;
    }
 
    theme_builder.build()
@@ -131,20 +173,8 @@ fn build_app_submenu<R: tauri::Runtime>(
    SubmenuBuilder::new(app, "Athas")
       .about_with_text("About Athas", Some(about_metadata))
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "open_settings",
-         "Settings...",
-         true,
-         Some("Cmd+,"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "check_updates",
-         "Check for Updates...",
-         true,
-         None::<String>,
-      )?)
+      .item(&command_item(app, "open_settings")?)
+      .item(&command_item(app, "check_updates")?)
       .separator()
       .services()
       .separator()
@@ -152,110 +182,64 @@ fn build_app_submenu<R: tauri::Runtime>(
       .hide_others()
       .show_all()
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "quit_app",
-         "Quit Athas",
-         true,
-         Some("Cmd+Q"),
-      )?)
+      .item(&command_item(app, "quit_app")?)
       .build()
 }
 
+/// Menu item for the command `id`, with the label from its spec and the user's accelerator
+fn command_item<R: tauri::Runtime>(
+   app: &tauri::AppHandle<R>,
+   id: &str,
+) -> Result<MenuItem<R>, tauri::Error> {
+   let Some(command) = menu_command(id) else {
+      log::warn!("Menu command '{}' has no spec on this platform", id);
+      return MenuItem::with_id(app, id, id, true, None::<&str>);
+   };
+   MenuItem::with_id(
+      app,
+      command.id,
+      command.label,
+      command.enabled,
+      accelerator(command),
+   )
+}
+
 pub fn create_menu<R: tauri::Runtime>(
    app: &tauri::AppHandle<R>,
 ) -> Result<tauri::menu::Menu<R>, tauri::Error> {
-   create_menu_with_themes(app, None)
+   let themes = MENU_THEMES
+      .lock()
+      .ok()
+      .and_then(|menu_themes| menu_themes.clone());
+   create_menu_with_themes(app, themes)
 }
 
 pub fn create_menu_with_themes<R: tauri::Runtime>(
    app: &tauri::AppHandle<R>,
    themes: Option<Vec<ThemeData>>,
 ) -> Result<tauri::menu::Menu<R>, tauri::Error> {
-   let close_tab_accelerator = close_tab_accelerator();
-
    // Unified File menu for all platforms - clean and consistent
    let file_menu_builder = SubmenuBuilder::new(app, "File")
-      .item(&MenuItem::with_id(
-         app,
-         "command_new_tab",
-         "New Tab",
-         true,
-         Some("CmdOrCtrl+N"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "new_window",
-         "New Window",
-         true,
-         Some("CmdOrCtrl+Shift+N"),
-      )?)
+      .item(&command_item(app, "command_new_tab")?)
+      .item(&command_item(app, "new_window")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "new_file",
-         "New File",
-         true,
-         None::<String>,
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "open_folder",
-         "Open Folder",
-         true,
-         Some("CmdOrCtrl+O"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "close_folder",
-         "Close Folder",
-         true,
-         None::<String>,
-      )?)
+      .item(&command_item(app, "new_file")?)
+      .item(&command_item(app, "open_folder")?)
+      .item(&command_item(app, "close_folder")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "save",
-         "Save",
-         true,
-         Some("CmdOrCtrl+S"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "save_as",
-         "Save As...",
-         true,
-         Some("CmdOrCtrl+Shift+S"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_save_all",
-         "Save All",
-         true,
-         Some("CmdOrCtrl+Option+S"),
-      )?)
-      .text("command_revert_file", "Revert File")
-      .text("command_local_history", "Show Local History")
+      .item(&command_item(app, "save")?)
+      .item(&command_item(app, "save_as")?)
+      .item(&command_item(app, "command_save_all")?)
+      .item(&command_item(app, "command_revert_file")?)
+      .item(&command_item(app, "command_local_history")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "close_tab",
-         "Close Tab",
-         true,
-         close_tab_accelerator,
-      )?)
-      .text("command_close_all_tabs", "Close All Tabs")
-      .text("command_close_other_tabs", "Close Other Tabs")
-      .text("command_close_saved_tabs", "Close Saved Tabs")
-      .text("command_close_tabs_to_left", "Close Tabs to the Left")
-      .text("command_close_tabs_to_right", "Close Tabs to the Right")
-      .item(&MenuItem::with_id(
-         app,
-         "command_reopen_closed_tab",
-         "Reopen Closed Tab",
-         true,
-         Some("CmdOrCtrl+Shift+T"),
-      )?);
+      .item(&command_item(app, "close_tab")?)
+      .item(&command_item(app, "command_close_all_tabs")?)
+      .item(&command_item(app, "command_close_other_tabs")?)
+      .item(&command_item(app, "command_close_saved_tabs")?)
+      .item(&command_item(app, "command_close_tabs_to_left")?)
+      .item(&command_item(app, "command_close_tabs_to_right")?)
+      .item(&command_item(app, "command_reopen_closed_tab")?);
 
    #[cfg(target_os = "macos")]
    let file_menu = file_menu_builder.build()?;
@@ -263,100 +247,34 @@ pub fn create_menu_with_themes<R: tauri::Runtime>(
    #[cfg(not(target_os = "macos"))]
    let file_menu = file_menu_builder
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "quit_app",
-         "Quit",
-         true,
-         Some("CmdOrCtrl+Q"),
-      )?)
+      .item(&command_item(app, "quit_app")?)
       .build()?;
 
    // Edit menu with native macOS items
    let edit_menu = SubmenuBuilder::new(app, "Edit")
-      .item(&MenuItem::with_id(
-         app,
-         "undo",
-         "Undo",
-         true,
-         Some("CmdOrCtrl+Z"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "redo",
-         "Redo",
-         true,
-         Some("CmdOrCtrl+Shift+Z"),
-      )?)
+      .item(&command_item(app, "undo")?)
+      .item(&command_item(app, "redo")?)
       .separator()
       .cut()
       .copy()
       .paste()
-      .item(&MenuItem::with_id(
-         app,
-         "select_all",
-         "Select All",
-         true,
-         Some("CmdOrCtrl+A"),
-      )?)
+      .item(&command_item(app, "select_all")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "find",
-         "Find",
-         true,
-         Some("CmdOrCtrl+F"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "find_replace",
-         "Find and Replace",
-         true,
-         Some("CmdOrCtrl+Option+F"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "toggle_comment",
-         "Toggle Comment",
-         true,
-         Some("CmdOrCtrl+Slash"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_quick_fix",
-         "Quick Fix",
-         true,
-         Some("CmdOrCtrl+."),
-      )?)
-      .text("command_trigger_parameter_hints", "Trigger Parameter Hints")
-      .text("command_show_hover", "Show Hover")
+      .item(&command_item(app, "find")?)
+      .item(&command_item(app, "find_replace")?)
+      .item(&command_item(app, "toggle_comment")?)
+      .item(&command_item(app, "command_quick_fix")?)
+      .item(&command_item(app, "command_trigger_parameter_hints")?)
+      .item(&command_item(app, "command_show_hover")?)
       .separator()
-      .text("command_duplicate_line", "Duplicate Line")
-      .item(&MenuItem::with_id(
-         app,
-         "command_delete_line",
-         "Delete Line",
-         true,
-         Some("CmdOrCtrl+Shift+K"),
-      )?)
-      .text("command_move_line_up", "Move Line Up")
-      .text("command_move_line_down", "Move Line Down")
-      .item(&MenuItem::with_id(
-         app,
-         "command_format_document",
-         "Format Document",
-         true,
-         Some("Shift+Alt+F"),
-      )?)
-      .text("command_format_selection", "Format Selection")
+      .item(&command_item(app, "command_duplicate_line")?)
+      .item(&command_item(app, "command_delete_line")?)
+      .item(&command_item(app, "command_move_line_up")?)
+      .item(&command_item(app, "command_move_line_down")?)
+      .item(&command_item(app, "command_format_document")?)
+      .item(&command_item(app, "command_format_selection")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "command_palette",
-         "Command Palette",
-         true,
-         command_palette_accelerator(),
-      )?)
+      .item(&command_item(app, "command_palette")?)
       .build()?;
 
    // Theme submenu - built dynamically from theme data
@@ -364,241 +282,88 @@ pub fn create_menu_with_themes<R: tauri::Runtime>(
 
    // View menu
    let view_menu = SubmenuBuilder::new(app, "View")
-      .item(&MenuItem::with_id(
-         app,
-         "toggle_sidebar",
-         "Toggle Sidebar",
-         true,
-         Some("CmdOrCtrl+B"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "toggle_terminal",
-         "Toggle Terminal",
-         true,
-         Some("CmdOrCtrl+J"),
-      )?)
+      .item(&command_item(app, "toggle_sidebar")?)
+      .item(&command_item(app, "toggle_terminal")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "command_global_search",
-         "Global Search",
-         true,
-         Some("CmdOrCtrl+Shift+F"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_diagnostics",
-         "Diagnostics",
-         true,
-         Some("CmdOrCtrl+Shift+J"),
-      )?)
+      .item(&command_item(app, "command_global_search")?)
+      .item(&command_item(app, "command_diagnostics")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "command_file_explorer",
-         "File Explorer",
-         true,
-         Some("CmdOrCtrl+Shift+E"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_source_control",
-         "Source Control",
-         true,
-         Some("CmdOrCtrl+Shift+G"),
-      )?)
-      .text("command_github", "GitHub")
-      .text("command_debugger", "Run and Debug")
+      .item(&command_item(app, "command_file_explorer")?)
+      .item(&command_item(app, "command_source_control")?)
+      .item(&command_item(app, "command_github")?)
+      .item(&command_item(app, "command_debugger")?)
       .separator()
-      .text("split_editor", "Split Editor")
-      .text("command_toggle_minimap", "Toggle Minimap")
-      .item(&MenuItem::with_id(
-         app,
-         "command_toggle_word_wrap",
-         "Toggle Word Wrap",
-         true,
-         Some("Alt+Z"),
-      )?)
-      .text("command_toggle_line_numbers", "Toggle Line Numbers")
-      .text(
-         "command_toggle_render_whitespace",
-         "Toggle Render Whitespace",
-      )
+      .item(&command_item(app, "split_editor")?)
+      .item(&command_item(app, "command_toggle_minimap")?)
+      .item(&command_item(app, "command_toggle_word_wrap")?)
+      .item(&command_item(app, "command_toggle_line_numbers")?)
+      .item(&command_item(app, "command_toggle_render_whitespace")?)
       .separator()
-      .text("command_zoom_in", "Zoom In")
-      .text("command_zoom_out", "Zoom Out")
-      .text("command_zoom_reset", "Reset Zoom")
+      .item(&command_item(app, "command_zoom_in")?)
+      .item(&command_item(app, "command_zoom_out")?)
+      .item(&command_item(app, "command_zoom_reset")?)
       .separator()
       .item(&theme_menu)
       .build()?;
 
    // Go menu with navigation shortcuts
    let go_menu = SubmenuBuilder::new(app, "Go")
-      .item(&MenuItem::with_id(
-         app,
-         "quick_open",
-         "Quick Open",
-         true,
-         Some("CmdOrCtrl+P"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "go_to_line",
-         "Go to Line",
-         true,
-         Some("CmdOrCtrl+G"),
-      )?)
+      .item(&command_item(app, "quick_open")?)
+      .item(&command_item(app, "go_to_line")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "command_go_back",
-         "Go Back",
-         true,
-         None::<String>,
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_go_forward",
-         "Go Forward",
-         true,
-         None::<String>,
-      )?)
+      .item(&command_item(app, "command_go_back")?)
+      .item(&command_item(app, "command_go_forward")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "command_go_to_definition",
-         "Go to Definition",
-         true,
-         Some("F12"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_go_to_implementation",
-         "Go to Implementation",
-         true,
-         Some("CmdOrCtrl+F12"),
-      )?)
-      .text("command_go_to_type_definition", "Go to Type Definition")
-      .item(&MenuItem::with_id(
-         app,
-         "command_go_to_references",
-         "Go to References",
-         true,
-         Some("Shift+F12"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_rename_symbol",
-         "Rename Symbol",
-         true,
-         Some("F2"),
-      )?)
+      .item(&command_item(app, "command_go_to_definition")?)
+      .item(&command_item(app, "command_go_to_implementation")?)
+      .item(&command_item(app, "command_go_to_type_definition")?)
+      .item(&command_item(app, "command_go_to_references")?)
+      .item(&command_item(app, "command_rename_symbol")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "next_tab",
-         "Next Tab",
-         true,
-         Some("CmdOrCtrl+Option+Right"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "prev_tab",
-         "Previous Tab",
-         true,
-         Some("CmdOrCtrl+Option+Left"),
-      )?)
+      .item(&command_item(app, "next_tab")?)
+      .item(&command_item(app, "prev_tab")?)
       .build()?;
 
    // Terminal menu
    let terminal_menu = SubmenuBuilder::new(app, "Terminal")
-      .text("command_new_terminal", "New Terminal")
-      .text("command_split_terminal", "Split Terminal")
-      .text("command_close_terminal", "Close Terminal")
+      .item(&command_item(app, "command_new_terminal")?)
+      .item(&command_item(app, "command_split_terminal")?)
+      .item(&command_item(app, "command_close_terminal")?)
       .build()?;
 
    // Run menu
    let run_menu = SubmenuBuilder::new(app, "Run")
-      .item(&MenuItem::with_id(
-         app,
-         "command_start_debugging",
-         "Start Debugging",
-         true,
-         Some("F5"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_stop_debugging",
-         "Stop Debugging",
-         true,
-         Some("Shift+F5"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_toggle_breakpoint",
-         "Toggle Breakpoint",
-         true,
-         Some("F9"),
-      )?)
+      .item(&command_item(app, "command_start_debugging")?)
+      .item(&command_item(app, "command_stop_debugging")?)
+      .item(&command_item(app, "command_toggle_breakpoint")?)
       .build()?;
 
    // Agent menu
    let ai_menu = SubmenuBuilder::new(app, "Agent")
-      .item(&MenuItem::with_id(
-         app,
-         "toggle_ai_chat",
-         "Toggle Agent",
-         true,
-         Some("CmdOrCtrl+R"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_new_agent",
-         "New Agent",
-         true,
-         Some("CmdOrCtrl+Shift+Space"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "command_inline_edit",
-         "Inline Edit",
-         true,
-         Some("CmdOrCtrl+I"),
-      )?)
+      .item(&command_item(app, "toggle_ai_chat")?)
+      .item(&command_item(app, "command_new_agent")?)
+      .item(&command_item(app, "command_inline_edit")?)
       .build()?;
 
    #[cfg(target_os = "macos")]
    let tools_menu = SubmenuBuilder::new(app, "Tools")
-      .text("command_connect_database", "Connect to Database")
+      .item(&command_item(app, "command_connect_database")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "open_web_inspector",
-         "Web Inspector",
-         cfg!(any(debug_assertions, feature = "devtools")),
-         Some("CmdOrCtrl+Option+I"),
-      )?)
+      .item(&command_item(app, "open_web_inspector")?)
       .separator()
-      .text("open_extensions", "Extensions")
-      .text("command_keyboard_shortcuts", "Keyboard Shortcuts")
+      .item(&command_item(app, "open_extensions")?)
+      .item(&command_item(app, "command_keyboard_shortcuts")?)
       .build()?;
 
    #[cfg(not(target_os = "macos"))]
    let tools_menu = SubmenuBuilder::new(app, "Tools")
-      .text("command_connect_database", "Connect to Database")
+      .item(&command_item(app, "command_connect_database")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "open_web_inspector",
-         "Web Inspector",
-         cfg!(any(debug_assertions, feature = "devtools")),
-         Some("CmdOrCtrl+Option+I"),
-      )?)
+      .item(&command_item(app, "open_web_inspector")?)
       .separator()
-      .text("open_settings", "Preferences")
-      .text("open_extensions", "Extensions")
-      .text("command_keyboard_shortcuts", "Keyboard Shortcuts")
+      .item(&command_item(app, "open_settings")?)
+      .item(&command_item(app, "open_extensions")?)
+      .item(&command_item(app, "command_keyboard_shortcuts")?)
       .build()?;
 
    // Window menu
@@ -608,57 +373,27 @@ pub fn create_menu_with_themes<R: tauri::Runtime>(
       .maximize()
       .fullscreen()
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "close_window",
-         "Close Window",
-         true,
-         Some("Cmd+Shift+W"),
-      )?)
+      .item(&command_item(app, "close_window")?)
       .build()?;
 
    #[cfg(not(target_os = "macos"))]
    let window_menu = SubmenuBuilder::new(app, "Window")
-      .item(&MenuItem::with_id(
-         app,
-         "minimize_window",
-         "Minimize",
-         true,
-         Some("Alt+F9"),
-      )?)
-      .item(&MenuItem::with_id(
-         app,
-         "maximize_window",
-         "Maximize",
-         true,
-         Some("Alt+F10"),
-      )?)
+      .item(&command_item(app, "minimize_window")?)
+      .item(&command_item(app, "maximize_window")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "toggle_menu_bar",
-         "Toggle Menu Bar",
-         true,
-         Some("Alt+M"),
-      )?)
+      .item(&command_item(app, "toggle_menu_bar")?)
       .separator()
-      .item(&MenuItem::with_id(
-         app,
-         "toggle_fullscreen",
-         "Toggle Fullscreen",
-         true,
-         Some("F11"),
-      )?)
+      .item(&command_item(app, "toggle_fullscreen")?)
       .build()?;
 
    let help_menu_builder = SubmenuBuilder::with_id(app, HELP_SUBMENU_ID, "Help")
-      .text("documentation", "Documentation")
-      .text("command_help_keyboard_shortcuts", "Keyboard Shortcuts")
-      .text("whats_new", "What's New")
-      .text("changelog", "Changelog")
+      .item(&command_item(app, "documentation")?)
+      .item(&command_item(app, "command_help_keyboard_shortcuts")?)
+      .item(&command_item(app, "whats_new")?)
+      .item(&command_item(app, "changelog")?)
       .separator()
-      .text("report_bug", "Report a Bug")
-      .text("request_feature", "Request a Feature");
+      .item(&command_item(app, "report_bug")?)
+      .item(&command_item(app, "request_feature")?);
 
    #[cfg(target_os = "macos")]
    let help_menu = help_menu_builder.build()?;
@@ -666,7 +401,7 @@ pub fn create_menu_with_themes<R: tauri::Runtime>(
    #[cfg(not(target_os = "macos"))]
    let help_menu = help_menu_builder
       .separator()
-      .text("check_updates", "Check for Updates")
+      .item(&command_item(app, "check_updates")?)
       .build()?;
 
    #[cfg(target_os = "macos")]
@@ -708,27 +443,3 @@ pub fn create_menu_with_themes<R: tauri::Runtime>(
          .build()
    }
 }
-
-fn close_tab_accelerator() -> Option<&'static str> {
-   #[cfg(target_os = "linux")]
-   {
-      None
-   }
-
-   #[cfg(not(target_os = "linux"))]
-   {
-      Some("CmdOrCtrl+W")
-   }
-}
-
-fn command_palette_accelerator() -> Option<&'static str> {
-   #[cfg(target_os = "macos")]
-   {
-      Some("Cmd+Shift+P")
-   }
-
-   #[cfg(not(target_os = "macos"))]
-   {
-      None
-   }
-}
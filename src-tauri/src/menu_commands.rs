@@ -0,0 +1,517 @@
+use serde::Serialize;
+use std::{collections::BTreeMap, fs, sync::Mutex};
+use tauri::Manager;
+
+/// Accelerator overrides from the user's `keybindings.json`, keyed by command id. A `None`
+/// value removes the default accelerator.
+static KEYBINDINGS: Mutex<BTreeMap<String, Option<String>>> = Mutex::new(BTreeMap::new());
+
+const MODIFIERS: &[&str] = &[
+   "cmdorctrl",
+   "cmdorcontrol",
+   "commandorctrl",
+   "commandorcontrol",
+   "cmd",
+   "command",
+   "super",
+   "ctrl",
+   "control",
+   "alt",
+   "option",
+   "shift",
+];
+
+const NAMED_KEYS: &[&str] = &[
+   "space",
+   "tab",
+   "enter",
+   "return",
+   "backspace",
+   "delete",
+   "insert",
+   "escape",
+   "esc",
+   "up",
+   "down",
+   "left",
+   "right",
+   "home",
+   "end",
+   "pageup",
+   "pagedown",
+   "plus",
+   "minus",
+   "equal",
+   "comma",
+   "period",
+   "slash",
+   "backslash",
+   "semicolon",
+   "quote",
+   "backquote",
+   "bracketleft",
+   "bracketright",
+];
+
+/// A native menu entry the frontend can list and trigger, e.g. from the command palette
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuCommand {
+   pub id: String,
+   pub label: String,
+   pub accelerator: Option<String>,
+   pub default_accelerator: Option<String>,
+}
+
+/// Label and default accelerator of a menu entry. `create_menu` builds its items from these,
+/// so the native menu and `list_menu_commands` cannot drift apart.
+#[derive(Debug, Clone, Copy)]
+pub struct MenuCommandSpec {
+   pub id: &'static str,
+   pub label: &'static str,
+   pub accelerator: Option<&'static str>,
+   pub enabled: bool,
+   /// Whether the entry is part of the menu on this platform
+   available: bool,
+}
+
+const fn command(
+   id: &'static str,
+   label: &'static str,
+   accelerator: Option<&'static str>,
+) -> MenuCommandSpec {
+   MenuCommandSpec {
+      id,
+      label,
+      accelerator,
+      enabled: true,
+      available: true,
+   }
+}
+
+const fn macos_only(spec: MenuCommandSpec) -> MenuCommandSpec {
+   MenuCommandSpec {
+      available: cfg!(target_os = "macos"),
+      ..spec
+   }
+}
+
+const fn except_macos(spec: MenuCommandSpec) -> MenuCommandSpec {
+   MenuCommandSpec {
+      available: !cfg!(target_os = "macos"),
+      ..spec
+   }
+}
+
+/// Every menu entry the app handles itself, in menu order. Native items such as Cut, Copy and
+/// Paste, and the theme submenu, are not commands.
+const MENU_COMMANDS: &[MenuCommandSpec] = &[
+   // File
+   command("command_new_tab", "New Tab", Some("CmdOrCtrl+N")),
+   command("new_window", "New Window", Some("CmdOrCtrl+Shift+N")),
+   command("new_file", "New File", None),
+   command("open_folder", "Open Folder", Some("CmdOrCtrl+O")),
+   command("close_folder", "Close Folder", None),
+   command("save", "Save", Some("CmdOrCtrl+S")),
+   command("save_as", "Save As...", Some("CmdOrCtrl+Shift+S")),
+   command("command_save_all", "Save All", Some("CmdOrCtrl+Option+S")),
+   command("command_revert_file", "Revert File", None),
+   command("command_local_history", "Show Local History", None),
+   command(
+      "close_tab",
+      "Close Tab",
+      if cfg!(target_os = "linux") {
+         None
+      } else {
+         Some("CmdOrCtrl+W")
+      },
+   ),
+   command("command_close_all_tabs", "Close All Tabs", None),
+   command("command_close_other_tabs", "Close Other Tabs", None),
+   command("command_close_saved_tabs", "Close Saved Tabs", None),
+   command("command_close_tabs_to_left", "Close Tabs to the Left", None),
+   command(
+      "command_close_tabs_to_right",
+      "Close Tabs to the Right",
+      None,
+   ),
+   command(
+      "command_reopen_closed_tab",
+      "Reopen Closed Tab",
+      Some("CmdOrCtrl+Shift+T"),
+   ),
+   command(
+      "quit_app",
+      if cfg!(target_os = "macos") {
+         "Quit Athas"
+      } else {
+         "Quit"
+      },
+      Some("CmdOrCtrl+Q"),
+   ),
+   // Edit
+   command("undo", "Undo", Some("CmdOrCtrl+Z")),
+   command("redo", "Redo", Some("CmdOrCtrl+Shift+Z")),
+   command("select_all", "Select All", Some("CmdOrCtrl+A")),
+   command("find", "Find", Some("CmdOrCtrl+F")),
+   command(
+      "find_replace",
+      "Find and Replace",
+      Some("CmdOrCtrl+Option+F"),
+   ),
+   command("toggle_comment", "Toggle Comment", Some("CmdOrCtrl+Slash")),
+   command("command_quick_fix", "Quick Fix", Some("CmdOrCtrl+.")),
+   command(
+      "command_trigger_parameter_hints",
+      "Trigger Parameter Hints",
+      None,
+   ),
+   command("command_show_hover", "Show Hover", None),
+   command("command_duplicate_line", "Duplicate Line", None),
+   command(
+      "command_delete_line",
+      "Delete Line",
+      Some("CmdOrCtrl+Shift+K"),
+   ),
+   command("command_move_line_up", "Move Line Up", None),
+   command("command_move_line_down", "Move Line Down", None),
+   command(
+      "command_format_document",
+      "Format Document",
+      Some("Shift+Alt+F"),
+   ),
+   command("command_format_selection", "Format Selection", None),
+   command(
+      "command_palette",
+      "Command Palette",
+      if cfg!(target_os = "macos") {
+         Some("Cmd+Shift+P")
+      } else {
+         None
+      },
+   ),
+   // View
+   command("toggle_sidebar", "Toggle Sidebar", Some("CmdOrCtrl+B")),
+   command("toggle_terminal", "Toggle Terminal", Some("CmdOrCtrl+J")),
+   command(
+      "command_global_search",
+      "Global Search",
+      Some("CmdOrCtrl+Shift+F"),
+   ),
+   command(
+      "command_diagnostics",
+      "Diagnostics",
+      Some("CmdOrCtrl+Shift+J"),
+   ),
+   command(
+      "command_file_explorer",
+      "File Explorer",
+      Some("CmdOrCtrl+Shift+E"),
+   ),
+   command(
+      "command_source_control",
+      "Source Control",
+      Some("CmdOrCtrl+Shift+G"),
+   ),
+   command("command_github", "GitHub", None),
+   command("command_debugger", "Run and Debug", None),
+   command("split_editor", "Split Editor", None),
+   command("command_toggle_minimap", "Toggle Minimap", None),
+   command(
+      "command_toggle_word_wrap",
+      "Toggle Word Wrap",
+      Some("Alt+Z"),
+   ),
+   command("command_toggle_line_numbers", "Toggle Line Numbers", None),
+   command(
+      "command_toggle_render_whitespace",
+      "Toggle Render Whitespace",
+      None,
+   ),
+   command("command_zoom_in", "Zoom In", None),
+   command("command_zoom_out", "Zoom Out", None),
+   command("command_zoom_reset", "Reset Zoom", None),
+   // Go
+   command("quick_open", "Quick Open", Some("CmdOrCtrl+P")),
+   command("go_to_line", "Go to Line", Some("CmdOrCtrl+G")),
+   command("command_go_back", "Go Back", None),
+   command("command_go_forward", "Go Forward", None),
+   command("command_go_to_definition", "Go to Definition", Some("F12")),
+   command(
+      "command_go_to_implementation",
+      "Go to Implementation",
+      Some("CmdOrCtrl+F12"),
+   ),
+   command(
+      "command_go_to_type_definition",
+      "Go to Type Definition",
+      None,
+   ),
+   command(
+      "command_go_to_references",
+      "Go to References",
+      Some("Shift+F12"),
+   ),
+   command("command_rename_symbol", "Rename Symbol", Some("F2")),
+   command("next_tab", "Next Tab", Some("CmdOrCtrl+Option+Right")),
+   command("prev_tab", "Previous Tab", Some("CmdOrCtrl+Option+Left")),
+   // Terminal
+   command("command_new_terminal", "New Terminal", None),
+   command("command_split_terminal", "Split Terminal", None),
+   command("command_close_terminal", "Close Terminal", None),
+   // Run
+   command("command_start_debugging", "Start Debugging", Some("F5")),
+   command("command_stop_debugging", "Stop Debugging", Some("Shift+F5")),
+   command("command_toggle_breakpoint", "Toggle Breakpoint", Some("F9")),
+   // Agent
+   command("toggle_ai_chat", "Toggle Agent", Some("CmdOrCtrl+R")),
+   command(
+      "command_new_agent",
+      "New Agent",
+      Some("CmdOrCtrl+Shift+Space"),
+   ),
+   command("command_inline_edit", "Inline Edit", Some("CmdOrCtrl+I")),
+   // Tools
+   command("command_connect_database", "Connect to Database", None),
+   MenuCommandSpec {
+      enabled: cfg!(any(debug_assertions, feature = "devtools")),
+      ..command(
+         "open_web_inspector",
+         "Web Inspector",
+         Some("CmdOrCtrl+Option+I"),
+      )
+   },
+   command(
+      "open_settings",
+      if cfg!(target_os = "macos") {
+         "Settings..."
+      } else {
+         "Preferences"
+      },
+      if cfg!(target_os = "macos") {
+         Some("Cmd+,")
+      } else {
+         None
+      },
+   ),
+   command("open_extensions", "Extensions", None),
+   command("command_keyboard_shortcuts", "Keyboard Shortcuts", None),
+   // Window
+   macos_only(command("close_window", "Close Window", Some("Cmd+Shift+W"))),
+   except_macos(command("minimize_window", "Minimize", Some("Alt+F9"))),
+   except_macos(command("maximize_window", "Maximize", Some("Alt+F10"))),
+   except_macos(command("toggle_menu_bar", "Toggle Menu Bar", Some("Alt+M"))),
+   except_macos(command(
+      "toggle_fullscreen",
+      "Toggle Fullscreen",
+      Some("F11"),
+   )),
+   // Help
+   command("documentation", "Documentation", None),
+   command(
+      "command_help_keyboard_shortcuts",
+      "Keyboard Shortcuts",
+      None,
+   ),
+   command("whats_new", "What's New", None),
+   command("changelog", "Changelog", None),
+   command("report_bug", "Report a Bug", None),
+   command("request_feature", "Request a Feature", None),
+   command(
+      "check_updates",
+      if cfg!(target_os = "macos") {
+         "Check for Updates..."
+      } else {
+         "Check for Updates"
+      },
+      None,
+   ),
+];
+
+/// Spec of the menu entry with `id` on this platform
+pub fn menu_command(id: &str) -> Option<&'static MenuCommandSpec> {
+   MENU_COMMANDS
+      .iter()
+      .find(|spec| spec.available && spec.id == id)
+}
+
+/// Accelerator of a menu entry, with the user's keybinding taking precedence over the default
+pub fn accelerator(spec: &MenuCommandSpec) -> Option<String> {
+   let keybindings = KEYBINDINGS.lock().ok();
+   match keybindings
+      .as_ref()
+      .and_then(|keybindings| keybindings.get(spec.id))
+   {
+      Some(accelerator) => accelerator.clone(),
+      None => spec.accelerator.map(str::to_string),
+   }
+}
+
+/// Read `keybindings.json` from the config dir. Entries for unknown commands or with an
+/// invalid accelerator are skipped so their defaults stay in place, and an unreadable file
+/// leaves every default in place.
+pub fn load_keybindings<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+   let keybindings = app
+      .path()
+      .app_config_dir()
+      .ok()
+      .map(|config_dir| config_dir.join("keybindings.json"))
+      .filter(|path| path.exists())
+      .and_then(|path| match fs::read_to_string(&path) {
+         Ok(contents) => Some(parse_keybindings(&contents)),
+         Err(e) => {
+            log::warn!("Failed to read {}: {}", path.display(), e);
+            None
+         }
+      })
+      .unwrap_or_default();
+
+   if let Ok(mut current) = KEYBINDINGS.lock() {
+      *current = keybindings;
+   }
+}
+
+fn parse_keybindings(contents: &str) -> BTreeMap<String, Option<String>> {
+   let entries: BTreeMap<String, Option<String>> = match serde_json::from_str(contents) {
+      Ok(entries) => entries,
+      Err(e) => {
+         log::warn!("Ignoring invalid keybindings.json: {}", e);
+         return BTreeMap::new();
+      }
+   };
+
+   entries
+      .into_iter()
+      .filter(|(id, accelerator)| {
+         if menu_command(id).is_none() {
+            log::warn!("Ignoring keybinding for unknown menu command '{}'", id);
+            return false;
+         }
+         if let Some(accelerator) = accelerator
+            && !is_valid_accelerator(accelerator)
+         {
+            log::warn!(
+               "Ignoring invalid accelerator '{}' for '{}'",
+               accelerator,
+               id
+            );
+            return false;
+         }
+         true
+      })
+      .collect()
+}
+
+/// Whether `accelerator` is a `+`-separated list of modifiers followed by a single key, such
+/// as `CmdOrCtrl+Shift+P` or `F5`
+fn is_valid_accelerator(accelerator: &str) -> bool {
+   let tokens: Vec<String> = accelerator
+      .split('+')
+      .map(|token| token.trim().to_lowercase())
+      .collect();
+   let Some((key, modifiers)) = tokens.split_last() else {
+      return false;
+   };
+
+   let modifiers_valid = modifiers.iter().enumerate().all(|(index, modifier)| {
+      MODIFIERS.contains(&modifier.as_str()) && !modifiers[..index].contains(modifier)
+   });
+   modifiers_valid && is_valid_key(key)
+}
+
+fn is_valid_key(key: &str) -> bool {
+   let mut chars = key.chars();
+   if let (Some(c), None) = (chars.next(), chars.next()) {
+      return c.is_ascii_alphanumeric() || "`-=[]\\;',./".contains(c);
+   }
+   if let Some(number) = key.strip_prefix('f') {
+      return number
+         .parse::<u8>()
+         .is_ok_and(|number| (1..=24).contains(&number));
+   }
+   NAMED_KEYS.contains(&key)
+}
+
+/// Commands in the native menu with their labels and accelerators, for the command palette
+#[tauri::command]
+pub fn list_menu_commands() -> Vec<MenuCommand> {
+   MENU_COMMANDS
+      .iter()
+      .filter(|spec| spec.available && spec.enabled)
+      .map(|spec| MenuCommand {
+         id: spec.id.to_string(),
+         label: spec.label.to_string(),
+         accelerator: accelerator(spec),
+         default_accelerator: spec.accelerator.map(str::to_string),
+      })
+      .collect()
+}
+
+/// Re-read `keybindings.json` and rebuild the native menu with the new accelerators
+#[tauri::command]
+pub async fn reload_keybindings(app: crate::app_runtime::AppHandle) -> Result<(), String> {
+   load_keybindings(&app);
+   crate::menu::rebuild_native_menu(&app)
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use std::collections::HashSet;
+
+   #[test]
+   fn lists_each_command_once() {
+      let commands = list_menu_commands();
+      let ids: HashSet<_> = commands.iter().map(|command| command.id.as_str()).collect();
+
+      assert_eq!(ids.len(), commands.len());
+      assert!(ids.contains("command_palette"));
+      assert_eq!(
+         menu_command("save").and_then(|spec| spec.accelerator),
+         Some("CmdOrCtrl+S")
+      );
+   }
+
+   #[test]
+   fn validates_accelerators() {
+      assert!(is_valid_accelerator("CmdOrCtrl+Shift+P"));
+      assert!(is_valid_accelerator("F12"));
+      assert!(is_valid_accelerator("Alt+Up"));
+      assert!(is_valid_accelerator("CmdOrCtrl+/"));
+      assert!(!is_valid_accelerator(""));
+      assert!(!is_valid_accelerator("CmdOrCtrl+"));
+      assert!(!is_valid_accelerator("Shift+Shift+P"));
+      assert!(!is_valid_accelerator("Hyper+P"));
+      assert!(!is_valid_accelerator("CmdOrCtrl+F25"));
+      assert!(
+         MENU_COMMANDS
+            .iter()
+            .filter_map(|spec| spec.accelerator)
+            .all(is_valid_accelerator)
+      );
+   }
+
+   #[test]
+   fn keeps_only_valid_keybindings() {
+      let keybindings = parse_keybindings(
+         r#"{
+            "command_palette": "CmdOrCtrl+Shift+K",
+            "save": "CmdOrCtrl+Nope",
+            "not_a_command": "CmdOrCtrl+J",
+            "command_new_tab": null
+         }"#,
+      );
+
+      assert_eq!(
+         keybindings,
+         BTreeMap::from([
+            ("command_new_tab".to_string(), None),
+            (
+               "command_palette".to_string(),
+               Some("CmdOrCtrl+Shift+K".to_string())
+            ),
+         ])
+      );
+      assert!(parse_keybindings("not json").is_empty());
+   }
+}
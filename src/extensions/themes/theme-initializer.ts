@@ -1,13 +1,35 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { extensionManager } from "@/features/editor/extensions/manager";
 import type { EditorAPI } from "@/features/editor/types/editor-extension.types";
 import { loadCustomThemes } from "./custom-theme-store";
 import { toThemeDefinition } from "./theme-file";
 import { themeLoader } from "./theme-loader";
 import { themeRegistry } from "./theme-registry";
+import type { Theme } from "./theme-schema";
+
+const USER_THEMES_EXTENSION_ID = "user-themes";
 
 let isThemeSystemInitialized = false;
 
+// Replace the themes loaded from the themes folder in the app config directory
+const registerUserThemes = (themes: Theme[]) => {
+  themeRegistry.unregisterThemesByExtension(USER_THEMES_EXTENSION_ID);
+  for (const theme of themes) {
+    const definition = toThemeDefinition(theme);
+    if (themeRegistry.getTheme(definition.id)) {
+      console.warn(
+        `initializeThemeSystem: Skipped user theme "${definition.id}" because that ID is already registered`,
+      );
+      continue;
+    }
+    themeRegistry.registerTheme(definition, {
+      extensionId: USER_THEMES_EXTENSION_ID,
+      kind: "custom",
+    });
+  }
+};
+
 // Helper function to rebuild native menu with current themes
 const rebuildNativeMenu = async () => {
   try {
@@ -122,6 +144,14 @@ export const initializeThemeSystem = async () => {
       console.error("initializeThemeSystem: Failed to load custom themes:", error);
     }
 
+    try {
+      registerUserThemes(await invoke<Theme[]>("load_user_themes"));
+      await listen<Theme[]>("themes-changed", (event) => registerUserThemes(event.payload));
+      await invoke("watch_user_themes");
+    } catch (error) {
+      console.error("initializeThemeSystem: Failed to load user themes:", error);
+    }
+
     // Mark theme registry as ready
     themeRegistry.markAsReady();
 
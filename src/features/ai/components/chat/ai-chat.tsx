@@ -294,14 +294,28 @@ const AIChat = memo(function AIChat({
             }
             break;
           }
-          case "status_changed":
+          case "status_changed": {
             store.setAcpStatus(payload.status);
             if (!payload.status.running) {
               store.setAvailableSlashCommands([]);
               store.setSessionModeState(null, []);
               store.setSessionConfigOptions([]);
+              break;
             }
+            // Commands advertised before this listener attached are only available on request.
+            const { agentId } = payload.status;
+            AcpStreamHandler.listSlashCommands(agentId)
+              .then((commands) => {
+                const current = useAIChatStore.getState();
+                if (commands.length > 0 && current.acpStatus?.agentId === agentId) {
+                  current.setAvailableSlashCommands(commands);
+                }
+              })
+              .catch((error) => {
+                console.error("Failed to load ACP slash commands:", error);
+              });
             break;
+          }
           default:
             break;
         }
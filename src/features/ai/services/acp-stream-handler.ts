@@ -7,9 +7,12 @@ import type {
   AcpPromptContentBlock,
   AcpSessionList,
   AgentConfig,
+  McpServerConfig,
+  SlashCommand,
 } from "@/features/ai/types/acp.types";
 import type { ContextInfo } from "@/features/ai/types/ai-context.types";
 import { useBufferStore } from "@/features/editor/stores/buffer.store";
+import { useFileSystemStore } from "@/features/file-system/stores/file-system.store";
 import { useProjectStore } from "@/features/window/stores/project.store";
 import { getAcpPathBaseName, toAcpFileUri } from "@/features/ai/lib/acp-file-uri";
 import { getChatTitleFromSessionInfo } from "@/features/ai/lib/acp-session-info";
@@ -142,16 +145,28 @@ export class AcpStreamHandler {
         shouldRestartForSession ||
         shouldRestartForWorkspace
       ) {
-        console.log(`Starting agent ${this.agentId}...`);
+        // A fresh chat on the running agent only needs a new session, not a new process.
+        const canSwitchSession =
+          shouldRestartForSession && !shouldRestartForWorkspace && desiredSessionId === null;
+        console.log(
+          canSwitchSession
+            ? `Starting a new ${this.agentId} session...`
+            : `Starting agent ${this.agentId}...`,
+        );
 
         let startStatus: AcpAgentStatus;
         try {
           startStatus = await withTimeout(
-            invoke<AcpAgentStatus>("start_acp_agent", {
-              agentId: this.agentId,
-              workspacePath,
-              sessionId: desiredSessionId,
-            }),
+            canSwitchSession
+              ? invoke<AcpAgentStatus>("new_acp_session", {
+                  agentId: this.agentId,
+                  workspacePath,
+                })
+              : invoke<AcpAgentStatus>("start_acp_agent", {
+                  agentId: this.agentId,
+                  workspacePath,
+                  sessionId: desiredSessionId,
+                }),
             ACP_START_TIMEOUT_MS,
             `${this.agentId} startup timed out`,
           );
@@ -453,6 +468,13 @@ export class AcpStreamHandler {
         });
         break;
 
+      case "open_file":
+        console.log("Opening file:", action.path);
+        void useFileSystemStore
+          .getState()
+          .handleFileSelect(action.path, false, action.line ?? undefined, action.column ?? undefined);
+        break;
+
       case "set_chat_title": {
         const targetChat = this.getTargetChat();
         const nextTitle = targetChat
@@ -660,6 +682,17 @@ export class AcpStreamHandler {
     });
   }
 
+  static async setAgentMcpServers(
+    agentId: string,
+    mcpServers: McpServerConfig[],
+  ): Promise<AgentConfig> {
+    return invoke<AgentConfig>("set_acp_agent_mcp_servers", { agentId, mcpServers });
+  }
+
+  static async listSlashCommands(agentId: string): Promise<SlashCommand[]> {
+    return invoke<SlashCommand[]>("list_acp_slash_commands", { agentId });
+  }
+
   static async deleteSession(sessionId: string): Promise<void> {
     await invoke("delete_acp_session", {
       args: { sessionId },
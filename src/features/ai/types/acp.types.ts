@@ -1,5 +1,15 @@
 // Types for Agent Client Protocol (ACP) integration
 
+export type McpServerConfig =
+  | {
+      type: "stdio";
+      name: string;
+      command: string;
+      args?: string[];
+      env?: Record<string, string>;
+    }
+  | { type: "http" | "sse"; name: string; url: string; headers?: Record<string, string> };
+
 export interface AgentConfig {
   id: string;
   name: string;
@@ -13,6 +23,7 @@ export interface AgentConfig {
   installRuntime: "node" | "python" | "go" | "rust" | "binary" | null;
   installPackage: string | null;
   canInstall: boolean;
+  mcpServers?: McpServerConfig[];
 }
 
 export interface AcpAgentStatus {
@@ -172,6 +183,7 @@ export type StopReason = "end_turn" | "max_tokens" | "max_turn_requests" | "refu
 export type UiAction =
   | { action: "open_web_viewer"; url: string }
   | { action: "open_terminal"; command: string | null }
+  | { action: "open_file"; path: string; line: number | null; column: number | null }
   | { action: "set_chat_title"; title: string };
 
 export type AcpEvent =
@@ -9,7 +9,7 @@ import {
   TagIcon as Tag,
   ArrowClockwiseIcon as RefreshCw,
 } from "@/ui/icons";
-import type { GitRemoteActionResult } from "@/features/git/api/git-remotes-api";
+import type { GitFetchResult, GitRemoteActionResult } from "@/features/git/api/git-remotes-api";
 import { showConfirmDialog, showPromptDialog } from "@/ui/dialog";
 import type { Action } from "../types/action.types";
 
@@ -30,7 +30,7 @@ interface GitActionsParams {
     commitChanges: (path: string, message: string) => Promise<boolean>;
     pushChanges: (path: string) => Promise<GitRemoteActionResult>;
     pullChanges: (path: string) => Promise<GitRemoteActionResult>;
-    fetchChanges: (path: string) => Promise<GitRemoteActionResult>;
+    fetchChanges: (path: string) => Promise<GitFetchResult>;
     discardAllChanges: (path: string) => Promise<boolean>;
   };
   onClose: () => void;
@@ -400,7 +400,11 @@ export const createGitActions = (params: GitActionsParams): Action[] => {
         try {
           const result = await gitOperations.fetchChanges(repoPath);
           if (result.success) {
-            showToast({ message: "Fetched successfully", type: "success" });
+            showToast({
+              message: "Fetched successfully",
+              description: result.summary || undefined,
+              type: "success",
+            });
           } else {
             showToast({
               message: result.error || "Failed to fetch",
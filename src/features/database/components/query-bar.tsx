@@ -14,6 +14,7 @@ import {
 } from "../lib/sql-completions";
 import { buildSqlHighlightSegments } from "../lib/sql-highlight";
 import { getSelectedSqlText } from "../lib/sql-selection";
+import { formatSql } from "../services/sql-format";
 
 interface QueryBarProps {
   searchTerm: string;
@@ -253,6 +254,15 @@ export default function QueryBar({
     executeCustomQuery(latestSelection || selectedQuery || undefined);
   };
 
+  const formatQuery = async () => {
+    if (!customQuery.trim()) return;
+    try {
+      setCustomQuery(await formatSql(customQuery));
+    } catch (error) {
+      console.error("Failed to format SQL:", error);
+    }
+  };
+
   if (isCustomQuery) {
     return (
       <div className="px-3 py-2">
@@ -264,6 +274,9 @@ export default function QueryBar({
             if ((event.metaKey || event.ctrlKey) && event.key === "Enter") {
               event.preventDefault();
               runQuery();
+            } else if (event.shiftKey && event.altKey && event.code === "KeyF") {
+              event.preventDefault();
+              void formatQuery();
             }
           }}
           textareaRef={textareaRef}
@@ -277,7 +290,7 @@ export default function QueryBar({
               ? "Selection will run"
               : lastQueryExecutionMs !== null && lastQueryExecutionMs !== undefined
                 ? `Last run ${lastQueryExecutionMs}ms`
-                : "Cmd/Ctrl+Enter to run"}
+                : "Cmd/Ctrl+Enter to run, Shift+Alt+F to format"}
           </div>
           <div className="flex justify-end gap-2">
             <Button onClick={() => setIsCustomQuery(false)} variant="ghost" size="xs">
@@ -0,0 +1,5 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export async function formatSql(query: string): Promise<string> {
+  return invoke<string>("format_sql", { query });
+}
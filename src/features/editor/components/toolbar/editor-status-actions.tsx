@@ -284,7 +284,7 @@ export function EditorStatusActions({ bufferId, editorViewKey }: EditorStatusAct
 
     setBulkLspAction("restart");
     try {
-      await lspClient.restartAllTrackedServers();
+      await lspClient.restartAll();
     } catch (error) {
       toast.error(error instanceof Error ? error.message : "Failed to restart language servers");
     } finally {
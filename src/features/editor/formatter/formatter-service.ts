@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import { extensionRegistry } from "@/extensions/registry/extension-registry";
+import type { LspFormattingOptions } from "@/features/editor/lsp/lsp-client";
 import { getLanguageIdFromPath } from "@/features/editor/utils/language-id";
 import { logger } from "@/features/editor/utils/logger";
 import { useFileSystemStore } from "@/features/file-system/stores/file-system.store";
@@ -17,6 +18,14 @@ export interface FormatRange {
 
 export interface FormatRangeOptions extends FormatOptions {
   range: FormatRange;
+  /** Indentation settings for language servers, which otherwise get the backend defaults */
+  formattingOptions?: LspFormattingOptions;
+}
+
+export interface FormatChangedRangesOptions extends FormatOptions {
+  /** Repository whose HEAD the content is compared against */
+  repoPath: string;
+  formattingOptions?: LspFormattingOptions;
 }
 
 export interface FormatResult {
@@ -120,7 +129,7 @@ export async function formatContent(options: FormatOptions): Promise<FormatResul
 }
 
 export async function formatRange(options: FormatRangeOptions): Promise<FormatResult> {
-  const { filePath, content, range } = options;
+  const { filePath, content, range, formattingOptions } = options;
 
   if (filePath.startsWith("wsl://")) {
     return {
@@ -130,7 +139,7 @@ export async function formatRange(options: FormatRangeOptions): Promise<FormatRe
   }
 
   try {
-    const lspFormatted = await formatRangeWithLsp(filePath, content, range);
+    const lspFormatted = await formatRangeWithLsp(filePath, content, range, formattingOptions);
     if (lspFormatted !== null) {
       return {
         success: true,
@@ -153,6 +162,58 @@ export async function formatRange(options: FormatRangeOptions): Promise<FormatRe
   }
 }
 
+/**
+ * Format only the lines changed since HEAD, leaving the rest of the file as it is
+ */
+export async function formatChangedRanges(
+  options: FormatChangedRangesOptions,
+): Promise<FormatResult> {
+  const { filePath, content, repoPath, formattingOptions } = options;
+
+  if (filePath.startsWith("wsl://")) {
+    return {
+      success: false,
+      error: "Range formatting is not available for WSL files.",
+    };
+  }
+
+  try {
+    const { LspClient } = await import("@/features/editor/lsp/lsp-client");
+    const version = LspClient.getInstance().nextDocumentVersion(filePath);
+    const response = await invoke<{
+      formatted_content: string;
+      success: boolean;
+      error?: string;
+    }>("format_changed_ranges", {
+      repoPath,
+      filePath,
+      content,
+      version,
+      options: formattingOptions,
+    });
+
+    if (response.success) {
+      return {
+        success: true,
+        formattedContent: response.formatted_content,
+      };
+    }
+
+    logger.debug("FormatterService", `Changed-line formatting failed: ${response.error}`);
+    return {
+      success: false,
+      error: response.error || "Formatting failed",
+    };
+  } catch (error) {
+    logger.error("FormatterService", `Failed to format changed lines in ${filePath}:`, error);
+
+    return {
+      success: false,
+      error: error instanceof Error ? error.message : String(error),
+    };
+  }
+}
+
 async function formatWithLsp(filePath: string, content: string): Promise<string | null> {
   try {
     const { LspClient } = await import("@/features/editor/lsp/lsp-client");
@@ -167,10 +228,11 @@ async function formatRangeWithLsp(
   filePath: string,
   content: string,
   range: FormatRange,
+  formattingOptions?: LspFormattingOptions,
 ): Promise<string | null> {
   try {
     const { LspClient } = await import("@/features/editor/lsp/lsp-client");
-    return await LspClient.getInstance().formatRange(filePath, content, range);
+    return await LspClient.getInstance().formatRange(filePath, content, range, formattingOptions);
   } catch (error) {
     logger.debug("FormatterService", `LSP range formatter unavailable for ${filePath}:`, error);
     return null;
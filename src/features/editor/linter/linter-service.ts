@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { extensionRegistry } from "@/extensions/registry/extension-registry";
 import { logger } from "@/features/editor/utils/logger";
 import { useFileSystemStore } from "@/features/file-system/stores/file-system.store";
@@ -20,6 +21,24 @@ export interface Diagnostic {
   source?: string;
 }
 
+export interface ProjectDiagnostic {
+  file_path: string;
+  line: number;
+  column: number;
+  end_line?: number;
+  end_column?: number;
+  severity: Diagnostic["severity"];
+  message: string;
+  code?: string;
+  source?: string;
+}
+
+export interface CargoCheckProgress {
+  checked_crates: number;
+  current_crate: string | null;
+  diagnostics: number;
+}
+
 export interface LintResult {
   success: boolean;
   diagnostics?: Diagnostic[];
@@ -117,6 +136,73 @@ export async function lintContent(options: LintOptions): Promise<LintResult> {
   }
 }
 
+/**
+ * Run cargo check (or clippy) over the whole Rust workspace
+ */
+export async function runCargoCheck(
+  workspace: string,
+  clippy: boolean,
+  onProgress?: (progress: CargoCheckProgress) => void,
+): Promise<{ success: boolean; diagnostics: ProjectDiagnostic[]; error?: string }> {
+  const unlisten = onProgress
+    ? await listen<CargoCheckProgress>("cargo-check-progress", (event) =>
+        onProgress(event.payload),
+      )
+    : undefined;
+
+  try {
+    const diagnostics = await invoke<ProjectDiagnostic[]>("run_cargo_check", {
+      workspace,
+      clippy,
+    });
+    return { success: true, diagnostics };
+  } catch (error) {
+    logger.error("LinterService", `Failed to run cargo ${clippy ? "clippy" : "check"}:`, error);
+    return {
+      success: false,
+      error: error instanceof Error ? error.message : String(error),
+      diagnostics: [],
+    };
+  } finally {
+    unlisten?.();
+  }
+}
+
+/**
+ * Stop the cargo check or clippy run for the workspace, if one is running
+ */
+export async function cancelCargoCheck(workspace: string): Promise<boolean> {
+  try {
+    return await invoke<boolean>("cancel_cargo_check", { workspace });
+  } catch (error) {
+    logger.error("LinterService", "Failed to cancel cargo check:", error);
+    return false;
+  }
+}
+
+/**
+ * Run golangci-lint over a package of the Go module, or over every package
+ */
+export async function runGoLint(
+  workspace: string,
+  goPackage?: string,
+): Promise<{ success: boolean; diagnostics: ProjectDiagnostic[]; error?: string }> {
+  try {
+    const diagnostics = await invoke<ProjectDiagnostic[]>("run_go_lint", {
+      workspace,
+      package: goPackage,
+    });
+    return { success: true, diagnostics };
+  } catch (error) {
+    logger.error("LinterService", "Failed to run golangci-lint:", error);
+    return {
+      success: false,
+      error: error instanceof Error ? error.message : String(error),
+      diagnostics: [],
+    };
+  }
+}
+
 /**
  * Check if linting is available for a file
  */
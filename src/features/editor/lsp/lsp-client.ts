@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import type {
   CompletionItem,
+  Diagnostic as LspDiagnostic,
   Hover,
   PublishDiagnosticsParams,
 } from "vscode-languageserver-protocol";
@@ -15,7 +16,7 @@ import type {
   DiagnosticCodeAction,
 } from "@/features/diagnostics/types/diagnostics.types";
 import type { BackendLanguageToolConfigSet } from "@/extensions/registry/extension-store-runtime";
-import { hasTextContent } from "@/features/panes/types/pane-content.types";
+import { hasTextContent, isEditorContent } from "@/features/panes/types/pane-content.types";
 import { useBufferStore } from "../stores/buffer.store";
 import { getSourceEditorBufferByPath } from "../utils/buffer-index";
 import { logger } from "../utils/logger";
@@ -29,6 +30,9 @@ import {
   type WorkspaceEdit,
 } from "./workspace-edit";
 
+/** Quiet period after an edit before diagnostics are pulled for the changed file */
+const PULL_DIAGNOSTICS_DEBOUNCE_MS = 500;
+
 export interface LspError {
   message: string;
   code?: string;
@@ -42,6 +46,22 @@ export interface LspLocation {
   };
 }
 
+/** What the language server handling a file supports */
+export interface LspCapabilities {
+  /** Supported feature names, e.g. "hover", "rename", "semanticTokens" */
+  features: string[];
+  /** The raw `ServerCapabilities` from the initialize response */
+  serverCapabilities: Record<string, unknown>;
+}
+
+/** Indentation settings sent with LSP formatting requests */
+export interface LspFormattingOptions {
+  tabSize: number;
+  insertSpaces: boolean;
+  trimTrailingWhitespace?: boolean;
+  insertFinalNewline?: boolean;
+}
+
 interface PrepareRenameResult {
   range?: {
     start: { line: number; character: number };
@@ -147,6 +167,7 @@ export class LspClient {
   private repairLanguageServerPromises = new Map<string, Promise<boolean>>();
   private openDocuments = new Set<string>();
   private documentVersions = new Map<string, number>();
+  private pullDiagnosticsTimers = new Map<string, ReturnType<typeof setTimeout>>();
 
   private constructor() {
     this.setupDiagnosticsListener();
@@ -810,12 +831,45 @@ export class LspClient {
     await Promise.all(serverKeys.map((serverKey) => this.restartTrackedServer(serverKey)));
   }
 
+  /**
+   * Restart every running server in place. The backend re-opens tracked documents from disk,
+   * so unsaved buffers are re-sent afterwards.
+   */
+  async restartAll(): Promise<void> {
+    const { actions } = useLspStore.getState();
+
+    try {
+      actions.updateLspStatus("connecting");
+      actions.clearLspError();
+      await invoke<number>("lsp_restart_all");
+
+      const { buffers } = useBufferStore.getState();
+      for (const buffer of buffers) {
+        if (!isEditorContent(buffer) || !buffer.isDirty) continue;
+        if (!this.openDocuments.has(buffer.path)) continue;
+        const version = (this.documentVersions.get(buffer.path) ?? 1) + 1;
+        await this.notifyDocumentChange(buffer.path, buffer.content, version);
+      }
+      this.updateLspStatus();
+    } catch (error) {
+      logger.error("LSPClient", "Failed to restart LSP servers:", error);
+      actions.setLspError(getUserFacingLspErrorMessage(error));
+      throw error;
+    }
+  }
+
   async stopAll(): Promise<void> {
-    const workspaces = new Set<string>();
-    for (const key of this.activeLanguageServers) {
-      workspaces.add(this.parseServerKey(key).workspacePath);
+    try {
+      await invoke<number>("lsp_stop_all");
+
+      this.activeLanguageServers.clear();
+      this.activeServerFiles.clear();
+      this.activeLanguages.clear();
+      this.updateLspStatus();
+    } catch (error) {
+      logger.error("LSPClient", "Failed to stop LSP servers:", error);
+      throw error;
     }
-    await Promise.all(Array.from(workspaces).map((ws) => this.stop(ws)));
   }
 
   async getCompletions(
@@ -841,7 +895,9 @@ export class LspClient {
       }
       return completions;
     } catch (error) {
-      logger.error("LSPClient", "LSP completion error:", error);
+      if (!isCanceledLspRequest(error)) {
+        logger.error("LSPClient", "LSP completion error:", error);
+      }
       return [];
     }
   }
@@ -1104,9 +1160,13 @@ export class LspClient {
     }
   }
 
-  async formatDocument(filePath: string, content: string): Promise<string | null> {
+  async formatDocument(
+    filePath: string,
+    content: string,
+    options?: LspFormattingOptions,
+  ): Promise<string | null> {
     try {
-      const edits = await invoke<LspTextEdit[]>("lsp_format_document", { filePath });
+      const edits = await invoke<LspTextEdit[]>("lsp_format_document", { filePath, options });
       if (!edits.length) return content;
       return applyTextEditsToContent(content, edits);
     } catch (error) {
@@ -1122,6 +1182,7 @@ export class LspClient {
       start: { line: number; character: number };
       end: { line: number; character: number };
     },
+    options?: LspFormattingOptions,
   ): Promise<string | null> {
     try {
       const edits = await invoke<LspTextEdit[]>("lsp_format_range", {
@@ -1130,6 +1191,7 @@ export class LspClient {
         startCharacter: range.start.character,
         endLine: range.end.line,
         endCharacter: range.end.character,
+        options,
       });
       if (!edits.length) return content;
       return applyTextEditsToContent(content, edits);
@@ -1296,20 +1358,77 @@ export class LspClient {
         content,
         version,
       });
+      this.schedulePullDiagnostics(filePath);
     } catch (error) {
       logger.error("LSPClient", "LSP document change error:", error);
     }
   }
 
+  /**
+   * Reserve the next version of a document for content the backend sends to the server itself,
+   * such as the buffer synced before range formatting.
+   */
+  nextDocumentVersion(filePath: string): number {
+    const version = (this.documentVersions.get(filePath) ?? 1) + 1;
+    this.documentVersions.set(filePath, version);
+    return version;
+  }
+
+  private schedulePullDiagnostics(filePath: string): void {
+    clearTimeout(this.pullDiagnosticsTimers.get(filePath));
+    this.pullDiagnosticsTimers.set(
+      filePath,
+      setTimeout(() => {
+        this.pullDiagnosticsTimers.delete(filePath);
+        void this.pullDiagnostics(filePath);
+      }, PULL_DIAGNOSTICS_DEBOUNCE_MS),
+    );
+  }
+
   async notifyDocumentSave(filePath: string, content: string): Promise<void> {
     try {
       await invoke<void>("lsp_document_save", { filePath, content });
     } catch (error) {
       logger.debug("LSPClient", "LSP document save notification skipped:", error);
+      return;
+    }
+
+    await this.pullDiagnostics(filePath);
+  }
+
+  /**
+   * Refresh diagnostics for a file from servers that support pull diagnostics.
+   * Servers that only push diagnostics keep updating through `lsp://diagnostics`.
+   */
+  async pullDiagnostics(filePath: string): Promise<void> {
+    const version = this.documentVersions.get(filePath);
+    try {
+      const diagnostics = await invoke<LspDiagnostic[] | null>("lsp_pull_diagnostics", {
+        filePath,
+      });
+      // A newer edit has its own pull scheduled, so results for older content are dropped.
+      if (
+        !diagnostics ||
+        !this.openDocuments.has(filePath) ||
+        this.documentVersions.get(filePath) !== version
+      ) {
+        return;
+      }
+
+      const { setDiagnostics } = useDiagnosticsStore.getState().actions;
+      setDiagnostics(
+        filePath,
+        diagnostics.map((d) => convertLSPDiagnostic(filePath, d)),
+        "lsp",
+      );
+    } catch (error) {
+      logger.debug("LSPClient", "LSP pull diagnostics unavailable:", error);
     }
   }
 
   async notifyDocumentClose(filePath: string): Promise<void> {
+    clearTimeout(this.pullDiagnosticsTimers.get(filePath));
+    this.pullDiagnosticsTimers.delete(filePath);
     this.openDocuments.delete(filePath);
     this.documentVersions.delete(filePath);
     useDiagnosticsStore.getState().actions.clearDiagnosticsForOwner(filePath, "lsp");
@@ -1321,6 +1440,19 @@ export class LspClient {
     }
   }
 
+  /**
+   * Capabilities of the running server for a file, or null while no server has initialized
+   * for it.
+   */
+  async getCapabilities(filePath: string): Promise<LspCapabilities | null> {
+    try {
+      return await invoke<LspCapabilities | null>("lsp_get_capabilities", { filePath });
+    } catch (error) {
+      logger.debug("LSPClient", "LSP capabilities unavailable:", error);
+      return null;
+    }
+  }
+
   async isLanguageSupported(filePath: string): Promise<boolean> {
     try {
       const { extensionRegistry } = await import("@/extensions/registry/extension-registry");
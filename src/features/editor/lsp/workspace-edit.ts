@@ -23,6 +23,11 @@ export interface WorkspaceEdit {
   documentChanges?: Array<TextDocumentEdit | unknown>;
 }
 
+type ResourceOperation =
+  | { kind: "create"; uri: string }
+  | { kind: "rename"; oldUri: string; newUri: string }
+  | { kind: "delete"; uri: string };
+
 export interface WorkspaceEditApplyResult {
   editedFiles: number;
 }
@@ -55,6 +60,13 @@ function isTextDocumentEdit(value: unknown): value is TextDocumentEdit {
   );
 }
 
+function isResourceOperation(value: unknown): value is ResourceOperation {
+  return (
+    isObject(value) &&
+    (value.kind === "create" || value.kind === "rename" || value.kind === "delete")
+  );
+}
+
 export function isWorkspaceEdit(value: unknown): value is WorkspaceEdit {
   if (!isObject(value)) return false;
 
@@ -62,7 +74,10 @@ export function isWorkspaceEdit(value: unknown): value is WorkspaceEdit {
     isObject(value.changes) &&
     Object.values(value.changes).every((edits) => Array.isArray(edits) && edits.every(isTextEdit));
   const hasDocumentChanges =
-    Array.isArray(value.documentChanges) && value.documentChanges.some(isTextDocumentEdit);
+    Array.isArray(value.documentChanges) &&
+    value.documentChanges.some(
+      (change) => isTextDocumentEdit(change) || isResourceOperation(change),
+    );
 
   return hasChanges || hasDocumentChanges;
 }
@@ -131,14 +146,30 @@ export function applyTextEditsToContent(content: string, edits: LspTextEdit[]):
   }, content);
 }
 
+interface AppliedWorkspaceEdit {
+  editedFiles: string[];
+  createdFiles: string[];
+  renamedFiles: [string, string][];
+  deletedFiles: string[];
+}
+
+/**
+ * The changes of `edit` in the order they apply. Per the LSP spec, `documentChanges` is used
+ * when present and `changes` is ignored, since servers may send both for older clients.
+ */
+function workspaceDocumentChanges(edit: WorkspaceEdit): unknown[] {
+  if (edit.documentChanges) return edit.documentChanges;
+
+  return Object.entries(edit.changes ?? {}).map(([uri, edits]) => ({
+    textDocument: { uri },
+    edits,
+  }));
+}
+
 export function collectWorkspaceTextEdits(edit: WorkspaceEdit): Map<string, LspTextEdit[]> {
   const editsByFile = new Map<string, LspTextEdit[]>();
 
-  for (const [uri, edits] of Object.entries(edit.changes ?? {})) {
-    editsByFile.set(filePathFromUri(uri), [...edits]);
-  }
-
-  for (const documentChange of edit.documentChanges ?? []) {
+  for (const documentChange of workspaceDocumentChanges(edit)) {
     if (!isTextDocumentEdit(documentChange)) continue;
 
     const filePath = filePathFromUri(documentChange.textDocument.uri);
@@ -149,54 +180,110 @@ export function collectWorkspaceTextEdits(edit: WorkspaceEdit): Map<string, LspT
   return editsByFile;
 }
 
-async function readEditableSource(
-  filePath: string,
-): Promise<{ bufferId: string | null; content: string }> {
-  const { useBufferStore } = await import("../stores/buffer.store");
-  const { readFile } = await import("@/features/file-system/controllers/platform");
-  const { buffers } = useBufferStore.getState();
-  const openBuffer = buffers.find(
-    (buffer) => buffer.type === "editor" && !buffer.isVirtual && buffer.path === filePath,
-  );
+function isPathOrChild(path: string, parent: string): boolean {
+  return path === parent || path.startsWith(`${parent}/`);
+}
 
-  if (openBuffer?.type === "editor") {
-    return { bufferId: openBuffer.id, content: openBuffer.content };
+async function openBufferIdsByPath(): Promise<Map<string, string>> {
+  const { useBufferStore } = await import("../stores/buffer.store");
+  const bufferIds = new Map<string, string>();
+  for (const buffer of useBufferStore.getState().buffers) {
+    if (buffer.type === "editor" && !buffer.isVirtual) bufferIds.set(buffer.path, buffer.id);
   }
 
-  return { bufferId: null, content: await readFile(filePath) };
+  return bufferIds;
 }
 
-async function writeEditableSource(filePath: string, bufferId: string | null, content: string) {
+async function applyEditsToBuffer(bufferId: string, edits: LspTextEdit[]) {
   const { useBufferStore } = await import("../stores/buffer.store");
   const { trackImmediateBufferHistoryChange } = await import("../stores/buffer-history-tracking");
-  const { writeFile } = await import("@/features/file-system/controllers/platform");
-
-  if (bufferId) {
-    const buffer = useBufferStore.getState().buffers.find((candidate) => candidate.id === bufferId);
-    if (buffer?.type === "editor") {
-      trackImmediateBufferHistoryChange({
-        bufferId,
-        currentContent: buffer.content,
-        nextContent: content,
-      });
-    }
-    useBufferStore.getState().actions.updateBufferContent(bufferId, content, true);
-    return;
-  }
-
-  await writeFile(filePath, content);
+  const buffer = useBufferStore.getState().buffers.find((candidate) => candidate.id === bufferId);
+  if (buffer?.type !== "editor") return;
+
+  const content = applyTextEditsToContent(buffer.content, edits);
+  trackImmediateBufferHistoryChange({
+    bufferId,
+    currentContent: buffer.content,
+    nextContent: content,
+  });
+  useBufferStore.getState().actions.updateBufferContent(bufferId, content, true);
 }
 
+/**
+ * Applies edits to open buffers in the editor and hands everything else, such as edits to
+ * closed files and file create/rename/delete operations, to the backend to apply on disk.
+ * Open buffers follow the files they show when the edit renames or deletes them.
+ */
 export async function applyWorkspaceEdit(edit: WorkspaceEdit): Promise<WorkspaceEditApplyResult> {
+  const documentChanges = workspaceDocumentChanges(edit);
   const editsByFile = collectWorkspaceTextEdits(edit);
+  const bufferIdsByPath = await openBufferIdsByPath();
+
+  // Walk the changes in order, sending each text edit to the buffer that holds its file at that
+  // point. A rename moves the buffer along with the file, and a delete drops it.
+  const bufferEdits = new Map<string, LspTextEdit[]>();
+  const diskDocumentChanges: unknown[] = [];
+  for (const change of documentChanges) {
+    if (isTextDocumentEdit(change)) {
+      const bufferId = bufferIdsByPath.get(filePathFromUri(change.textDocument.uri));
+      if (bufferId) {
+        bufferEdits.set(bufferId, [...(bufferEdits.get(bufferId) ?? []), ...change.edits]);
+      } else {
+        diskDocumentChanges.push(change);
+      }
+    } else if (isResourceOperation(change)) {
+      diskDocumentChanges.push(change);
+      if (change.kind === "create") continue;
+
+      const affectedPath = filePathFromUri(change.kind === "rename" ? change.oldUri : change.uri);
+      for (const [path, bufferId] of Array.from(bufferIdsByPath)) {
+        if (!isPathOrChild(path, affectedPath)) continue;
+        bufferIdsByPath.delete(path);
+        if (change.kind === "rename") {
+          const newPath = filePathFromUri(change.newUri) + path.slice(affectedPath.length);
+          bufferIdsByPath.set(newPath, bufferId);
+        }
+      }
+    }
+  }
 
   await Promise.all(
-    Array.from(editsByFile, async ([filePath, edits]) => {
-      const source = await readEditableSource(filePath);
-      const nextContent = applyTextEditsToContent(source.content, edits);
-      await writeEditableSource(filePath, source.bufferId, nextContent);
-    }),
+    Array.from(bufferEdits, ([bufferId, edits]) => applyEditsToBuffer(bufferId, edits)),
   );
 
+  if (diskDocumentChanges.length > 0) {
+    const { invoke } = await import("@tauri-apps/api/core");
+    const { useFileSystemStore } = await import("@/features/file-system/stores/file-system.store");
+    const applied = await invoke<AppliedWorkspaceEdit>("lsp_apply_workspace_edit", {
+      edit: { documentChanges: diskDocumentChanges },
+      workspaceRoot: useFileSystemStore.getState().rootFolderPath ?? null,
+    });
+    await syncBuffersWithResourceOperations(applied);
+  }
+
   return { editedFiles: editsByFile.size };
 }
+
+/** Point open buffers at renamed files and close the buffers of deleted ones */
+async function syncBuffersWithResourceOperations(applied: AppliedWorkspaceEdit) {
+  const { useBufferStore } = await import("../stores/buffer.store");
+  const { actions } = useBufferStore.getState();
+
+  for (const [oldPath, newPath] of applied.renamedFiles) {
+    for (const buffer of useBufferStore.getState().buffers) {
+      if (buffer.type !== "editor" || buffer.isVirtual || !isPathOrChild(buffer.path, oldPath)) {
+        continue;
+      }
+      const wasDirty = buffer.isDirty;
+      actions.updateBufferPath(buffer.id, newPath + buffer.path.slice(oldPath.length));
+      if (wasDirty) actions.markBufferDirty(buffer.id, true);
+    }
+  }
+
+  for (const deletedPath of applied.deletedFiles) {
+    useBufferStore
+      .getState()
+      .buffers.filter((buffer) => isPathOrChild(buffer.path, deletedPath))
+      .forEach((buffer) => actions.closeBuffer(buffer.id));
+  }
+}
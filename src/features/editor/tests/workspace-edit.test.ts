@@ -64,7 +64,7 @@ describe("workspace edit utilities", () => {
     ).toBe("01AA45BB89");
   });
 
-  it("collects edits from changes and documentChanges", () => {
+  it("prefers documentChanges over changes", () => {
     const edit = {
       changes: {
         "file:///tmp/a.ts": [
@@ -94,6 +94,8 @@ describe("workspace edit utilities", () => {
     };
 
     expect(isWorkspaceEdit(edit)).toBe(true);
-    expect(collectWorkspaceTextEdits(edit).get("/tmp/a.ts")).toHaveLength(2);
+    expect(collectWorkspaceTextEdits(edit).get("/tmp/a.ts")).toEqual([
+      edit.documentChanges[0].edits[0],
+    ]);
   });
 });
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 export interface SearchMatchRange {
   start: number;
@@ -38,13 +39,32 @@ export interface SearchFilesRequest {
   root_paths: string[];
   query: string;
   case_sensitive?: boolean;
+  smart_case?: boolean;
   whole_word?: boolean;
   use_regex?: boolean;
+  fixed_string?: boolean;
   max_results?: number;
   file_offset?: number;
   context_lines?: number;
 }
 
+export interface SearchResultBatch {
+  search_id: string;
+  results: FileSearchResult[];
+}
+
+export interface SearchComplete {
+  search_id: string;
+  searched_files: number;
+  searchable_files: number;
+  files_with_matches: number;
+  total_matches: number;
+  is_indexing: boolean;
+  cancelled: boolean;
+  regex_fallback_error?: string | null;
+  error?: string | null;
+}
+
 export interface FffSearchHit {
   path: string;
   name: string;
@@ -72,10 +92,50 @@ export async function searchFilesContent(
   return invoke<SearchFilesResponse>("search_files_content", { request });
 }
 
+/**
+ * Search the whole workspace in the background. `onBatch` receives each page of results as it is
+ * found and `onComplete` the totals once the search ends. Returns a function that stops listening.
+ */
+export async function searchFilesStream(
+  searchId: string,
+  request: SearchFilesRequest,
+  onBatch: (batch: SearchResultBatch) => void,
+  onComplete: (complete: SearchComplete) => void,
+): Promise<UnlistenFn> {
+  const unlistenBatch = await listen<SearchResultBatch>(`search-result-${searchId}`, (event) =>
+    onBatch(event.payload),
+  );
+  const unlistenComplete = await listen<SearchComplete>("search-complete", (event) => {
+    if (event.payload.search_id !== searchId) return;
+    unlisten();
+    onComplete(event.payload);
+  });
+  const unlisten = () => {
+    unlistenBatch();
+    unlistenComplete();
+  };
+
+  try {
+    await invoke("search_files_stream", { searchId, request });
+  } catch (error) {
+    unlisten();
+    throw error;
+  }
+  return unlisten;
+}
+
+export async function cancelSearch(searchId: string): Promise<void> {
+  return invoke("search_cancel", { searchId });
+}
+
 export async function fffEnsureWorkspaces(rootPaths: readonly string[]): Promise<void> {
   return invoke("fff_ensure_workspaces", { rootPaths });
 }
 
+export async function fffRefreshWorkspaces(rootPaths: readonly string[]): Promise<void> {
+  return invoke("fff_refresh_workspaces", { rootPaths });
+}
+
 export async function fffScanStatus(rootPaths: readonly string[]): Promise<FffScanStatus> {
   return invoke<FffScanStatus>("fff_scan_status", { rootPaths });
 }
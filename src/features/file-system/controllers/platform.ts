@@ -10,7 +10,6 @@ import {
   readFile as readBinaryFile,
   readDir,
   remove,
-  writeTextFile,
 } from "@tauri-apps/plugin-fs";
 
 const utf8Decoder = new TextDecoder("utf-8");
@@ -55,12 +54,24 @@ export async function readFile(path: string): Promise<string> {
   }
 }
 
+interface WriteFileOptions {
+  /** Keep the previous content of a local file in `<path>.bak` */
+  backup?: boolean;
+  /** Line ending to save with instead of the one detected in the existing file */
+  endOfLine?: "lf" | "crlf" | "cr";
+}
+
 /**
  * Write content to a file
  * @param path The path to the file to write
  * @param content The content to write
+ * @param options Backup and line ending options for local files
  */
-export async function writeFile(path: string, content: string): Promise<void> {
+export async function writeFile(
+  path: string,
+  content: string,
+  options: WriteFileOptions = {},
+): Promise<void> {
   const wslInfo = parseWslPath(path);
   if (wslInfo) {
     await invoke("wsl_write_file", {
@@ -71,13 +82,13 @@ export async function writeFile(path: string, content: string): Promise<void> {
     return;
   }
 
-  try {
-    // Try to write as absolute path first
-    await writeTextFile(path, content);
-  } catch {
-    // Fallback to writing to app data directory
-    await writeTextFile(path, content, { baseDir: BaseDirectory.AppData });
-  }
+  // Replace the file atomically. A failed save must reach the caller rather than land elsewhere.
+  await invoke("write_local_file", {
+    path,
+    content,
+    backup: options.backup ?? false,
+    normalizeEol: options.endOfLine,
+  });
 }
 
 /**
@@ -241,6 +252,15 @@ export async function renameFile(sourcePath: string, targetPath: string): Promis
   await invoke("rename_file", { sourcePath, targetPath });
 }
 
+/**
+ * Create an empty file in the OS temp dir for a buffer that has no location yet
+ * @param extension Optional extension, with or without the leading dot
+ * @returns The path of the new scratch file
+ */
+export async function createScratchFile(extension?: string): Promise<string> {
+  return invoke<string>("create_scratch_file", { extension });
+}
+
 export interface SymlinkInfo {
   is_symlink: boolean;
   target?: string;
@@ -263,3 +283,43 @@ export async function getSymlinkInfo(path: string, workspaceRoot?: string): Prom
 
   return await invoke("get_symlink_info", { path, workspaceRoot });
 }
+
+export type FileOpenCategory = "text" | "image" | "archive" | "binary";
+
+/**
+ * Classify a local file so it can be opened in the editor or handed to the OS
+ * @param path The path to classify
+ */
+export async function getFileCategory(path: string): Promise<FileOpenCategory> {
+  return await invoke<FileOpenCategory>("get_file_category", { path });
+}
+
+/**
+ * Open a local file with the OS default application
+ * @param path The path to open
+ */
+export async function openExternally(path: string): Promise<void> {
+  await invoke("open_externally", { path });
+}
+
+export interface FileStats {
+  files: number;
+  lines: number;
+  words: number;
+  chars: number;
+  bytes: number;
+}
+
+/**
+ * Count lines, words and characters of a file or of the text files in a folder
+ * @param path The file or folder to count
+ * @param recursive Include subfolders of a folder
+ * @param respectGitignore Skip files ignored by git, on by default
+ */
+export async function countFileStats(
+  path: string,
+  recursive: boolean,
+  respectGitignore?: boolean,
+): Promise<FileStats> {
+  return await invoke<FileStats>("count_file_stats", { path, recursive, respectGitignore });
+}
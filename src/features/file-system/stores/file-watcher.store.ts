@@ -10,7 +10,7 @@ import { useFileSystemStore } from "../stores/file-system.store";
 
 interface FileChangeEvent {
   path: string;
-  event_type: "opened" | "reloaded" | "deleted";
+  event_type: "opened" | "created" | "reloaded" | "deleted";
 }
 
 // Store the unlisten function outside of the store to prevent re-renders
@@ -156,7 +156,7 @@ export async function initializeFileWatcherListener() {
     }
 
     // Handle new files created externally - refresh parent directory
-    if (event_type === "opened") {
+    if (event_type === "opened" || event_type === "created") {
       scheduleDirectoryRefresh(parentDir);
       return;
     }
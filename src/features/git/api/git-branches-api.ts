@@ -28,6 +28,22 @@ export const getBranches = async (repoPath: string): Promise<string[]> => {
   }
 };
 
+export const getDefaultBranch = async (repoPath: string): Promise<string | null> => {
+  try {
+    const resolvedRepoPath = await resolveRepositoryPath(repoPath);
+    if (!resolvedRepoPath) {
+      return null;
+    }
+
+    return await tauriInvoke<string>("git_default_branch", { repoPath: resolvedRepoPath });
+  } catch (error) {
+    if (!isNotGitRepositoryError(error)) {
+      console.error("Failed to get default branch:", error);
+    }
+    return null;
+  }
+};
+
 export const checkoutBranch = async (
   repoPath: string,
   branchName: string,
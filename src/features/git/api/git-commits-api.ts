@@ -1,5 +1,5 @@
 import { invoke as tauriInvoke } from "@tauri-apps/api/core";
-import type { GitCommit } from "../types/git.types";
+import type { GitCommit, GitGraphNode } from "../types/git.types";
 import {
   isNotGitRepositoryError,
   resolveRepositoryPath,
@@ -37,3 +37,45 @@ export const getGitLog = async (repoPath: string, limit = 50, skip = 0): Promise
     return [];
   }
 };
+
+export const getCommitGraph = async (
+  repoPath: string,
+  limit = 200,
+  branches?: string[],
+): Promise<GitGraphNode[]> => {
+  try {
+    const resolvedRepoPath = await resolveRepositoryPath(repoPath);
+    if (!resolvedRepoPath) {
+      return [];
+    }
+
+    return await tauriInvoke<GitGraphNode[]>("git_commit_graph", {
+      repoPath: resolvedRepoPath,
+      limit,
+      branches,
+    });
+  } catch (error) {
+    if (!isNotGitRepositoryError(error)) {
+      console.error("Failed to get commit graph:", error);
+    }
+    return [];
+  }
+};
+
+export type GitResetMode = "soft" | "mixed" | "hard";
+
+export const resetToRef = async (
+  repoPath: string,
+  targetRef: string,
+  mode: GitResetMode,
+  confirm = false,
+): Promise<boolean> => {
+  try {
+    const resolvedRepoPath = await resolveRepositoryPathOrThrow(repoPath);
+    await tauriInvoke("git_reset_to", { repoPath: resolvedRepoPath, targetRef, mode, confirm });
+    return true;
+  } catch (error) {
+    console.error("Failed to reset branch:", error);
+    return false;
+  }
+};
@@ -0,0 +1,37 @@
+import { invoke as tauriInvoke } from "@tauri-apps/api/core";
+import { isNotGitRepositoryError, resolveRepositoryPath } from "./git-repo-api";
+
+export const getGitConfig = async (repoPath: string, key: string): Promise<string | null> => {
+  try {
+    const resolvedRepoPath = await resolveRepositoryPath(repoPath);
+    if (!resolvedRepoPath) {
+      return null;
+    }
+
+    return await tauriInvoke<string | null>("git_get_config", {
+      repoPath: resolvedRepoPath,
+      key,
+    });
+  } catch (error) {
+    if (!isNotGitRepositoryError(error)) {
+      console.error("Failed to read git config:", error);
+    }
+    return null;
+  }
+};
+
+export const setGitConfig = async (
+  repoPath: string,
+  key: string,
+  value: string,
+  global = false,
+): Promise<boolean> => {
+  try {
+    const resolvedRepoPath = (await resolveRepositoryPath(repoPath)) ?? repoPath;
+    await tauriInvoke("git_set_config", { repoPath: resolvedRepoPath, key, value, global });
+    return true;
+  } catch (error) {
+    console.error("Failed to write git config:", error);
+    return false;
+  }
+};
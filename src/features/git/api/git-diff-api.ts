@@ -1,5 +1,5 @@
 import { invoke as tauriInvoke } from "@tauri-apps/api/core";
-import type { GitDiff, GitDiffStat } from "../types/git.types";
+import type { GitDiff, GitDiffLine, GitDiffStat } from "../types/git.types";
 import { gitDiffCache } from "../utils/git-diff-cache";
 import {
   isNotGitRepositoryError,
@@ -168,6 +168,38 @@ export const getFileDiffAgainstContent = async (
   }
 };
 
+export const getBufferDiffAgainstDisk = async (
+  path: string,
+  bufferContent: string,
+): Promise<GitDiffLine[] | null> => {
+  try {
+    return await tauriInvoke<GitDiffLine[]>("diff_buffer_against_disk", {
+      path,
+      bufferContent,
+    });
+  } catch (error) {
+    console.error("Failed to diff buffer against disk:", error);
+    return null;
+  }
+};
+
+export const getTextDiff = async (
+  oldText: string,
+  newText: string,
+  contextLines?: number,
+): Promise<GitDiffLine[] | null> => {
+  try {
+    return await tauriInvoke<GitDiffLine[]>("diff_texts", {
+      old: oldText,
+      new: newText,
+      contextLines,
+    });
+  } catch (error) {
+    console.error("Failed to diff texts:", error);
+    return null;
+  }
+};
+
 export const getStatusDiffStats = async (repoPath: string): Promise<GitDiffStat[]> => {
   try {
     const resolvedRepoPath = await resolveRepositoryPath(repoPath);
@@ -203,6 +235,48 @@ export const getStatusDiffStats = async (repoPath: string): Promise<GitDiffStat[
   }
 };
 
+export const getAllDiffs = async (repoPath: string, staged: boolean): Promise<GitDiff[]> => {
+  try {
+    const resolvedRepoPath = await resolveRepositoryPath(repoPath);
+    if (!resolvedRepoPath) {
+      return [];
+    }
+
+    const diffs = await tauriInvoke<GitDiff[]>("git_diff_all", {
+      repoPath: resolvedRepoPath,
+      staged,
+    });
+    for (const diff of diffs) {
+      gitDiffCache.set(resolvedRepoPath, diff.file_path, staged, diff);
+    }
+    return diffs;
+  } catch (error) {
+    if (!isNotGitRepositoryError(error)) {
+      console.error("Failed to get diffs:", error);
+    }
+    return [];
+  }
+};
+
+export const getDiffStat = async (repoPath: string, staged: boolean): Promise<GitDiffStat[]> => {
+  try {
+    const resolvedRepoPath = await resolveRepositoryPath(repoPath);
+    if (!resolvedRepoPath) {
+      return [];
+    }
+
+    return await tauriInvoke<GitDiffStat[]>("git_diff_stat", {
+      repoPath: resolvedRepoPath,
+      staged,
+    });
+  } catch (error) {
+    if (!isNotGitRepositoryError(error)) {
+      console.error("Failed to get diff stat:", error);
+    }
+    return [];
+  }
+};
+
 export const getCommitDiff = async (
   repoPath: string,
   commitHash: string,
@@ -265,9 +339,29 @@ export const getRefDiff = async (
   }
 };
 
+export const getBranchDiff = async (repoPath: string, base?: string): Promise<GitDiff[] | null> => {
+  try {
+    const resolvedRepoPath = await resolveRepositoryPath(repoPath);
+    if (!resolvedRepoPath) {
+      return null;
+    }
+
+    return await tauriInvoke<GitDiff[]>("git_branch_diff", {
+      repoPath: resolvedRepoPath,
+      base,
+    });
+  } catch (error) {
+    if (!isNotGitRepositoryError(error)) {
+      console.error("Failed to get branch diff:", error);
+    }
+    return null;
+  }
+};
+
 export const getStashDiff = async (
   repoPath: string,
   stashIndex: number,
+  filePath?: string,
 ): Promise<GitDiff[] | null> => {
   try {
     const resolvedRepoPath = await resolveRepositoryPath(repoPath);
@@ -275,7 +369,7 @@ export const getStashDiff = async (
       return null;
     }
 
-    const cacheKey = `${resolvedRepoPath}:${stashIndex}`;
+    const cacheKey = `${resolvedRepoPath}:${stashIndex}:${filePath ?? ""}`;
     const cached = getMultiFileDiffCacheEntry(stashDiffCache, cacheKey);
     if (cached) {
       return cached;
@@ -284,6 +378,7 @@ export const getStashDiff = async (
     const diffs = await tauriInvoke<GitDiff[]>("git_stash_diff", {
       repoPath: resolvedRepoPath,
       stashIndex,
+      filePath,
     });
     setMultiFileDiffCacheEntry(stashDiffCache, cacheKey, diffs);
     return diffs;
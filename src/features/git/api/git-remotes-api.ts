@@ -1,4 +1,5 @@
 import { invoke as tauriInvoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type { GitRemote } from "../types/git.types";
 import {
   isNotGitRepositoryError,
@@ -11,6 +12,22 @@ export interface GitRemoteActionResult {
   error?: string;
 }
 
+export interface GitFetchResult extends GitRemoteActionResult {
+  /** Git's report of the refs the fetch updated, empty when nothing changed */
+  summary?: string;
+}
+
+export interface GitCloneProgress {
+  receivedObjects: number;
+  indexedObjects: number;
+  totalObjects: number;
+  receivedBytes: number;
+}
+
+export interface GitCloneResult extends GitRemoteActionResult {
+  path?: string;
+}
+
 export const getRemotes = async (repoPath: string): Promise<GitRemote[]> => {
   try {
     const resolvedRepoPath = await resolveRepositoryPath(repoPath);
@@ -91,11 +108,16 @@ export const pullChanges = async (
 export const fetchChanges = async (
   repoPath: string,
   remote?: string,
-): Promise<GitRemoteActionResult> => {
+  prune: boolean = false,
+): Promise<GitFetchResult> => {
   try {
     const resolvedRepoPath = await resolveRepositoryPathOrThrow(repoPath);
-    await tauriInvoke("git_fetch", { repoPath: resolvedRepoPath, remote });
-    return { success: true };
+    const summary = await tauriInvoke<string>("git_fetch", {
+      repoPath: resolvedRepoPath,
+      remote,
+      prune,
+    });
+    return { success: true, summary };
   } catch (error) {
     console.error("Failed to fetch changes:", error);
     return {
@@ -104,3 +126,26 @@ export const fetchChanges = async (
     };
   }
 };
+
+export const cloneRepository = async (
+  url: string,
+  destPath: string,
+  onProgress?: (progress: GitCloneProgress) => void,
+): Promise<GitCloneResult> => {
+  const unlisten = onProgress
+    ? await listen<GitCloneProgress>("git-clone-progress", (event) => onProgress(event.payload))
+    : undefined;
+
+  try {
+    const path = await tauriInvoke<string>("git_clone", { url, destPath });
+    return { success: true, path };
+  } catch (error) {
+    console.error("Failed to clone repository:", error);
+    return {
+      success: false,
+      error: error instanceof Error ? error.message : String(error),
+    };
+  } finally {
+    unlisten?.();
+  }
+};
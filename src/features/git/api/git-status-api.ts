@@ -44,6 +44,27 @@ export const getGitStatus = async (repoPath: string): Promise<GitStatus | null>
   return request;
 };
 
+export const checkIgnored = async (repoPath: string, paths: string[]): Promise<boolean[]> => {
+  if (paths.length === 0) return [];
+
+  try {
+    const resolvedRepoPath = await resolveRepositoryPath(repoPath);
+    if (!resolvedRepoPath) {
+      return paths.map(() => false);
+    }
+
+    return await tauriInvoke<boolean[]>("git_check_ignore", {
+      repoPath: resolvedRepoPath,
+      paths,
+    });
+  } catch (error) {
+    if (!isNotGitRepositoryError(error)) {
+      console.error("Failed to check ignored paths:", error);
+    }
+    return paths.map(() => false);
+  }
+};
+
 export const stageFile = async (repoPath: string, filePath: string): Promise<boolean> => {
   try {
     const resolvedRepoPath = await resolveRepositoryPathOrThrow(repoPath);
@@ -121,20 +142,36 @@ export const discardAllChanges = async (repoPath: string): Promise<boolean> => {
   }
 };
 
-export const discardFileChanges = async (repoPath: string, filePath: string): Promise<boolean> => {
+interface DiscardFileResult {
+  success: boolean;
+  error?: string;
+}
+
+export const discardFileChanges = async (
+  repoPath: string,
+  filePath: string,
+  discardStaged: boolean = false,
+): Promise<DiscardFileResult> => {
   try {
     const resolvedRepoPath = await resolveRepositoryPathOrThrow(repoPath);
-    await tauriInvoke("git_discard_file_changes", { repoPath: resolvedRepoPath, filePath });
-    return true;
+    await tauriInvoke("git_discard_file_changes", {
+      repoPath: resolvedRepoPath,
+      filePath,
+      discardStaged,
+    });
+    return { success: true };
   } catch (error) {
     console.error("Failed to discard file changes:", error);
-    return false;
+    return { success: false, error: error instanceof Error ? error.message : String(error) };
   }
 };
 
-export const initRepository = async (repoPath: string): Promise<boolean> => {
+export const initRepository = async (
+  repoPath: string,
+  initialBranch?: string,
+): Promise<boolean> => {
   try {
-    await tauriInvoke("git_init", { repoPath });
+    await tauriInvoke("git_init", { repoPath, initialBranch });
     return true;
   } catch (error) {
     console.error("Failed to initialize repository:", error);
@@ -21,7 +21,7 @@ import {
   fetchChanges,
   pullChanges,
   pushChanges,
-  type GitRemoteActionResult,
+  type GitFetchResult,
 } from "../api/git-remotes-api";
 import { discardAllChanges, initRepository } from "../api/git-status-api";
 import { useGitStore } from "../stores/git.store";
@@ -67,7 +67,7 @@ const GitActionsMenu = ({
   const confirmBeforeDiscard = useSettingsStore((state) => state.settings.confirmBeforeDiscard);
 
   const handleAction = async (
-    action: () => Promise<boolean | GitRemoteActionResult>,
+    action: () => Promise<boolean | GitFetchResult>,
     actionName: string,
     messages?: {
       loading?: string;
@@ -87,12 +87,14 @@ const GitActionsMenu = ({
       }
 
       const result = await action();
-      const remoteResult =
+      const remoteResult: GitFetchResult =
         typeof result === "boolean" ? { success: result, error: undefined } : result;
 
       if (remoteResult.success) {
         if (toastId) toast.dismiss(toastId);
-        toast.success(messages?.success ?? `${actionName} completed.`);
+        toast.success(messages?.success ?? `${actionName} completed.`, {
+          description: remoteResult.summary || undefined,
+        });
         onRefresh?.();
       } else {
         const errorMessage = remoteResult.error || messages?.error || `${actionName} failed.`;
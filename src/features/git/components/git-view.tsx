@@ -43,7 +43,12 @@ import {
   getStatusDiffStats,
 } from "../api/git-diff-api";
 import { clearRepositoryDiscoveryCache, resolveRepositoryPath } from "../api/git-repo-api";
-import { fetchChanges, pullChanges, pushChanges } from "../api/git-remotes-api";
+import {
+  fetchChanges,
+  pullChanges,
+  pushChanges,
+  type GitFetchResult,
+} from "../api/git-remotes-api";
 import { applyStash, dropStash, getStashes, popStash } from "../api/git-stash-api";
 import { getGitStatus, initRepository } from "../api/git-status-api";
 import { useRepositoryStore } from "../stores/git-repository.store";
@@ -424,7 +429,7 @@ const GitView = ({ repoPath, onFileSelect, isActive }: GitViewProps) => {
       });
 
       try {
-        const result =
+        const result: GitFetchResult =
           action === "push"
             ? await pushChanges(activeRepoPath)
             : action === "pull"
@@ -434,7 +439,9 @@ const GitView = ({ repoPath, onFileSelect, isActive }: GitViewProps) => {
         toast.dismiss(toastId);
 
         if (result.success) {
-          toast.success(`${label.past} changes successfully.`);
+          toast.success(`${label.past} changes successfully.`, {
+            description: result.summary || undefined,
+          });
           await handleManualRefresh();
           return;
         }
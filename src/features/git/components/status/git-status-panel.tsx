@@ -11,6 +11,7 @@ import {
 import type React from "react";
 import { Fragment, useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { ThemedFileIcon } from "@/extensions/icon-themes/components/themed-file-icon";
+import { useToast } from "@/features/layout/contexts/toast-context";
 import { writeSidebarResourceDragData } from "@/features/sidebar/utils/sidebar-resource-drag";
 import { useSettingsStore } from "@/features/settings/stores/settings.store";
 import Badge from "@/ui/badge";
@@ -164,6 +165,7 @@ const GitStatusPanel = ({
 }: GitStatusPanelProps) => {
   const gitChangesFolderView = useSettingsStore((state) => state.settings.gitChangesFolderView);
   const confirmBeforeDiscard = useSettingsStore((state) => state.settings.confirmBeforeDiscard);
+  const { showToast } = useToast();
   const contextMenu = useDropdownMenu<ContextMenuState>();
   const diffMenuAnchorRef = useRef<HTMLDivElement>(null);
   const [isLoading, setIsLoading] = useState(false);
@@ -383,9 +385,30 @@ const GitStatusPanel = ({
     ) {
       return;
     }
+    await discardFile(filePath, false);
+  };
+
+  const discardFile = async (filePath: string, discardStaged: boolean) => {
+    if (!repoPath) return;
     setIsLoading(true);
     try {
-      await discardFileChanges(repoPath, filePath);
+      const result = await discardFileChanges(repoPath, filePath, discardStaged);
+      if (!result.success) {
+        // The backend refuses to drop staged changes unless asked to, so offer that explicitly
+        const hasStagedChanges =
+          !discardStaged && stagedFiles.some((file) => file.path === filePath);
+        showToast({
+          message: result.error || `Failed to discard changes for "${filePath}".`,
+          type: hasStagedChanges ? "warning" : "error",
+          duration: hasStagedChanges ? 0 : undefined,
+          action: hasStagedChanges
+            ? {
+                label: "Discard Staged Too",
+                onClick: () => void discardFile(filePath, true),
+              }
+            : undefined,
+        });
+      }
       onRefresh?.();
     } finally {
       setIsLoading(false);
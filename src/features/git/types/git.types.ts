@@ -20,6 +20,16 @@ export interface GitCommit {
   date: string;
 }
 
+export interface GitGraphNode {
+  hash: string;
+  parents: string[];
+  refs: string[];
+  author: string;
+  email: string;
+  date: string;
+  summary: string;
+}
+
 export interface GitDiffLine {
   line_type: "added" | "removed" | "context" | "header";
   content: string;
@@ -50,6 +60,7 @@ export interface GitDiffStat {
   staged: boolean;
   additions: number;
   deletions: number;
+  binary: boolean;
 }
 
 export interface GitHunk {
@@ -413,6 +413,11 @@ export async function formatActiveEditorSelection(): Promise<void> {
   }
 
   const { formatRange } = await import("@/features/editor/formatter/formatter-service");
+  const { useEditorConfigStore } = await import("@/features/editor/stores/editorconfig.store");
+  const { useEditorSettingsStore } = await import("@/features/editor/stores/settings.store");
+  const editorConfig = await useEditorConfigStore
+    .getState()
+    .actions.resolveProperties(activeBuffer.path);
   const result = await formatRange({
     filePath: activeBuffer.path,
     content: activeBuffer.content,
@@ -422,6 +427,15 @@ export async function formatActiveEditorSelection(): Promise<void> {
       start: { line: selection.start.line, character: selection.start.column },
       end: { line: selection.end.line, character: selection.end.column },
     },
+    formattingOptions: {
+      tabSize:
+        editorConfig.indentSize ??
+        editorConfig.tabWidth ??
+        useEditorSettingsStore.getState().tabSize,
+      insertSpaces: editorConfig.indentStyle !== "tab",
+      trimTrailingWhitespace: editorConfig.trimTrailingWhitespace,
+      insertFinalNewline: editorConfig.insertFinalNewline,
+    },
   });
 
   if (!result.success || result.formattedContent === undefined) {
@@ -13,7 +13,7 @@ export async function restartAllLanguageServers(): Promise<void> {
   }
 
   try {
-    await lspClient.restartAllTrackedServers();
+    await lspClient.restartAll();
     toast.success("Language servers restarted.");
   } catch (error) {
     toast.error(error instanceof Error ? error.message : "Failed to restart language servers.");
@@ -1,3 +1,5 @@
+import { invoke } from "@tauri-apps/api/core";
+
 export const fuzzyScore = (text: string, query: string): number => {
   if (!query) return 0;
 
@@ -49,3 +51,37 @@ export const fuzzyScore = (text: string, query: string): number => {
 
   return 0;
 };
+
+export interface FuzzyMatch {
+  /** Position of the candidate in the list passed to fuzzyMatch */
+  index: number;
+  text: string;
+  score: number;
+  /** Matched character (code point) positions, for highlighting */
+  indices: number[];
+}
+
+export interface FuzzyMatchOptions {
+  caseSensitive?: boolean;
+  /** Favor matches at the start of path segments, for file lists */
+  matchPaths?: boolean;
+}
+
+/**
+ * Rank candidates with the backend matcher, best match first. Candidates that don't match are
+ * left out.
+ */
+export const fuzzyMatch = async (
+  query: string,
+  candidates: string[],
+  options: FuzzyMatchOptions = {},
+): Promise<FuzzyMatch[]> => {
+  return invoke<FuzzyMatch[]>("fuzzy_match", {
+    request: {
+      pattern: query,
+      items: candidates,
+      case_sensitive: options.caseSensitive,
+      match_paths: options.matchPaths,
+    },
+  });
+};
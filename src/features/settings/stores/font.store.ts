@@ -131,7 +131,9 @@ export const useFontStore = createSelectors(
             });
 
             try {
-              const fonts = await invoke<FontInfo[]>("get_system_fonts");
+              const fonts = await invoke<FontInfo[]>("list_system_fonts", {
+                refresh: forceRefresh,
+              });
               const monospaceFonts = fonts.filter((font) => font.is_monospace);
 
               set((state) => {
@@ -3,4 +3,6 @@ export interface FontInfo {
   family: string;
   style: string;
   is_monospace: boolean;
+  weights?: number[];
+  styles?: string[];
 }
@@ -51,7 +51,10 @@ export interface TerminalSize {
   pixelHeight: number;
 }
 
-export type TerminalInput = { kind: "text"; data: string } | { kind: "binary"; data: number[] };
+export type TerminalInput =
+  | { kind: "text"; data: string }
+  | { kind: "binary"; data: number[] }
+  | { kind: "paste"; data: string; bracketed?: boolean };
 
 export type TerminalEvent =
   | { event: "output"; data: number[] }
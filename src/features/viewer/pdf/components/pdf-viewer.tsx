@@ -148,7 +148,7 @@ export function PdfViewer({ filePath, fileName }: PdfViewerProps) {
 
   const handleOpenExternal = async () => {
     try {
-      await invoke("open_file_external", { path: filePath });
+      await invoke("open_externally", { path: filePath });
     } catch (err) {
       console.error("Failed to open external viewer (rust):", err);
       // Fallback to opener plugin just in case
@@ -0,0 +1,61 @@
+import { invoke } from "@tauri-apps/api/core";
+import { PhysicalPosition, PhysicalSize } from "@tauri-apps/api/dpi";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+
+interface WindowGeometry {
+  width: number;
+  height: number;
+  x: number;
+  y: number;
+  maximized: boolean;
+}
+
+/**
+ * Per-workspace layout kept by the backend. Panel visibility and pane splits already live in
+ * the project UI session, so only the window geometry is stored here.
+ */
+interface WorkspaceLayout {
+  window?: WindowGeometry;
+}
+
+const readWindowGeometry = async (): Promise<WindowGeometry> => {
+  const currentWindow = getCurrentWindow();
+  const [size, position, maximized] = await Promise.all([
+    currentWindow.innerSize(),
+    currentWindow.outerPosition(),
+    currentWindow.isMaximized(),
+  ]);
+  return { width: size.width, height: size.height, x: position.x, y: position.y, maximized };
+};
+
+export const saveWorkspaceWindowLayout = async (workspacePath: string): Promise<void> => {
+  try {
+    const layout: WorkspaceLayout = { window: await readWindowGeometry() };
+    await invoke("save_workspace_layout", { workspacePath, layout });
+  } catch (error) {
+    console.error("Failed to save workspace layout:", error);
+  }
+};
+
+export const restoreWorkspaceWindowLayout = async (workspacePath: string): Promise<void> => {
+  try {
+    const layout = await invoke<WorkspaceLayout | null>("load_workspace_layout", {
+      workspacePath,
+    });
+    const geometry = layout?.window;
+    if (!geometry) return;
+
+    const currentWindow = getCurrentWindow();
+    if (geometry.maximized) {
+      await currentWindow.maximize();
+      return;
+    }
+    if (await currentWindow.isMaximized()) {
+      await currentWindow.unmaximize();
+    }
+    await currentWindow.setSize(new PhysicalSize(geometry.width, geometry.height));
+    await currentWindow.setPosition(new PhysicalPosition(geometry.x, geometry.y));
+  } catch (error) {
+    console.error("Failed to restore workspace layout:", error);
+  }
+};
@@ -1,5 +1,9 @@
 import { useBufferStore } from "@/features/editor/stores/buffer.store";
 import { usePaneStore } from "@/features/panes/stores/pane.store";
+import {
+  restoreWorkspaceWindowLayout,
+  saveWorkspaceWindowLayout,
+} from "@/features/window/lib/workspace-window-layout";
 import type { ProjectUiSession } from "@/features/window/stores/session.store";
 import { workspaceSessionRepository } from "@/features/workspace/persistence/workspace-session-repository";
 import {
@@ -29,6 +33,7 @@ export const persistCurrentProjectUiState = (projectPath: string | undefined) =>
   }
 
   workspaceSessionRepository.saveUi(projectPath, getCurrentProjectUiState());
+  void saveWorkspaceWindowLayout(projectPath);
 };
 
 export const restoreProjectUiState = (projectPath: string | undefined, workspaceId?: string) => {
@@ -45,6 +50,9 @@ export const restoreProjectUiState = (projectPath: string | undefined, workspace
     legacyDebuggerSidebar ? "debugger" : nextUiState.bottomPaneActiveTab,
   );
   state.setActiveView(legacyToolBufferSidebar ? "files" : nextUiState.activeSidebarView);
+  if (projectPath) {
+    void restoreWorkspaceWindowLayout(projectPath);
+  }
 };
 
 export const restoreProjectPaneState = (projectPath: string | undefined, workspaceId?: string) => {